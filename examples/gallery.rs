@@ -263,6 +263,10 @@ impl Application for MyApp {
         }
     }
 
+    fn on_tick(&mut self, dt: f64, _cx: &mut AppContext<Self::Action>) {
+        self.main_window_elements.knobs_and_sliders.on_tick(dt);
+    }
+
     fn on_action_emitted(&mut self, cx: &mut AppContext<Self::Action>) {
         while let Ok(action) = cx.action_receiver.try_recv() {
             #[cfg(debug_assertions)]
@@ -23,6 +23,8 @@ impl DropDownOption {
 #[repr(usize)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
 pub enum TextMenuOption {
+    Undo,
+    Redo,
     Cut,
     Copy,
     Paste,
@@ -30,10 +32,19 @@ pub enum TextMenuOption {
     SelectAll,
 }
 impl TextMenuOption {
-    pub const ALL: [Self; 4] = [Self::Cut, Self::Copy, Self::Paste, Self::SelectAll];
+    pub const ALL: [Self; 6] = [
+        Self::Undo,
+        Self::Redo,
+        Self::Cut,
+        Self::Copy,
+        Self::Paste,
+        Self::SelectAll,
+    ];
 
     pub fn right_text(&self) -> &'static str {
         match self {
+            Self::Undo => "Ctrl+Z",
+            Self::Redo => "Ctrl+Shift+Z",
             Self::Cut => "Ctrl+X",
             Self::Copy => "Ctrl+C",
             Self::Paste => "Ctrl+V",
@@ -43,6 +54,8 @@ impl TextMenuOption {
 
     pub fn as_text_input_option(&self) -> TextInputAction {
         match self {
+            Self::Undo => TextInputAction::Undo,
+            Self::Redo => TextInputAction::Redo,
             Self::Cut => TextInputAction::Cut,
             Self::Copy => TextInputAction::Copy,
             Self::Paste => TextInputAction::Paste,
@@ -63,7 +76,6 @@ pub enum Action {
     IconBtnPressed,
     ToggleValue(bool),
     OptionSelected(DropDownOption),
-    OpenDropDown,
     OpenRightClickMenu(Point),
     RightClickOptionSelected(usize),
     TextChanged(String),
@@ -86,8 +98,7 @@ pub struct Elements {
     icon_toggle_btn: ToggleButton,
     icon_label_toggle_btn: ToggleButton,
     radio_group: RadioButtonGroup,
-    drop_down_menu_btn: Button,
-    drop_down_menu: DropDownMenu,
+    combo_box: ComboBox,
     text_input: TextInput,
     text_input_menu: DropDownMenu,
     search_text_input: IconTextInput,
@@ -107,15 +118,16 @@ impl Elements {
                     .iter()
                     .enumerate()
                     .map(|(i, s)| {
-                        let icon = match i {
-                            0 => MyIcon::Cut,
-                            1 => MyIcon::Copy,
-                            2 => MyIcon::Paste,
-                            _ => MyIcon::Select,
-                        } as IconID;
+                        let icon = match s {
+                            TextMenuOption::Undo | TextMenuOption::Redo => None,
+                            TextMenuOption::Cut => Some(MyIcon::Cut as IconID),
+                            TextMenuOption::Copy => Some(MyIcon::Copy as IconID),
+                            TextMenuOption::Paste => Some(MyIcon::Paste as IconID),
+                            TextMenuOption::SelectAll => Some(MyIcon::Select as IconID),
+                        };
 
                         MenuEntry::Option {
-                            left_icon: Some(icon),
+                            left_icon: icon,
                             icon_scale: 1.0.into(),
                             left_text: format!("{s}"),
                             right_text: Some(s.right_text().into()),
@@ -130,18 +142,6 @@ impl Elements {
             .z_index(OVERLAY_Z_INDEX)
             .build(window_cx);
 
-        let drop_down_menu = DropDownMenu::builder()
-            .entries(
-                DropDownOption::ALL
-                    .iter()
-                    .enumerate()
-                    .map(|(i, s)| MenuEntry::option(format!("{s}"), i))
-                    .collect(),
-            )
-            .on_entry_selected(|id| Action::OptionSelected(DropDownOption::ALL[id]).into())
-            .z_index(OVERLAY_Z_INDEX)
-            .build(window_cx);
-
         let right_click_area = ClickArea::builder()
             .button(PointerButton::Secondary)
             .on_clicked(|info| Action::OpenRightClickMenu(info.click_position).into())
@@ -253,18 +253,23 @@ impl Elements {
                     .password_mode(false) // There is an optional password mode if desired.
                     .build(window_cx),
 
-                drop_down_menu_btn: Button::builder()
-                    .text_icon_layout(TextIconLayout::LeftAlignTextRightAlignIcon)
-                    .text(format!("{}", DropDownOption::ALL[0]))
-                    .icon(MyIcon::Dropdown)
-                    .on_select(Action::OpenDropDown.into())
+                combo_box: ComboBox::builder()
+                    .entries(
+                        DropDownOption::ALL
+                            .iter()
+                            .enumerate()
+                            .map(|(i, s)| MenuEntry::option(format!("{s}"), i))
+                            .collect(),
+                    )
+                    .selected(0)
+                    .on_selected(|id| Action::OptionSelected(DropDownOption::ALL[id]).into())
+                    .menu_z_index(OVERLAY_Z_INDEX)
                     .build(window_cx),
 
                 separator_1: Separator::builder().build(window_cx),
                 separator_2: Separator::builder().build(window_cx),
 
                 text_input_menu,
-                drop_down_menu,
                 right_click_area,
                 right_click_menu,
                 scroll_area,
@@ -305,15 +310,7 @@ impl Elements {
             }
             Action::OptionSelected(option) => {
                 self.radio_group.updated_selected(option as usize);
-                self.drop_down_menu_btn
-                    .set_text(Some(&format!("{}", option)), window_cx.res);
-            }
-            Action::OpenDropDown => {
-                // Because the drop-down menu button may be offset by the scroll area,
-                // get the correct position via this method.
-                let rect = self.drop_down_menu_btn.rect_in_window(window_cx);
-                self.drop_down_menu
-                    .open(Some(Point::new(rect.min_x(), rect.max_y())));
+                self.combo_box.set_selected(Some(option as usize));
             }
             Action::OpenRightClickMenu(position) => {
                 self.right_click_menu.open(Some(position));
@@ -418,18 +415,15 @@ impl Elements {
             style.separator_width,
         ));
 
-        self.drop_down_menu_btn.set_rect(rect(
+        self.combo_box.set_rect(rect(
             start_pos.x,
             self.separator_1.max_y() + style.element_padding,
             style.drop_down_btn_width,
-            self.drop_down_menu_btn.desired_size(window_cx.res).height,
+            self.combo_box.desired_size(window_cx.res).height,
         ));
 
         self.radio_group.layout(
-            point(
-                start_pos.x,
-                self.drop_down_menu_btn.max_y() + style.element_padding,
-            ),
+            point(start_pos.x, self.combo_box.max_y() + style.element_padding),
             style.radio_group_row_padding,
             style.radio_group_column_padding,
             None,
@@ -476,8 +470,7 @@ impl Elements {
             icon_toggle_btn,
             icon_label_toggle_btn,
             radio_group,
-            drop_down_menu_btn,
-            drop_down_menu,
+            combo_box,
             text_input,
             text_input_menu,
             search_text_input,
@@ -499,8 +492,7 @@ impl Elements {
         icon_btn.set_hidden(hidden);
         icon_label_toggle_btn.set_hidden(hidden);
         radio_group.set_hidden(hidden);
-        drop_down_menu_btn.set_hidden(hidden);
-        drop_down_menu.set_hidden(hidden);
+        combo_box.set_hidden(hidden);
         text_input.set_hidden(hidden);
         text_input_menu.set_hidden(hidden);
         search_text_input.set_hidden(hidden);
@@ -1,6 +1,7 @@
 use crate::style::MyStyle;
 use crate::{MyAction, OVERLAY_Z_INDEX, RIGHT_CLICK_AREA_Z_INDEX};
 use smol_str::SmolStr;
+use std::rc::Rc;
 use yarrow::prelude::*;
 
 const SCROLL_AREA_SRECT: ScissorRectID = ScissorRectID(1);
@@ -11,6 +12,7 @@ pub enum Action {
     ShowParamTooltip(ParamElementTooltipInfo),
     OpenTextInput(ParamOpenTextEntryInfo),
     FloatingTextInput(Option<String>),
+    ModulationDepth(ModulationDepthUpdate),
 }
 
 pub struct Elements {
@@ -28,6 +30,20 @@ pub struct Elements {
     slider_5: Slider,
     slider_6: Slider,
 
+    number_field_7: NumberField,
+
+    meter_8: Meter,
+    meter_8_phase: f64,
+
+    waveform_9: Waveform,
+
+    spectrum_10: Spectrum,
+    spectrum_10_magnitudes: Vec<f32>,
+
+    eq_curve_11: EqCurve,
+
+    envelope_12: EnvelopeEditor,
+
     separator: Separator,
 
     scroll_area: ScrollArea,
@@ -49,10 +65,12 @@ impl Elements {
             .z_index(OVERLAY_Z_INDEX)
             .build(window_cx);
 
-        window_cx.with_scissor_rect(SCROLL_AREA_SRECT, |window_cx| Self {
+        let mut elements = window_cx.with_scissor_rect(SCROLL_AREA_SRECT, |window_cx| Self {
             knob_0: Knob::builder("knob_0")
                 .on_gesture(|param_update| Action::ParamUpdate(param_update).into())
+                .formatter(DecibelFormatter::new(-60.0, 6.0).min_is_negative_infinity(true))
                 .on_open_text_entry(|info| Action::OpenTextInput(info).into())
+                .on_modulation_depth(|update| Action::ModulationDepth(update).into())
                 .on_tooltip_request(
                     |info| Action::ShowParamTooltip(info).into(),
                     Align2::TOP_CENTER,
@@ -62,6 +80,7 @@ impl Elements {
 
             knob_1: Knob::builder("knob_1")
                 .on_gesture(|param_update| Action::ParamUpdate(param_update).into())
+                .formatter(PercentFormatter::default().bipolar(true))
                 .on_open_text_entry(|info| Action::OpenTextInput(info).into())
                 .on_tooltip_request(
                     |info| Action::ShowParamTooltip(info).into(),
@@ -76,6 +95,9 @@ impl Elements {
             knob_2: Knob::builder("knob_2")
                 .class(MyStyle::CLASS_KNOB_2)
                 .on_gesture(|param_update| Action::ParamUpdate(param_update).into())
+                .formatter(EnumFormatter::new([
+                    "Sine", "Triangle", "Saw", "Square", "Noise",
+                ]))
                 .on_open_text_entry(|info| Action::OpenTextInput(info).into())
                 .on_tooltip_request(
                     |info| Action::ShowParamTooltip(info).into(),
@@ -87,6 +109,7 @@ impl Elements {
 
             slider_3: Slider::builder("slider_3")
                 .on_gesture(|param_update| Action::ParamUpdate(param_update).into())
+                .formatter(FrequencyFormatter::new(20.0, 20_000.0))
                 .on_open_text_entry(|info| Action::OpenTextInput(info).into())
                 .on_tooltip_request(
                     |info| Action::ShowParamTooltip(info).into(),
@@ -96,6 +119,7 @@ impl Elements {
 
             slider_4: Slider::builder("slider_4")
                 .on_gesture(|param_update| Action::ParamUpdate(param_update).into())
+                .formatter(PercentFormatter::default().bipolar(true))
                 .on_open_text_entry(|info| Action::OpenTextInput(info).into())
                 .on_tooltip_request(
                     |info| Action::ShowParamTooltip(info).into(),
@@ -108,7 +132,8 @@ impl Elements {
 
             slider_5: Slider::builder("slider_5")
                 .on_gesture(|param_update| Action::ParamUpdate(param_update).into())
-                .on_open_text_entry(|info| Action::OpenTextInput(info).into())
+                .formatter(LinearFormatter::new(0.0, 1000.0, "ms").decimals(0))
+                .inline_text_entry(true)
                 .on_tooltip_request(
                     |info| Action::ShowParamTooltip(info).into(),
                     Align2::TOP_CENTER,
@@ -119,6 +144,7 @@ impl Elements {
 
             slider_6: Slider::builder("slider_6")
                 .on_gesture(|param_update| Action::ParamUpdate(param_update).into())
+                .formatter(PercentFormatter::default().bipolar(true))
                 .on_open_text_entry(|info| Action::OpenTextInput(info).into())
                 .on_tooltip_request(
                     |info| Action::ShowParamTooltip(info).into(),
@@ -131,13 +157,127 @@ impl Elements {
                 .normal_value(0.5)
                 .build(window_cx),
 
+            number_field_7: NumberField::builder("number_field_7")
+                .on_gesture(|param_update| Action::ParamUpdate(param_update).into())
+                .format(
+                    NumberFieldFormat::new(20.0, 20_000.0, "Hz")
+                        .decimals(1)
+                        .kilo_prefix(true),
+                )
+                .normal_value(0.5)
+                .default_normal(0.5)
+                .build(window_cx),
+
+            meter_8: Meter::builder()
+                .num_channels(2)
+                .ticks([0.0, -12.0, -24.0, -48.0])
+                .build(window_cx),
+            meter_8_phase: 0.0,
+
+            waveform_9: Waveform::builder()
+                .peaks(Rc::new(test_waveform_peaks()))
+                .loop_region(Some(12_000.0..36_000.0))
+                .playhead(Some(12_000.0))
+                .build(window_cx),
+
+            spectrum_10: Spectrum::builder()
+                .grid_db([-24.0, -48.0, -72.0])
+                .build(window_cx),
+            spectrum_10_magnitudes: vec![0.0; 1025],
+
+            eq_curve_11: EqCurve::builder()
+                .on_gesture(|param_update| Action::ParamUpdate(param_update).into())
+                .bands([
+                    EqBand::new(
+                        EqFilterType::LowShelf,
+                        EqBandParam::new("eq_low_freq", 0.2),
+                        EqBandParam::new("eq_low_gain", 0.6),
+                        EqBandParam::new("eq_low_q", 0.35),
+                    ),
+                    EqBand::new(
+                        EqFilterType::Peak,
+                        EqBandParam::new("eq_mid_freq", 0.5),
+                        EqBandParam::new("eq_mid_gain", 0.35),
+                        EqBandParam::new("eq_mid_q", 0.5),
+                    ),
+                    EqBand::new(
+                        EqFilterType::HighShelf,
+                        EqBandParam::new("eq_high_freq", 0.8),
+                        EqBandParam::new("eq_high_gain", 0.55),
+                        EqBandParam::new("eq_high_q", 0.35),
+                    ),
+                ])
+                .build(window_cx),
+
+            envelope_12: EnvelopeEditor::builder()
+                .points([
+                    EnvelopePoint::new(0.0, 0.0).curvature(-0.4),
+                    EnvelopePoint::new(0.1, 1.0).curvature(0.5),
+                    EnvelopePoint::new(0.35, 0.6),
+                    EnvelopePoint::new(0.75, 0.6).curvature(0.6),
+                    EnvelopePoint::new(1.0, 0.0),
+                ])
+                .sustain_point(Some(3))
+                .loop_points(Some((1, 3)))
+                .build(window_cx),
+
             separator: Separator::builder().build(window_cx),
 
             scroll_area,
             floating_text_input,
 
             text_input_param_id: None,
-        })
+        });
+
+        elements.knob_0.set_automation_info(AutomationInfo {
+            modulation: vec![
+                ModulationSource::new("lfo_1", 0.3).bipolar(true),
+                ModulationSource::new("env_1", -0.2),
+            ],
+            selected_modulation: Some(0),
+            ..Default::default()
+        });
+
+        elements
+    }
+
+    /// Feed the meter with a made-up signal.
+    pub fn on_tick(&mut self, dt: f64) {
+        self.meter_8_phase += dt;
+
+        let level = |phase: f64| {
+            let peak_db = (-24.0 + (phase * 1.3).sin() * 16.0 + (phase * 7.1).sin() * 6.0) as f32;
+            MeterLevel::with_rms(peak_db, peak_db - 8.0)
+        };
+
+        self.meter_8.update(
+            &[level(self.meter_8_phase), level(self.meter_8_phase + 0.4)],
+            dt,
+        );
+
+        let loop_region = self.waveform_9.loop_region().clone();
+        if let (Some(playhead), Some(loop_region)) = (self.waveform_9.playhead(), loop_region) {
+            let mut playhead = playhead + (dt * 44_100.0);
+            if playhead >= loop_region.end || playhead < loop_region.start {
+                playhead = loop_region.start;
+            }
+
+            self.waveform_9.set_playhead(Some(playhead));
+        }
+
+        // A falling noise floor with a resonant peak that sweeps back and forth.
+        let sweep_hz = 1_000.0 * 8.0f64.powf((self.meter_8_phase * 0.4).sin());
+        for (i, magnitude) in self.spectrum_10_magnitudes.iter_mut().enumerate() {
+            let hz = i.max(1) as f64 * 44_100.0 / 2_048.0;
+            let floor = 0.02 / (hz / 100.0).sqrt().max(1.0);
+            let resonance = 0.3 / (1.0 + ((hz / sweep_hz).log2() * 6.0).powi(2));
+            let jitter = 0.75 + 0.25 * (i as f64 * 12.9898 + self.meter_8_phase * 78.233).sin();
+
+            *magnitude = ((floor + resonance) * jitter) as f32;
+        }
+
+        self.spectrum_10
+            .update(&self.spectrum_10_magnitudes, 44_100.0, dt);
     }
 
     /// Returns `true` if the the contents need to be laid out.
@@ -161,10 +301,7 @@ impl Elements {
             Action::ShowParamTooltip(info) => self.show_param_tooltip(&info.param_info, window_cx),
             Action::OpenTextInput(info) => {
                 self.text_input_param_id = Some(info.param_info.id.clone());
-                let text = match info.param_info.value() {
-                    ParamValue::Normal(n) => format!("{:.4}", n),
-                    ParamValue::Stepped(s) => format!("{}", s),
-                };
+                let text = info.text.unwrap_or_default();
 
                 self.floating_text_input.show(
                     Some(&text),
@@ -179,56 +316,45 @@ impl Elements {
                 if let Some(param_id) = self.text_input_param_id.take() {
                     if let Some(new_text) = new_text {
                         match param_id.as_str() {
-                            "knob_0" => {
-                                if let Ok(v) = new_text.parse::<f64>() {
-                                    self.knob_0.set_normal_value(v);
-                                }
-                            }
-                            "knob_1" => {
-                                if let Ok(v) = new_text.parse::<f64>() {
-                                    self.knob_1.set_normal_value(v);
-                                }
-                            }
-                            "knob_2" => {
-                                if let Ok(v) = new_text.parse::<u32>() {
-                                    self.knob_2.set_stepped_value(v);
-                                }
-                            }
-                            "slider_3" => {
-                                if let Ok(v) = new_text.parse::<f64>() {
-                                    self.slider_3.set_normal_value(v);
-                                }
-                            }
-                            "slider_4" => {
-                                if let Ok(v) = new_text.parse::<f64>() {
-                                    self.slider_4.set_normal_value(v);
-                                }
-                            }
-                            "slider_5" => {
-                                if let Ok(v) = new_text.parse::<f64>() {
-                                    self.slider_5.set_normal_value(v);
-                                }
-                            }
-                            "slider_6" => {
-                                if let Ok(v) = new_text.parse::<f64>() {
-                                    self.slider_6.set_normal_value(v);
-                                }
-                            }
-                            _ => {}
-                        }
+                            "knob_0" => self.knob_0.set_value_from_text(&new_text),
+                            "knob_1" => self.knob_1.set_value_from_text(&new_text),
+                            "knob_2" => self.knob_2.set_value_from_text(&new_text),
+                            "slider_3" => self.slider_3.set_value_from_text(&new_text),
+                            "slider_4" => self.slider_4.set_value_from_text(&new_text),
+                            "slider_5" => self.slider_5.set_value_from_text(&new_text),
+                            "slider_6" => self.slider_6.set_value_from_text(&new_text),
+                            _ => false,
+                        };
                     }
                 }
             }
+            Action::ModulationDepth(update) => {
+                // A real application would send the new depth to its engine
+                // here.
+                log::debug!(
+                    "{}: {} depth = {:.2}",
+                    update.param_id,
+                    update.source_id,
+                    update.depth
+                );
+            }
         }
 
         needs_layout
     }
 
     fn show_param_tooltip(&mut self, param_info: &ParamInfo, window_cx: &WindowContext<MyAction>) {
-        let get_text = || match param_info.value() {
-            ParamValue::Normal(n) => format!("{:.4}", n),
-            ParamValue::Stepped(s) => format!("{}", s),
+        let text = match param_info.id.as_str() {
+            "knob_0" => self.knob_0.formatted_value(),
+            "knob_1" => self.knob_1.formatted_value(),
+            "knob_2" => self.knob_2.formatted_value(),
+            "slider_3" => self.slider_3.formatted_value(),
+            "slider_4" => self.slider_4.formatted_value(),
+            "slider_5" => self.slider_5.formatted_value(),
+            "slider_6" => self.slider_6.formatted_value(),
+            _ => return,
         };
+        let get_text = || text.unwrap_or_default();
 
         match param_info.id.as_str() {
             "knob_0" => self
@@ -252,7 +378,7 @@ impl Elements {
             "slider_6" => self
                 .slider_6
                 .show_tooltip(get_text, Align2::TOP_CENTER, window_cx),
-            _ => return,
+            _ => {}
         };
     }
 
@@ -346,9 +472,51 @@ impl Elements {
             22.0,
         ));
 
+        self.number_field_7.set_rect(rect(
+            self.slider_6.min_x(),
+            self.slider_6.max_y() + style.element_padding,
+            100.0,
+            style.text_input_size.height,
+        ));
+
+        self.meter_8.set_rect(rect(
+            self.slider_5.max_x() + style.param_spacing,
+            self.slider_3.min_y(),
+            50.0,
+            100.0,
+        ));
+
+        self.waveform_9.set_rect(rect(
+            start_pos.x,
+            self.number_field_7.max_y() + style.element_padding,
+            self.meter_8.max_x() - start_pos.x,
+            80.0,
+        ));
+
+        self.spectrum_10.set_rect(rect(
+            start_pos.x,
+            self.waveform_9.max_y() + style.element_padding,
+            self.waveform_9.width(),
+            100.0,
+        ));
+
+        self.eq_curve_11.set_rect(rect(
+            start_pos.x,
+            self.spectrum_10.max_y() + style.element_padding,
+            self.waveform_9.width(),
+            120.0,
+        ));
+
+        self.envelope_12.set_rect(rect(
+            start_pos.x,
+            self.eq_curve_11.max_y() + style.element_padding,
+            self.waveform_9.width(),
+            120.0,
+        ));
+
         self.scroll_area.set_content_size(size(
-            self.slider_6.max_x() + style.content_padding,
-            self.slider_4.max_y() + style.content_padding,
+            self.meter_8.max_x() + style.content_padding,
+            self.envelope_12.max_y() + style.content_padding,
         ));
     }
 
@@ -365,6 +533,14 @@ impl Elements {
             slider_4,
             slider_5,
             slider_6,
+            number_field_7,
+            meter_8,
+            meter_8_phase: _,
+            waveform_9,
+            spectrum_10,
+            spectrum_10_magnitudes: _,
+            eq_curve_11,
+            envelope_12,
             scroll_area,
             floating_text_input,
             separator,
@@ -381,8 +557,37 @@ impl Elements {
         slider_4.set_hidden(hidden);
         slider_5.set_hidden(hidden);
         slider_6.set_hidden(hidden);
+        number_field_7.set_hidden(hidden);
+        meter_8.set_hidden(hidden);
+        waveform_9.set_hidden(hidden);
+        spectrum_10.set_hidden(hidden);
+        eq_curve_11.set_hidden(hidden);
+        envelope_12.set_hidden(hidden);
         scroll_area.set_hidden(hidden);
         separator.set_hidden(hidden);
         floating_text_input.hide();
     }
 }
+
+/// Two seconds of a repeating, slightly detuned chord in stereo.
+fn test_waveform_peaks() -> WaveformPeaks {
+    const SAMPLE_RATE: f32 = 44_100.0;
+
+    let channel = |detune: f32| -> Vec<f32> {
+        (0..(SAMPLE_RATE as usize * 2))
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE;
+                let envelope = (-(t % 0.5) * 6.0).exp();
+
+                [110.0, 164.8, 220.0]
+                    .iter()
+                    .map(|hz| (t * (hz + detune) * std::f32::consts::TAU).sin())
+                    .sum::<f32>()
+                    * envelope
+                    * 0.3
+            })
+            .collect()
+    };
+
+    WaveformPeaks::new(&[channel(0.0), channel(0.7)])
+}
@@ -14,7 +14,10 @@ use thunderdome::Arena;
 
 use crate::action_queue::ActionSender;
 use crate::clipboard::Clipboard;
-use crate::event::{CanvasEvent, ElementEvent, EventCaptureStatus, KeyboardEvent, PointerEvent};
+use crate::event::{
+    CanvasEvent, Code, ElementEvent, EventCaptureStatus, KeyState, KeyboardEvent, Modifiers,
+    PointerEvent,
+};
 use crate::layout::Align2;
 use crate::math::{Point, PointI32, Rect, RectI32, ScaleFactor, Size, ZIndex};
 use crate::prelude::TooltipData;
@@ -23,10 +26,13 @@ use crate::stmpsc_queue;
 use crate::CursorIcon;
 use crate::WindowID;
 
+mod accessibility;
 mod cache;
 pub mod element;
 mod scissor_rect;
 
+pub use self::accessibility::{AccessAction, AccessNode, AccessRole, AccessValue};
+
 use self::element::ChangeFocusRequest;
 use self::element::RenderContext;
 pub use self::scissor_rect::ScissorRectID;
@@ -101,6 +107,12 @@ pub(crate) struct ElementSystem<A: Clone + 'static> {
     painted_elements: Vec<CachedElementPrimitives>,
     elements_listening_to_clicked_off: FxHashSet<ElementID>,
     element_with_active_tooltip: Option<ActiveTooltipInfo>,
+    /// The elements which can be focused with the Tab key, in the order they
+    /// were added.
+    focus_chain: Vec<ElementID>,
+    /// The elements which receive keyboard events that were not captured by
+    /// the focused element, in the order they were added.
+    elements_listening_to_uncaptured_keys: Vec<ElementID>,
 
     physical_size: PhysicalSizeI32,
     hover_timeout_duration: Duration,
@@ -112,6 +124,12 @@ pub(crate) struct ElementSystem<A: Clone + 'static> {
 
     needs_repaint: bool,
     window_visible: bool,
+    #[cfg(feature = "accesskit")]
+    accessibility_dirty: bool,
+    /// The accessibility node of each element as of the last time it was
+    /// queried, used to detect when the tree needs to be rebuilt.
+    #[cfg(feature = "accesskit")]
+    accessibility_nodes: FxHashMap<ElementID, AccessNode>,
 
     render_caches: FxHashMap<u32, Box<dyn ElementRenderCache>>,
 
@@ -188,6 +206,8 @@ impl<A: Clone + 'static> ElementSystem<A> {
             painted_elements: Vec::new(),
             elements_listening_to_clicked_off: FxHashSet::default(),
             element_with_active_tooltip: None,
+            focus_chain: Vec::new(),
+            elements_listening_to_uncaptured_keys: Vec::new(),
 
             physical_size,
             hover_timeout_duration,
@@ -196,6 +216,10 @@ impl<A: Clone + 'static> ElementSystem<A> {
 
             needs_repaint: true,
             window_visible: true,
+            #[cfg(feature = "accesskit")]
+            accessibility_dirty: true,
+            #[cfg(feature = "accesskit")]
+            accessibility_nodes: FxHashMap::default(),
 
             show_tooltip_action: None,
             hide_tooltip_action: None,
@@ -374,12 +398,27 @@ impl<A: Clone + 'static> ElementSystem<A> {
             }
         }
 
+        let focusable = flags.contains(ElementFlags::FOCUSABLE);
+        if focusable {
+            self.focus_chain.push(element_id);
+        }
+
+        if flags.contains(ElementFlags::LISTENS_TO_UNCAPTURED_KEYS) {
+            self.elements_listening_to_uncaptured_keys.push(element_id);
+        }
+
+        #[cfg(feature = "accesskit")]
+        {
+            self.accessibility_dirty = true;
+        }
+
         self::element::new_element_handle(
             element_id,
             self.context.mod_queue_sender.clone(),
             rect,
             z_index,
             manually_hidden,
+            focusable,
             class,
         )
     }
@@ -440,6 +479,11 @@ impl<A: Clone + 'static> ElementSystem<A> {
         );
 
         self.needs_repaint = true;
+
+        #[cfg(feature = "accesskit")]
+        {
+            self.accessibility_dirty = true;
+        }
     }
 
     pub fn on_theme_changed(&mut self, res: &mut ResourceCtx, clipboard: &mut Clipboard) {
@@ -1042,9 +1086,147 @@ impl<A: Clone + 'static> ElementSystem<A> {
             }
         }
 
+        let focused_id = self
+            .context
+            .current_focus_info
+            .as_ref()
+            .map(|info| info.element_id);
+        for element_id in self.elements_listening_to_uncaptured_keys.iter() {
+            if Some(*element_id) == focused_id {
+                continue;
+            }
+
+            let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+                continue;
+            };
+
+            if element_entry.stack_data.visible_rect.is_none() {
+                continue;
+            }
+
+            let capture_status = send_event_to_element(
+                ElementEvent::Keyboard(event.clone()),
+                element_entry,
+                *element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
+
+            if let EventCaptureStatus::Captured = capture_status {
+                return EventCaptureStatus::Captured;
+            }
+        }
+
+        if event.state == KeyState::Down
+            && event.code == Code::Tab
+            && !event
+                .modifiers
+                .intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::META)
+        {
+            let reverse = event.modifiers.contains(Modifiers::SHIFT);
+            if self.cycle_focus(reverse, res, clipboard) {
+                return EventCaptureStatus::Captured;
+            }
+        }
+
         EventCaptureStatus::NotCaptured
     }
 
+    /// Give focus to the next (or previous if `reverse` is `true`) element in the
+    /// focus chain.
+    ///
+    /// Returns `true` if the focus has changed.
+    fn cycle_focus(
+        &mut self,
+        reverse: bool,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) -> bool {
+        if self.focus_chain.is_empty() {
+            return false;
+        }
+
+        let current_index = self.context.current_focus_info.and_then(|info| {
+            self.focus_chain
+                .iter()
+                .position(|id| *id == info.element_id)
+        });
+
+        let len = self.focus_chain.len();
+        let mut index = match (current_index, reverse) {
+            (Some(i), false) => (i + 1) % len,
+            (Some(i), true) => (i + len - 1) % len,
+            (None, false) => 0,
+            (None, true) => len - 1,
+        };
+
+        for _ in 0..len {
+            let element_id = self.focus_chain[index];
+
+            if Some(index) == current_index {
+                // We have wrapped around to the currently focused element.
+                return false;
+            }
+
+            if let Some(element_entry) = self.element_arena.get(element_id.0) {
+                if element_entry
+                    .stack_data
+                    .flags
+                    .contains(ElementFlags::FOCUSABLE)
+                    && !element_entry.stack_data.manually_hidden
+                    && element_entry.stack_data.visible()
+                {
+                    self.element_steal_focus(element_id, false, res, clipboard);
+                    return true;
+                }
+            }
+
+            index = if reverse {
+                (index + len - 1) % len
+            } else {
+                (index + 1) % len
+            };
+        }
+
+        false
+    }
+
+    fn update_element_focusable(
+        &mut self,
+        element_id: ElementID,
+        focusable: bool,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        if focusable {
+            element_entry
+                .stack_data
+                .flags
+                .insert(ElementFlags::FOCUSABLE);
+
+            if !self.focus_chain.contains(&element_id) {
+                self.focus_chain.push(element_id);
+            }
+        } else {
+            element_entry
+                .stack_data
+                .flags
+                .remove(ElementFlags::FOCUSABLE);
+
+            if let Some(focus_info) = &self.context.current_focus_info {
+                if focus_info.element_id == element_id {
+                    self.element_release_focus(element_id, res, clipboard);
+                }
+            }
+        }
+    }
+
     fn handle_text_composition_event(
         &mut self,
         event: &CompositionEvent,
@@ -1088,12 +1270,23 @@ impl<A: Clone + 'static> ElementSystem<A> {
                         res,
                         clipboard,
                     );
+
+                    #[cfg(feature = "accesskit")]
+                    self.refresh_accessibility_node(modification.element_id);
                 }
                 ElementModificationType::MarkDirty => {
                     self.mark_element_dirty(modification.element_id);
+
+                    #[cfg(feature = "accesskit")]
+                    self.refresh_accessibility_node(modification.element_id);
                 }
                 ElementModificationType::RectChanged(new_rect) => {
                     self.update_element_rect(modification.element_id, new_rect, res, clipboard);
+
+                    #[cfg(feature = "accesskit")]
+                    {
+                        self.accessibility_dirty = true;
+                    }
                 }
                 ElementModificationType::ScissorRectChanged => {
                     self.handle_scissor_rect_changed_for_element(
@@ -1101,6 +1294,11 @@ impl<A: Clone + 'static> ElementSystem<A> {
                         res,
                         clipboard,
                     );
+
+                    #[cfg(feature = "accesskit")]
+                    {
+                        self.accessibility_dirty = true;
+                    }
                 }
                 ElementModificationType::ZIndexChanged(new_z_index) => {
                     self.update_element_z_index(
@@ -1117,6 +1315,24 @@ impl<A: Clone + 'static> ElementSystem<A> {
                         res,
                         clipboard,
                     );
+
+                    #[cfg(feature = "accesskit")]
+                    {
+                        self.accessibility_dirty = true;
+                    }
+                }
+                ElementModificationType::FocusableChanged(focusable) => {
+                    self.update_element_focusable(
+                        modification.element_id,
+                        focusable,
+                        res,
+                        clipboard,
+                    );
+
+                    #[cfg(feature = "accesskit")]
+                    {
+                        self.accessibility_dirty = true;
+                    }
                 }
                 ElementModificationType::ClassChanged(new_class) => {
                     self.handle_element_class_changed(
@@ -1142,6 +1358,15 @@ impl<A: Clone + 'static> ElementSystem<A> {
                 },
                 ElementModificationType::HandleDropped => {
                     self.drop_element(modification.element_id, res, clipboard);
+
+                    #[cfg(feature = "accesskit")]
+                    if self
+                        .accessibility_nodes
+                        .remove(&modification.element_id)
+                        .is_some()
+                    {
+                        self.accessibility_dirty = true;
+                    }
                 }
                 ElementModificationType::ListenToClickOff => {
                     self.handle_element_listen_to_click_off(modification.element_id);
@@ -1168,6 +1393,37 @@ impl<A: Clone + 'static> ElementSystem<A> {
         processed_update
     }
 
+    /// Query the accessibility node of the given element again, and mark the
+    /// accessibility tree as dirty if its label, value, or disabled state has
+    /// changed.
+    ///
+    /// This is called whenever an element repaints or its custom state
+    /// changes, so that redraws which don't change anything (i.e. animations)
+    /// don't cause the whole tree to be rebuilt.
+    #[cfg(feature = "accesskit")]
+    fn refresh_accessibility_node(&mut self, element_id: ElementID) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        let node = element_entry.element.accessibility_node();
+        if self.accessibility_nodes.get(&element_id) == node.as_ref() {
+            return;
+        }
+
+        match node {
+            Some(node) => {
+                self.accessibility_nodes.insert(element_id, node);
+            }
+            None => {
+                self.accessibility_nodes.remove(&element_id);
+            }
+        }
+
+        self.accessibility_dirty = true;
+    }
+
     fn handle_element_listen_to_click_off(&mut self, element_id: ElementID) {
         if self.element_arena.contains(element_id.0) {
             self.elements_listening_to_clicked_off.insert(element_id);
@@ -1668,6 +1924,11 @@ impl<A: Clone + 'static> ElementSystem<A> {
                 .contains(ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED),
         });
 
+        #[cfg(feature = "accesskit")]
+        {
+            self.accessibility_dirty = true;
+        }
+
         self.context.prev_element_with_exclusive_focus = if is_temporary {
             prev_element_with_exclusive_focus
         } else {
@@ -1701,6 +1962,11 @@ impl<A: Clone + 'static> ElementSystem<A> {
             return;
         };
 
+        #[cfg(feature = "accesskit")]
+        {
+            self.accessibility_dirty = true;
+        }
+
         release_focus_for_element(element_id, element_entry, &mut self.context, res, clipboard);
     }
 
@@ -1721,6 +1987,17 @@ impl<A: Clone + 'static> ElementSystem<A> {
             return;
         };
 
+        if let Some(i) = self.focus_chain.iter().position(|id| *id == element_id) {
+            self.focus_chain.remove(i);
+        }
+        if let Some(i) = self
+            .elements_listening_to_uncaptured_keys
+            .iter()
+            .position(|id| *id == element_id)
+        {
+            self.elements_listening_to_uncaptured_keys.remove(i);
+        }
+
         release_focus_for_element(
             element_id,
             &mut element_entry,
@@ -1855,6 +2132,83 @@ impl<A: Clone + 'static> ElementSystem<A> {
         let mut encoder =
             device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
 
+        self.encode_frame(
+            &view,
+            &mut encoder,
+            device,
+            queue,
+            #[cfg(feature = "custom-shaders")]
+            texture_format,
+            #[cfg(feature = "custom-shaders")]
+            multisample,
+            vg,
+            res,
+        );
+
+        pre_present_notify();
+
+        // Submit the commands and present the frame.
+        queue.submit(Some(encoder.finish()));
+        frame.present();
+
+        self.needs_repaint = false;
+
+        Ok(())
+    }
+
+    /// Render the elements into an offscreen texture instead of a surface.
+    ///
+    /// Returns `true` if anything was rendered, or `false` if the contents of
+    /// the texture are already up-to-date.
+    #[cfg(feature = "headless")]
+    pub fn render_offscreen(
+        &mut self,
+        view: &wgpu::TextureView,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        #[cfg(feature = "custom-shaders")] texture_format: wgpu::TextureFormat,
+        #[cfg(feature = "custom-shaders")] multisample: wgpu::MultisampleState,
+        vg: &mut rootvg::Canvas,
+        res: &mut ResourceCtx,
+    ) -> bool {
+        if !self.needs_repaint {
+            return false;
+        }
+
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        self.encode_frame(
+            view,
+            &mut encoder,
+            device,
+            queue,
+            #[cfg(feature = "custom-shaders")]
+            texture_format,
+            #[cfg(feature = "custom-shaders")]
+            multisample,
+            vg,
+            res,
+        );
+
+        queue.submit(Some(encoder.finish()));
+
+        self.needs_repaint = false;
+
+        true
+    }
+
+    fn encode_frame(
+        &mut self,
+        view: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        #[cfg(feature = "custom-shaders")] texture_format: wgpu::TextureFormat,
+        #[cfg(feature = "custom-shaders")] multisample: wgpu::MultisampleState,
+        vg: &mut rootvg::Canvas,
+        res: &mut ResourceCtx,
+    ) {
         for render_cache in self.render_caches.values_mut() {
             render_cache.pre_render();
         }
@@ -1922,8 +2276,8 @@ impl<A: Clone + 'static> ElementSystem<A> {
             Some(self.clear_color),
             device,
             queue,
-            &mut encoder,
-            &view,
+            encoder,
+            view,
             self.physical_size,
             &mut res.font_system,
             #[cfg(feature = "svg-icons")]
@@ -1934,16 +2288,6 @@ impl<A: Clone + 'static> ElementSystem<A> {
         for render_cache in self.render_caches.values_mut() {
             render_cache.post_render();
         }
-
-        pre_present_notify();
-
-        // Submit the commands and present the frame.
-        queue.submit(Some(encoder.finish()));
-        frame.present();
-
-        self.needs_repaint = false;
-
-        Ok(())
     }
 
     pub fn cursor_icon(&self) -> CursorIcon {
@@ -2201,3 +2545,100 @@ fn release_focus_for_element<A: Clone + 'static>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        Pressed,
+        OtherPressed,
+        Toggled(bool),
+    }
+
+    struct FocusTestApp {
+        _button: Button,
+        _disabled_button: Button,
+        _switch: Switch,
+        other_button: Button,
+    }
+
+    impl Application for FocusTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let mut window_cx = cx.main_window();
+
+            let _button = Button::builder()
+                .text("First")
+                .on_select(TestAction::Pressed)
+                .rect(rect(10.0, 10.0, 100.0, 30.0))
+                .build(&mut window_cx);
+            let _disabled_button = Button::builder()
+                .text("Disabled")
+                .on_select(TestAction::Pressed)
+                .disabled(true)
+                .rect(rect(10.0, 50.0, 100.0, 30.0))
+                .build(&mut window_cx);
+            let _switch = Switch::builder()
+                .on_toggled(TestAction::Toggled)
+                .rect(rect(10.0, 90.0, 40.0, 20.0))
+                .build(&mut window_cx);
+            let other_button = Button::builder()
+                .text("Other")
+                .on_select(TestAction::OtherPressed)
+                .rect(rect(10.0, 120.0, 100.0, 30.0))
+                .build(&mut window_cx);
+
+            Ok(Self {
+                _button,
+                _disabled_button,
+                _switch,
+                other_button,
+            })
+        }
+    }
+
+    #[test]
+    fn test_tab_focus_traversal() {
+        let mut app = HeadlessApp::<FocusTestApp>::new(AppConfig::default()).unwrap();
+
+        // Nothing has focus yet, so activation keys do nothing.
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        assert!(app.drain_actions().is_empty());
+
+        app.key_press(MAIN_WINDOW, Code::Tab, Modifiers::empty());
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        assert_eq!(app.drain_actions(), vec![TestAction::Pressed]);
+
+        // The disabled button is skipped.
+        app.key_press(MAIN_WINDOW, Code::Tab, Modifiers::empty());
+        app.key_press(MAIN_WINDOW, Code::Space, Modifiers::empty());
+        assert_eq!(app.drain_actions(), vec![TestAction::Toggled(true)]);
+
+        app.key_press(MAIN_WINDOW, Code::Tab, Modifiers::empty());
+        app.key_press(MAIN_WINDOW, Code::Space, Modifiers::empty());
+        assert_eq!(app.drain_actions(), vec![TestAction::OtherPressed]);
+
+        // Wraps around to the start.
+        app.key_press(MAIN_WINDOW, Code::Tab, Modifiers::empty());
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        assert_eq!(app.drain_actions(), vec![TestAction::Pressed]);
+
+        // Shift+Tab goes backwards and wraps around to the end.
+        app.key_press(MAIN_WINDOW, Code::Tab, Modifiers::SHIFT);
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        assert_eq!(app.drain_actions(), vec![TestAction::OtherPressed]);
+
+        // Disabling the focused element removes it from the focus chain.
+        app.app_mut().other_button.set_disabled(true);
+        app.process_updates();
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        assert!(app.drain_actions().is_empty());
+
+        app.key_press(MAIN_WINDOW, Code::Tab, Modifiers::SHIFT);
+        app.key_press(MAIN_WINDOW, Code::Space, Modifiers::empty());
+        assert_eq!(app.drain_actions(), vec![TestAction::Toggled(false)]);
+    }
+}
@@ -0,0 +1,478 @@
+/// The semantic role of an element, as reported to assistive technologies
+/// such as screen readers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessRole {
+    Button,
+    /// A button which has an on/off state.
+    ToggleButton,
+    CheckBox,
+    Switch,
+    RadioButton,
+    Tab,
+    /// A control which holds a value in a range, such as a knob or a slider.
+    Slider,
+    TextInput,
+    /// Static, non-interactive text.
+    Label,
+    ComboBox,
+    Group,
+}
+
+impl AccessRole {
+    /// Returns `true` if elements with this role can be activated with a
+    /// click action.
+    pub fn is_clickable(&self) -> bool {
+        matches!(
+            self,
+            Self::Button
+                | Self::ToggleButton
+                | Self::CheckBox
+                | Self::Switch
+                | Self::RadioButton
+                | Self::Tab
+                | Self::ComboBox
+        )
+    }
+}
+
+/// The current value of an element, as reported to assistive technologies.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub enum AccessValue {
+    /// The element has no value.
+    #[default]
+    None,
+    /// The element is either on or off.
+    Toggled(bool),
+    /// The element has a numeric value in the given range.
+    Range {
+        value: f64,
+        min: f64,
+        max: f64,
+        /// The amount the value changes by in a single step, or `None` if
+        /// the value is continuous.
+        step: Option<f64>,
+    },
+    /// The element has a text value.
+    Text(String),
+}
+
+/// A description of an element in the accessibility tree.
+///
+/// Elements describe themselves by returning this from
+/// [`Element::accessibility_node`](super::element::Element::accessibility_node).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessNode {
+    pub role: AccessRole,
+    /// The name of the element which is read out by assistive technologies.
+    pub label: Option<String>,
+    pub value: AccessValue,
+    /// A human-readable version of a [`AccessValue::Range`] value, i.e.
+    /// "-6.0 dB".
+    pub value_text: Option<String>,
+    pub disabled: bool,
+}
+
+impl AccessNode {
+    pub fn new(role: AccessRole) -> Self {
+        Self {
+            role,
+            label: None,
+            value: AccessValue::None,
+            value_text: None,
+            disabled: false,
+        }
+    }
+
+    pub fn label(mut self, label: Option<impl Into<String>>) -> Self {
+        self.label = label.map(|l| l.into());
+        self
+    }
+
+    pub fn value(mut self, value: AccessValue) -> Self {
+        self.value = value;
+        self
+    }
+
+    pub fn value_text(mut self, value_text: Option<impl Into<String>>) -> Self {
+        self.value_text = value_text.map(|t| t.into());
+        self
+    }
+
+    pub const fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// An action requested by an assistive technology, sent to elements via
+/// `ElementEvent::AccessAction`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccessAction {
+    /// Activate the element, as if it were clicked.
+    Click,
+    /// Increase the value of the element by one step.
+    Increment,
+    /// Decrease the value of the element by one step.
+    Decrement,
+    /// Set the value of the element. For elements with an
+    /// [`AccessValue::Range`] value, this is in the same range.
+    SetValue(f64),
+}
+
+#[cfg(feature = "accesskit")]
+mod adapter {
+    use accesskit::{
+        Action, ActionData, ActionRequest, Affine, NodeBuilder, NodeId, Role, Toggled, Tree,
+        TreeUpdate,
+    };
+
+    use super::{AccessAction, AccessRole, AccessValue};
+    use crate::clipboard::Clipboard;
+    use crate::element_system::element::{ElementFlags, ElementID};
+    use crate::element_system::{send_event_to_element, ElementSystem};
+    use crate::event::ElementEvent;
+    use crate::prelude::ResourceCtx;
+
+    /// The ID of the root (window) node in the accessibility tree. Element
+    /// nodes use the bits of their arena index, which are never zero.
+    const ROOT_ID: NodeId = NodeId(0);
+
+    impl<A: Clone + 'static> ElementSystem<A> {
+        /// Whether or not the accessibility tree has changed since the last
+        /// call to [`ElementSystem::accessibility_tree_update`].
+        pub fn accessibility_needs_update(&self) -> bool {
+            self.accessibility_dirty
+        }
+
+        /// Build a full update of the accessibility tree.
+        pub fn accessibility_tree_update(&mut self) -> TreeUpdate {
+            self.accessibility_dirty = false;
+
+            let mut entries = Vec::new();
+            let mut focus = ROOT_ID;
+            let focused_id = self
+                .context
+                .current_focus_info
+                .as_ref()
+                .map(|info| info.element_id);
+
+            for (index, entry) in self.element_arena.iter_mut() {
+                let Some(visible_rect) = entry.stack_data.visible_rect else {
+                    continue;
+                };
+
+                let Some(access_node) = entry.element.accessibility_node() else {
+                    continue;
+                };
+
+                let element_id = ElementID(index);
+                self.accessibility_nodes
+                    .insert(element_id, access_node.clone());
+
+                let node_id = NodeId(index.to_bits());
+                if focused_id == Some(element_id) {
+                    focus = node_id;
+                }
+
+                let mut builder = NodeBuilder::new(role_to_accesskit(access_node.role));
+
+                if let Some(label) = access_node.label {
+                    builder.set_name(label);
+                }
+
+                match access_node.value {
+                    AccessValue::None => {}
+                    AccessValue::Toggled(selected) if access_node.role == AccessRole::Tab => {
+                        builder.set_selected(selected);
+                    }
+                    AccessValue::Toggled(toggled) => {
+                        builder.set_toggled(if toggled {
+                            Toggled::True
+                        } else {
+                            Toggled::False
+                        });
+                    }
+                    AccessValue::Range {
+                        value,
+                        min,
+                        max,
+                        step,
+                    } => {
+                        builder.set_numeric_value(value);
+                        builder.set_min_numeric_value(min);
+                        builder.set_max_numeric_value(max);
+                        if let Some(step) = step {
+                            builder.set_numeric_value_step(step);
+                        }
+                        if let Some(value_text) = access_node.value_text {
+                            builder.set_value(value_text);
+                        }
+                    }
+                    AccessValue::Text(text) => {
+                        builder.set_value(text);
+                    }
+                }
+
+                builder.set_bounds(accesskit::Rect::new(
+                    visible_rect.min_x() as f64,
+                    visible_rect.min_y() as f64,
+                    visible_rect.max_x() as f64,
+                    visible_rect.max_y() as f64,
+                ));
+
+                if access_node.disabled {
+                    builder.set_disabled();
+                } else {
+                    if entry.stack_data.flags.contains(ElementFlags::FOCUSABLE) {
+                        builder.add_action(Action::Focus);
+                    }
+                    if access_node.role.is_clickable() {
+                        builder.add_action(Action::Default);
+                    }
+                    if access_node.role == AccessRole::Slider {
+                        builder.add_action(Action::Increment);
+                        builder.add_action(Action::Decrement);
+                        builder.add_action(Action::SetValue);
+                    }
+                }
+
+                entries.push((visible_rect.origin, node_id, builder.build()));
+            }
+
+            // Report elements in reading order.
+            entries.sort_by(|(a, _, _), (b, _, _)| {
+                a.y.total_cmp(&b.y).then_with(|| a.x.total_cmp(&b.x))
+            });
+
+            let mut root = NodeBuilder::new(Role::Window);
+            root.set_children(entries.iter().map(|(_, id, _)| *id).collect::<Vec<_>>());
+            // Element rects are in logical coordinates, while AccessKit expects
+            // physical coordinates.
+            root.set_transform(Affine::scale(self.context.scale_factor.0 as f64));
+
+            let mut nodes = Vec::with_capacity(entries.len() + 1);
+            nodes.push((ROOT_ID, root.build()));
+            nodes.extend(entries.into_iter().map(|(_, id, node)| (id, node)));
+
+            TreeUpdate {
+                nodes,
+                tree: Some(Tree::new(ROOT_ID)),
+                focus,
+            }
+        }
+
+        /// Route an action requested by an assistive technology to its
+        /// target element.
+        pub fn handle_accessibility_action(
+            &mut self,
+            request: ActionRequest,
+            res: &mut ResourceCtx,
+            clipboard: &mut Clipboard,
+        ) {
+            let Some(index) = thunderdome::Index::from_bits(request.target.0) else {
+                return;
+            };
+            let element_id = ElementID(index);
+
+            let access_action = match request.action {
+                Action::Focus => {
+                    self.element_steal_focus(element_id, false, res, clipboard);
+                    return;
+                }
+                Action::Default => AccessAction::Click,
+                Action::Increment => AccessAction::Increment,
+                Action::Decrement => AccessAction::Decrement,
+                Action::SetValue => match request.data {
+                    Some(ActionData::NumericValue(value)) => AccessAction::SetValue(value),
+                    Some(ActionData::Value(value)) => match value.trim().parse::<f64>() {
+                        Ok(value) => AccessAction::SetValue(value),
+                        Err(_) => return,
+                    },
+                    _ => return,
+                },
+                _ => return,
+            };
+
+            let Some(element_entry) = self.element_arena.get_mut(index) else {
+                // Element has been dropped. Do nothing and return.
+                return;
+            };
+
+            send_event_to_element(
+                ElementEvent::AccessAction(access_action),
+                element_entry,
+                element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
+        }
+    }
+
+    fn role_to_accesskit(role: AccessRole) -> Role {
+        match role {
+            AccessRole::Button | AccessRole::ToggleButton => Role::Button,
+            AccessRole::CheckBox => Role::CheckBox,
+            AccessRole::Switch => Role::Switch,
+            AccessRole::RadioButton => Role::RadioButton,
+            AccessRole::Tab => Role::Tab,
+            AccessRole::Slider => Role::Slider,
+            AccessRole::TextInput => Role::TextInput,
+            AccessRole::Label => Role::Label,
+            AccessRole::ComboBox => Role::ComboBox,
+            AccessRole::Group => Role::Group,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "accesskit"))]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        Pressed,
+        OtherPressed,
+        Toggled(bool),
+    }
+
+    struct AccessibilityTestApp {
+        _button: Button,
+        _disabled_button: Button,
+        _switch: Switch,
+        _other_button: Button,
+    }
+
+    impl Application for AccessibilityTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let mut window_cx = cx.main_window();
+
+            let _button = Button::builder()
+                .text("First")
+                .on_select(TestAction::Pressed)
+                .rect(rect(10.0, 10.0, 100.0, 30.0))
+                .build(&mut window_cx);
+            let _disabled_button = Button::builder()
+                .text("Disabled")
+                .on_select(TestAction::Pressed)
+                .disabled(true)
+                .rect(rect(10.0, 50.0, 100.0, 30.0))
+                .build(&mut window_cx);
+            let _switch = Switch::builder()
+                .on_toggled(TestAction::Toggled)
+                .rect(rect(10.0, 90.0, 40.0, 20.0))
+                .build(&mut window_cx);
+            let _other_button = Button::builder()
+                .text("Other")
+                .on_select(TestAction::OtherPressed)
+                .rect(rect(10.0, 120.0, 100.0, 30.0))
+                .build(&mut window_cx);
+
+            Ok(Self {
+                _button,
+                _disabled_button,
+                _switch,
+                _other_button,
+            })
+        }
+    }
+
+    #[test]
+    fn test_accessibility_tree() {
+        use accesskit::{Action, ActionRequest, Role, Toggled};
+
+        let mut app = HeadlessApp::<AccessibilityTestApp>::new(AppConfig::default()).unwrap();
+
+        let update = app.accessibility_tree_update(MAIN_WINDOW).unwrap();
+        let root_id = update.tree.as_ref().unwrap().root;
+        assert_eq!(update.focus, root_id);
+
+        let node = |id| {
+            update
+                .nodes
+                .iter()
+                .find(|(i, _)| *i == id)
+                .unwrap()
+                .1
+                .clone()
+        };
+
+        let root = node(root_id);
+        assert_eq!(root.role(), Role::Window);
+
+        // Children are reported from top to bottom.
+        let children = root.children().to_vec();
+        let names: Vec<_> = children
+            .iter()
+            .map(|id| node(*id).name().map(String::from))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("First".into()),
+                Some("Disabled".into()),
+                None,
+                Some("Other".into())
+            ]
+        );
+
+        assert!(node(children[1]).is_disabled());
+        assert!(!node(children[1]).supports_action(Action::Default));
+
+        let switch_id = children[2];
+        assert_eq!(node(switch_id).role(), Role::Switch);
+        assert_eq!(node(switch_id).toggled(), Some(Toggled::False));
+
+        app.accessibility_action(
+            MAIN_WINDOW,
+            ActionRequest {
+                action: Action::Default,
+                target: switch_id,
+                data: None,
+            },
+        );
+        assert_eq!(app.drain_actions(), vec![TestAction::Toggled(true)]);
+        app.process_updates();
+
+        let update = app.accessibility_tree_update(MAIN_WINDOW).unwrap();
+        let (_, switch) = update.nodes.iter().find(|(i, _)| *i == switch_id).unwrap();
+        assert_eq!(switch.toggled(), Some(Toggled::True));
+
+        // Focus requests move keyboard focus.
+        let other_id = children[3];
+        app.accessibility_action(
+            MAIN_WINDOW,
+            ActionRequest {
+                action: Action::Focus,
+                target: other_id,
+                data: None,
+            },
+        );
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        assert_eq!(app.drain_actions(), vec![TestAction::OtherPressed]);
+
+        let update = app.accessibility_tree_update(MAIN_WINDOW).unwrap();
+        assert_eq!(update.focus, other_id);
+    }
+
+    #[test]
+    fn test_accessibility_tree_only_rebuilt_on_changes() {
+        let mut app = HeadlessApp::<AccessibilityTestApp>::new(AppConfig::default()).unwrap();
+        app.process_updates();
+        app.accessibility_tree_update(MAIN_WINDOW).unwrap();
+        assert!(!app.accessibility_needs_update(MAIN_WINDOW));
+
+        // Hovering only repaints the button.
+        app.pointer_moved(MAIN_WINDOW, point(50.0, 25.0));
+        app.process_updates();
+        assert!(!app.accessibility_needs_update(MAIN_WINDOW));
+
+        // Toggling the switch changes its value.
+        app.click(MAIN_WINDOW, point(30.0, 100.0), PointerButton::Primary);
+        app.process_updates();
+        assert_eq!(app.drain_actions(), vec![TestAction::Toggled(true)]);
+        assert!(app.accessibility_needs_update(MAIN_WINDOW));
+    }
+}
@@ -11,7 +11,7 @@ pub use handle::ElementHandle;
 use rootvg::math::Point;
 use rootvg::PrimitiveGroup;
 
-use super::ScissorRectID;
+use super::{AccessNode, ScissorRectID};
 use crate::action_queue::ActionSender;
 use crate::event::{ElementEvent, EventCaptureStatus};
 use crate::math::{Rect, Size, ZIndex};
@@ -20,6 +20,7 @@ use crate::style::ClassID;
 use crate::{stmpsc_queue, WindowContext};
 
 pub(crate) use context::ChangeFocusRequest;
+pub(crate) use handle::ElementNotifier;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) struct ElementID(pub thunderdome::Index);
@@ -55,6 +56,17 @@ pub trait Element<A: Clone + 'static> {
     fn global_render_cache(&self) -> Option<Box<dyn ElementRenderCache>> {
         None
     }
+
+    /// Describe this element to assistive technologies such as screen readers.
+    ///
+    /// Return `None` if this element should not appear in the accessibility
+    /// tree (the default).
+    ///
+    /// Actions requested by assistive technologies are sent to the element
+    /// with `ElementEvent::AccessAction`.
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        None
+    }
 }
 
 pub trait ElementRenderCache {
@@ -159,6 +171,7 @@ pub(super) enum ElementModificationType {
     ScissorRectChanged,
     ZIndexChanged(ZIndex),
     ExplicitlyHiddenChanged(bool),
+    FocusableChanged(bool),
     ClassChanged(ClassID),
     SetAnimating(bool),
     ChangeFocus(ChangeFocusRequest),
@@ -178,6 +191,7 @@ pub(super) fn new_element_handle(
     rect: Rect,
     z_index: ZIndex,
     manually_hidden: bool,
+    focusable: bool,
     class: ClassID,
 ) -> ElementHandle {
     ElementHandle::new(
@@ -186,6 +200,7 @@ pub(super) fn new_element_handle(
         rect,
         z_index,
         manually_hidden,
+        focusable,
         class,
     )
 }
@@ -66,5 +66,24 @@ bitflags::bitflags! {
         /// Whether or not this element should receive an `init` event when it gets
         /// added to the view.
         const LISTENS_TO_INIT = 1 << 12;
+
+        /// Whether or not this element is part of the focus chain, meaning the user
+        /// can give it exclusive focus by cycling through elements with the Tab and
+        /// Shift+Tab keys.
+        ///
+        /// Elements are visited in the order they were added to the view. Elements
+        /// which are hidden are skipped.
+        ///
+        /// Elements with this flag will usually also want to set
+        /// `LISTENS_TO_FOCUS_CHANGE` (to show a focus ring) and
+        /// `LISTENS_TO_KEYS_WHEN_FOCUSED` (to be activated with the keyboard).
+        const FOCUSABLE = 1 << 13;
+
+        /// Whether or not this element should receive keyboard events which
+        /// were not captured by the element with exclusive focus (or all keyboard
+        /// events if no element has exclusive focus).
+        ///
+        /// This can be useful for example to activate a menu bar with the Alt key.
+        const LISTENS_TO_UNCAPTURED_KEYS = 1 << 14;
     }
 }
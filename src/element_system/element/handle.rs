@@ -14,6 +14,7 @@ pub struct ElementHandle {
     rect: Rect,
     z_index: ZIndex,
     manually_hidden: bool,
+    focusable: bool,
     class: ClassID,
 }
 
@@ -24,6 +25,7 @@ impl ElementHandle {
         rect: Rect,
         z_index: ZIndex,
         manually_hidden: bool,
+        focusable: bool,
         class: ClassID,
     ) -> Self {
         Self {
@@ -32,6 +34,7 @@ impl ElementHandle {
             rect,
             z_index,
             manually_hidden,
+            focusable,
             class,
         }
     }
@@ -346,6 +349,39 @@ impl ElementHandle {
         }
     }
 
+    /// Returns `true` if this element can currently be focused by cycling through
+    /// elements with the Tab key.
+    ///
+    /// This is cached directly in the handle so this is very cheap to call frequently.
+    pub fn focusable(&self) -> bool {
+        self.focusable
+    }
+
+    /// Set whether this element can be focused by cycling through elements with
+    /// the Tab key (i.e. elements should not be focusable while they are disabled).
+    ///
+    /// If the element is made unfocusable while it has focus, then its focus will
+    /// be released.
+    ///
+    /// An update will only be sent to the view if the value has changed.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is very cheap to call frequently.
+    pub fn set_focusable(&mut self, focusable: bool) -> bool {
+        if self.focusable != focusable {
+            self.focusable = focusable;
+            self.mod_queue_sender.send(ElementModification {
+                element_id: self.element_id,
+                type_: ElementModificationType::FocusableChanged(focusable),
+            });
+            true
+        } else {
+            false
+        }
+    }
+
     /// Get the actual bounding rectangle of this element, accounting for the offset
     /// introduced by its assigned scissoring rectangle.
     pub fn rect_in_window<A: Clone + 'static>(&self, window_cx: &WindowContext<'_, A>) -> Rect {
@@ -355,6 +391,36 @@ impl ElementHandle {
     pub(crate) fn id(&self) -> ElementID {
         self.element_id
     }
+
+    /// Create a notifier which can send custom state changes to this
+    /// element instance.
+    pub(crate) fn notifier(&self) -> ElementNotifier {
+        ElementNotifier {
+            element_id: self.element_id,
+            mod_queue_sender: self.mod_queue_sender.clone(),
+        }
+    }
+}
+
+/// A weak counterpart to an [`ElementHandle`] which can only notify the
+/// element instance of a custom state change.
+///
+/// This is useful for elements which are made up of multiple element
+/// instances. Unlike an [`ElementHandle`], dropping this does not drop the
+/// element instance, and notifying an element instance which has already
+/// been dropped does nothing.
+pub(crate) struct ElementNotifier {
+    element_id: ElementID,
+    mod_queue_sender: stmpsc_queue::Sender<ElementModification>,
+}
+
+impl ElementNotifier {
+    pub fn notify_custom_state_change(&mut self) {
+        self.mod_queue_sender.send(ElementModification {
+            element_id: self.element_id,
+            type_: ElementModificationType::CustomStateChanged,
+        });
+    }
 }
 
 impl Drop for ElementHandle {
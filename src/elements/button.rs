@@ -157,6 +157,11 @@ pub struct ButtonStyle {
     ///
     /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
     pub quad_flags: QuadFlags,
+
+    /// The ring drawn around the button when it has keyboard focus.
+    ///
+    /// By default this is transparent.
+    pub focus_ring: FocusRingStyle,
 }
 
 impl Default for ButtonStyle {
@@ -190,6 +195,7 @@ impl Default for ButtonStyle {
             back_border_radius: Default::default(),
             cursor_icon: None,
             quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            focus_ring: FocusRingStyle::default(),
         }
     }
 }
@@ -616,15 +622,24 @@ impl<A: Clone + 'static> ButtonBuilder<A> {
             tooltip_inner: TooltipInner::new(tooltip_data),
         }));
 
+        let mut flags = ElementFlags::PAINTS
+            | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+            | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+            | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED;
+        if !disabled {
+            flags.insert(ElementFlags::FOCUSABLE);
+        }
+
         let el = ElementBuilder::new(ButtonElement {
             shared_state: Rc::clone(&shared_state),
             on_select_action,
             cursor_icon,
+            focused: false,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
         .rect(rect)
         .hidden(manually_hidden)
-        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .flags(flags)
         .build(window_cx);
 
         Button { el, shared_state }
@@ -636,6 +651,7 @@ struct ButtonElement<A: Clone + 'static> {
     shared_state: Rc<RefCell<SharedState>>,
     on_select_action: Option<A>,
     cursor_icon: Option<CursorIcon>,
+    focused: bool,
 }
 
 impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
@@ -658,6 +674,30 @@ impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
                 let style = cx.res.style_system.get::<ButtonStyle>(cx.class());
                 self.cursor_icon = style.cursor_icon;
             }
+            ElementEvent::Focus(focused) => {
+                if self.focused != focused {
+                    self.focused = focused;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Keyboard(key_event) => {
+                if key_event.is_activation_press()
+                    && shared_state.inner.state != ButtonState::Disabled
+                {
+                    if let Some(action) = &self.on_select_action {
+                        cx.send_action(action.clone()).unwrap();
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::AccessAction(AccessAction::Click) => {
+                if shared_state.inner.state != ButtonState::Disabled {
+                    if let Some(action) = &self.on_select_action {
+                        cx.send_action(action.clone()).unwrap();
+                    }
+                }
+            }
             ElementEvent::Pointer(PointerEvent::Moved { .. }) => {
                 if shared_state.inner.state == ButtonState::Disabled {
                     return EventCaptureStatus::NotCaptured;
@@ -739,9 +779,11 @@ impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
     fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
 
+        let style: &ButtonStyle = cx.res.style_system.get(cx.class);
+
         let label_primitives = shared_state.inner.render(
             Rect::from_size(cx.bounds_size),
-            cx.res.style_system.get(cx.class),
+            style,
             &mut cx.res.font_system,
         );
 
@@ -758,6 +800,30 @@ impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
             primitives.set_z_index(1);
             primitives.add_text(p);
         }
+
+        if self.focused && !style.focus_ring.is_transparent() {
+            primitives.set_z_index(1);
+            primitives.add(
+                style
+                    .focus_ring
+                    .create_primitive(Rect::from_size(cx.bounds_size)),
+            );
+        }
+    }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        Some(
+            AccessNode::new(AccessRole::Button)
+                .label(
+                    shared_state
+                        .inner
+                        .text()
+                        .or_else(|| shared_state.tooltip_inner.text()),
+                )
+                .disabled(shared_state.inner.disabled()),
+        )
     }
 }
 
@@ -939,10 +1005,12 @@ impl Button {
 
         if disabled && shared_state.inner.state != ButtonState::Disabled {
             shared_state.inner.set_state(ButtonState::Disabled);
+            self.el.set_focusable(false);
             self.el.notify_custom_state_change();
             true
         } else if !disabled && shared_state.inner.state == ButtonState::Disabled {
             shared_state.inner.set_state(ButtonState::Idle);
+            self.el.set_focusable(true);
             self.el.notify_custom_state_change();
             true
         } else {
@@ -0,0 +1,826 @@
+use derive_where::derive_where;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::element_system::element::ElementNotifier;
+use crate::prelude::*;
+
+use super::button::{ButtonInner, ButtonState};
+use super::label::{LabelInner, LabelPaddingInfo, LabelStyle};
+
+/// The number of points the scroll wheel has to move to select the next or
+/// previous entry.
+const SCROLL_POINTS_PER_STEP: f32 = 24.0;
+
+/// The style of a [`ComboBox`] element
+///
+/// The list of entries is styled with the [`DropDownMenuStyle`] of the same
+/// class.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComboBoxStyle {
+    /// The style of the box which shows the selected entry.
+    pub button_style: ButtonStyle,
+
+    /// The text of the indicator drawn on the right side of the box.
+    ///
+    /// If this is `None`, then no indicator will be drawn.
+    ///
+    /// By default this is set to `Some("▾")`.
+    pub indicator: Option<String>,
+    /// The padding around the indicator.
+    ///
+    /// By default this has all values set to `0.0`.
+    pub indicator_padding: Padding,
+}
+
+impl Default for ComboBoxStyle {
+    fn default() -> Self {
+        Self {
+            button_style: ButtonStyle::default(),
+            indicator: Some(String::from("▾")),
+            indicator_padding: Padding::default(),
+        }
+    }
+}
+
+impl ComboBoxStyle {
+    fn indicator_label_style(&self, state: ButtonState) -> LabelStyle {
+        LabelStyle {
+            text_properties: self.button_style.text_properties,
+            text_color: self.button_style.label_style(state).text_color,
+            text_padding: self.indicator_padding,
+            ..Default::default()
+        }
+    }
+
+    fn indicator_padding_info(&self) -> LabelPaddingInfo {
+        LabelPaddingInfo {
+            default_icon_size: 0.0,
+            text_padding: self.indicator_padding,
+            icon_padding: Padding::zero(),
+            text_icon_spacing: 0.0,
+        }
+    }
+}
+
+impl ElementStyle for ComboBoxStyle {
+    const ID: &'static str = "combobox";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            button_style: ButtonStyle::default_light_style(),
+            ..Default::default()
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+#[derive_where(Default)]
+pub struct ComboBoxBuilder<A: Clone + 'static> {
+    pub action: Option<Box<dyn FnMut(usize) -> A>>,
+    pub entries: Vec<MenuEntry>,
+    pub selected: Option<usize>,
+    pub menu_z_index: Option<ZIndex>,
+}
+
+impl<A: Clone + 'static> ComboBoxBuilder<A> {
+    /// The action to send when the user selects a different entry, either
+    /// from the list or by cycling through the entries with the arrow keys
+    /// or the scroll wheel.
+    pub fn on_selected<F: FnMut(usize) -> A + 'static>(mut self, f: F) -> Self {
+        self.action = Some(Box::new(f));
+        self
+    }
+
+    /// The entries in the list.
+    ///
+    /// Only [`MenuEntry::Option`] entries (including those in nested menus)
+    /// can be selected and shown in the box.
+    pub fn entries(mut self, entries: Vec<MenuEntry>) -> Self {
+        self.entries = entries;
+        self
+    }
+
+    /// The `unique_id` of the initially selected entry.
+    ///
+    /// By default this is set to `None`.
+    pub fn selected(mut self, unique_id: impl Into<Option<usize>>) -> Self {
+        self.selected = unique_id.into();
+        self
+    }
+
+    /// The z index of the list of entries.
+    ///
+    /// The list is its own element which does not belong to the scissoring
+    /// rectangle of the box, so it is not clipped by scroll areas.
+    ///
+    /// If this method is not used, then the list will be drawn on top of
+    /// all other elements.
+    pub const fn menu_z_index(mut self, z_index: ZIndex) -> Self {
+        self.menu_z_index = Some(z_index);
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> ComboBox {
+        let ComboBoxBuilder {
+            action,
+            entries,
+            selected,
+            menu_z_index,
+            disabled,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+        } = self;
+
+        let class = window_cx.builder_class(class);
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<ComboBoxStyle>(class)
+            .clone();
+
+        let (text, icon, icon_scale) = match selected.and_then(|id| find_option(&entries, id)) {
+            Some((text, icon, icon_scale)) => (Some(text), icon, icon_scale),
+            None => (None, None, IconScale::default()),
+        };
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            inner: ButtonInner::new(
+                text,
+                icon,
+                Vector::default(),
+                Vector::default(),
+                None,
+                icon_scale,
+                disabled,
+                TextIconLayout::default(),
+                &style.button_style,
+                &mut window_cx.res.font_system,
+            ),
+            indicator: build_indicator(&style, &mut window_cx.res.font_system),
+            selected,
+            new_entries: None,
+            selected_changed: false,
+            menu_selection: None,
+            notifier: None,
+        }));
+
+        let listener_state = Rc::clone(&shared_state);
+        let menu = DropDownMenu::builder()
+            .entries(entries.clone())
+            .class(class)
+            .z_index(menu_z_index.unwrap_or(ZIndex::MAX))
+            .scissor_rect(ScissorRectID::DEFAULT)
+            .build_with_listener(
+                Box::new(move |unique_id| {
+                    let mut shared_state = RefCell::borrow_mut(&listener_state);
+                    shared_state.menu_selection = Some(unique_id);
+
+                    if let Some(notifier) = &mut shared_state.notifier {
+                        notifier.notify_custom_state_change();
+                    }
+                }),
+                window_cx,
+            );
+
+        let mut flags = ElementFlags::PAINTS
+            | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+            | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+            | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED;
+        if !disabled {
+            flags.insert(ElementFlags::FOCUSABLE);
+        }
+
+        let el = ElementBuilder::new(ComboBoxElement {
+            shared_state: Rc::clone(&shared_state),
+            action,
+            entries,
+            menu,
+            focused: false,
+            scroll_accumulator: 0.0,
+            cursor_icon: style.button_style.cursor_icon,
+        })
+        .builder_values(z_index, scissor_rect, Some(class), window_cx)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(flags)
+        .build(window_cx);
+
+        RefCell::borrow_mut(&shared_state).notifier = Some(el.notifier());
+
+        ComboBox { el, shared_state }
+    }
+}
+
+struct ComboBoxElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    action: Option<Box<dyn FnMut(usize) -> A>>,
+    entries: Vec<MenuEntry>,
+    /// The list of entries, which is dropped along with this element.
+    menu: DropDownMenu,
+    focused: bool,
+    /// The scroll wheel movement which has not yet added up to a full step.
+    scroll_accumulator: f32,
+    cursor_icon: Option<CursorIcon>,
+}
+
+impl<A: Clone + 'static> ComboBoxElement<A> {
+    fn open_menu(&mut self, cx: &mut ElementContext<'_, A>) {
+        let selected = RefCell::borrow(&self.shared_state).selected;
+        self.menu.open_below(cx.rect(), selected);
+    }
+
+    fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).inner.disabled()
+    }
+
+    /// Select the entry with the given `unique_id` and send the action if
+    /// it is different from the current selection.
+    fn select(&mut self, unique_id: usize, cx: &mut ElementContext<'_, A>) {
+        {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+            if shared_state.selected == Some(unique_id) {
+                return;
+            }
+            shared_state.selected = Some(unique_id);
+        }
+
+        self.sync_label(cx.class(), cx.res);
+        cx.request_repaint();
+
+        if let Some(action) = &mut self.action {
+            cx.send_action((action)(unique_id)).unwrap();
+        }
+    }
+
+    /// Move the selection by the given number of selectable entries,
+    /// stopping at the first and last entries.
+    fn step_selection(&mut self, steps: isize, cx: &mut ElementContext<'_, A>) {
+        let mut ids = Vec::new();
+        collect_option_ids(&self.entries, &mut ids);
+
+        if ids.is_empty() || steps == 0 {
+            return;
+        }
+
+        let selected = RefCell::borrow(&self.shared_state).selected;
+        let current = selected.and_then(|id| ids.iter().position(|i| *i == id));
+
+        let index = match current {
+            Some(i) => (i as isize + steps).clamp(0, ids.len() as isize - 1) as usize,
+            None if steps > 0 => 0,
+            None => ids.len() - 1,
+        };
+
+        self.select(ids[index], cx);
+    }
+
+    /// Select the first (or last if `last` is `true`) selectable entry.
+    fn select_edge(&mut self, last: bool, cx: &mut ElementContext<'_, A>) {
+        let mut ids = Vec::new();
+        collect_option_ids(&self.entries, &mut ids);
+
+        let id = if last { ids.last() } else { ids.first() };
+        if let Some(id) = id.copied() {
+            self.select(id, cx);
+        }
+    }
+
+    /// Update the text and icon in the box to match the selected entry.
+    fn sync_label(&mut self, class: ClassID, res: &mut ResourceCtx) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let (text, icon, icon_scale) = match shared_state
+            .selected
+            .and_then(|id| find_option(&self.entries, id))
+        {
+            Some((text, icon, icon_scale)) => (Some(text), icon, icon_scale),
+            None => (None, None, IconScale::default()),
+        };
+
+        shared_state.inner.set_text(text, &mut res.font_system, || {
+            res.style_system
+                .get::<ComboBoxStyle>(class)
+                .button_style
+                .text_properties
+        });
+        shared_state.inner.set_icon(icon);
+        shared_state.inner.set_icon_scale(icon_scale);
+    }
+
+    fn set_state(&mut self, state: ButtonState, cx: &mut ElementContext<'_, A>) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.inner.state() != ButtonState::Disabled
+            && shared_state.inner.set_state(state)
+        {
+            cx.request_repaint();
+        }
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for ComboBoxElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged => {
+                let (new_entries, selected_changed, menu_selection) = {
+                    let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                    let selected_changed = shared_state.selected_changed;
+                    shared_state.selected_changed = false;
+                    (
+                        shared_state.new_entries.take(),
+                        selected_changed,
+                        shared_state.menu_selection.take(),
+                    )
+                };
+
+                if let Some(new_entries) = new_entries {
+                    self.menu.set_entries(new_entries.clone());
+                    self.entries = new_entries;
+                }
+
+                if selected_changed {
+                    self.sync_label(cx.class(), cx.res);
+                }
+
+                if let Some(unique_id) = menu_selection {
+                    self.select(unique_id, cx);
+                }
+
+                if self.focused && self.disabled() {
+                    cx.release_focus();
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                let style = cx.res.style_system.get::<ComboBoxStyle>(cx.class()).clone();
+                self.cursor_icon = style.button_style.cursor_icon;
+
+                let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                shared_state
+                    .inner
+                    .sync_new_style(&style.button_style, &mut cx.res.font_system);
+                shared_state.indicator = build_indicator(&style, &mut cx.res.font_system);
+            }
+            ElementEvent::Hidden => {
+                self.menu.set_hidden(true);
+            }
+            ElementEvent::Shown => {
+                self.menu.set_hidden(false);
+            }
+            ElementEvent::Focus(focused) => {
+                if self.focused != focused {
+                    self.focused = focused;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Keyboard(key_event) => {
+                if key_event.state != KeyState::Down || self.disabled() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if key_event.is_activation_press()
+                    || (key_event.code == Code::ArrowDown && key_event.modifiers == Modifiers::ALT)
+                {
+                    self.open_menu(cx);
+                    return EventCaptureStatus::Captured;
+                }
+
+                if !key_event.modifiers.is_empty() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                match key_event.code {
+                    Code::ArrowDown => self.step_selection(1, cx),
+                    Code::ArrowUp => self.step_selection(-1, cx),
+                    Code::Home => self.select_edge(false, cx),
+                    Code::End => self.select_edge(true, cx),
+                    _ => return EventCaptureStatus::NotCaptured,
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::AccessAction(action) => {
+                if self.disabled() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                match action {
+                    AccessAction::Click => self.open_menu(cx),
+                    AccessAction::Increment => self.step_selection(1, cx),
+                    AccessAction::Decrement => self.step_selection(-1, cx),
+                    AccessAction::SetValue(_) => {}
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { .. }) => {
+                if self.disabled() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if let Some(cursor_icon) = self.cursor_icon {
+                    cx.cursor_icon = cursor_icon;
+                }
+
+                self.set_state(ButtonState::Hovered, cx);
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                self.set_state(ButtonState::Idle, cx);
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed { button, .. }) => {
+                if self.disabled() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if button == PointerButton::Primary {
+                    self.open_menu(cx);
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheel { delta_type, .. }) => {
+                if self.disabled() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let delta = delta_type.points(SCROLL_POINTS_PER_STEP, cx.rect().height());
+
+                self.scroll_accumulator += delta.y;
+                let steps = (self.scroll_accumulator / SCROLL_POINTS_PER_STEP).trunc();
+                self.scroll_accumulator -= steps * SCROLL_POINTS_PER_STEP;
+
+                self.step_selection(steps as isize, cx);
+
+                return EventCaptureStatus::Captured;
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let shared_state = &mut *shared_state;
+
+        let style: &ComboBoxStyle = cx.res.style_system.get(cx.class);
+        let state = shared_state.inner.state();
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        let back_quad = style.button_style.label_style(state).back_quad;
+        if !back_quad.is_transparent() {
+            primitives.add(back_quad.create_primitive(bounds));
+        }
+
+        let indicator_width = shared_state
+            .indicator
+            .as_mut()
+            .map(|indicator| {
+                indicator
+                    .desired_size(|| style.indicator_padding_info())
+                    .width
+            })
+            .unwrap_or(0.0);
+
+        // The background quad is drawn above so that it also covers the
+        // indicator.
+        let label_primitives = shared_state.inner.render(
+            Rect::from_size(Size::new(
+                (bounds.width() - indicator_width).max(0.0),
+                bounds.height(),
+            )),
+            &style.button_style,
+            &mut cx.res.font_system,
+        );
+
+        primitives.set_z_index(1);
+
+        if let Some(p) = label_primitives.text {
+            primitives.add_text(p);
+        }
+
+        if let Some(p) = label_primitives.icon {
+            primitives.add_text(p);
+        }
+
+        if let Some(indicator) = &mut shared_state.indicator {
+            let indicator_primitives = indicator.render(
+                Rect::new(
+                    Point::new(bounds.max_x() - indicator_width, 0.0),
+                    Size::new(indicator_width, bounds.height()),
+                ),
+                &style.indicator_label_style(state),
+                &mut cx.res.font_system,
+            );
+
+            if let Some(p) = indicator_primitives.text {
+                primitives.add_text(p);
+            }
+        }
+
+        if self.focused && !style.button_style.focus_ring.is_transparent() {
+            primitives.add(style.button_style.focus_ring.create_primitive(bounds));
+        }
+    }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        Some(
+            AccessNode::new(AccessRole::ComboBox)
+                .value(
+                    shared_state
+                        .inner
+                        .text()
+                        .map(|text| AccessValue::Text(text.to_string()))
+                        .unwrap_or_default(),
+                )
+                .disabled(shared_state.inner.disabled()),
+        )
+    }
+}
+
+struct SharedState {
+    inner: ButtonInner,
+    indicator: Option<LabelInner>,
+    selected: Option<usize>,
+    new_entries: Option<Vec<MenuEntry>>,
+    selected_changed: bool,
+    /// The entry which was just selected from the list.
+    menu_selection: Option<usize>,
+    /// Used by the list to notify the box of a selection.
+    notifier: Option<ElementNotifier>,
+}
+
+/// A handle to a [`ComboBoxElement`], a box which shows the selected entry
+/// and opens a list of the entries below itself when clicked.
+///
+/// While the box has keyboard focus, the up and down arrow keys select the
+/// previous and next entries without opening the list, and the Enter or
+/// Space key (or Alt + Down) opens the list. Scrolling the mouse wheel over
+/// the box also cycles through the entries.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct ComboBox {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl ComboBox {
+    pub fn builder<A: Clone + 'static>() -> ComboBoxBuilder<A> {
+        ComboBoxBuilder::default()
+    }
+
+    /// Returns the size of the padded background rectangle if it were to
+    /// cover the text and icon of the selected entry and the indicator.
+    ///
+    /// This size is automatically cached, so it should be relatively
+    /// inexpensive to call.
+    pub fn desired_size(&self, res: &mut ResourceCtx) -> Size {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let shared_state = &mut *shared_state;
+
+        let style = res.style_system.get::<ComboBoxStyle>(self.el.class());
+
+        let mut size = shared_state
+            .inner
+            .desired_size(|| style.button_style.padding_info());
+
+        if let Some(indicator) = &mut shared_state.indicator {
+            let indicator_size = indicator.desired_size(|| style.indicator_padding_info());
+            size.width += indicator_size.width;
+            size.height = size.height.max(indicator_size.height);
+        }
+
+        size
+    }
+
+    /// The `unique_id` of the selected entry.
+    pub fn selected(&self) -> Option<usize> {
+        RefCell::borrow(&self.shared_state).selected
+    }
+
+    /// Set the selected entry. This will not send the `on_selected` action.
+    ///
+    /// Returns `true` if the selection has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_selected(&mut self, unique_id: Option<usize>) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.selected != unique_id {
+            shared_state.selected = unique_id;
+            shared_state.selected_changed = true;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set the entries in the list.
+    ///
+    /// Note this will *always* trigger an element update, so use
+    /// this method sparingly.
+    pub fn set_entries(&mut self, entries: Vec<MenuEntry>) {
+        {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+            shared_state.new_entries = Some(entries);
+            shared_state.selected_changed = true;
+        }
+        self.el.notify_custom_state_change();
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if disabled != shared_state.inner.disabled() {
+            shared_state.inner.set_state(ButtonState::new(disabled));
+            self.el.set_focusable(!disabled);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).inner.disabled()
+    }
+}
+
+fn build_indicator(style: &ComboBoxStyle, font_system: &mut FontSystem) -> Option<LabelInner> {
+    style.indicator.as_ref().map(|indicator| {
+        LabelInner::new(
+            Some(indicator.clone()),
+            None,
+            Vector::default(),
+            Vector::default(),
+            None,
+            IconScale::default(),
+            Default::default(),
+            &style.indicator_label_style(ButtonState::Idle),
+            font_system,
+        )
+    })
+}
+
+/// The text and icon of the [`MenuEntry::Option`] with the given `unique_id`,
+/// searching nested menus as well.
+fn find_option(
+    entries: &[MenuEntry],
+    unique_id: usize,
+) -> Option<(&str, Option<IconID>, IconScale)> {
+    entries.iter().find_map(|entry| match entry {
+        MenuEntry::Option {
+            left_icon,
+            icon_scale,
+            left_text,
+            unique_id: id,
+            ..
+        } if *id == unique_id => Some((left_text.as_str(), *left_icon, *icon_scale)),
+        MenuEntry::SubMenu { entries, .. } => find_option(entries, unique_id),
+        MenuEntry::Disabled(entry) => find_option(std::slice::from_ref(&**entry), unique_id),
+        _ => None,
+    })
+}
+
+/// Collect the `unique_id` of every enabled [`MenuEntry::Option`], including
+/// those in enabled nested menus, in the order they are displayed.
+fn collect_option_ids(entries: &[MenuEntry], ids: &mut Vec<usize>) {
+    for entry in entries.iter() {
+        match entry {
+            MenuEntry::Option { unique_id, .. } => ids.push(*unique_id),
+            MenuEntry::SubMenu { entries, .. } => collect_option_ids(entries, ids),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        MenuSelected(usize),
+    }
+
+    struct ComboBoxTestApp {
+        combo_box: ComboBox,
+    }
+
+    impl Application for ComboBoxTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let combo_box = ComboBox::builder()
+                .entries(vec![
+                    MenuEntry::option("A", 0),
+                    MenuEntry::option("B", 1),
+                    MenuEntry::Divider,
+                    MenuEntry::option("C", 2),
+                    MenuEntry::option("D", 3),
+                ])
+                .selected(1)
+                .on_selected(TestAction::MenuSelected)
+                .rect(rect(10.0, 10.0, 120.0, 24.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { combo_box })
+        }
+    }
+
+    #[test]
+    fn test_combo_box() {
+        let mut app = HeadlessApp::<ComboBoxTestApp>::new(AppConfig::default()).unwrap();
+
+        let press = |app: &mut HeadlessApp<ComboBoxTestApp>, code| {
+            app.key_press(MAIN_WINDOW, code, Modifiers::empty());
+            app.process_updates();
+        };
+
+        // The arrow keys cycle through the entries without opening the list.
+        press(&mut app, Code::Tab);
+        press(&mut app, Code::ArrowDown);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(2)]);
+        press(&mut app, Code::ArrowUp);
+        press(&mut app, Code::ArrowUp);
+        assert_eq!(
+            app.drain_actions(),
+            vec![TestAction::MenuSelected(1), TestAction::MenuSelected(0)]
+        );
+
+        // The selection stops at the first and last entries.
+        press(&mut app, Code::ArrowUp);
+        assert!(app.drain_actions().is_empty());
+        press(&mut app, Code::End);
+        press(&mut app, Code::ArrowDown);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(3)]);
+        assert_eq!(app.app().combo_box.selected(), Some(3));
+
+        // Enter opens the list with the selected entry highlighted.
+        press(&mut app, Code::Enter);
+        press(&mut app, Code::ArrowUp);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(2)]);
+        assert_eq!(app.app().combo_box.selected(), Some(2));
+
+        // Selecting the already selected entry does not send an action.
+        press(&mut app, Code::Enter);
+        press(&mut app, Code::Enter);
+        assert!(app.drain_actions().is_empty());
+
+        // Focus returns to the box once the list is closed.
+        press(&mut app, Code::Home);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(0)]);
+
+        // Clicking the box opens the list.
+        app.click(MAIN_WINDOW, point(20.0, 20.0), PointerButton::Primary);
+        app.process_updates();
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(1)]);
+
+        // Scrolling down over the box selects the next entry.
+        app.scroll_wheel(MAIN_WINDOW, WheelDeltaType::Points(Vector::new(0.0, 24.0)));
+        app.process_updates();
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(2)]);
+        app.scroll_wheel(MAIN_WINDOW, WheelDeltaType::Points(Vector::new(0.0, -12.0)));
+        assert!(app.drain_actions().is_empty());
+        app.scroll_wheel(MAIN_WINDOW, WheelDeltaType::Points(Vector::new(0.0, -12.0)));
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(1)]);
+
+        app.app_mut().combo_box.set_selected(Some(3));
+        app.process_updates();
+        assert!(app.drain_actions().is_empty());
+        assert_eq!(app.app().combo_box.selected(), Some(3));
+    }
+}
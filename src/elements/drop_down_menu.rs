@@ -1,6 +1,8 @@
 use derive_where::derive_where;
+use keyboard_types::CompositionState;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use crate::derive::*;
 use crate::prelude::*;
@@ -17,8 +19,6 @@ use super::label::{LabelInner, LabelPaddingInfo, LabelStyle};
 // two selectable behaviors:
 //   * option A: use scroll wheel
 //   * option B: stack horizontally
-// * nested menus
-// * keyboard navigation
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -30,8 +30,37 @@ pub enum MenuEntry {
         right_text: Option<String>,
         unique_id: usize,
     },
+    /// An entry which is toggled on and off when selected. A check mark is
+    /// shown next to the entry while it is checked.
+    Check {
+        left_text: String,
+        right_text: Option<String>,
+        checked: bool,
+        unique_id: usize,
+    },
+    /// An entry in a group of mutually exclusive entries. Selecting this
+    /// entry checks it and unchecks all other entries in the same menu with
+    /// the same `group`.
+    Radio {
+        left_text: String,
+        right_text: Option<String>,
+        group: usize,
+        checked: bool,
+        unique_id: usize,
+    },
+    /// An entry which opens a nested menu when hovered.
+    SubMenu {
+        left_icon: Option<IconID>,
+        icon_scale: IconScale,
+        text: String,
+        entries: Vec<MenuEntry>,
+    },
+    /// A title for a section of the menu. This entry cannot be selected.
+    Header(String),
     Divider,
-    // TODO: Nested menus
+    /// An entry which is shown with `DropDownMenuStyle::text_color_disabled`
+    /// and cannot be selected. Use [`MenuEntry::disabled`] to create one.
+    Disabled(Box<MenuEntry>),
 }
 
 impl MenuEntry {
@@ -73,20 +102,289 @@ impl MenuEntry {
             unique_id,
         }
     }
+
+    pub fn check(text: impl Into<String>, checked: bool, unique_id: usize) -> Self {
+        Self::Check {
+            left_text: text.into(),
+            right_text: None,
+            checked,
+            unique_id,
+        }
+    }
+
+    pub fn radio(text: impl Into<String>, group: usize, checked: bool, unique_id: usize) -> Self {
+        Self::Radio {
+            left_text: text.into(),
+            right_text: None,
+            group,
+            checked,
+            unique_id,
+        }
+    }
+
+    pub fn sub_menu(text: impl Into<String>, entries: Vec<MenuEntry>) -> Self {
+        Self::SubMenu {
+            left_icon: None,
+            icon_scale: IconScale::default(),
+            text: text.into(),
+            entries,
+        }
+    }
+
+    pub fn sub_menu_with_icon(
+        text: impl Into<String>,
+        icon_id: Option<impl Into<IconID>>,
+        icon_scale: impl Into<IconScale>,
+        entries: Vec<MenuEntry>,
+    ) -> Self {
+        Self::SubMenu {
+            left_icon: icon_id.map(|i| i.into()),
+            icon_scale: icon_scale.into(),
+            text: text.into(),
+            entries,
+        }
+    }
+
+    pub fn header(text: impl Into<String>) -> Self {
+        Self::Header(text.into())
+    }
+
+    /// Set whether or not this entry is disabled. Disabled entries are shown
+    /// with `DropDownMenuStyle::text_color_disabled` and cannot be selected.
+    ///
+    /// This has no effect on headers and dividers.
+    pub fn disabled(self, disabled: bool) -> Self {
+        match self.unwrap_disabled().0 {
+            entry @ (Self::Header(_) | Self::Divider) => entry,
+            entry if disabled => Self::Disabled(Box::new(entry)),
+            entry => entry,
+        }
+    }
+
+    /// Whether or not this entry is disabled.
+    pub fn is_disabled(&self) -> bool {
+        matches!(self, Self::Disabled(_))
+    }
+
+    /// Returns the entry inside of any `Disabled` wrappers, and whether or
+    /// not it was disabled.
+    fn unwrap_disabled(mut self) -> (Self, bool) {
+        let mut disabled = false;
+        while let Self::Disabled(entry) = self {
+            self = *entry;
+            disabled = true;
+        }
+        (self, disabled)
+    }
+}
+
+/// How long to wait after the user types a character before the next
+/// character starts a new type-ahead search.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// The entries of a menu and their layout.
+pub(crate) struct MenuInner {
+    entries: Vec<MenuEntryInner>,
+    /// The width of the column which holds the check marks, or `0.0` if
+    /// this menu has no checkable entries.
+    check_column_width: f32,
+    size: Size,
+}
+
+impl MenuInner {
+    pub fn new(
+        entries: Vec<MenuEntry>,
+        style: &DropDownMenuStyle,
+        font_system: &mut FontSystem,
+    ) -> Self {
+        let mut entries = build_entries(entries, style, font_system);
+
+        let check_column_width = if entries
+            .iter()
+            .any(|entry| matches!(entry, MenuEntryInner::Toggle { .. }))
+        {
+            style.check_column_width
+        } else {
+            0.0
+        };
+
+        let size = style.measure(&mut entries, check_column_width);
+
+        Self {
+            entries,
+            check_column_width,
+            size,
+        }
+    }
+
+    /// The index of the selectable entry at the given y coordinate (relative
+    /// to the top of the menu).
+    fn entry_at(&self, y: f32) -> Option<usize> {
+        self.entries.iter().position(|entry| {
+            entry.is_selectable()
+                && entry
+                    .row()
+                    .map(|row| y >= row.start_y && y < row.end_y)
+                    .unwrap_or(false)
+        })
+    }
+
+    /// The index of the next (or previous if `reverse` is `true`) selectable
+    /// entry after `current`, wrapping around at the ends.
+    fn next_entry(&self, current: Option<usize>, reverse: bool) -> Option<usize> {
+        let len = self.entries.len();
+        if len == 0 {
+            return None;
+        }
+
+        let mut index = match (current, reverse) {
+            (Some(i), false) => (i + 1) % len,
+            (Some(i), true) => (i + len - 1) % len,
+            (None, false) => 0,
+            (None, true) => len - 1,
+        };
+
+        for _ in 0..len {
+            if self.entries[index].is_selectable() {
+                return Some(index);
+            }
+
+            index = if reverse {
+                (index + len - 1) % len
+            } else {
+                (index + 1) % len
+            };
+        }
+
+        None
+    }
+
+    /// The index of the first selectable entry at or after `start` whose
+    /// text starts with `prefix` (ignoring case), wrapping around at the end.
+    fn find_by_prefix(&self, prefix: &str, start: usize) -> Option<usize> {
+        let len = self.entries.len();
+
+        (0..len).map(|i| (start + i) % len).find(|i| {
+            let entry = &self.entries[*i];
+
+            entry.is_selectable()
+                && entry
+                    .row()
+                    .and_then(|row| row.left_label.text())
+                    .map(|text| text.to_lowercase().starts_with(prefix))
+                    .unwrap_or(false)
+        })
+    }
+
+    /// The index of the top-level entry with the given `unique_id`.
+    pub fn index_of(&self, unique_id: usize) -> Option<usize> {
+        self.entries.iter().position(|entry| match entry {
+            MenuEntryInner::Option { unique_id: id, .. }
+            | MenuEntryInner::Toggle { unique_id: id, .. } => *id == unique_id,
+            _ => false,
+        })
+    }
+
+    fn sub_menu(&self, index: usize) -> Option<&MenuInner> {
+        match self.entries.get(index) {
+            Some(MenuEntryInner::SubMenu { menu, .. }) if !menu.entries.is_empty() => Some(menu),
+            _ => None,
+        }
+    }
+
+    /// Set the checked state of the checkable entry at `index`. Checking a
+    /// radio entry unchecks the other entries in its group.
+    fn set_checked(&mut self, index: usize, checked: bool) {
+        let checked_group = match &mut self.entries[index] {
+            MenuEntryInner::Toggle {
+                checked: c, group, ..
+            } => {
+                *c = checked;
+                group.filter(|_| checked)
+            }
+            _ => return,
+        };
+
+        if let Some(checked_group) = checked_group {
+            for (i, entry) in self.entries.iter_mut().enumerate() {
+                if let MenuEntryInner::Toggle { checked, group, .. } = entry {
+                    if i != index && *group == Some(checked_group) {
+                        *checked = false;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Set the checked state of the checkable entry with the given
+    /// `unique_id` in this menu or any of its nested menus.
+    pub fn set_checked_by_id(&mut self, unique_id: usize, checked: bool) {
+        let index = self.entries.iter().position(|entry| {
+            matches!(entry, MenuEntryInner::Toggle { unique_id: id, .. } if *id == unique_id)
+        });
+
+        if let Some(index) = index {
+            self.set_checked(index, checked);
+        }
+
+        for entry in self.entries.iter_mut() {
+            if let MenuEntryInner::SubMenu { menu, .. } = entry {
+                menu.set_checked_by_id(unique_id, checked);
+            }
+        }
+    }
+}
+
+struct MenuRow {
+    left_label: LabelInner,
+    right_label: Option<LabelInner>,
+    start_y: f32,
+    end_y: f32,
+    disabled: bool,
 }
 
 enum MenuEntryInner {
     Option {
-        left_label: LabelInner,
-        right_label: Option<LabelInner>,
-        start_y: f32,
-        end_y: f32,
+        row: MenuRow,
+        unique_id: usize,
+    },
+    /// A check or radio entry.
+    Toggle {
+        row: MenuRow,
+        /// The check mark, or `None` if the style has no mark for this
+        /// kind of entry.
+        mark: Option<LabelInner>,
         unique_id: usize,
+        /// The radio group of this entry, or `None` if this is a check entry.
+        group: Option<usize>,
+        checked: bool,
+    },
+    SubMenu {
+        row: MenuRow,
+        menu: MenuInner,
+    },
+    Header {
+        label: LabelInner,
+        y: f32,
     },
     Divider {
         y: f32,
     },
-    // TODO: Nested menus
+}
+
+impl MenuEntryInner {
+    fn row(&self) -> Option<&MenuRow> {
+        match self {
+            Self::Option { row, .. } | Self::Toggle { row, .. } | Self::SubMenu { row, .. } => {
+                Some(row)
+            }
+            Self::Header { .. } | Self::Divider { .. } => None,
+        }
+    }
+
+    fn is_selectable(&self) -> bool {
+        self.row().map(|row| !row.disabled).unwrap_or(false)
+    }
 }
 
 /// The style of a [`DropDownMenu`] element
@@ -121,6 +419,22 @@ pub struct DropDownMenuStyle {
     ///
     /// By default this is set to `None`.
     pub text_color_hover: Option<RGBA8>,
+    /// The color of the text of disabled entries. This is also used for the
+    /// icon, right text, and check mark of disabled entries.
+    pub text_color_disabled: DisabledColor,
+
+    /// The properties of the text of section headers.
+    ///
+    /// If this is `None`, then `text_properties` will be used.
+    ///
+    /// By default this is set to `None`.
+    pub header_text_properties: Option<TextProperties>,
+    /// The color of the text of section headers.
+    ///
+    /// If this is `None`, then `text_color` will be used.
+    ///
+    /// By default this is set to `None`.
+    pub header_text_color: Option<RGBA8>,
 
     /// The color of the icon.
     ///
@@ -170,6 +484,29 @@ pub struct DropDownMenuStyle {
     /// The padding of the right text.
     pub right_text_padding: Padding,
 
+    /// The text shown on the right side of entries which open a nested
+    /// menu. This uses the same style as the right text.
+    ///
+    /// By default this is set to `Some("›")`.
+    pub sub_menu_indicator: Option<String>,
+
+    /// The text shown next to checked [`MenuEntry::Check`] entries. This
+    /// uses the same style as the left text.
+    ///
+    /// By default this is set to `Some("✓")`.
+    pub check_mark: Option<String>,
+    /// The text shown next to checked [`MenuEntry::Radio`] entries. This
+    /// uses the same style as the left text.
+    ///
+    /// By default this is set to `Some("•")`.
+    pub radio_mark: Option<String>,
+    /// The width of the column on the left side of the menu which holds
+    /// the check marks. This space is only reserved in menus which contain
+    /// check or radio entries.
+    ///
+    /// By default this is set to `20.0`.
+    pub check_column_width: f32,
+
     pub divider_color: RGBA8,
     pub divider_width: f32,
     pub divider_padding: f32,
@@ -191,6 +528,9 @@ impl Default for DropDownMenuStyle {
             snap_icon_to_physical_pixel: true,
             text_color: color::WHITE,
             text_color_hover: None,
+            text_color_disabled: Default::default(),
+            header_text_properties: None,
+            header_text_color: None,
             icon_color: None,
             icon_color_hover: None,
             right_text_color: None,
@@ -202,6 +542,10 @@ impl Default for DropDownMenuStyle {
             left_text_padding: Padding::default(),
             left_text_icon_spacing: 0.0,
             right_text_padding: Padding::default(),
+            sub_menu_indicator: Some(String::from("›")),
+            check_mark: Some(String::from("✓")),
+            radio_mark: Some(String::from("•")),
+            check_column_width: 20.0,
             divider_color: color::TRANSPARENT,
             divider_width: 1.0,
             divider_padding: 0.0,
@@ -211,8 +555,8 @@ impl Default for DropDownMenuStyle {
 }
 
 impl DropDownMenuStyle {
-    fn label_styles(&self, hovered: bool) -> (LabelStyle, LabelStyle) {
-        (
+    fn label_styles(&self, hovered: bool, disabled: bool) -> (LabelStyle, LabelStyle) {
+        let (mut left_style, mut right_style) = (
             LabelStyle {
                 text_properties: self.text_properties,
                 default_icon_size: self.icon_size,
@@ -254,7 +598,37 @@ impl DropDownMenuStyle {
                 text_padding: self.right_text_padding,
                 ..Default::default()
             },
-        )
+        );
+
+        if disabled {
+            left_style.text_color = self.text_color_disabled.get(left_style.text_color);
+            left_style.icon_color = left_style
+                .icon_color
+                .map(|c| self.text_color_disabled.get(c));
+            right_style.text_color = self.text_color_disabled.get(right_style.text_color);
+        }
+
+        (left_style, right_style)
+    }
+
+    fn header_label_style(&self) -> LabelStyle {
+        LabelStyle {
+            text_properties: self.header_text_properties.unwrap_or(self.text_properties),
+            default_icon_size: self.icon_size,
+            text_color: self.header_text_color.unwrap_or(self.text_color),
+            text_padding: self.left_text_padding,
+            ..Default::default()
+        }
+    }
+
+    /// The style of a check mark, which is centered in the check column.
+    fn mark_label_style(&self, left_style: &LabelStyle) -> LabelStyle {
+        LabelStyle {
+            text_padding: Padding::zero(),
+            icon_padding: Padding::zero(),
+            text_icon_spacing: 0.0,
+            ..left_style.clone()
+        }
     }
 
     fn text_row_height(&self) -> f32 {
@@ -263,6 +637,15 @@ impl DropDownMenuStyle {
             + self.left_text_padding.bottom
     }
 
+    fn header_row_height(&self) -> f32 {
+        self.header_text_properties
+            .unwrap_or(self.text_properties)
+            .metrics
+            .line_height
+            + self.left_text_padding.top
+            + self.left_text_padding.bottom
+    }
+
     fn left_padding_info(&self) -> LabelPaddingInfo {
         LabelPaddingInfo {
             default_icon_size: self.icon_size,
@@ -281,7 +664,16 @@ impl DropDownMenuStyle {
         }
     }
 
-    fn measure(&self, entries: &mut [MenuEntryInner]) -> Size {
+    fn mark_padding_info(&self) -> LabelPaddingInfo {
+        LabelPaddingInfo {
+            default_icon_size: 0.0,
+            text_padding: Padding::zero(),
+            icon_padding: Padding::zero(),
+            text_icon_spacing: 0.0,
+        }
+    }
+
+    fn measure(&self, entries: &mut [MenuEntryInner], check_column_width: f32) -> Size {
         if entries.is_empty() {
             return Size::default();
         }
@@ -292,26 +684,31 @@ impl DropDownMenuStyle {
         let mut total_height: f32 = self.outer_padding;
         for entry in entries.iter_mut() {
             match entry {
-                MenuEntryInner::Option {
-                    left_label,
-                    right_label,
-                    start_y,
-                    end_y,
-                    ..
-                } => {
-                    let left_size = left_label.desired_size(|| self.left_padding_info());
-                    let right_size = right_label
+                MenuEntryInner::Option { row, .. }
+                | MenuEntryInner::Toggle { row, .. }
+                | MenuEntryInner::SubMenu { row, .. } => {
+                    let left_size = row.left_label.desired_size(|| self.left_padding_info());
+                    let right_size = row
+                        .right_label
                         .as_mut()
                         .map(|l| l.desired_size(|| self.right_padding_info()))
                         .unwrap_or(Size::zero());
 
-                    let total_width = left_size.width + right_size.width;
+                    let total_width = check_column_width + left_size.width + right_size.width;
 
                     max_width = max_width.max(total_width);
 
-                    *start_y = total_height;
+                    row.start_y = total_height;
                     total_height += text_row_height;
-                    *end_y = total_height;
+                    row.end_y = total_height;
+                }
+                MenuEntryInner::Header { label, y } => {
+                    let size = label.desired_size(|| self.left_padding_info());
+
+                    max_width = max_width.max(check_column_width + size.width);
+
+                    *y = total_height;
+                    total_height += self.header_row_height();
                 }
                 MenuEntryInner::Divider { y } => {
                     *y = total_height + self.divider_padding;
@@ -365,6 +762,25 @@ impl<A: Clone + 'static> DropDownMenuBuilder<A> {
     }
 
     pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> DropDownMenu {
+        self.build_inner(None, window_cx)
+    }
+
+    /// Build the menu with a listener which is called with the `unique_id`
+    /// of the selected entry before the action is sent. This is used by
+    /// elements which open a drop-down menu of their own.
+    pub(crate) fn build_with_listener(
+        self,
+        listener: Box<dyn FnMut(usize)>,
+        window_cx: &mut WindowContext<'_, A>,
+    ) -> DropDownMenu {
+        self.build_inner(Some(listener), window_cx)
+    }
+
+    fn build_inner(
+        self,
+        listener: Option<Box<dyn FnMut(usize)>>,
+        window_cx: &mut WindowContext<'_, A>,
+    ) -> DropDownMenu {
         let DropDownMenuBuilder {
             action,
             entries,
@@ -376,7 +792,10 @@ impl<A: Clone + 'static> DropDownMenuBuilder<A> {
 
         let shared_state = Rc::new(RefCell::new(SharedState {
             new_entries: None,
+            checked_changes: Vec::new(),
             open_requested: false,
+            open_below_requested: None,
+            open_bounds: None,
         }));
 
         let style = window_cx
@@ -385,17 +804,18 @@ impl<A: Clone + 'static> DropDownMenuBuilder<A> {
             .get::<DropDownMenuStyle>(window_cx.builder_class(class));
         let cursor_icon = style.cursor_icon;
 
-        let mut entries = build_entries(entries, &style, &mut window_cx.res.font_system);
-
-        let size = style.measure(&mut entries);
+        let menu = MenuInner::new(entries, &style, &mut window_cx.res.font_system);
 
         let el = ElementBuilder::new(DropDownMenuElement {
             shared_state: Rc::clone(&shared_state),
             action,
-            entries,
-            size,
+            listener,
+            menu,
             active: false,
-            hovered_entry_index: None,
+            position,
+            anchor: None,
+            bounds: Rect::new(position, Size::zero()),
+            popup: MenuPopup::new(),
             cursor_icon,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
@@ -405,7 +825,9 @@ impl<A: Clone + 'static> DropDownMenuBuilder<A> {
                 | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
                 | ElementFlags::LISTENS_TO_FOCUS_CHANGE
                 | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
-                | ElementFlags::LISTENS_TO_POSITION_CHANGE,
+                | ElementFlags::LISTENS_TO_POSITION_CHANGE
+                | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED,
         )
         .build(window_cx);
 
@@ -413,71 +835,763 @@ impl<A: Clone + 'static> DropDownMenuBuilder<A> {
     }
 }
 
-struct DropDownMenuElement<A: Clone + 'static> {
-    shared_state: Rc<RefCell<SharedState>>,
-    action: Option<Box<dyn FnMut(usize) -> A>>,
-    entries: Vec<MenuEntryInner>,
-    size: Size,
-    active: bool,
-    hovered_entry_index: Option<usize>,
-    cursor_icon: Option<CursorIcon>,
+/// A menu (or nested menu) which is currently open.
+struct OpenMenu {
+    /// The bounds of the menu in window coordinates.
+    bounds: Rect,
+    /// The entry which is hovered or highlighted with the keyboard. If this
+    /// menu is not the last open menu, then this is the entry whose nested
+    /// menu is open.
+    highlighted_entry: Option<usize>,
 }
 
-impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
-    fn on_event(
+/// The result of sending an event to a [`MenuPopup`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum MenuPopupResponse {
+    NotCaptured,
+    Captured,
+    /// The entry with the given unique ID was selected. The menu should be
+    /// closed.
+    Selected(usize),
+    /// The user pressed Escape in the top-level menu. The menu should be
+    /// closed.
+    Close,
+    /// The user pressed the left or right arrow key when there was no nested
+    /// menu to close or open.
+    NavigateOut {
+        reverse: bool,
+    },
+}
+
+/// The state of an open menu and its open nested menus, shared by the
+/// [`DropDownMenu`] and [`MenuBar`](super::menu_bar::MenuBar) elements.
+///
+/// The menu entries themselves are stored separately in a [`MenuInner`].
+/// Methods which change what is displayed request a repaint, but it is up to
+/// the element to resize itself to contain [`MenuPopup::bounds`].
+pub(crate) struct MenuPopup {
+    /// The top-level menu followed by any open nested menus.
+    open_menus: Vec<OpenMenu>,
+    type_ahead_text: String,
+    type_ahead_instant: Option<Instant>,
+}
+
+impl MenuPopup {
+    pub fn new() -> Self {
+        Self {
+            open_menus: Vec::new(),
+            type_ahead_text: String::new(),
+            type_ahead_instant: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.open_menus.is_empty()
+    }
+
+    /// The bounds containing all open menus, or `None` if the menu is closed.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.open_menus
+            .iter()
+            .map(|m| m.bounds)
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Open the top-level menu at the given position (in window coordinates),
+    /// moving it if needed so that it fits inside the window.
+    ///
+    /// If `highlight_first` is `true`, then the first selectable entry is
+    /// highlighted.
+    pub fn open<A: Clone + 'static>(
         &mut self,
-        event: ElementEvent,
+        root: &MenuInner,
+        position: Point,
+        highlight_first: bool,
         cx: &mut ElementContext<'_, A>,
-    ) -> EventCaptureStatus {
-        match event {
-            ElementEvent::CustomStateChanged => {
-                let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+    ) {
+        let highlighted_entry = if highlight_first {
+            root.next_entry(None, false)
+        } else {
+            None
+        };
+
+        self.open_rect(Rect::new(position, root.size), highlighted_entry, cx);
+    }
 
-                let mut show = false;
-                let mut request_focus = false;
-                if shared_state.open_requested && !self.active {
-                    self.active = true;
-                    show = true;
-                    request_focus = true;
-                }
-                shared_state.open_requested = false;
+    /// Open the top-level menu just below `anchor` (in window coordinates),
+    /// with the menu stretched to be at least as wide as `anchor`.
+    ///
+    /// The given entry is highlighted if it is selectable.
+    pub fn open_below<A: Clone + 'static>(
+        &mut self,
+        root: &MenuInner,
+        anchor: Rect,
+        highlighted_entry: Option<usize>,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        let rect = Rect::new(
+            Point::new(anchor.min_x(), anchor.max_y()),
+            Size::new(root.size.width.max(anchor.width()), root.size.height),
+        );
+        let highlighted_entry = highlighted_entry.filter(|i| {
+            root.entries
+                .get(*i)
+                .map(|entry| entry.is_selectable())
+                .unwrap_or(false)
+        });
+
+        self.open_rect(rect, highlighted_entry, cx);
+    }
 
-                if let Some(new_entries) = shared_state.new_entries.take() {
-                    let style = cx.res.style_system.get(cx.class());
+    fn open_rect<A: Clone + 'static>(
+        &mut self,
+        rect: Rect,
+        highlighted_entry: Option<usize>,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        let layout_info = layout(rect, cx.window_size());
 
-                    self.entries = build_entries(new_entries, style, &mut cx.res.font_system);
+        self.open_menus.clear();
+        self.open_menus.push(OpenMenu {
+            bounds: layout_info.new_bounds.unwrap_or(rect),
+            highlighted_entry,
+        });
+        self.type_ahead_text.clear();
 
-                    self.size = style.measure(&mut self.entries);
+        cx.request_repaint();
+    }
 
-                    if self.active {
-                        let rect = Rect::new(cx.rect().origin, self.size);
-                        let layout_info = layout(rect, cx.window_size());
-                        if let Some(new_bounds) = layout_info.new_bounds {
-                            cx.set_rect(new_bounds);
-                        } else {
-                            cx.set_rect(rect)
-                        }
-                        cx.request_repaint();
-                    } else {
-                        cx.set_rect(Rect::new(cx.rect().origin, Size::zero()));
-                    }
+    pub fn close(&mut self) {
+        self.open_menus.clear();
+        self.type_ahead_text.clear();
+    }
 
-                    show = false;
-                }
+    /// Highlight the given entry in the open menu at `depth`, closing any
+    /// menus nested deeper than that.
+    ///
+    /// If `open_sub_menu` is `true` and the entry has a nested menu, then
+    /// the nested menu will be opened.
+    fn highlight_entry<A: Clone + 'static>(
+        &mut self,
+        root: &MenuInner,
+        depth: usize,
+        index: Option<usize>,
+        open_sub_menu: bool,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        self.open_menus.truncate(depth + 1);
+        self.open_menus[depth].highlighted_entry = index;
+
+        if let Some(index) = index.filter(|_| open_sub_menu) {
+            let parent_bounds = self.open_menus[depth].bounds;
+            let menu = menu_at_depth(root, &self.open_menus, depth);
+
+            if let (Some(sub_menu), Some(row)) = (menu.sub_menu(index), menu.entries[index].row()) {
+                let outer_padding = cx
+                    .res
+                    .style_system
+                    .get::<DropDownMenuStyle>(cx.class())
+                    .outer_padding;
+
+                let bounds = sub_menu_layout(
+                    parent_bounds,
+                    row.start_y - outer_padding,
+                    sub_menu.size,
+                    cx.window_size(),
+                );
+
+                self.open_menus.push(OpenMenu {
+                    bounds,
+                    highlighted_entry: None,
+                });
+            }
+        }
 
-                if show {
-                    let rect = Rect::new(cx.rect().origin, self.size);
-                    let layout_info = layout(rect, cx.window_size());
-                    if let Some(new_bounds) = layout_info.new_bounds {
-                        cx.set_rect(new_bounds);
-                    } else {
-                        cx.set_rect(rect)
-                    }
-                }
+        cx.request_repaint();
+    }
 
-                if request_focus {
-                    cx.steal_temporary_focus();
-                    cx.listen_to_pointer_clicked_off();
+    /// Open the nested menu of the highlighted entry in the last open menu
+    /// and highlight its first entry.
+    ///
+    /// Returns `false` if the highlighted entry has no nested menu.
+    fn enter_sub_menu<A: Clone + 'static>(
+        &mut self,
+        root: &MenuInner,
+        cx: &mut ElementContext<'_, A>,
+    ) -> bool {
+        let depth = self.open_menus.len() - 1;
+        let highlighted_entry = self.open_menus[depth].highlighted_entry;
+
+        self.highlight_entry(root, depth, highlighted_entry, true, cx);
+
+        if self.open_menus.len() == depth + 1 {
+            return false;
+        }
+
+        let first_entry = menu_at_depth(root, &self.open_menus, depth + 1).next_entry(None, false);
+        self.highlight_entry(root, depth + 1, first_entry, false, cx);
+
+        true
+    }
+
+    /// Close the last open nested menu.
+    fn leave_sub_menu<A: Clone + 'static>(&mut self, cx: &mut ElementContext<'_, A>) {
+        self.type_ahead_text.clear();
+        self.open_menus.pop();
+        cx.request_repaint();
+    }
+
+    /// Activate the given entry in the open menu at `depth`.
+    fn select_entry<A: Clone + 'static>(
+        &mut self,
+        root: &mut MenuInner,
+        depth: usize,
+        index: usize,
+        cx: &mut ElementContext<'_, A>,
+    ) -> MenuPopupResponse {
+        let menu = menu_at_depth_mut(root, &self.open_menus, depth);
+        if !menu.entries[index].is_selectable() {
+            return MenuPopupResponse::Captured;
+        }
+
+        match &menu.entries[index] {
+            MenuEntryInner::Option { unique_id, .. } => MenuPopupResponse::Selected(*unique_id),
+            MenuEntryInner::Toggle {
+                unique_id,
+                group,
+                checked,
+                ..
+            } => {
+                // Radio entries stay checked when selected again.
+                let new_checked = group.is_some() || !*checked;
+                let unique_id = *unique_id;
+
+                menu.set_checked(index, new_checked);
+
+                MenuPopupResponse::Selected(unique_id)
+            }
+            MenuEntryInner::SubMenu { .. } => {
+                if self.open_menus.len() == depth + 1 {
+                    self.highlight_entry(root, depth, Some(index), true, cx);
+                }
+
+                MenuPopupResponse::Captured
+            }
+            MenuEntryInner::Header { .. } | MenuEntryInner::Divider { .. } => {
+                MenuPopupResponse::Captured
+            }
+        }
+    }
+
+    fn type_ahead<A: Clone + 'static>(
+        &mut self,
+        root: &MenuInner,
+        text: &str,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        let now = Instant::now();
+        if self
+            .type_ahead_instant
+            .map(|instant| now.duration_since(instant) > TYPE_AHEAD_TIMEOUT)
+            .unwrap_or(true)
+        {
+            self.type_ahead_text.clear();
+        }
+        self.type_ahead_instant = Some(now);
+        self.type_ahead_text.push_str(&text.to_lowercase());
+
+        let depth = self.open_menus.len() - 1;
+        let menu = menu_at_depth(root, &self.open_menus, depth);
+        let current = self.open_menus[depth].highlighted_entry;
+
+        let mut chars = self.type_ahead_text.chars();
+        let first_char = chars.next();
+        let found = if chars.all(|c| Some(c) == first_char) {
+            // Typing the same character repeatedly cycles through the entries
+            // starting with that character.
+            let prefix = &self.type_ahead_text[..first_char.map(|c| c.len_utf8()).unwrap_or(0)];
+            menu.find_by_prefix(prefix, current.map(|i| i + 1).unwrap_or(0))
+        } else {
+            menu.find_by_prefix(&self.type_ahead_text, current.unwrap_or(0))
+        };
+
+        if found.is_some() && found != current {
+            self.highlight_entry(root, depth, found, false, cx);
+        }
+    }
+
+    /// Returns `true` if the pointer is over an open menu.
+    pub fn contains(&self, position: Point) -> bool {
+        self.open_menus.iter().any(|m| m.bounds.contains(position))
+    }
+
+    /// Update the hovered entry.
+    ///
+    /// Returns `true` if the pointer is over a selectable entry.
+    pub fn pointer_moved<A: Clone + 'static>(
+        &mut self,
+        root: &MenuInner,
+        position: Point,
+        cx: &mut ElementContext<'_, A>,
+    ) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+
+        // Nested menus are drawn on top of their parents.
+        let hovered_depth = self
+            .open_menus
+            .iter()
+            .rposition(|m| m.bounds.contains(position));
+
+        if let Some(depth) = hovered_depth {
+            let menu = menu_at_depth(root, &self.open_menus, depth);
+            let index = menu.entry_at(position.y - self.open_menus[depth].bounds.min_y());
+
+            let open_sub_menu = index.and_then(|i| menu.sub_menu(i)).is_some()
+                && self.open_menus.len() == depth + 1;
+
+            if self.open_menus[depth].highlighted_entry != index || open_sub_menu {
+                self.highlight_entry(root, depth, index, true, cx);
+            }
+
+            index.is_some()
+        } else {
+            // Parent menus keep their highlighted entries so that
+            // their nested menus stay open.
+            let depth = self.open_menus.len() - 1;
+            if self.open_menus[depth].highlighted_entry.is_some() {
+                self.highlight_entry(root, depth, None, false, cx);
+            }
+
+            false
+        }
+    }
+
+    /// Returns `MenuPopupResponse::NotCaptured` if the pointer is not over an
+    /// open menu.
+    pub fn pointer_pressed<A: Clone + 'static>(
+        &mut self,
+        root: &mut MenuInner,
+        position: Point,
+        button: PointerButton,
+        cx: &mut ElementContext<'_, A>,
+    ) -> MenuPopupResponse {
+        let Some(depth) = self
+            .open_menus
+            .iter()
+            .rposition(|m| m.bounds.contains(position))
+        else {
+            return MenuPopupResponse::NotCaptured;
+        };
+
+        if button != PointerButton::Primary {
+            return MenuPopupResponse::Captured;
+        }
+
+        let menu = menu_at_depth(root, &self.open_menus, depth);
+        match menu.entry_at(position.y - self.open_menus[depth].bounds.min_y()) {
+            Some(index) => self.select_entry(root, depth, index, cx),
+            None => MenuPopupResponse::Captured,
+        }
+    }
+
+    pub fn key_down<A: Clone + 'static>(
+        &mut self,
+        root: &mut MenuInner,
+        key_event: &KeyboardEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> MenuPopupResponse {
+        if !self.is_open()
+            || key_event.state != KeyState::Down
+            || key_event
+                .modifiers
+                .intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::META)
+        {
+            return MenuPopupResponse::NotCaptured;
+        }
+
+        let depth = self.open_menus.len() - 1;
+        let menu = menu_at_depth(root, &self.open_menus, depth);
+        let highlighted_entry = self.open_menus[depth].highlighted_entry;
+
+        match key_event.code {
+            Code::ArrowDown | Code::ArrowUp => {
+                let index = menu.next_entry(highlighted_entry, key_event.code == Code::ArrowUp);
+                self.type_ahead_text.clear();
+                self.highlight_entry(root, depth, index, false, cx);
+            }
+            Code::Home | Code::End => {
+                let index = menu.next_entry(None, key_event.code == Code::End);
+                self.type_ahead_text.clear();
+                self.highlight_entry(root, depth, index, false, cx);
+            }
+            Code::ArrowRight => {
+                if !self.enter_sub_menu(root, cx) {
+                    return MenuPopupResponse::NavigateOut { reverse: false };
+                }
+
+                self.type_ahead_text.clear();
+            }
+            Code::ArrowLeft => {
+                if depth == 0 {
+                    return MenuPopupResponse::NavigateOut { reverse: true };
+                }
+
+                self.leave_sub_menu(cx);
+            }
+            Code::Escape => {
+                if depth == 0 {
+                    return MenuPopupResponse::Close;
+                }
+
+                self.leave_sub_menu(cx);
+            }
+            Code::Enter | Code::NumpadEnter | Code::Space => {
+                // Let the space character be used for type-ahead
+                // searches which contain spaces.
+                if key_event.code == Code::Space && !self.type_ahead_text.is_empty() {
+                    return MenuPopupResponse::NotCaptured;
+                }
+
+                if let Some(index) = highlighted_entry {
+                    if menu.sub_menu(index).is_some() {
+                        self.enter_sub_menu(root, cx);
+                    } else {
+                        return self.select_entry(root, depth, index, cx);
+                    }
+                }
+            }
+            _ => return MenuPopupResponse::NotCaptured,
+        }
+
+        MenuPopupResponse::Captured
+    }
+
+    pub fn text_composition<A: Clone + 'static>(
+        &mut self,
+        root: &MenuInner,
+        event: &CompositionEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> MenuPopupResponse {
+        if !self.is_open() {
+            return MenuPopupResponse::NotCaptured;
+        }
+
+        if event.state == CompositionState::End {
+            let text: String = event.data.chars().filter(|c| !c.is_control()).collect();
+            if !text.is_empty() {
+                self.type_ahead(root, &text, cx);
+            }
+        }
+
+        MenuPopupResponse::Captured
+    }
+
+    /// Render the open menus. `origin` is the position of the element in
+    /// window coordinates.
+    pub fn render(
+        &self,
+        root: &mut MenuInner,
+        origin: Point,
+        z_index_base: ZIndex,
+        style: &DropDownMenuStyle,
+        font_system: &mut FontSystem,
+        primitives: &mut PrimitiveGroup,
+    ) {
+        let (left_style_idle, right_style_idle) = style.label_styles(false, false);
+        let (left_style_hover, right_style_hover) = style.label_styles(true, false);
+        let (left_style_disabled, right_style_disabled) = style.label_styles(false, true);
+        let mark_style_idle = style.mark_label_style(&left_style_idle);
+        let mark_style_hover = style.mark_label_style(&left_style_hover);
+        let mark_style_disabled = style.mark_label_style(&left_style_disabled);
+        let header_style = style.header_label_style();
+
+        for (depth, open_menu) in self.open_menus.iter().enumerate() {
+            let menu = menu_at_depth_mut(root, &self.open_menus, depth);
+            let menu_origin = open_menu.bounds.origin - origin.to_vector();
+            let menu_width = open_menu.bounds.width();
+            let check_column_width = menu.check_column_width;
+
+            let label_size = Size::new(
+                menu_width - (style.outer_padding * 2.0),
+                style.text_row_height(),
+            );
+            let left_label_size =
+                Size::new(label_size.width - check_column_width, label_size.height);
+
+            let mut text_primitives: Vec<TextPrimitive> =
+                Vec::with_capacity(menu.entries.len() * 3);
+            let mut divider_primitives: Vec<SolidQuadPrimitive> =
+                Vec::with_capacity(menu.entries.len());
+
+            // Nested menus are drawn on top of their parents.
+            let z_index = z_index_base + (depth * 3) as ZIndex;
+
+            primitives.set_z_index(z_index);
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive(Rect::new(menu_origin, open_menu.bounds.size)),
+            );
+
+            for (i, entry) in menu.entries.iter_mut().enumerate() {
+                let (row, checked_mark) = match entry {
+                    MenuEntryInner::Option { row, .. } | MenuEntryInner::SubMenu { row, .. } => {
+                        (row, None)
+                    }
+                    MenuEntryInner::Toggle {
+                        row, mark, checked, ..
+                    } => (row, mark.as_mut().filter(|_| *checked)),
+                    MenuEntryInner::Header { label, y } => {
+                        let header_primitives = label.render(
+                            Rect::new(
+                                menu_origin
+                                    + Vector::new(style.outer_padding + check_column_width, *y),
+                                Size::new(left_label_size.width, style.header_row_height()),
+                            ),
+                            &header_style,
+                            font_system,
+                        );
+
+                        if let Some(p) = header_primitives.text {
+                            text_primitives.push(p);
+                        }
+
+                        continue;
+                    }
+                    MenuEntryInner::Divider { y } => {
+                        divider_primitives.push(
+                            SolidQuadBuilder::new(Size::new(label_size.width, style.divider_width))
+                                .bg_color(style.divider_color)
+                                .position(menu_origin + Vector::new(style.outer_padding, *y))
+                                .into(),
+                        );
+
+                        continue;
+                    }
+                };
+
+                let hovered = open_menu.highlighted_entry == Some(i);
+                let row_origin = menu_origin + Vector::new(style.outer_padding, row.start_y);
+
+                let (left_style, right_style, mark_style) = if row.disabled {
+                    (
+                        &left_style_disabled,
+                        &right_style_disabled,
+                        &mark_style_disabled,
+                    )
+                } else if hovered {
+                    (&left_style_hover, &right_style_hover, &mark_style_hover)
+                } else {
+                    (&left_style_idle, &right_style_idle, &mark_style_idle)
+                };
+
+                if hovered {
+                    primitives.set_z_index(z_index + 1);
+                    primitives.add(
+                        style
+                            .entry_bg_quad_hover
+                            .create_primitive(Rect::new(row_origin, label_size)),
+                    );
+                }
+
+                let left_primitives = row.left_label.render(
+                    Rect::new(
+                        row_origin + Vector::new(check_column_width, 0.0),
+                        left_label_size,
+                    ),
+                    left_style,
+                    font_system,
+                );
+
+                if let Some(p) = left_primitives.icon {
+                    text_primitives.push(p);
+                }
+                if let Some(p) = left_primitives.text {
+                    text_primitives.push(p);
+                }
+
+                if let Some(right_label) = &mut row.right_label {
+                    let right_x = menu_origin.x + menu_width
+                        - style.outer_padding
+                        - right_label
+                            .desired_size(|| style.right_padding_info())
+                            .width;
+
+                    let right_primitives = right_label.render(
+                        Rect::new(Point::new(right_x, row_origin.y), label_size),
+                        right_style,
+                        font_system,
+                    );
+
+                    if let Some(p) = right_primitives.text {
+                        text_primitives.push(p);
+                    }
+                }
+
+                if let Some(mark) = checked_mark {
+                    // Center the check mark in the check column.
+                    let mark_width = mark.desired_size(|| style.mark_padding_info()).width;
+                    let mark_x = row_origin.x + ((check_column_width - mark_width) * 0.5);
+
+                    let mark_primitives = mark.render(
+                        Rect::new(
+                            Point::new(mark_x, row_origin.y),
+                            Size::new(mark_width, label_size.height),
+                        ),
+                        mark_style,
+                        font_system,
+                    );
+
+                    if let Some(p) = mark_primitives.text {
+                        text_primitives.push(p);
+                    }
+                }
+            }
+
+            primitives.set_z_index(z_index + 2);
+
+            // It is more efficient to batch primitives together.
+            primitives.add_text_batch(text_primitives);
+            primitives.add_solid_quad_batch(divider_primitives);
+        }
+    }
+}
+
+struct DropDownMenuElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    action: Option<Box<dyn FnMut(usize) -> A>>,
+    listener: Option<Box<dyn FnMut(usize)>>,
+    menu: MenuInner,
+    popup: MenuPopup,
+    active: bool,
+    /// The requested position of the top-level menu.
+    position: Point,
+    /// The rectangle to open the top-level menu below and the `unique_id`
+    /// of the entry to highlight, if the menu was opened with
+    /// [`DropDownMenu::open_below`].
+    anchor: Option<(Rect, Option<usize>)>,
+    /// The bounds of this element, which contain all open menus.
+    bounds: Rect,
+    cursor_icon: Option<CursorIcon>,
+}
+
+impl<A: Clone + 'static> DropDownMenuElement<A> {
+    fn open_popup(&mut self, cx: &mut ElementContext<'_, A>) {
+        match self.anchor {
+            Some((anchor, highlighted_id)) => {
+                let index = highlighted_id.and_then(|id| self.menu.index_of(id));
+                self.popup.open_below(&self.menu, anchor, index, cx);
+            }
+            None => self.popup.open(&self.menu, self.position, false, cx),
+        }
+
+        self.update_bounds(cx);
+    }
+
+    /// Resize this element to contain all of the open menus.
+    fn update_bounds(&mut self, cx: &mut ElementContext<'_, A>) {
+        let open_bounds = self.popup.bounds();
+        RefCell::borrow_mut(&self.shared_state).open_bounds = open_bounds;
+
+        let bounds = open_bounds.unwrap_or(Rect::new(self.position, Size::zero()));
+
+        if self.bounds != bounds {
+            self.bounds = bounds;
+            cx.set_rect(bounds);
+        }
+    }
+
+    fn handle_popup_response(
+        &mut self,
+        response: MenuPopupResponse,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match response {
+            MenuPopupResponse::NotCaptured => return EventCaptureStatus::NotCaptured,
+            MenuPopupResponse::Selected(unique_id) => {
+                if let Some(listener) = &mut self.listener {
+                    (listener)(unique_id);
+                }
+
+                if let Some(action) = &mut self.action {
+                    cx.send_action((action)(unique_id)).unwrap();
+                }
+
+                cx.release_focus();
+                cx.cursor_icon = CursorIcon::Default;
+            }
+            MenuPopupResponse::Close => {
+                cx.release_focus();
+            }
+            MenuPopupResponse::Captured | MenuPopupResponse::NavigateOut { .. } => {
+                self.update_bounds(cx);
+            }
+        }
+
+        EventCaptureStatus::Captured
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged => {
+                let (open_requested, open_below_requested, new_entries, checked_changes) = {
+                    let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                    let open_requested = shared_state.open_requested;
+                    shared_state.open_requested = false;
+                    (
+                        open_requested,
+                        shared_state.open_below_requested.take(),
+                        shared_state.new_entries.take(),
+                        std::mem::take(&mut shared_state.checked_changes),
+                    )
+                };
+
+                let mut show = false;
+                let mut request_focus = false;
+                if open_requested && !self.active {
+                    self.active = true;
+                    show = true;
+                    request_focus = true;
+                }
+
+                if let Some(new_entries) = new_entries {
+                    let style = cx.res.style_system.get(cx.class());
+
+                    self.menu = MenuInner::new(new_entries, style, &mut cx.res.font_system);
+
+                    show = self.active;
+                }
+
+                if !checked_changes.is_empty() {
+                    for (unique_id, checked) in checked_changes {
+                        self.menu.set_checked_by_id(unique_id, checked);
+                    }
+
+                    if self.active {
+                        cx.request_repaint();
+                    }
+                }
+
+                if show {
+                    if request_focus {
+                        self.position = cx.rect().origin;
+                        self.anchor = open_below_requested;
+                    }
+
+                    self.open_popup(cx);
+                }
+
+                if request_focus {
+                    cx.steal_temporary_focus();
+                    cx.listen_to_pointer_clicked_off();
                 }
             }
             ElementEvent::StyleChanged => {
@@ -489,50 +1603,25 @@ impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
             }
             ElementEvent::Focus(false) => {
                 self.active = false;
-                self.hovered_entry_index = None;
-                cx.set_rect(Rect::new(cx.rect().origin, Size::zero()));
+                self.popup.close();
+                self.update_bounds(cx);
             }
             ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
                 if !self.active {
                     return EventCaptureStatus::NotCaptured;
                 }
 
-                let mut new_hovered_entry_index = None;
-                let pointer_y = position.y - cx.rect().min_y();
-                if cx.rect().contains(position) {
-                    for (i, entry) in self.entries.iter().enumerate() {
-                        match entry {
-                            MenuEntryInner::Option { start_y, end_y, .. } => {
-                                if pointer_y >= *start_y && pointer_y < *end_y {
-                                    new_hovered_entry_index = Some(i);
-                                    break;
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-
-                if self.hovered_entry_index != new_hovered_entry_index {
-                    self.hovered_entry_index = new_hovered_entry_index;
-                    cx.request_repaint();
-                }
+                let entry_hovered = self.popup.pointer_moved(&self.menu, position, cx);
+                self.update_bounds(cx);
 
                 if let Some(cursor_icon) = self.cursor_icon {
-                    if self.hovered_entry_index.is_some() {
+                    if entry_hovered {
                         cx.cursor_icon = cursor_icon;
                     }
                 }
 
                 return EventCaptureStatus::Captured;
             }
-            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
-                if !self.active {
-                    return EventCaptureStatus::NotCaptured;
-                }
-
-                return EventCaptureStatus::Captured;
-            }
             ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
                 button, position, ..
             }) => {
@@ -540,37 +1629,20 @@ impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
                     return EventCaptureStatus::NotCaptured;
                 }
 
-                if button == PointerButton::Primary && cx.rect().contains(position) {
-                    let mut selected_entry_id = None;
-                    let pointer_y = position.y - cx.rect().min_y();
-                    for entry in self.entries.iter() {
-                        match entry {
-                            MenuEntryInner::Option {
-                                start_y,
-                                end_y,
-                                unique_id,
-                                ..
-                            } => {
-                                if pointer_y >= *start_y && pointer_y < *end_y {
-                                    selected_entry_id = Some(*unique_id);
-                                    break;
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-
-                    if let Some(id) = selected_entry_id {
-                        if let Some(action) = &mut self.action {
-                            cx.send_action((action)(id)).unwrap();
-                        }
+                let response = self
+                    .popup
+                    .pointer_pressed(&mut self.menu, position, button, cx);
 
+                if response == MenuPopupResponse::NotCaptured {
+                    if cx.rect().contains(position) {
+                        // The user clicked in the empty space between menus.
                         cx.release_focus();
-                        cx.cursor_icon = CursorIcon::Default;
                     }
+
+                    return EventCaptureStatus::Captured;
                 }
 
-                return EventCaptureStatus::Captured;
+                return self.handle_popup_response(response, cx);
             }
             ElementEvent::Pointer(..) => {
                 if !self.active {
@@ -579,15 +1651,34 @@ impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
 
                 return EventCaptureStatus::Captured;
             }
-            ElementEvent::PositionChanged => {
+            ElementEvent::Keyboard(key_event) => {
                 if !self.active {
                     return EventCaptureStatus::NotCaptured;
                 }
 
-                let layout_info = layout(cx.rect(), cx.window_size());
-                if let Some(new_bounds) = layout_info.new_bounds {
-                    cx.set_rect(new_bounds);
+                let response = self.popup.key_down(&mut self.menu, &key_event, cx);
+                return self.handle_popup_response(response, cx);
+            }
+            ElementEvent::TextComposition(composition_event) => {
+                if !self.active {
+                    return EventCaptureStatus::NotCaptured;
                 }
+
+                let response = self
+                    .popup
+                    .text_composition(&self.menu, &composition_event, cx);
+                return self.handle_popup_response(response, cx);
+            }
+            ElementEvent::PositionChanged => {
+                // Ignore position changes caused by opening nested menus.
+                if !self.active || cx.rect() == self.bounds {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                self.position = cx.rect().origin;
+                self.bounds = cx.rect();
+                self.anchor = None;
+                self.open_popup(cx);
             }
             _ => {}
         }
@@ -598,107 +1689,23 @@ impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
     fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
         let style: &DropDownMenuStyle = cx.res.style_system.get(cx.class);
 
-        let (left_style_idle, right_style_idle) = style.label_styles(false);
-        let (left_style_hover, right_style_hover) = style.label_styles(true);
-
-        let label_size = Size::new(
-            self.size.width - (style.outer_padding * 2.0),
-            style.text_row_height(),
-        );
-
-        let mut text_primitives: Vec<TextPrimitive> = Vec::with_capacity(self.entries.len() * 3);
-        let mut divider_primitives: Vec<SolidQuadPrimitive> =
-            Vec::with_capacity(self.entries.len());
-
-        primitives.add(
-            style
-                .back_quad
-                .create_primitive(Rect::from_size(cx.bounds_size)),
+        self.popup.render(
+            &mut self.menu,
+            self.bounds.origin,
+            0,
+            style,
+            &mut cx.res.font_system,
+            primitives,
         );
-
-        for (i, entry) in self.entries.iter_mut().enumerate() {
-            match entry {
-                MenuEntryInner::Option {
-                    left_label,
-                    right_label,
-                    start_y,
-                    ..
-                } => {
-                    let hovered = if let Some(hovered_index) = self.hovered_entry_index {
-                        i == hovered_index
-                    } else {
-                        false
-                    };
-
-                    if hovered {
-                        primitives.set_z_index(1);
-                        primitives.add(style.entry_bg_quad_hover.create_primitive(Rect::new(
-                            Point::new(style.outer_padding, *start_y),
-                            label_size,
-                        )));
-                    }
-
-                    let left_primitives = left_label.render(
-                        Rect::new(Point::new(style.outer_padding, *start_y), label_size),
-                        if hovered {
-                            &left_style_hover
-                        } else {
-                            &left_style_idle
-                        },
-                        &mut cx.res.font_system,
-                    );
-
-                    if let Some(p) = left_primitives.icon {
-                        text_primitives.push(p);
-                    }
-                    if let Some(p) = left_primitives.text {
-                        text_primitives.push(p);
-                    }
-
-                    if let Some(right_label) = right_label {
-                        let right_style = if hovered {
-                            &right_style_hover
-                        } else {
-                            &right_style_idle
-                        };
-
-                        let right_x = cx.bounds_size.width
-                            - style.outer_padding
-                            - right_label
-                                .desired_size(|| style.right_padding_info())
-                                .width;
-
-                        let right_primitives = right_label.render(
-                            Rect::new(Point::new(right_x, *start_y), label_size),
-                            right_style,
-                            &mut cx.res.font_system,
-                        );
-
-                        if let Some(p) = right_primitives.text {
-                            text_primitives.push(p);
-                        }
-                    }
-                }
-                MenuEntryInner::Divider { y } => divider_primitives.push(
-                    SolidQuadBuilder::new(Size::new(label_size.width, style.divider_width))
-                        .bg_color(style.divider_color)
-                        .position(Point::new(style.outer_padding, *y))
-                        .into(),
-                ),
-            }
-        }
-
-        primitives.set_z_index(2);
-
-        // It is more efficient to batch primitives together.
-        primitives.add_text_batch(text_primitives);
-        primitives.add_solid_quad_batch(divider_primitives);
     }
 }
 
 struct SharedState {
     new_entries: Option<Vec<MenuEntry>>,
+    checked_changes: Vec<(usize, bool)>,
     open_requested: bool,
+    open_below_requested: Option<(Rect, Option<usize>)>,
+    open_bounds: Option<Rect>,
 }
 
 /// A handle to a [`DropDownMenuElement`].
@@ -728,18 +1735,105 @@ impl DropDownMenu {
     /// Note this will *always* trigger an element update, so use
     /// this method sparingly.
     pub fn set_entries(&mut self, entries: Vec<MenuEntry>) {
-        RefCell::borrow_mut(&self.shared_state).new_entries = Some(entries);
+        {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+            shared_state.new_entries = Some(entries);
+            shared_state.checked_changes.clear();
+        }
+        self.el.notify_custom_state_change();
+    }
+
+    /// Set the checked state of the [`MenuEntry::Check`] or
+    /// [`MenuEntry::Radio`] entry with the given `unique_id`. Checking a
+    /// radio entry unchecks the other entries in its group.
+    ///
+    /// Note, check and radio entries update their own checked state when
+    /// they are selected, so this only needs to be called when the state
+    /// is changed by something other than this menu.
+    ///
+    /// Note this will *always* trigger an element update, but it is much
+    /// cheaper than rebuilding the menu with [`DropDownMenu::set_entries`].
+    pub fn set_checked(&mut self, unique_id: usize, checked: bool) {
+        RefCell::borrow_mut(&self.shared_state)
+            .checked_changes
+            .push((unique_id, checked));
         self.el.notify_custom_state_change();
     }
 
+    /// The bounding rectangle of all of the open menus (including nested
+    /// menus), or `None` if the menu is closed.
+    ///
+    /// The element resizes itself to fit the open menus, so unlike
+    /// [`DropDownMenu::rect`], this is always up to date.
+    pub fn open_bounds(&self) -> Option<Rect> {
+        RefCell::borrow(&self.shared_state).open_bounds
+    }
+
     pub fn open(&mut self, position: Option<Point>) {
         if let Some(pos) = position {
             self.set_position(pos);
         }
 
-        RefCell::borrow_mut(&self.shared_state).open_requested = true;
+        {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+            shared_state.open_requested = true;
+            shared_state.open_below_requested = None;
+        }
         self.el.notify_custom_state_change();
     }
+
+    /// Open the menu just below `anchor` (in window coordinates), with the
+    /// menu stretched to be at least as wide as `anchor`. This is useful for
+    /// menus which are opened by clicking a button.
+    ///
+    /// If `highlighted_id` is the `unique_id` of a top-level entry, then that
+    /// entry will be highlighted.
+    pub fn open_below(&mut self, anchor: Rect, highlighted_id: Option<usize>) {
+        {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+            shared_state.open_requested = true;
+            shared_state.open_below_requested = Some((anchor, highlighted_id));
+        }
+        self.el.notify_custom_state_change();
+    }
+}
+
+/// The open menu at the given depth, where `0` is the top-level menu.
+///
+/// Every menu above the given depth must have its sub-menu entry
+/// highlighted.
+fn menu_at_depth<'a>(root: &'a MenuInner, open_menus: &[OpenMenu], depth: usize) -> &'a MenuInner {
+    let mut menu = root;
+    for open_menu in open_menus[..depth].iter() {
+        let index = open_menu
+            .highlighted_entry
+            .expect("parent menu has a highlighted sub-menu entry");
+
+        menu = match &menu.entries[index] {
+            MenuEntryInner::SubMenu { menu, .. } => menu,
+            _ => unreachable!(),
+        };
+    }
+    menu
+}
+
+fn menu_at_depth_mut<'a>(
+    root: &'a mut MenuInner,
+    open_menus: &[OpenMenu],
+    depth: usize,
+) -> &'a mut MenuInner {
+    let mut menu = root;
+    for open_menu in open_menus[..depth].iter() {
+        let index = open_menu
+            .highlighted_entry
+            .expect("parent menu has a highlighted sub-menu entry");
+
+        menu = match &mut menu.entries[index] {
+            MenuEntryInner::SubMenu { menu, .. } => menu,
+            _ => unreachable!(),
+        };
+    }
+    menu
 }
 
 fn build_entries(
@@ -747,11 +1841,46 @@ fn build_entries(
     style: &DropDownMenuStyle,
     font_system: &mut FontSystem,
 ) -> Vec<MenuEntryInner> {
-    let (left_style, right_style) = style.label_styles(false);
+    let (left_style, right_style) = style.label_styles(false, false);
+    let mark_style = style.mark_label_style(&left_style);
+    let header_style = style.header_label_style();
+
+    let new_label = |text: String,
+                     icon: Option<IconID>,
+                     icon_scale: IconScale,
+                     style: &LabelStyle,
+                     font_system: &mut FontSystem| {
+        LabelInner::new(
+            Some(text),
+            icon,
+            Vector::default(),
+            Vector::default(),
+            None,
+            icon_scale,
+            Default::default(),
+            style,
+            font_system,
+        )
+    };
+
+    let new_row = |left_text: String,
+                   left_icon: Option<IconID>,
+                   icon_scale: IconScale,
+                   right_text: Option<String>,
+                   disabled: bool,
+                   font_system: &mut FontSystem| MenuRow {
+        left_label: new_label(left_text, left_icon, icon_scale, &left_style, font_system),
+        right_label: right_text
+            .map(|text| new_label(text, None, IconScale::default(), &right_style, font_system)),
+        start_y: 0.0,
+        end_y: 0.0,
+        disabled,
+    };
 
     entries
         .into_iter()
-        .map(|entry| match entry {
+        .map(MenuEntry::unwrap_disabled)
+        .map(|(entry, disabled)| match entry {
             MenuEntry::Option {
                 left_icon,
                 icon_scale,
@@ -759,35 +1888,81 @@ fn build_entries(
                 right_text,
                 unique_id,
             } => MenuEntryInner::Option {
-                left_label: LabelInner::new(
-                    Some(left_text),
+                row: new_row(
+                    left_text,
                     left_icon,
-                    Vector::default(),
-                    Vector::default(),
-                    None,
                     icon_scale,
-                    Default::default(),
-                    &left_style,
+                    right_text,
+                    disabled,
                     font_system,
                 ),
-                right_label: right_text.map(|text| {
-                    LabelInner::new(
-                        Some(text),
-                        None,
-                        Vector::default(),
-                        Vector::default(),
-                        None,
-                        IconScale::default(),
-                        Default::default(),
-                        &right_style,
-                        font_system,
-                    )
+                unique_id,
+            },
+            MenuEntry::Check {
+                left_text,
+                right_text,
+                checked,
+                unique_id,
+            } => MenuEntryInner::Toggle {
+                row: new_row(
+                    left_text,
+                    None,
+                    IconScale::default(),
+                    right_text,
+                    disabled,
+                    font_system,
+                ),
+                mark: style.check_mark.clone().map(|text| {
+                    new_label(text, None, IconScale::default(), &mark_style, font_system)
+                }),
+                unique_id,
+                group: None,
+                checked,
+            },
+            MenuEntry::Radio {
+                left_text,
+                right_text,
+                group,
+                checked,
+                unique_id,
+            } => MenuEntryInner::Toggle {
+                row: new_row(
+                    left_text,
+                    None,
+                    IconScale::default(),
+                    right_text,
+                    disabled,
+                    font_system,
+                ),
+                mark: style.radio_mark.clone().map(|text| {
+                    new_label(text, None, IconScale::default(), &mark_style, font_system)
                 }),
-                start_y: 0.0,
-                end_y: 0.0,
                 unique_id,
+                group: Some(group),
+                checked,
+            },
+            MenuEntry::SubMenu {
+                left_icon,
+                icon_scale,
+                text,
+                entries,
+            } => MenuEntryInner::SubMenu {
+                row: new_row(
+                    text,
+                    left_icon,
+                    icon_scale,
+                    style.sub_menu_indicator.clone(),
+                    disabled,
+                    font_system,
+                ),
+                menu: MenuInner::new(entries, style, font_system),
+            },
+            MenuEntry::Header(text) => MenuEntryInner::Header {
+                label: new_label(text, None, IconScale::default(), &header_style, font_system),
+                y: 0.0,
             },
             MenuEntry::Divider => MenuEntryInner::Divider { y: 0.0 },
+            MenuEntry::Disabled(_) => unreachable!(),
         })
         .collect()
 }
@@ -834,6 +2009,33 @@ fn layout(current_bounds: Rect, window_size: Size) -> LayoutInfo {
     }
 }
 
+/// The bounds of a nested menu.
+///
+/// The nested menu is placed to the right of its parent with its top edge at
+/// `entry_y` (relative to the top of the parent). If there is not enough room
+/// on the right side of the window, then it is placed on the left side of its
+/// parent instead. It is then moved up if it would extend past the bottom of
+/// the window.
+fn sub_menu_layout(parent_bounds: Rect, entry_y: f32, size: Size, window_size: Size) -> Rect {
+    let width = size.width.min(window_size.width);
+    let height = size.height.min(window_size.height);
+
+    let x = if parent_bounds.max_x() + width <= window_size.width {
+        parent_bounds.max_x()
+    } else if parent_bounds.min_x() - width >= 0.0 {
+        parent_bounds.min_x() - width
+    } else {
+        // There is not enough room on either side, so overlap the parent.
+        (window_size.width - width).max(0.0)
+    };
+
+    let y = (parent_bounds.min_y() + entry_y)
+        .min(window_size.height - height)
+        .max(0.0);
+
+    Rect::new(Point::new(x, y), Size::new(width, height))
+}
+
 struct LayoutInfo {
     new_bounds: Option<Rect>,
     // TODO: use these
@@ -842,3 +2044,234 @@ struct LayoutInfo {
     #[allow(unused)]
     height_clipped: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        MenuSelected(usize),
+    }
+
+    struct MenuTestApp {
+        menu: DropDownMenu,
+    }
+
+    impl Application for MenuTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let menu = DropDownMenu::builder()
+                .entries(vec![
+                    MenuEntry::option("Apple", 0),
+                    MenuEntry::option("Banana", 1),
+                    MenuEntry::Divider,
+                    MenuEntry::sub_menu(
+                        "More",
+                        vec![MenuEntry::option("Cherry", 2), MenuEntry::option("Date", 3)],
+                    ),
+                    MenuEntry::option("Blueberry", 4),
+                ])
+                .on_entry_selected(TestAction::MenuSelected)
+                .build(&mut cx.main_window());
+
+            Ok(Self { menu })
+        }
+    }
+
+    fn open_menu(app: &mut HeadlessApp<MenuTestApp>, position: Point) -> Rect {
+        app.app_mut().menu.open(Some(position));
+        app.process_updates();
+        app.app().menu.open_bounds().unwrap()
+    }
+
+    #[test]
+    fn test_drop_down_menu_keyboard_navigation() {
+        let mut app = HeadlessApp::<MenuTestApp>::new(AppConfig::default()).unwrap();
+
+        let press = |app: &mut HeadlessApp<MenuTestApp>, code| {
+            app.key_press(MAIN_WINDOW, code, Modifiers::empty());
+        };
+
+        open_menu(&mut app, point(10.0, 10.0));
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(1)]);
+
+        // The menu is closed after selecting an entry.
+        press(&mut app, Code::Enter);
+        assert!(app.drain_actions().is_empty());
+
+        // Navigate into a nested menu, back out of it, and into it again.
+        open_menu(&mut app, point(10.0, 10.0));
+        press(&mut app, Code::ArrowUp);
+        press(&mut app, Code::ArrowUp);
+        press(&mut app, Code::ArrowRight);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::ArrowLeft);
+        press(&mut app, Code::Enter);
+        press(&mut app, Code::End);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(3)]);
+
+        // Escape closes the nested menu, and then the menu itself.
+        open_menu(&mut app, point(10.0, 10.0));
+        press(&mut app, Code::End);
+        press(&mut app, Code::ArrowUp);
+        press(&mut app, Code::ArrowRight);
+        press(&mut app, Code::Escape);
+        press(&mut app, Code::Escape);
+        press(&mut app, Code::Enter);
+        assert!(app.drain_actions().is_empty());
+
+        // Type-ahead selection.
+        open_menu(&mut app, point(10.0, 10.0));
+        app.type_text(MAIN_WINDOW, "b");
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(1)]);
+
+        open_menu(&mut app, point(10.0, 10.0));
+        app.type_text(MAIN_WINDOW, "b");
+        app.type_text(MAIN_WINDOW, "b");
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(4)]);
+
+        open_menu(&mut app, point(10.0, 10.0));
+        app.type_text(MAIN_WINDOW, "B");
+        app.type_text(MAIN_WINDOW, "l");
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(4)]);
+    }
+
+    #[test]
+    fn test_drop_down_sub_menu_placement() {
+        let mut app = HeadlessApp::<MenuTestApp>::new(AppConfig::default()).unwrap();
+        let window_size = app.window_size(MAIN_WINDOW).unwrap();
+
+        let root = open_menu(&mut app, point(10.0, 10.0));
+
+        // Four rows and a divider.
+        let row_height = (root.height() - 1.0) / 4.0;
+        let sub_menu_row_y = root.min_y() + (row_height * 2.0) + 1.0 + (row_height * 0.5);
+
+        // Hovering the entry opens the nested menu on the right.
+        app.pointer_moved(MAIN_WINDOW, point(root.min_x() + 5.0, sub_menu_row_y));
+        let rect = app.app().menu.open_bounds().unwrap();
+        assert_eq!(rect.min_x(), root.min_x());
+        assert!(rect.max_x() > root.max_x());
+
+        // Hovering a different entry closes it.
+        app.pointer_moved(MAIN_WINDOW, point(root.min_x() + 5.0, root.min_y() + 1.0));
+        let rect = app.app().menu.open_bounds().unwrap();
+        assert_eq!(rect, root);
+
+        app.pointer_moved(MAIN_WINDOW, point(root.min_x() + 5.0, sub_menu_row_y));
+        app.click(
+            MAIN_WINDOW,
+            point(root.max_x() + 5.0, sub_menu_row_y),
+            PointerButton::Primary,
+        );
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(2)]);
+
+        // Near the right edge of the window, the nested menu opens on the left.
+        let root = open_menu(
+            &mut app,
+            point(window_size.width - root.width() - 1.0, 10.0),
+        );
+        app.pointer_moved(MAIN_WINDOW, point(root.min_x() + 5.0, sub_menu_row_y));
+        let rect = app.app().menu.open_bounds().unwrap();
+        assert!(rect.min_x() < root.min_x());
+        assert_eq!(rect.max_x(), root.max_x());
+
+        app.click(
+            MAIN_WINDOW,
+            point(root.min_x() - 5.0, sub_menu_row_y),
+            PointerButton::Primary,
+        );
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(2)]);
+    }
+
+    struct CheckMenuTestApp {
+        menu: DropDownMenu,
+    }
+
+    impl Application for CheckMenuTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let menu = DropDownMenu::builder()
+                .entries(vec![
+                    MenuEntry::header("View"),
+                    MenuEntry::check("Snap to grid", true, 0),
+                    MenuEntry::check("Show automation", false, 1).disabled(true),
+                    MenuEntry::Divider,
+                    MenuEntry::header("Zoom"),
+                    MenuEntry::radio("Fit", 0, true, 2),
+                    MenuEntry::radio("Fill", 0, false, 3),
+                ])
+                .on_entry_selected(TestAction::MenuSelected)
+                .build(&mut cx.main_window());
+
+            Ok(Self { menu })
+        }
+    }
+
+    #[test]
+    fn test_drop_down_menu_checkable_entries() {
+        let mut app = HeadlessApp::<CheckMenuTestApp>::new(AppConfig::default()).unwrap();
+
+        let press = |app: &mut HeadlessApp<CheckMenuTestApp>, code| {
+            app.key_press(MAIN_WINDOW, code, Modifiers::empty());
+        };
+        let open = |app: &mut HeadlessApp<CheckMenuTestApp>| {
+            app.app_mut().menu.open(Some(point(10.0, 10.0)));
+            app.process_updates();
+        };
+
+        // Headers are skipped.
+        open(&mut app);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(0)]);
+
+        // Disabled entries, dividers, and headers are skipped.
+        open(&mut app);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(3)]);
+
+        open(&mut app);
+        press(&mut app, Code::End);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(0)]);
+
+        // Type-ahead skips disabled entries.
+        open(&mut app);
+        app.type_text(MAIN_WINDOW, "s");
+        app.type_text(MAIN_WINDOW, "s");
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(0)]);
+
+        // Changing the checked state does not select the entry.
+        app.app_mut().menu.set_checked(2, true);
+        app.process_updates();
+        assert!(app.drain_actions().is_empty());
+    }
+
+    #[test]
+    fn test_menu_entry_disabled() {
+        let entry = MenuEntry::option("Apple", 0);
+
+        let disabled = entry.clone().disabled(true);
+        assert!(disabled.is_disabled());
+        assert_eq!(disabled.clone().disabled(true), disabled);
+        assert_eq!(disabled.disabled(false), entry);
+
+        assert_eq!(MenuEntry::Divider.disabled(true), MenuEntry::Divider);
+    }
+}
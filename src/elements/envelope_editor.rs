@@ -0,0 +1,1620 @@
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use derive_where::derive_where;
+use rootvg::{
+    mesh::MeshPrimitive,
+    tessellation::{
+        fill::{Fill, FillStyle},
+        path::PathBuilder,
+        stroke::{LineCap, LineDash, LineJoin, Stroke},
+        Tessellator,
+    },
+};
+
+use crate::derive::*;
+use crate::prelude::*;
+
+/// How steep a segment with a curvature of `1.0` or `-1.0` is.
+const CURVATURE_STEEPNESS: f64 = 10.0;
+
+/// The shape of a segment of an envelope with the given curvature, where
+/// `t` is the normalized position within the segment in the range
+/// `[0.0, 1.0]`.
+///
+/// The result is in the range `[0.0, 1.0]`, where `0.0` is the value of the
+/// start of the segment and `1.0` is the value of the end of the segment.
+/// Applications should use this when processing the envelope so that it
+/// sounds the way it looks in an [`EnvelopeEditor`].
+pub fn curvature_shape(t: f64, curvature: f32) -> f64 {
+    let k = f64::from(curvature) * CURVATURE_STEEPNESS;
+
+    if k.abs() < 1e-6 {
+        t
+    } else {
+        ((k * t).exp() - 1.0) / (k.exp() - 1.0)
+    }
+}
+
+/// The curvature for which [`curvature_shape`] at the middle of a segment
+/// is the given value.
+fn curvature_for_midpoint(shape: f64) -> f32 {
+    let shape = shape.clamp(0.001, 0.999);
+
+    ((2.0 * ((1.0 / shape) - 1.0).ln()) / CURVATURE_STEEPNESS).clamp(-1.0, 1.0) as f32
+}
+
+/// A breakpoint in an [`EnvelopeEditor`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvelopePoint {
+    /// The time of this point, in the same units as
+    /// [`EnvelopeEditorConfig::max_time`].
+    pub time: f64,
+    /// The value of this point in the range `[0.0, 1.0]`.
+    pub value: f64,
+    /// The curvature of the segment from this point to the next one in the
+    /// range `[-1.0, 1.0]`, where `0.0` is a straight line. See
+    /// [`curvature_shape`].
+    pub curvature: f32,
+}
+
+impl EnvelopePoint {
+    pub const fn new(time: f64, value: f64) -> Self {
+        Self {
+            time,
+            value,
+            curvature: 0.0,
+        }
+    }
+
+    pub const fn curvature(mut self, curvature: f32) -> Self {
+        self.curvature = curvature;
+        self
+    }
+}
+
+/// Sent when the user moves a point of an [`EnvelopeEditor`] or changes the
+/// curvature of the segment after it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvelopePointUpdate {
+    /// The index of the point.
+    pub index: usize,
+    /// The new state of the point.
+    pub point: EnvelopePoint,
+    /// The current state of gesturing (dragging)
+    ///
+    /// If this is update is not the result of the user gesturing,
+    /// then this will be `None`.
+    pub gesture_state: Option<GestureState>,
+}
+
+/// A marker in an [`EnvelopeEditor`], which is attached to a point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EnvelopeMarkerKind {
+    Sustain,
+    LoopStart,
+    LoopEnd,
+}
+
+/// Sent when the user drags a marker of an [`EnvelopeEditor`] to another
+/// point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvelopeMarkerUpdate {
+    pub kind: EnvelopeMarkerKind,
+    /// The index of the point the marker is attached to.
+    pub index: usize,
+    pub gesture_state: GestureState,
+}
+
+/// The part of the time axis that is visible in an [`EnvelopeEditor`]
+/// element.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct EnvelopeView {
+    /// The time at the left edge of the element.
+    pub start_time: f64,
+    /// The amount of time shown per logical point.
+    pub time_per_point: f64,
+}
+
+impl EnvelopeView {
+    pub const fn new(start_time: f64, time_per_point: f64) -> Self {
+        Self {
+            start_time,
+            time_per_point,
+        }
+    }
+
+    /// Convert a time to an x coordinate relative to the element.
+    pub fn time_to_x(&self, time: f64) -> f32 {
+        ((time - self.start_time) / self.time_per_point) as f32
+    }
+
+    /// Convert an x coordinate relative to the element to a time.
+    pub fn x_to_time(&self, x: f32) -> f64 {
+        self.start_time + (f64::from(x) * self.time_per_point)
+    }
+
+    /// Returns the view clamped so that it stays within the time axis.
+    fn clamped(&self, width: f32, config: &EnvelopeEditorConfig) -> Self {
+        let width = f64::from(width.max(1.0));
+        let max_time_per_point = (config.max_time / width).max(config.min_time_per_point);
+
+        let time_per_point = self
+            .time_per_point
+            .clamp(config.min_time_per_point, max_time_per_point);
+        let max_start_time = (config.max_time - (width * time_per_point)).max(0.0);
+
+        Self {
+            start_time: self.start_time.clamp(0.0, max_start_time),
+            time_per_point,
+        }
+    }
+
+    fn fit(width: f32, config: &EnvelopeEditorConfig) -> Self {
+        Self::new(0.0, config.max_time / f64::from(width.max(1.0))).clamped(width, config)
+    }
+}
+
+/// The behavior of an [`EnvelopeEditor`] element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnvelopeEditorConfig {
+    /// The length of the time axis. The units are up to the application
+    /// (i.e. seconds or beats).
+    ///
+    /// By default this is set to `1.0`.
+    pub max_time: f64,
+    /// The smallest amount of time per logical point (how far the user can
+    /// zoom in).
+    ///
+    /// By default this is set to `0.0001`.
+    pub min_time_per_point: f64,
+    /// The spacing of the vertical grid lines.
+    ///
+    /// By default this is set to `0.125`.
+    pub grid_time: f64,
+    /// The number of divisions of the value axis by the horizontal grid
+    /// lines.
+    ///
+    /// By default this is set to `4`.
+    pub grid_value_divisions: u32,
+    /// Whether or not dragged points snap to the grid.
+    ///
+    /// By default this is set to `false`.
+    pub snap_to_grid: bool,
+    /// The modifier key that temporarily disables snapping to the grid
+    /// while held down.
+    ///
+    /// Set this to `None` to disable this.
+    ///
+    /// By default this is set to `Some(Modifiers::SHIFT)`
+    pub snap_bypass_modifier: Option<Modifiers>,
+    /// The modifier keys that make the scroll wheel zoom instead of scroll.
+    ///
+    /// By default this is set to `Modifiers::CONTROL`.
+    pub zoom_modifier: Modifiers,
+    /// How much the view is zoomed per point of scrolling. A value of
+    /// `0.01` zooms by a factor of two every 100 points.
+    ///
+    /// By default this is set to `0.01`.
+    pub zoom_per_point: f64,
+    /// The number of points to scroll per line when the scroll wheel
+    /// reports lines instead of points.
+    ///
+    /// By default this is set to `24.0`.
+    pub points_per_line: f32,
+    /// How close (in points) the pointer needs to be to a point or a
+    /// curvature handle to grab it.
+    ///
+    /// By default this is set to `6.0`.
+    pub point_grab_radius: f32,
+    /// How close (in points) the pointer needs to be to a marker to grab it.
+    ///
+    /// By default this is set to `4.0`.
+    pub marker_grab_width: f32,
+    /// Markers can only be grabbed within this distance (in points) from
+    /// the top of the element, so that they don't get in the way of the
+    /// points they are attached to.
+    ///
+    /// By default this is set to `10.0`.
+    pub marker_grab_height: f32,
+}
+
+impl Default for EnvelopeEditorConfig {
+    fn default() -> Self {
+        Self {
+            max_time: 1.0,
+            min_time_per_point: 0.0001,
+            grid_time: 0.125,
+            grid_value_divisions: 4,
+            snap_to_grid: false,
+            snap_bypass_modifier: Some(Modifiers::SHIFT),
+            zoom_modifier: Modifiers::CONTROL,
+            zoom_per_point: 0.01,
+            points_per_line: 24.0,
+            point_grab_radius: 6.0,
+            marker_grab_width: 4.0,
+            marker_grab_height: 10.0,
+        }
+    }
+}
+
+/// The style of an [`EnvelopeEditor`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvelopeEditorStyle {
+    /// The background quad.
+    ///
+    /// By default this is set to `QuadStyle::TRANSPARENT`.
+    pub back_quad: QuadStyle,
+
+    /// The color of the grid lines.
+    pub grid_color: RGBA8,
+    /// The thickness of the grid lines.
+    ///
+    /// By default this is set to `1.0`.
+    pub grid_line_width: f32,
+
+    /// The color of the envelope line.
+    pub line_color: RGBA8,
+    /// The thickness of the envelope line.
+    ///
+    /// By default this is set to `1.5`.
+    pub line_width: f32,
+    /// The color of the area under the envelope line.
+    ///
+    /// By default this is transparent (no fill).
+    pub fill_color: RGBA8,
+
+    /// The radius of a point.
+    ///
+    /// By default this is set to `4.0`.
+    pub point_radius: f32,
+    /// The color of a point.
+    pub point_color: RGBA8,
+    /// The color of a point when the pointer is hovering over it.
+    pub point_color_hover: RGBA8,
+    /// The color of a point while the user is dragging it.
+    pub point_color_active: RGBA8,
+    /// The border of a point.
+    ///
+    /// The radius of the border is ignored.
+    pub point_border: BorderStyle,
+
+    /// The radius of a curvature handle.
+    ///
+    /// By default this is set to `3.0`.
+    pub handle_radius: f32,
+    /// The color of a curvature handle.
+    pub handle_color: RGBA8,
+    /// The color of a curvature handle when the pointer is hovering over it.
+    pub handle_color_hover: RGBA8,
+    /// The color of a curvature handle while the user is dragging it.
+    pub handle_color_active: RGBA8,
+
+    /// The color of the sustain marker.
+    pub sustain_color: RGBA8,
+    /// The background of the loop region.
+    pub loop_color: RGBA8,
+    /// The color of the loop start and loop end markers.
+    pub loop_edge_color: RGBA8,
+    /// The thickness of the marker lines.
+    ///
+    /// By default this is set to `1.0`.
+    pub marker_line_width: f32,
+    /// The size of the handle at the top of each marker line.
+    ///
+    /// By default this is set to `Size::new(8.0, 6.0)`.
+    pub marker_handle_size: Size,
+
+    /// Additional flags for the quad primitives.
+    ///
+    /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
+    pub quad_flags: QuadFlags,
+}
+
+impl EnvelopeEditorStyle {
+    fn quad(&self, color: RGBA8) -> QuadStyle {
+        QuadStyle::new_with_flags(
+            Background::Solid(color),
+            BorderStyle::default(),
+            self.quad_flags,
+        )
+    }
+
+    fn circle_quad(&self, color: RGBA8, radius: f32, border: BorderStyle) -> QuadStyle {
+        QuadStyle::new_with_flags(
+            Background::Solid(color),
+            BorderStyle {
+                radius: radius.into(),
+                ..border
+            },
+            QuadFlags::empty(),
+        )
+    }
+}
+
+impl Default for EnvelopeEditorStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle::TRANSPARENT,
+            grid_color: gray_a(255, 24),
+            grid_line_width: 1.0,
+            line_color: RGBA8::new(120, 180, 220, 255),
+            line_width: 1.5,
+            fill_color: color::TRANSPARENT,
+            point_radius: 4.0,
+            point_color: gray_a(255, 180),
+            point_color_hover: color::WHITE,
+            point_color_active: RGBA8::new(120, 180, 220, 255),
+            point_border: BorderStyle::default(),
+            handle_radius: 3.0,
+            handle_color: gray_a(255, 80),
+            handle_color_hover: gray_a(255, 180),
+            handle_color_active: RGBA8::new(120, 180, 220, 255),
+            sustain_color: RGBA8::new(214, 196, 86, 200),
+            loop_color: RGBA8::new(120, 200, 120, 30),
+            loop_edge_color: RGBA8::new(120, 200, 120, 160),
+            marker_line_width: 1.0,
+            marker_handle_size: Size::new(8.0, 6.0),
+            quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        }
+    }
+}
+
+impl ElementStyle for EnvelopeEditorStyle {
+    const ID: &'static str = "envedt";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            grid_color: gray_a(0, 24),
+            line_color: RGBA8::new(40, 100, 160, 255),
+            point_color: gray_a(0, 180),
+            point_color_hover: color::BLACK,
+            point_color_active: RGBA8::new(40, 100, 160, 255),
+            handle_color: gray_a(0, 80),
+            handle_color_hover: gray_a(0, 180),
+            handle_color_active: RGBA8::new(40, 100, 160, 255),
+            ..Default::default()
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[derive_where(Default)]
+pub struct EnvelopeEditorBuilder<A: Clone + 'static> {
+    pub on_point_changed: Option<Box<dyn FnMut(EnvelopePointUpdate) -> A>>,
+    pub on_marker_changed: Option<Box<dyn FnMut(EnvelopeMarkerUpdate) -> A>>,
+    pub on_view_changed: Option<Box<dyn FnMut(EnvelopeView) -> A>>,
+    pub points: Vec<EnvelopePoint>,
+    pub sustain_point: Option<usize>,
+    pub loop_points: Option<(usize, usize)>,
+    pub config: EnvelopeEditorConfig,
+}
+
+impl<A: Clone + 'static> EnvelopeEditorBuilder<A> {
+    /// Called when the user drags a point or a curvature handle.
+    pub fn on_point_changed<F: FnMut(EnvelopePointUpdate) -> A + 'static>(mut self, f: F) -> Self {
+        self.on_point_changed = Some(Box::new(f));
+        self
+    }
+
+    /// Called when the user drags the sustain marker or a loop marker.
+    pub fn on_marker_changed<F: FnMut(EnvelopeMarkerUpdate) -> A + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.on_marker_changed = Some(Box::new(f));
+        self
+    }
+
+    /// Called when the user scrolls or zooms the view.
+    pub fn on_view_changed<F: FnMut(EnvelopeView) -> A + 'static>(mut self, f: F) -> Self {
+        self.on_view_changed = Some(Box::new(f));
+        self
+    }
+
+    /// The points of the envelope, sorted by time.
+    pub fn points(mut self, points: impl IntoIterator<Item = EnvelopePoint>) -> Self {
+        self.points = points.into_iter().collect();
+        self
+    }
+
+    /// The index of the point with the sustain marker.
+    pub const fn sustain_point(mut self, sustain_point: Option<usize>) -> Self {
+        self.sustain_point = sustain_point;
+        self
+    }
+
+    /// The indices of the points with the loop start and loop end markers.
+    pub const fn loop_points(mut self, loop_points: Option<(usize, usize)>) -> Self {
+        self.loop_points = loop_points;
+        self
+    }
+
+    pub const fn config(mut self, config: EnvelopeEditorConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> EnvelopeEditor {
+        let EnvelopeEditorBuilder {
+            on_point_changed,
+            on_marker_changed,
+            on_view_changed,
+            points,
+            sustain_point,
+            loop_points,
+            config,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+        } = self;
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            points,
+            sustain_point,
+            loop_points,
+            view: EnvelopeView::default(),
+            fit_view: true,
+            config,
+        }));
+
+        let el = ElementBuilder::new(EnvelopeEditorElement {
+            shared_state: Rc::clone(&shared_state),
+            on_point_changed,
+            on_marker_changed,
+            on_view_changed,
+            hovered: None,
+            dragging: None,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE
+                | ElementFlags::LISTENS_TO_INIT,
+        )
+        .build(window_cx);
+
+        EnvelopeEditor { el, shared_state }
+    }
+}
+
+/// The part of an [`EnvelopeEditor`] that the user can drag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DragTarget {
+    Point(usize),
+    /// The curvature handle of the segment after the given point.
+    Curvature(usize),
+    Marker(EnvelopeMarkerKind),
+}
+
+struct EnvelopeEditorElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    on_point_changed: Option<Box<dyn FnMut(EnvelopePointUpdate) -> A>>,
+    on_marker_changed: Option<Box<dyn FnMut(EnvelopeMarkerUpdate) -> A>>,
+    on_view_changed: Option<Box<dyn FnMut(EnvelopeView) -> A>>,
+    hovered: Option<DragTarget>,
+    dragging: Option<DragTarget>,
+}
+
+impl<A: Clone + 'static> EnvelopeEditorElement<A> {
+    /// Send the current state of the given target.
+    fn send_update(
+        &mut self,
+        shared_state: &SharedState,
+        target: DragTarget,
+        gesture_state: Option<GestureState>,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        match target {
+            DragTarget::Point(index) | DragTarget::Curvature(index) => {
+                let (Some(f), Some(point)) = (
+                    self.on_point_changed.as_mut(),
+                    shared_state.points.get(index),
+                ) else {
+                    return;
+                };
+
+                cx.send_action((f)(EnvelopePointUpdate {
+                    index,
+                    point: *point,
+                    gesture_state,
+                }))
+                .unwrap();
+            }
+            DragTarget::Marker(kind) => {
+                let (Some(f), Some(index), Some(gesture_state)) = (
+                    self.on_marker_changed.as_mut(),
+                    shared_state.marker(kind),
+                    gesture_state,
+                ) else {
+                    return;
+                };
+
+                cx.send_action((f)(EnvelopeMarkerUpdate {
+                    kind,
+                    index,
+                    gesture_state,
+                }))
+                .unwrap();
+            }
+        }
+    }
+
+    fn finish_drag(&mut self, cx: &mut ElementContext<'_, A>) {
+        let Some(target) = self.dragging.take() else {
+            return;
+        };
+
+        let shared_state = Rc::clone(&self.shared_state);
+        self.send_update(
+            &RefCell::borrow(&shared_state),
+            target,
+            Some(GestureState::GestureFinished),
+            cx,
+        );
+        cx.request_repaint();
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for EnvelopeEditorElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::Init | ElementEvent::CustomStateChanged | ElementEvent::SizeChanged => {
+                let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                shared_state.sync_view(cx.rect().width());
+
+                // The application may have removed the point being dragged.
+                let num_points = shared_state.points.len();
+                let is_valid = |target: &DragTarget| match target {
+                    DragTarget::Point(i) => *i < num_points,
+                    DragTarget::Curvature(i) => *i + 1 < num_points,
+                    DragTarget::Marker(kind) => shared_state.marker(*kind).is_some(),
+                };
+                if !self.dragging.as_ref().map(is_valid).unwrap_or(true) {
+                    self.dragging = None;
+                }
+                if !self.hovered.as_ref().map(is_valid).unwrap_or(true) {
+                    self.hovered = None;
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved {
+                position,
+                modifiers,
+                ..
+            }) => {
+                let rect = cx.rect();
+                let pos = position - rect.origin.to_vector();
+
+                if let Some(target) = self.dragging {
+                    let shared_state = Rc::clone(&self.shared_state);
+                    let mut shared_state = RefCell::borrow_mut(&shared_state);
+
+                    if shared_state.drag_to(target, pos, rect.size, modifiers) {
+                        cx.request_repaint();
+                        self.send_update(&shared_state, target, Some(GestureState::Gesturing), cx);
+                    }
+
+                    if let DragTarget::Marker(_) = target {
+                        cx.cursor_icon = CursorIcon::EwResize;
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+
+                let hovered = if rect.contains(position) {
+                    RefCell::borrow(&self.shared_state).target_at(pos, rect.size)
+                } else {
+                    None
+                };
+
+                if self.hovered != hovered {
+                    self.hovered = hovered;
+                    cx.request_repaint();
+                }
+
+                if let Some(DragTarget::Marker(_)) = hovered {
+                    cx.cursor_icon = CursorIcon::EwResize;
+                }
+
+                if rect.contains(position) {
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hovered.take().is_some() {
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                click_count,
+                ..
+            }) => {
+                let rect = cx.rect();
+                if !rect.contains(position) {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if button != PointerButton::Primary {
+                    return EventCaptureStatus::Captured;
+                }
+
+                self.finish_drag(cx);
+
+                let shared_state = Rc::clone(&self.shared_state);
+                let mut shared_state = RefCell::borrow_mut(&shared_state);
+
+                let Some(target) =
+                    shared_state.target_at(position - rect.origin.to_vector(), rect.size)
+                else {
+                    return EventCaptureStatus::Captured;
+                };
+
+                if let (2, DragTarget::Curvature(index)) = (click_count, target) {
+                    // Double-clicking a curvature handle straightens the
+                    // segment.
+                    let point = &mut shared_state.points[index];
+                    if point.curvature != 0.0 {
+                        point.curvature = 0.0;
+                        cx.request_repaint();
+                        self.send_update(&shared_state, target, None, cx);
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+
+                self.dragging = Some(target);
+                self.send_update(
+                    &shared_state,
+                    target,
+                    Some(GestureState::GestureStarted),
+                    cx,
+                );
+
+                cx.steal_focus();
+                cx.request_repaint();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
+                button, position, ..
+            }) => {
+                if button == PointerButton::Primary && cx.has_focus() {
+                    cx.release_focus();
+                    return EventCaptureStatus::Captured;
+                }
+
+                if cx.rect().contains(position) {
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Focus(false) => {
+                self.finish_drag(cx);
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheel {
+                position,
+                delta_type,
+                modifiers,
+                ..
+            }) => {
+                let rect = cx.rect();
+                if !rect.contains(position) {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let new_view = {
+                    let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                    let config = shared_state.config;
+                    let view = shared_state.view;
+
+                    let delta = delta_type.points(config.points_per_line, rect.width());
+
+                    let new_view = if modifiers.contains(config.zoom_modifier)
+                        && !config.zoom_modifier.is_empty()
+                    {
+                        // Zoom around the time under the pointer.
+                        let x = position.x - rect.min_x();
+                        let pointer_time = view.x_to_time(x);
+                        let time_per_point = view.time_per_point
+                            * 2.0f64.powf(f64::from(delta.y) * config.zoom_per_point);
+
+                        EnvelopeView::new(
+                            pointer_time - (f64::from(x) * time_per_point),
+                            time_per_point,
+                        )
+                    } else {
+                        let delta = if delta.x != 0.0 { delta.x } else { delta.y };
+
+                        EnvelopeView::new(
+                            view.start_time + (f64::from(delta) * view.time_per_point),
+                            view.time_per_point,
+                        )
+                    };
+
+                    let new_view = new_view.clamped(rect.width(), &config);
+
+                    if new_view == view {
+                        None
+                    } else {
+                        shared_state.view = new_view;
+                        shared_state.fit_view = false;
+                        Some(new_view)
+                    }
+                };
+
+                if let Some(new_view) = new_view {
+                    cx.request_repaint();
+
+                    if let Some(f) = self.on_view_changed.as_mut() {
+                        cx.send_action((f)(new_view)).unwrap();
+                    }
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<EnvelopeEditorStyle>(cx.class);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+        let view = shared_state.view;
+        let config = &shared_state.config;
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(style.back_quad.create_primitive(bounds));
+        }
+
+        let in_view = |x: f32, margin: f32| x >= -margin && x <= bounds.width() + margin;
+        let vertical_line = |x: f32, width: f32| {
+            Rect::new(
+                Point::new(x - (width * 0.5), 0.0),
+                Size::new(width, bounds.height()),
+            )
+        };
+
+        if style.grid_color != color::TRANSPARENT && style.grid_line_width > 0.0 {
+            let grid_quad = style.quad(style.grid_color);
+
+            primitives.set_z_index(1);
+
+            // Don't draw the vertical lines when zoomed out so far that they
+            // would turn into a solid block.
+            if config.grid_time > 0.0 && config.grid_time / view.time_per_point >= 4.0 {
+                let mut time = (view.start_time / config.grid_time).ceil() * config.grid_time;
+                let end_time = view.x_to_time(bounds.width()).min(config.max_time);
+
+                while time <= end_time {
+                    primitives.add(grid_quad.create_primitive(vertical_line(
+                        view.time_to_x(time),
+                        style.grid_line_width,
+                    )));
+
+                    time += config.grid_time;
+                }
+            }
+
+            for i in 1..config.grid_value_divisions {
+                let y = bounds.height() * (i as f32 / config.grid_value_divisions as f32);
+
+                primitives.add(grid_quad.create_primitive(Rect::new(
+                    Point::new(0.0, y - (style.grid_line_width * 0.5)),
+                    Size::new(bounds.width(), style.grid_line_width),
+                )));
+            }
+        }
+
+        let marker_x = |index: usize| -> Option<f32> {
+            let x = view.time_to_x(shared_state.points.get(index)?.time);
+            in_view(x, style.marker_handle_size.width).then_some(x)
+        };
+
+        if let Some((start, end)) = shared_state.loop_points.and_then(|(start, end)| {
+            Some((
+                shared_state.points.get(start)?,
+                shared_state.points.get(end)?,
+            ))
+        }) {
+            let start_x = view.time_to_x(start.time).max(0.0);
+            let end_x = view.time_to_x(end.time).min(bounds.width());
+
+            if end_x > start_x {
+                primitives.set_z_index(1);
+                primitives.add(style.quad(style.loop_color).create_primitive(Rect::new(
+                    Point::new(start_x, 0.0),
+                    Size::new(end_x - start_x, bounds.height()),
+                )));
+            }
+        }
+
+        let markers = [
+            (EnvelopeMarkerKind::LoopStart, style.loop_edge_color),
+            (EnvelopeMarkerKind::LoopEnd, style.loop_edge_color),
+            (EnvelopeMarkerKind::Sustain, style.sustain_color),
+        ];
+
+        for (kind, color) in markers {
+            let Some(x) = shared_state.marker(kind).and_then(marker_x) else {
+                continue;
+            };
+
+            let quad = style.quad(color);
+            let handle_size = style.marker_handle_size;
+
+            primitives.set_z_index(3);
+            primitives.add(quad.create_primitive(vertical_line(x, style.marker_line_width)));
+            primitives.add(quad.create_primitive(Rect::new(
+                Point::new(x - (handle_size.width * 0.5), 0.0),
+                handle_size,
+            )));
+        }
+
+        let to_point = |time: f64, value: f64| {
+            Point::new(
+                view.time_to_x(time),
+                (1.0 - value.clamp(0.0, 1.0) as f32) * bounds.height(),
+            )
+        };
+
+        let mut curve: Vec<Point> = Vec::new();
+        for segment in shared_state.points.windows(2) {
+            let (p0, p1) = (segment[0], segment[1]);
+            let (x0, x1) = (view.time_to_x(p0.time), view.time_to_x(p1.time));
+
+            if x1 < 0.0 || x0 > bounds.width() {
+                continue;
+            }
+
+            // About one line for every two points.
+            let num_lines = ((x1 - x0) * 0.5).ceil().clamp(1.0, 256.0) as usize;
+            let first = if curve.is_empty() { 0 } else { 1 };
+
+            for i in first..=num_lines {
+                let t = i as f64 / num_lines as f64;
+                let shape = curvature_shape(t, p0.curvature);
+
+                curve.push(to_point(
+                    p0.time + ((p1.time - p0.time) * t),
+                    p0.value + ((p1.value - p0.value) * shape),
+                ));
+            }
+        }
+
+        if style.fill_color != color::TRANSPARENT {
+            if let Some(mesh) = fill_curve(&curve, bounds.max_y(), style.fill_color) {
+                primitives.set_z_index(2);
+                primitives.add_mesh(mesh);
+            }
+        }
+
+        if let Some(mesh) = stroke_curve(&curve, style.line_color, style.line_width) {
+            primitives.set_z_index(2);
+            primitives.add_mesh(mesh);
+        }
+
+        let circle = |center: Point, radius: f32| {
+            Rect::new(
+                Point::new(center.x - radius, center.y - radius),
+                Size::new(radius * 2.0, radius * 2.0),
+            )
+        };
+        let color_for = |target: DragTarget, idle: RGBA8, hover: RGBA8, active: RGBA8| {
+            if self.dragging == Some(target) {
+                active
+            } else if self.hovered == Some(target) {
+                hover
+            } else {
+                idle
+            }
+        };
+
+        primitives.set_z_index(4);
+
+        for i in 0..shared_state.points.len().saturating_sub(1) {
+            let Some(center) = shared_state.curvature_handle_position(i, cx.bounds_size) else {
+                continue;
+            };
+            if !in_view(center.x, style.handle_radius) {
+                continue;
+            }
+
+            let color = color_for(
+                DragTarget::Curvature(i),
+                style.handle_color,
+                style.handle_color_hover,
+                style.handle_color_active,
+            );
+
+            primitives.add(
+                style
+                    .circle_quad(color, style.handle_radius, BorderStyle::default())
+                    .create_primitive(circle(center, style.handle_radius)),
+            );
+        }
+
+        primitives.set_z_index(5);
+
+        for (i, point) in shared_state.points.iter().enumerate() {
+            let center = to_point(point.time, point.value);
+            if !in_view(center.x, style.point_radius) {
+                continue;
+            }
+
+            let color = color_for(
+                DragTarget::Point(i),
+                style.point_color,
+                style.point_color_hover,
+                style.point_color_active,
+            );
+
+            primitives.add(
+                style
+                    .circle_quad(color, style.point_radius, style.point_border)
+                    .create_primitive(circle(center, style.point_radius)),
+            );
+        }
+    }
+}
+
+fn stroke_curve(points: &[Point], color: RGBA8, width: f32) -> Option<MeshPrimitive> {
+    let (first, rest) = points.split_first()?;
+
+    let path = rest
+        .iter()
+        .fold(PathBuilder::new().move_to(*first), |path, point| {
+            path.line_to(*point)
+        })
+        .build();
+
+    let stroke = Stroke {
+        style: FillStyle::Solid(color.into()),
+        width,
+        line_cap: LineCap::Round,
+        line_join: LineJoin::default(),
+        line_dash: LineDash::default(),
+    };
+
+    Tessellator::new().stroke(&path, stroke).into_primitive()
+}
+
+fn fill_curve(points: &[Point], bottom: f32, color: RGBA8) -> Option<MeshPrimitive> {
+    let (first, last) = (points.first()?, points.last()?);
+
+    let path = points
+        .iter()
+        .fold(
+            PathBuilder::new().move_to(Point::new(first.x, bottom)),
+            |path, point| path.line_to(*point),
+        )
+        .line_to(Point::new(last.x, bottom))
+        .build();
+
+    let fill = Fill {
+        style: FillStyle::Solid(color.into()),
+        ..Default::default()
+    };
+
+    Tessellator::new().fill(&path, fill).into_primitive()
+}
+
+fn snap(value: f64, step: f64) -> f64 {
+    if step > 0.0 {
+        (value / step).round() * step
+    } else {
+        value
+    }
+}
+
+struct SharedState {
+    /// Sorted by time.
+    points: Vec<EnvelopePoint>,
+    sustain_point: Option<usize>,
+    loop_points: Option<(usize, usize)>,
+    view: EnvelopeView,
+    /// Whether the view should be zoomed to fit the whole time axis once
+    /// the size of the element is known.
+    fit_view: bool,
+    config: EnvelopeEditorConfig,
+}
+
+impl SharedState {
+    fn sync_view(&mut self, width: f32) {
+        self.view = if self.fit_view {
+            EnvelopeView::fit(width, &self.config)
+        } else {
+            self.view.clamped(width, &self.config)
+        };
+    }
+
+    fn marker(&self, kind: EnvelopeMarkerKind) -> Option<usize> {
+        let index = match kind {
+            EnvelopeMarkerKind::Sustain => self.sustain_point,
+            EnvelopeMarkerKind::LoopStart => self.loop_points.map(|(start, _)| start),
+            EnvelopeMarkerKind::LoopEnd => self.loop_points.map(|(_, end)| end),
+        }?;
+
+        (index < self.points.len()).then_some(index)
+    }
+
+    /// The position of the given point relative to the element.
+    fn point_position(&self, index: usize, bounds_size: Size) -> Option<Point> {
+        let point = self.points.get(index)?;
+
+        Some(Point::new(
+            self.view.time_to_x(point.time),
+            (1.0 - point.value.clamp(0.0, 1.0) as f32) * bounds_size.height,
+        ))
+    }
+
+    /// The position of the curvature handle of the segment after the given
+    /// point relative to the element. The handle sits on the curve halfway
+    /// through the segment.
+    fn curvature_handle_position(&self, index: usize, bounds_size: Size) -> Option<Point> {
+        let (p0, p1) = (self.points.get(index)?, self.points.get(index + 1)?);
+
+        let time = (p0.time + p1.time) * 0.5;
+        let value = p0.value + ((p1.value - p0.value) * curvature_shape(0.5, p0.curvature));
+
+        Some(Point::new(
+            self.view.time_to_x(time),
+            (1.0 - value.clamp(0.0, 1.0) as f32) * bounds_size.height,
+        ))
+    }
+
+    /// The part of the envelope under the given position (relative to the
+    /// element). Points take priority over curvature handles, and curvature
+    /// handles take priority over markers.
+    fn target_at(&self, pos: Point, bounds_size: Size) -> Option<DragTarget> {
+        let grab_radius = self.config.point_grab_radius;
+        let closest = |positions: &mut dyn Iterator<Item = (usize, Point)>| {
+            positions
+                .map(|(i, p)| (i, (p - pos).length()))
+                .filter(|(_, distance)| *distance <= grab_radius)
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(i, _)| i)
+        };
+
+        let point = closest(
+            &mut (0..self.points.len())
+                .filter_map(|i| Some((i, self.point_position(i, bounds_size)?))),
+        );
+        if let Some(i) = point {
+            return Some(DragTarget::Point(i));
+        }
+
+        let handle = closest(
+            &mut (0..self.points.len())
+                .filter_map(|i| Some((i, self.curvature_handle_position(i, bounds_size)?))),
+        );
+        if let Some(i) = handle {
+            return Some(DragTarget::Curvature(i));
+        }
+
+        if pos.y > self.config.marker_grab_height {
+            return None;
+        }
+
+        [
+            EnvelopeMarkerKind::Sustain,
+            EnvelopeMarkerKind::LoopEnd,
+            EnvelopeMarkerKind::LoopStart,
+        ]
+        .into_iter()
+        .find(|kind| {
+            self.marker(*kind)
+                .and_then(|i| self.point_position(i, bounds_size))
+                .is_some_and(|p| (p.x - pos.x).abs() <= self.config.marker_grab_width)
+        })
+        .map(DragTarget::Marker)
+    }
+
+    /// Move the given target to the given position (relative to the
+    /// element).
+    ///
+    /// Returns `true` if anything has changed.
+    fn drag_to(
+        &mut self,
+        target: DragTarget,
+        pos: Point,
+        bounds_size: Size,
+        modifiers: Modifiers,
+    ) -> bool {
+        let value = 1.0 - f64::from(pos.y / bounds_size.height.max(1.0));
+
+        match target {
+            DragTarget::Point(index) => {
+                let mut time = self.view.x_to_time(pos.x);
+                let mut value = value;
+
+                let bypass_snap = self
+                    .config
+                    .snap_bypass_modifier
+                    .is_some_and(|m| modifiers == m);
+                if self.config.snap_to_grid && !bypass_snap {
+                    time = snap(time, self.config.grid_time);
+                    value = snap(value, 1.0 / f64::from(self.config.grid_value_divisions));
+                }
+
+                // Points can't be dragged past their neighbors.
+                let min_time = index
+                    .checked_sub(1)
+                    .and_then(|i| self.points.get(i))
+                    .map(|p| p.time)
+                    .unwrap_or(0.0);
+                let max_time = self
+                    .points
+                    .get(index + 1)
+                    .map(|p| p.time)
+                    .unwrap_or(self.config.max_time)
+                    .max(min_time);
+
+                let Some(point) = self.points.get_mut(index) else {
+                    return false;
+                };
+
+                let time = time.clamp(min_time, max_time);
+                let value = value.clamp(0.0, 1.0);
+
+                if point.time == time && point.value == value {
+                    return false;
+                }
+
+                point.time = time;
+                point.value = value;
+                true
+            }
+            DragTarget::Curvature(index) => {
+                let Some(next_value) = self.points.get(index + 1).map(|p| p.value) else {
+                    return false;
+                };
+                let point = &mut self.points[index];
+
+                // The curvature has no effect on flat segments.
+                if next_value == point.value {
+                    return false;
+                }
+
+                let curvature =
+                    curvature_for_midpoint((value - point.value) / (next_value - point.value));
+
+                if point.curvature == curvature {
+                    return false;
+                }
+
+                point.curvature = curvature;
+                true
+            }
+            DragTarget::Marker(kind) => {
+                let time = self.view.x_to_time(pos.x);
+
+                let Some(closest) = self
+                    .points
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| {
+                        (a.time - time).abs().total_cmp(&(b.time - time).abs())
+                    })
+                    .map(|(i, _)| i)
+                else {
+                    return false;
+                };
+
+                match kind {
+                    EnvelopeMarkerKind::Sustain => {
+                        if self.sustain_point == Some(closest) {
+                            return false;
+                        }
+                        self.sustain_point = Some(closest);
+                    }
+                    EnvelopeMarkerKind::LoopStart | EnvelopeMarkerKind::LoopEnd => {
+                        let Some((start, end)) = self.loop_points else {
+                            return false;
+                        };
+
+                        // The loop can't be dragged inside out.
+                        let new_loop_points = if kind == EnvelopeMarkerKind::LoopStart {
+                            (closest.min(end), end)
+                        } else {
+                            (start, closest.max(start))
+                        };
+
+                        if self.loop_points == Some(new_loop_points) {
+                            return false;
+                        }
+                        self.loop_points = Some(new_loop_points);
+                    }
+                }
+
+                true
+            }
+        }
+    }
+}
+
+/// A handle to an [`EnvelopeEditorElement`], which lets the user edit a
+/// multi-segment envelope.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct EnvelopeEditor {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl EnvelopeEditor {
+    pub fn builder<A: Clone + 'static>() -> EnvelopeEditorBuilder<A> {
+        EnvelopeEditorBuilder::default()
+    }
+
+    /// Set the points of the envelope. The points must be sorted by time.
+    ///
+    /// Returns `true` if the points have changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_points(&mut self, points: &[EnvelopePoint]) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.points != points {
+            shared_state.points = points.to_vec();
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn points(&self) -> Ref<'_, [EnvelopePoint]> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| s.points.as_slice())
+    }
+
+    /// Set the point at the given index. The point must stay between its
+    /// neighbors in time.
+    ///
+    /// Returns `true` if the point has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_point(&mut self, index: usize, point: EnvelopePoint) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        match shared_state.points.get_mut(index) {
+            Some(p) if *p != point => {
+                *p = point;
+                self.el.notify_custom_state_change();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Set the index of the point with the sustain marker.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_sustain_point(&mut self, sustain_point: Option<usize>) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.sustain_point != sustain_point {
+            shared_state.sustain_point = sustain_point;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn sustain_point(&self) -> Option<usize> {
+        RefCell::borrow(&self.shared_state).sustain_point
+    }
+
+    /// Set the indices of the points with the loop start and loop end
+    /// markers.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_loop_points(&mut self, loop_points: Option<(usize, usize)>) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.loop_points != loop_points {
+            shared_state.loop_points = loop_points;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn loop_points(&self) -> Option<(usize, usize)> {
+        RefCell::borrow(&self.shared_state).loop_points
+    }
+
+    /// Set the visible part of the time axis. The view is clamped to stay
+    /// within the time axis.
+    ///
+    /// Returns `true` if the view has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_view(&mut self, view: EnvelopeView) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.view != view || shared_state.fit_view {
+            shared_state.view = view;
+            shared_state.fit_view = false;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The visible part of the time axis.
+    pub fn view(&self) -> EnvelopeView {
+        RefCell::borrow(&self.shared_state).view
+    }
+
+    /// Zoom out to show the whole time axis.
+    pub fn zoom_to_fit(&mut self) {
+        RefCell::borrow_mut(&self.shared_state).fit_view = true;
+        self.el.notify_custom_state_change();
+    }
+
+    /// Returns `true` if the config has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_config(&mut self, config: EnvelopeEditorConfig) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.config != config {
+            shared_state.config = config;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn config(&self) -> EnvelopeEditorConfig {
+        RefCell::borrow(&self.shared_state).config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        EnvelopePoint(EnvelopePointUpdate),
+        EnvelopeMarker(EnvelopeMarkerUpdate),
+    }
+
+    struct EnvelopeTestApp {
+        envelope: EnvelopeEditor,
+    }
+
+    impl Application for EnvelopeTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let envelope = EnvelopeEditor::builder()
+                .on_point_changed(TestAction::EnvelopePoint)
+                .on_marker_changed(TestAction::EnvelopeMarker)
+                .points([
+                    EnvelopePoint::new(0.0, 0.0),
+                    EnvelopePoint::new(200.0, 0.75),
+                    EnvelopePoint::new(600.0, 0.5),
+                    EnvelopePoint::new(1000.0, 0.0),
+                ])
+                .sustain_point(Some(2))
+                .loop_points(Some((1, 2)))
+                .config(EnvelopeEditorConfig {
+                    max_time: 1000.0,
+                    grid_time: 100.0,
+                    ..Default::default()
+                })
+                .rect(rect(10.0, 10.0, 250.0, 100.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { envelope })
+        }
+    }
+
+    #[test]
+    fn test_envelope_editor() {
+        let mut app = HeadlessApp::<EnvelopeTestApp>::new(AppConfig::default()).unwrap();
+        app.process_updates();
+
+        let point_update =
+            |index: usize, time: f64, value: f64, gesture_state: Option<GestureState>| {
+                TestAction::EnvelopePoint(EnvelopePointUpdate {
+                    index,
+                    point: EnvelopePoint::new(time, value),
+                    gesture_state,
+                })
+            };
+        let marker_update =
+            |kind: EnvelopeMarkerKind, index: usize, gesture_state: GestureState| {
+                TestAction::EnvelopeMarker(EnvelopeMarkerUpdate {
+                    kind,
+                    index,
+                    gesture_state,
+                })
+            };
+
+        // The whole time axis fits in the element at first.
+        assert_eq!(app.app().envelope.view(), EnvelopeView::new(0.0, 4.0));
+
+        // Drag the curvature handle in the middle of the first segment, which
+        // bends the segment so that it passes through the pointer.
+        app.pointer_moved(MAIN_WINDOW, point(35.0, 72.5));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.pointer_moved(MAIN_WINDOW, point(35.0, 50.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+        let actions = app.drain_actions();
+        assert_eq!(actions.len(), 3);
+        assert_eq!(
+            actions[0],
+            point_update(0, 0.0, 0.0, Some(GestureState::GestureStarted))
+        );
+        let curvature = app.app().envelope.points()[0].curvature;
+        assert!(curvature < 0.0);
+        assert!((curvature_shape(0.5, curvature) - 0.8).abs() < 1e-3);
+        assert!(matches!(
+            actions[2],
+            TestAction::EnvelopePoint(EnvelopePointUpdate {
+                index: 0,
+                gesture_state: Some(GestureState::GestureFinished),
+                ..
+            })
+        ));
+
+        // Double-clicking the handle straightens the segment again.
+        app.click(MAIN_WINDOW, point(35.0, 50.0), PointerButton::Primary);
+        app.process_updates();
+        assert_eq!(app.drain_actions(), vec![point_update(0, 0.0, 0.0, None)]);
+        assert_eq!(app.app().envelope.points()[0].curvature, 0.0);
+
+        // Drag a point.
+        app.pointer_moved(MAIN_WINDOW, point(60.0, 35.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.pointer_moved(MAIN_WINDOW, point(80.0, 60.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+        assert_eq!(
+            app.drain_actions(),
+            vec![
+                point_update(1, 200.0, 0.75, Some(GestureState::GestureStarted)),
+                point_update(1, 280.0, 0.5, Some(GestureState::Gesturing)),
+                point_update(1, 280.0, 0.5, Some(GestureState::GestureFinished)),
+            ]
+        );
+
+        // Markers snap to the closest point.
+        app.pointer_moved(MAIN_WINDOW, point(160.0, 12.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.pointer_moved(MAIN_WINDOW, point(255.0, 12.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+        assert_eq!(app.app().envelope.sustain_point(), Some(3));
+        assert_eq!(
+            app.drain_actions(),
+            vec![
+                marker_update(EnvelopeMarkerKind::Sustain, 2, GestureState::GestureStarted),
+                marker_update(EnvelopeMarkerKind::Sustain, 3, GestureState::Gesturing),
+                marker_update(
+                    EnvelopeMarkerKind::Sustain,
+                    3,
+                    GestureState::GestureFinished
+                ),
+            ]
+        );
+
+        // The loop end can't be dragged before the loop start.
+        app.pointer_moved(MAIN_WINDOW, point(160.0, 12.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.pointer_moved(MAIN_WINDOW, point(20.0, 12.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+        assert_eq!(app.app().envelope.loop_points(), Some((1, 1)));
+        assert_eq!(
+            app.drain_actions(),
+            vec![
+                marker_update(EnvelopeMarkerKind::LoopEnd, 2, GestureState::GestureStarted),
+                marker_update(EnvelopeMarkerKind::LoopEnd, 1, GestureState::Gesturing),
+                marker_update(
+                    EnvelopeMarkerKind::LoopEnd,
+                    1,
+                    GestureState::GestureFinished
+                ),
+            ]
+        );
+
+        // Points snap to the grid unless the bypass modifier is held down,
+        // and they can't be dragged past their neighbors.
+        let config = app.app().envelope.config();
+        assert!(app.app_mut().envelope.set_config(EnvelopeEditorConfig {
+            snap_to_grid: true,
+            ..config
+        }));
+        app.process_updates();
+
+        app.pointer_moved(MAIN_WINDOW, point(160.0, 60.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.pointer_moved(MAIN_WINDOW, point(182.0, 67.0));
+        app.set_modifiers(MAIN_WINDOW, Modifiers::SHIFT);
+        app.pointer_moved(MAIN_WINDOW, point(246.0, 10.0));
+        app.set_modifiers(MAIN_WINDOW, Modifiers::empty());
+        app.pointer_moved(MAIN_WINDOW, point(0.0, 60.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+        assert_eq!(
+            app.drain_actions(),
+            vec![
+                point_update(2, 600.0, 0.5, Some(GestureState::GestureStarted)),
+                point_update(2, 700.0, 0.5, Some(GestureState::Gesturing)),
+                point_update(2, 944.0, 1.0, Some(GestureState::Gesturing)),
+                point_update(2, 280.0, 0.5, Some(GestureState::Gesturing)),
+                point_update(2, 280.0, 0.5, Some(GestureState::GestureFinished)),
+            ]
+        );
+
+        // Zoom in around the pointer, then scroll.
+        app.pointer_moved(MAIN_WINDOW, point(110.0, 40.0));
+        app.set_modifiers(MAIN_WINDOW, Modifiers::CONTROL);
+        app.scroll_wheel(
+            MAIN_WINDOW,
+            WheelDeltaType::Points(Vector::new(0.0, -100.0)),
+        );
+        app.process_updates();
+        assert_eq!(app.app().envelope.view(), EnvelopeView::new(200.0, 2.0));
+
+        app.set_modifiers(MAIN_WINDOW, Modifiers::empty());
+        app.scroll_wheel(MAIN_WINDOW, WheelDeltaType::Points(Vector::new(0.0, 10.0)));
+        app.process_updates();
+        assert_eq!(app.app().envelope.view(), EnvelopeView::new(220.0, 2.0));
+
+        app.app_mut().envelope.zoom_to_fit();
+        app.process_updates();
+        assert_eq!(app.app().envelope.view(), EnvelopeView::new(0.0, 4.0));
+    }
+}
@@ -0,0 +1,1404 @@
+use std::cell::RefCell;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use rootvg::{
+    mesh::MeshPrimitive,
+    tessellation::{
+        fill::{Fill, FillStyle},
+        path::PathBuilder,
+        stroke::{LineCap, LineDash, LineJoin, Stroke},
+        Tessellator,
+    },
+};
+use smol_str::{SmolStr, ToSmolStr};
+
+use crate::derive::*;
+use crate::prelude::*;
+
+use super::virtual_slider::{InnerParamUpdate, VirtualSliderInner};
+
+/// The type of filter of a band in an [`EqCurve`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EqFilterType {
+    #[default]
+    Peak,
+    LowShelf,
+    HighShelf,
+    LowPass,
+    HighPass,
+    BandPass,
+    Notch,
+}
+
+impl EqFilterType {
+    /// Whether or not the gain parameter has an effect on this type of
+    /// filter.
+    pub const fn has_gain(&self) -> bool {
+        matches!(self, Self::Peak | Self::LowShelf | Self::HighShelf)
+    }
+}
+
+/// The coefficients of a biquad filter, normalized so that `a0` is `1.0`.
+///
+/// These follow the formulas in Robert Bristow-Johnson's "Audio EQ
+/// Cookbook", which is what most EQ plugins use for their bands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiquadCoefficients {
+    pub b0: f64,
+    pub b1: f64,
+    pub b2: f64,
+    pub a1: f64,
+    pub a2: f64,
+}
+
+impl BiquadCoefficients {
+    pub fn new(filter_type: EqFilterType, hz: f32, gain_db: f32, q: f32, sample_rate: f32) -> Self {
+        let sample_rate = f64::from(sample_rate);
+        let hz = f64::from(hz).clamp(1.0, sample_rate * 0.499);
+        let q = f64::from(q).max(0.001);
+
+        let w0 = 2.0 * PI * hz / sample_rate;
+        let (sin_w0, cos_w0) = w0.sin_cos();
+        let alpha = sin_w0 / (2.0 * q);
+        let a = 10.0f64.powf(f64::from(gain_db) / 40.0);
+
+        let (b0, b1, b2, a0, a1, a2) = match filter_type {
+            EqFilterType::Peak => (
+                1.0 + (alpha * a),
+                -2.0 * cos_w0,
+                1.0 - (alpha * a),
+                1.0 + (alpha / a),
+                -2.0 * cos_w0,
+                1.0 - (alpha / a),
+            ),
+            EqFilterType::LowShelf => {
+                let sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+                (
+                    a * ((a + 1.0) - ((a - 1.0) * cos_w0) + sqrt_a_alpha),
+                    2.0 * a * ((a - 1.0) - ((a + 1.0) * cos_w0)),
+                    a * ((a + 1.0) - ((a - 1.0) * cos_w0) - sqrt_a_alpha),
+                    (a + 1.0) + ((a - 1.0) * cos_w0) + sqrt_a_alpha,
+                    -2.0 * ((a - 1.0) + ((a + 1.0) * cos_w0)),
+                    (a + 1.0) + ((a - 1.0) * cos_w0) - sqrt_a_alpha,
+                )
+            }
+            EqFilterType::HighShelf => {
+                let sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+                (
+                    a * ((a + 1.0) + ((a - 1.0) * cos_w0) + sqrt_a_alpha),
+                    -2.0 * a * ((a - 1.0) + ((a + 1.0) * cos_w0)),
+                    a * ((a + 1.0) + ((a - 1.0) * cos_w0) - sqrt_a_alpha),
+                    (a + 1.0) - ((a - 1.0) * cos_w0) + sqrt_a_alpha,
+                    2.0 * ((a - 1.0) - ((a + 1.0) * cos_w0)),
+                    (a + 1.0) - ((a - 1.0) * cos_w0) - sqrt_a_alpha,
+                )
+            }
+            EqFilterType::LowPass => (
+                (1.0 - cos_w0) * 0.5,
+                1.0 - cos_w0,
+                (1.0 - cos_w0) * 0.5,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+            EqFilterType::HighPass => (
+                (1.0 + cos_w0) * 0.5,
+                -(1.0 + cos_w0),
+                (1.0 + cos_w0) * 0.5,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+            EqFilterType::BandPass => (alpha, 0.0, -alpha, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha),
+            EqFilterType::Notch => (
+                1.0,
+                -2.0 * cos_w0,
+                1.0,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+        };
+
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+
+    /// The magnitude of the frequency response of the filter at the given
+    /// frequency, in decibels.
+    pub fn magnitude_db(&self, hz: f32, sample_rate: f32) -> f32 {
+        let w = 2.0 * PI * f64::from(hz) / f64::from(sample_rate);
+        let (cos_w, cos_2w) = (w.cos(), (2.0 * w).cos());
+        let Self { b0, b1, b2, a1, a2 } = *self;
+
+        let num = (b0 * b0)
+            + (b1 * b1)
+            + (b2 * b2)
+            + (2.0 * ((b0 * b1) + (b1 * b2)) * cos_w)
+            + (2.0 * b0 * b2 * cos_2w);
+        let den =
+            1.0 + (a1 * a1) + (a2 * a2) + (2.0 * (a1 + (a1 * a2)) * cos_w) + (2.0 * a2 * cos_2w);
+
+        (10.0 * (num.max(1e-12) / den.max(1e-12)).log10()) as f32
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EqCurveConfig {
+    /// The lowest frequency shown, as well as the frequency that a
+    /// normalized frequency parameter value of `0.0` maps to.
+    ///
+    /// By default this is set to `20.0`.
+    pub min_hz: f32,
+    /// The highest frequency shown, as well as the frequency that a
+    /// normalized frequency parameter value of `1.0` maps to.
+    ///
+    /// By default this is set to `20_000.0`.
+    pub max_hz: f32,
+    /// The gain in decibels at the bottom of the element, as well as the
+    /// gain that a normalized gain parameter value of `0.0` maps to.
+    ///
+    /// By default this is set to `-24.0`.
+    pub min_db: f32,
+    /// The gain in decibels at the top of the element, as well as the
+    /// gain that a normalized gain parameter value of `1.0` maps to.
+    ///
+    /// By default this is set to `24.0`.
+    pub max_db: f32,
+    /// The Q factor that a normalized Q parameter value of `0.0` maps to.
+    ///
+    /// By default this is set to `0.1`.
+    pub min_q: f32,
+    /// The Q factor that a normalized Q parameter value of `1.0` maps to.
+    ///
+    /// By default this is set to `18.0`.
+    pub max_q: f32,
+    /// The sample rate used to compute the response curve.
+    ///
+    /// By default this is set to `48_000.0`.
+    pub sample_rate: f32,
+    /// How close (in points) the pointer needs to be to the center of a
+    /// band node to grab it.
+    ///
+    /// By default this is set to `8.0`.
+    pub node_grab_radius: f32,
+    /// The configuration used when dragging and scrolling band nodes.
+    ///
+    /// The `drag_scalar` is ignored, since dragging a node always follows
+    /// the pointer.
+    pub slider: VirtualSliderConfig,
+}
+
+impl Default for EqCurveConfig {
+    fn default() -> Self {
+        Self {
+            min_hz: 20.0,
+            max_hz: 20_000.0,
+            min_db: -24.0,
+            max_db: 24.0,
+            min_q: 0.1,
+            max_q: 18.0,
+            sample_rate: 48_000.0,
+            node_grab_radius: 8.0,
+            slider: VirtualSliderConfig::default(),
+        }
+    }
+}
+
+impl EqCurveConfig {
+    /// Map a frequency to the range `[0.0, 1.0]` on a logarithmic scale.
+    pub fn hz_to_normal(&self, hz: f32) -> f32 {
+        let min = self.min_hz.max(1.0).log2();
+        let max = self.max_hz.max(1.0).log2();
+
+        if max <= min {
+            return 0.0;
+        }
+
+        (hz.max(1.0).log2() - min) / (max - min)
+    }
+
+    /// Map a value in the range `[0.0, 1.0]` to a frequency on a logarithmic
+    /// scale.
+    pub fn normal_to_hz(&self, normal: f32) -> f32 {
+        let min = self.min_hz.max(1.0).log2();
+        let max = self.max_hz.max(1.0).log2();
+
+        2.0f32.powf(min + (normal * (max - min)))
+    }
+
+    /// Map a gain in decibels to the range `[0.0, 1.0]`.
+    pub fn db_to_normal(&self, db: f32) -> f32 {
+        if self.max_db <= self.min_db {
+            return 0.0;
+        }
+
+        (db - self.min_db) / (self.max_db - self.min_db)
+    }
+
+    /// Map a value in the range `[0.0, 1.0]` to a gain in decibels.
+    pub fn normal_to_db(&self, normal: f32) -> f32 {
+        self.min_db + (normal * (self.max_db - self.min_db))
+    }
+
+    /// Map a Q factor to the range `[0.0, 1.0]` on a logarithmic scale.
+    pub fn q_to_normal(&self, q: f32) -> f32 {
+        let min = self.min_q.max(0.001).ln();
+        let max = self.max_q.max(0.001).ln();
+
+        if max <= min {
+            return 0.0;
+        }
+
+        (q.max(0.001).ln() - min) / (max - min)
+    }
+
+    /// Map a value in the range `[0.0, 1.0]` to a Q factor on a logarithmic
+    /// scale.
+    pub fn normal_to_q(&self, normal: f32) -> f32 {
+        let min = self.min_q.max(0.001).ln();
+        let max = self.max_q.max(0.001).ln();
+
+        (min + (normal * (max - min))).exp()
+    }
+}
+
+/// A parameter of a band in an [`EqCurve`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EqBandParam {
+    /// The parameter ID
+    pub id: SmolStr,
+    /// The normalized value in the range `[0.0, 1.0]`
+    pub normal: f64,
+    /// The normalized value to reset to when the user double-clicks the
+    /// band node.
+    pub default_normal: f64,
+}
+
+impl EqBandParam {
+    /// Create a new parameter, using `normal` as the default value.
+    pub fn new(id: impl ToSmolStr, normal: f64) -> Self {
+        Self {
+            id: id.to_smolstr(),
+            normal,
+            default_normal: normal,
+        }
+    }
+
+    pub fn default_normal(mut self, default_normal: f64) -> Self {
+        self.default_normal = default_normal;
+        self
+    }
+}
+
+/// A band in an [`EqCurve`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EqBand {
+    pub filter_type: EqFilterType,
+    /// Whether or not this band contributes to the response curve.
+    pub enabled: bool,
+    /// The frequency parameter, mapped with [`EqCurveConfig::normal_to_hz`].
+    pub freq: EqBandParam,
+    /// The gain parameter, mapped with [`EqCurveConfig::normal_to_db`].
+    pub gain: EqBandParam,
+    /// The Q parameter, mapped with [`EqCurveConfig::normal_to_q`].
+    pub q: EqBandParam,
+}
+
+impl EqBand {
+    pub fn new(
+        filter_type: EqFilterType,
+        freq: EqBandParam,
+        gain: EqBandParam,
+        q: EqBandParam,
+    ) -> Self {
+        Self {
+            filter_type,
+            enabled: true,
+            freq,
+            gain,
+            q,
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EqBandRightClickInfo {
+    /// The index of the band that was right-clicked.
+    pub band_index: usize,
+    pub pointer_pos: Point,
+}
+
+/// The style of an [`EqCurve`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct EqCurveStyle {
+    /// The background quad.
+    ///
+    /// By default this is set to `QuadStyle::TRANSPARENT`.
+    pub back_quad: QuadStyle,
+
+    /// The color of the grid lines.
+    pub grid_color: RGBA8,
+    /// The thickness of the grid lines.
+    ///
+    /// By default this is set to `1.0`.
+    pub grid_line_width: f32,
+
+    /// The color of the combined response curve.
+    pub curve_color: RGBA8,
+    /// The thickness of the combined response curve.
+    ///
+    /// By default this is set to `2.0`.
+    pub curve_width: f32,
+    /// The color of the area between the combined response curve and the
+    /// 0 dB line.
+    ///
+    /// By default this is transparent (no fill).
+    pub fill_color: RGBA8,
+
+    /// The color of the response curve of each individual band.
+    ///
+    /// By default this is transparent (not shown).
+    pub band_curve_color: RGBA8,
+    /// The thickness of the response curve of each individual band.
+    ///
+    /// By default this is set to `1.0`.
+    pub band_curve_width: f32,
+
+    /// The radius of a band node.
+    ///
+    /// By default this is set to `5.0`.
+    pub node_radius: f32,
+    /// The color of a band node.
+    pub node_color: RGBA8,
+    /// The color of a band node when the pointer is hovering over it.
+    pub node_color_hover: RGBA8,
+    /// The color of a band node while the user is dragging or scrolling it.
+    pub node_color_active: RGBA8,
+    /// The color of a band node when the band is disabled.
+    pub node_color_bypassed: RGBA8,
+    /// The border of a band node.
+    ///
+    /// The radius of the border is ignored.
+    pub node_border: BorderStyle,
+
+    /// Additional flags for the quad primitives.
+    ///
+    /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
+    pub quad_flags: QuadFlags,
+}
+
+impl EqCurveStyle {
+    fn grid_quad(&self) -> QuadStyle {
+        QuadStyle::new_with_flags(
+            Background::Solid(self.grid_color),
+            BorderStyle::default(),
+            self.quad_flags,
+        )
+    }
+
+    fn node_quad(&self, color: RGBA8) -> QuadStyle {
+        QuadStyle::new_with_flags(
+            Background::Solid(color),
+            BorderStyle {
+                radius: self.node_radius.into(),
+                ..self.node_border
+            },
+            QuadFlags::empty(),
+        )
+    }
+}
+
+impl Default for EqCurveStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle::TRANSPARENT,
+            grid_color: gray_a(255, 24),
+            grid_line_width: 1.0,
+            curve_color: RGBA8::new(120, 180, 220, 255),
+            curve_width: 2.0,
+            fill_color: color::TRANSPARENT,
+            band_curve_color: color::TRANSPARENT,
+            band_curve_width: 1.0,
+            node_radius: 5.0,
+            node_color: gray_a(255, 180),
+            node_color_hover: color::WHITE,
+            node_color_active: RGBA8::new(120, 180, 220, 255),
+            node_color_bypassed: gray_a(255, 60),
+            node_border: BorderStyle::default(),
+            quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        }
+    }
+}
+
+impl ElementStyle for EqCurveStyle {
+    const ID: &'static str = "eqcrv";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            grid_color: gray_a(0, 24),
+            curve_color: RGBA8::new(40, 100, 160, 255),
+            node_color: gray_a(0, 180),
+            node_color_hover: color::BLACK,
+            node_color_active: RGBA8::new(40, 100, 160, 255),
+            node_color_bypassed: gray_a(0, 60),
+            ..Default::default()
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+pub struct EqCurveBuilder<A: Clone + 'static> {
+    pub on_gesture: Option<Box<dyn FnMut(ParamUpdate) -> A>>,
+    pub on_right_click: Option<Box<dyn FnMut(EqBandRightClickInfo) -> A>>,
+    pub bands: Vec<EqBand>,
+    pub config: EqCurveConfig,
+    pub grid_hz: Vec<f32>,
+    pub grid_db: Vec<f32>,
+}
+
+impl<A: Clone + 'static> Default for EqCurveBuilder<A> {
+    fn default() -> Self {
+        Self {
+            on_gesture: None,
+            on_right_click: None,
+            bands: Vec::new(),
+            config: EqCurveConfig::default(),
+            grid_hz: vec![100.0, 1_000.0, 10_000.0],
+            grid_db: vec![-12.0, 0.0, 12.0],
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            scissor_rect: None,
+        }
+    }
+}
+
+impl<A: Clone + 'static> EqCurveBuilder<A> {
+    /// Called when the user changes the frequency, gain, or Q of a band.
+    pub fn on_gesture<F: FnMut(ParamUpdate) -> A + 'static>(mut self, f: F) -> Self {
+        self.on_gesture = Some(Box::new(f));
+        self
+    }
+
+    /// Called when the user right-clicks a band node.
+    pub fn on_right_click<F: FnMut(EqBandRightClickInfo) -> A + 'static>(mut self, f: F) -> Self {
+        self.on_right_click = Some(Box::new(f));
+        self
+    }
+
+    pub fn bands(mut self, bands: impl IntoIterator<Item = EqBand>) -> Self {
+        self.bands = bands.into_iter().collect();
+        self
+    }
+
+    pub const fn config(mut self, config: EqCurveConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The frequencies where grid lines are drawn.
+    ///
+    /// By default this is set to `[100.0, 1_000.0, 10_000.0]`.
+    pub fn grid_hz(mut self, grid_hz: impl IntoIterator<Item = f32>) -> Self {
+        self.grid_hz = grid_hz.into_iter().collect();
+        self
+    }
+
+    /// The gains in decibels where grid lines are drawn.
+    ///
+    /// By default this is set to `[-12.0, 0.0, 12.0]`.
+    pub fn grid_db(mut self, grid_db: impl IntoIterator<Item = f32>) -> Self {
+        self.grid_db = grid_db.into_iter().collect();
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> EqCurve {
+        let EqCurveBuilder {
+            on_gesture,
+            on_right_click,
+            bands,
+            config,
+            grid_hz,
+            grid_db,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+        } = self;
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            bands: bands
+                .into_iter()
+                .map(|band| BandState::new(band, &config))
+                .collect(),
+            config,
+            grid_hz,
+            grid_db,
+            queued_values: Vec::new(),
+        }));
+
+        let el = ElementBuilder::new(EqCurveElement {
+            shared_state: Rc::clone(&shared_state),
+            on_gesture,
+            on_right_click,
+            hovered_band: None,
+            dragging_band: None,
+            scrolling_band: None,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE
+                | ElementFlags::LISTENS_TO_INIT,
+        )
+        .build(window_cx);
+
+        EqCurve { el, shared_state }
+    }
+}
+
+struct BandState {
+    filter_type: EqFilterType,
+    enabled: bool,
+    freq: VirtualSliderInner,
+    gain: VirtualSliderInner,
+    q: VirtualSliderInner,
+}
+
+impl BandState {
+    fn new(band: EqBand, config: &EqCurveConfig) -> Self {
+        let new_inner = |param: EqBandParam, drag_horizontally: bool| {
+            VirtualSliderInner::new(
+                param.id,
+                param.normal,
+                param.default_normal,
+                None,
+                config.slider,
+                drag_horizontally,
+                false,
+            )
+        };
+
+        Self {
+            filter_type: band.filter_type,
+            enabled: band.enabled,
+            freq: new_inner(band.freq, true),
+            gain: new_inner(band.gain, false),
+            q: new_inner(band.q, false),
+        }
+    }
+
+    fn params(&self) -> [&VirtualSliderInner; 3] {
+        [&self.freq, &self.gain, &self.q]
+    }
+
+    fn params_mut(&mut self) -> [&mut VirtualSliderInner; 3] {
+        [&mut self.freq, &mut self.gain, &mut self.q]
+    }
+
+    /// The parameters that are adjusted when the user drags the band node.
+    fn drag_params_mut(&mut self) -> impl Iterator<Item = &mut VirtualSliderInner> {
+        let has_gain = self.filter_type.has_gain();
+
+        [Some(&mut self.freq), has_gain.then_some(&mut self.gain)]
+            .into_iter()
+            .flatten()
+    }
+
+    fn coefficients(&self, config: &EqCurveConfig) -> BiquadCoefficients {
+        BiquadCoefficients::new(
+            self.filter_type,
+            config.normal_to_hz(self.freq.normal_value() as f32),
+            config.normal_to_db(self.gain.normal_value() as f32),
+            config.normal_to_q(self.q.normal_value() as f32),
+            config.sample_rate,
+        )
+    }
+
+    /// The center of the band node relative to the element.
+    fn node_position(&self, config: &EqCurveConfig, bounds_size: Size) -> Point {
+        let gain_normal = if self.filter_type.has_gain() {
+            self.gain.normal_value() as f32
+        } else {
+            config.db_to_normal(0.0)
+        };
+
+        Point::new(
+            self.freq.normal_value() as f32 * bounds_size.width,
+            (1.0 - gain_normal) * bounds_size.height,
+        )
+    }
+}
+
+struct EqCurveElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    on_gesture: Option<Box<dyn FnMut(ParamUpdate) -> A>>,
+    on_right_click: Option<Box<dyn FnMut(EqBandRightClickInfo) -> A>>,
+    hovered_band: Option<usize>,
+    dragging_band: Option<usize>,
+    scrolling_band: Option<usize>,
+}
+
+fn send_param_update<A: Clone + 'static>(
+    param_update: InnerParamUpdate,
+    on_gesture: &mut Option<Box<dyn FnMut(ParamUpdate) -> A>>,
+    cx: &mut ElementContext<'_, A>,
+) {
+    if let Some(f) = on_gesture.as_mut() {
+        cx.send_action((f)(param_update.inner)).unwrap();
+    }
+
+    cx.request_repaint();
+
+    if let Some(lock) = param_update.pointer_lock_request {
+        cx.request_pointer_lock(lock);
+    }
+}
+
+fn finish_gestures<A: Clone + 'static>(
+    bands: &mut [BandState],
+    on_gesture: &mut Option<Box<dyn FnMut(ParamUpdate) -> A>>,
+    cx: &mut ElementContext<'_, A>,
+) {
+    for band in bands.iter_mut() {
+        for inner in band.params_mut() {
+            if let Some(param_update) = inner.finish_gesture() {
+                send_param_update(param_update, on_gesture, cx);
+            }
+        }
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for EqCurveElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let SharedState {
+            bands,
+            config,
+            queued_values,
+            ..
+        } = &mut *shared_state;
+
+        match event {
+            ElementEvent::Init | ElementEvent::SizeChanged | ElementEvent::CustomStateChanged => {
+                // Dragging a node moves it by exactly as much as the pointer.
+                let size = cx.rect().size;
+                for band in bands.iter_mut() {
+                    band.freq.config.drag_scalar = 1.0 / size.width.max(1.0);
+                    band.gain.config.drag_scalar = 1.0 / size.height.max(1.0);
+                }
+
+                for (param_id, new_normal) in queued_values.drain(..) {
+                    let Some(inner) = bands
+                        .iter_mut()
+                        .flat_map(|band| band.params_mut())
+                        .find(|inner| inner.param_id == param_id)
+                    else {
+                        continue;
+                    };
+
+                    if inner.normal_value() != new_normal {
+                        if let Some(param_update) = inner.set_normal_value(new_normal) {
+                            send_param_update(param_update, &mut self.on_gesture, cx);
+                        }
+                    }
+                }
+
+                if self.hovered_band.is_some_and(|i| i >= bands.len()) {
+                    self.hovered_band = None;
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved {
+                position,
+                delta,
+                modifiers,
+                ..
+            }) => {
+                if let Some(band) = self.dragging_band.and_then(|i| bands.get_mut(i)) {
+                    if let Some(cursor_icon) = config.slider.cursor_icon_gesturing {
+                        cx.cursor_icon = cursor_icon;
+                    }
+
+                    let delta = delta.filter(|_| cx.is_pointer_locked());
+
+                    for inner in band.drag_params_mut() {
+                        if let Some(param_update) =
+                            inner.handle_pointer_moved(position, delta, modifiers)
+                        {
+                            send_param_update(
+                                InnerParamUpdate {
+                                    inner: param_update,
+                                    pointer_lock_request: None,
+                                },
+                                &mut self.on_gesture,
+                                cx,
+                            );
+                        }
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+
+                let rect = cx.rect();
+                let hovered_band = if rect.contains(position) {
+                    band_at(bands, config, position - rect.origin.to_vector(), rect.size)
+                } else {
+                    None
+                };
+
+                if self.hovered_band != hovered_band {
+                    self.hovered_band = hovered_band;
+                    cx.request_repaint();
+                }
+
+                if hovered_band.is_some() {
+                    if let Some(cursor_icon) = config.slider.cursor_icon_hover {
+                        cx.cursor_icon = cursor_icon;
+                    }
+                }
+
+                if rect.contains(position) {
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hovered_band.take().is_some() {
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                click_count,
+                ..
+            }) => {
+                let rect = cx.rect();
+                if !rect.contains(position) {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let Some(band_index) =
+                    band_at(bands, config, position - rect.origin.to_vector(), rect.size)
+                else {
+                    return EventCaptureStatus::Captured;
+                };
+
+                if button == PointerButton::Secondary {
+                    if let Some(f) = self.on_right_click.as_mut() {
+                        finish_gestures(bands, &mut self.on_gesture, cx);
+                        self.dragging_band = None;
+                        self.scrolling_band = None;
+
+                        cx.send_action((f)(EqBandRightClickInfo {
+                            band_index,
+                            pointer_pos: position,
+                        }))
+                        .unwrap();
+                    }
+                }
+
+                if button != PointerButton::Primary {
+                    return EventCaptureStatus::Captured;
+                }
+
+                finish_gestures(bands, &mut self.on_gesture, cx);
+                self.dragging_band = None;
+                self.scrolling_band = None;
+
+                let band = &mut bands[band_index];
+
+                if click_count == 1 {
+                    for inner in band.drag_params_mut() {
+                        if let Some(param_update) = inner.begin_drag_gesture(position) {
+                            send_param_update(param_update, &mut self.on_gesture, cx);
+                        }
+                    }
+
+                    self.dragging_band = Some(band_index);
+                    cx.steal_focus();
+                } else if click_count == 2 {
+                    for inner in band.params_mut() {
+                        if let Some(param_update) = inner.reset_to_default() {
+                            send_param_update(param_update, &mut self.on_gesture, cx);
+                        }
+                    }
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
+                button, position, ..
+            }) => {
+                if button == PointerButton::Primary && cx.has_focus() {
+                    cx.release_focus();
+                    return EventCaptureStatus::Captured;
+                }
+
+                if cx.rect().contains(position) {
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Focus(false) => {
+                finish_gestures(bands, &mut self.on_gesture, cx);
+                self.dragging_band = None;
+                self.scrolling_band = None;
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheel {
+                position,
+                delta_type,
+                modifiers,
+                ..
+            }) => {
+                let rect = cx.rect();
+                if !rect.contains(position) || !config.slider.use_scroll_wheel {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if self.dragging_band.is_some() {
+                    return EventCaptureStatus::Captured;
+                }
+
+                let Some(band_index) = self.scrolling_band.or_else(|| {
+                    band_at(bands, config, position - rect.origin.to_vector(), rect.size)
+                }) else {
+                    return EventCaptureStatus::NotCaptured;
+                };
+
+                let inner = &mut bands[band_index].q;
+
+                if let Some(param_update) = inner.begin_scroll_wheel_gesture() {
+                    send_param_update(
+                        InnerParamUpdate {
+                            inner: param_update,
+                            pointer_lock_request: None,
+                        },
+                        &mut self.on_gesture,
+                        cx,
+                    );
+
+                    self.scrolling_band = Some(band_index);
+                    cx.steal_focus();
+                    cx.start_scroll_wheel_timeout();
+                }
+
+                if let Some(param_update) = inner.handle_scroll_wheel(delta_type, modifiers) {
+                    send_param_update(
+                        InnerParamUpdate {
+                            inner: param_update,
+                            pointer_lock_request: None,
+                        },
+                        &mut self.on_gesture,
+                        cx,
+                    );
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheelTimeout) => {
+                if cx.has_focus() {
+                    cx.release_focus();
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<EqCurveStyle>(cx.class);
+        let config = &shared_state.config;
+
+        let bounds = Rect::from_size(cx.bounds_size);
+        let db_to_y =
+            |db: f32| bounds.max_y() - (config.db_to_normal(db).clamp(0.0, 1.0) * bounds.height());
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(style.back_quad.create_primitive(bounds));
+        }
+
+        if style.grid_color != color::TRANSPARENT && style.grid_line_width > 0.0 {
+            let grid_quad = style.grid_quad();
+
+            primitives.set_z_index(1);
+
+            for hz in shared_state.grid_hz.iter() {
+                let x = config.hz_to_normal(*hz) * bounds.width();
+
+                primitives.add(grid_quad.create_primitive(Rect::new(
+                    Point::new(x - (style.grid_line_width * 0.5), 0.0),
+                    Size::new(style.grid_line_width, bounds.height()),
+                )));
+            }
+
+            for db in shared_state.grid_db.iter() {
+                let y = db_to_y(*db);
+
+                primitives.add(grid_quad.create_primitive(Rect::new(
+                    Point::new(0.0, y - (style.grid_line_width * 0.5)),
+                    Size::new(bounds.width(), style.grid_line_width),
+                )));
+            }
+        }
+
+        let enabled_coefficients: Vec<BiquadCoefficients> = shared_state
+            .bands
+            .iter()
+            .filter(|band| band.enabled)
+            .map(|band| band.coefficients(config))
+            .collect();
+
+        // One point per logical point across the element.
+        let curve_points = |response_db: &dyn Fn(f32) -> f32| -> Vec<Point> {
+            let width = bounds.width();
+            let num_columns = width.ceil() as usize;
+
+            (0..=num_columns)
+                .map(|column| {
+                    let x = (column as f32).min(width);
+                    let hz = config.normal_to_hz(x / width);
+
+                    Point::new(x, db_to_y(response_db(hz)))
+                })
+                .collect()
+        };
+
+        if bounds.width() > 0.0 {
+            if style.band_curve_color != color::TRANSPARENT {
+                for coefficients in enabled_coefficients.iter() {
+                    let points =
+                        curve_points(&|hz| coefficients.magnitude_db(hz, config.sample_rate));
+
+                    if let Some(mesh) =
+                        stroke_curve(&points, style.band_curve_color, style.band_curve_width)
+                    {
+                        primitives.set_z_index(2);
+                        primitives.add_mesh(mesh);
+                    }
+                }
+            }
+
+            let points = curve_points(&|hz| {
+                enabled_coefficients
+                    .iter()
+                    .map(|c| c.magnitude_db(hz, config.sample_rate))
+                    .sum()
+            });
+
+            if style.fill_color != color::TRANSPARENT {
+                if let Some(mesh) = fill_curve(&points, db_to_y(0.0), style.fill_color) {
+                    primitives.set_z_index(2);
+                    primitives.add_mesh(mesh);
+                }
+            }
+
+            if let Some(mesh) = stroke_curve(&points, style.curve_color, style.curve_width) {
+                primitives.set_z_index(3);
+                primitives.add_mesh(mesh);
+            }
+        }
+
+        primitives.set_z_index(4);
+
+        for (i, band) in shared_state.bands.iter().enumerate() {
+            let color = if self.dragging_band == Some(i) || self.scrolling_band == Some(i) {
+                style.node_color_active
+            } else if self.hovered_band == Some(i) {
+                style.node_color_hover
+            } else if !band.enabled {
+                style.node_color_bypassed
+            } else {
+                style.node_color
+            };
+
+            let center = band.node_position(config, cx.bounds_size);
+
+            primitives.add(style.node_quad(color).create_primitive(Rect::new(
+                Point::new(center.x - style.node_radius, center.y - style.node_radius),
+                Size::new(style.node_radius * 2.0, style.node_radius * 2.0),
+            )));
+        }
+    }
+}
+
+/// The index of the band node closest to the given position (relative to
+/// the element), if it is within grabbing distance.
+fn band_at(
+    bands: &[BandState],
+    config: &EqCurveConfig,
+    position: Point,
+    bounds_size: Size,
+) -> Option<usize> {
+    bands
+        .iter()
+        .enumerate()
+        // Prefer the band drawn on top if nodes overlap.
+        .rev()
+        .map(|(i, band)| {
+            let distance = (band.node_position(config, bounds_size) - position).length();
+            (i, distance)
+        })
+        .filter(|(_, distance)| *distance <= config.node_grab_radius)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(i, _)| i)
+}
+
+fn stroke_curve(points: &[Point], color: RGBA8, width: f32) -> Option<MeshPrimitive> {
+    let (first, rest) = points.split_first()?;
+
+    let path = rest
+        .iter()
+        .fold(PathBuilder::new().move_to(*first), |path, point| {
+            path.line_to(*point)
+        })
+        .build();
+
+    let stroke = Stroke {
+        style: FillStyle::Solid(color.into()),
+        width,
+        line_cap: LineCap::Round,
+        line_join: LineJoin::default(),
+        line_dash: LineDash::default(),
+    };
+
+    Tessellator::new().stroke(&path, stroke).into_primitive()
+}
+
+fn fill_curve(points: &[Point], zero_y: f32, color: RGBA8) -> Option<MeshPrimitive> {
+    let (first, last) = (points.first()?, points.last()?);
+
+    let path = points
+        .iter()
+        .fold(
+            PathBuilder::new().move_to(Point::new(first.x, zero_y)),
+            |path, point| path.line_to(*point),
+        )
+        .line_to(Point::new(last.x, zero_y))
+        .build();
+
+    let fill = Fill {
+        style: FillStyle::Solid(color.into()),
+        ..Default::default()
+    };
+
+    Tessellator::new().fill(&path, fill).into_primitive()
+}
+
+struct SharedState {
+    bands: Vec<BandState>,
+    config: EqCurveConfig,
+    grid_hz: Vec<f32>,
+    grid_db: Vec<f32>,
+    queued_values: Vec<(SmolStr, f64)>,
+}
+
+impl SharedState {
+    fn param(&self, param_id: &str) -> Option<&VirtualSliderInner> {
+        self.bands
+            .iter()
+            .flat_map(|band| band.params())
+            .find(|inner| inner.param_id.as_str() == param_id)
+    }
+}
+
+/// A handle to an [`EqCurveElement`], which displays the combined frequency
+/// response of a set of EQ bands and lets the user edit them.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct EqCurve {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl EqCurve {
+    pub fn builder<A: Clone + 'static>() -> EqCurveBuilder<A> {
+        EqCurveBuilder::default()
+    }
+
+    /// Set the normalized value of the band parameter with the given ID.
+    ///
+    /// If the user is currently gesturing this parameter, then the gesture
+    /// will be cancelled.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_normal_value(&mut self, param_id: &str, new_normal: f64) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let Some(inner) = shared_state.param(param_id) else {
+            return false;
+        };
+
+        if inner.normal_value() == new_normal {
+            return false;
+        }
+
+        let param_id = inner.param_id.clone();
+        shared_state.queued_values.retain(|(id, _)| *id != param_id);
+        shared_state.queued_values.push((param_id, new_normal));
+        self.el.notify_custom_state_change();
+        true
+    }
+
+    /// The normalized value of the band parameter with the given ID.
+    pub fn normal_value(&self, param_id: &str) -> Option<f64> {
+        RefCell::borrow(&self.shared_state)
+            .param(param_id)
+            .map(|inner| inner.normal_value())
+    }
+
+    /// Set the type of filter of the band at the given index.
+    ///
+    /// Returns `true` if the filter type has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_filter_type(&mut self, band_index: usize, filter_type: EqFilterType) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        match shared_state.bands.get_mut(band_index) {
+            Some(band) if band.filter_type != filter_type => {
+                band.filter_type = filter_type;
+                self.el.notify_custom_state_change();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn filter_type(&self, band_index: usize) -> Option<EqFilterType> {
+        RefCell::borrow(&self.shared_state)
+            .bands
+            .get(band_index)
+            .map(|band| band.filter_type)
+    }
+
+    /// Set whether or not the band at the given index contributes to the
+    /// response curve.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_band_enabled(&mut self, band_index: usize, enabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        match shared_state.bands.get_mut(band_index) {
+            Some(band) if band.enabled != enabled => {
+                band.enabled = enabled;
+                self.el.notify_custom_state_change();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn band_enabled(&self, band_index: usize) -> Option<bool> {
+        RefCell::borrow(&self.shared_state)
+            .bands
+            .get(band_index)
+            .map(|band| band.enabled)
+    }
+
+    pub fn num_bands(&self) -> usize {
+        RefCell::borrow(&self.shared_state).bands.len()
+    }
+
+    /// The combined response of all enabled bands at the given frequency,
+    /// in decibels.
+    pub fn response_db(&self, hz: f32) -> f32 {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let config = &shared_state.config;
+
+        shared_state
+            .bands
+            .iter()
+            .filter(|band| band.enabled)
+            .map(|band| {
+                band.coefficients(config)
+                    .magnitude_db(hz, config.sample_rate)
+            })
+            .sum()
+    }
+
+    /// Returns `true` if the config has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_config(&mut self, config: EqCurveConfig) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.config != config {
+            shared_state.config = config;
+
+            // The drag scalars are synced in the element.
+            for band in shared_state.bands.iter_mut() {
+                for inner in band.params_mut() {
+                    inner.config = config.slider;
+                }
+            }
+
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn config(&self) -> EqCurveConfig {
+        RefCell::borrow(&self.shared_state).config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        Gesture(ParamUpdate),
+    }
+
+    struct EqCurveTestApp {
+        eq_curve: EqCurve,
+    }
+
+    impl Application for EqCurveTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let eq_curve = EqCurve::builder()
+                .on_gesture(TestAction::Gesture)
+                .bands([EqBand::new(
+                    EqFilterType::Peak,
+                    EqBandParam::new("freq", 0.5),
+                    EqBandParam::new("gain", 0.5),
+                    EqBandParam::new("q", 0.5),
+                )])
+                .rect(rect(10.0, 10.0, 256.0, 128.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { eq_curve })
+        }
+    }
+
+    #[test]
+    fn test_eq_curve() {
+        let mut app = HeadlessApp::<EqCurveTestApp>::new(AppConfig::default()).unwrap();
+        app.process_updates();
+
+        let gesture = |id: &str, normal_value: f64, gesture_state: Option<GestureState>| {
+            TestAction::Gesture(ParamUpdate {
+                param_info: ParamInfo {
+                    id: id.into(),
+                    normal_value,
+                    stepped_value: None,
+                },
+                gesture_state,
+            })
+        };
+
+        // A peak band at 0 dB has no effect.
+        let config = app.app().eq_curve.config();
+        assert!(app.app().eq_curve.response_db(1_000.0).abs() < 1e-3);
+
+        // Dragging the node moves it along with the pointer, changing both
+        // the frequency and the gain.
+        app.pointer_moved(MAIN_WINDOW, point(138.0, 74.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.pointer_moved(MAIN_WINDOW, point(170.0, 42.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+        assert_eq!(
+            app.drain_actions(),
+            vec![
+                gesture("freq", 0.5, Some(GestureState::GestureStarted)),
+                gesture("gain", 0.5, Some(GestureState::GestureStarted)),
+                gesture("freq", 0.625, Some(GestureState::Gesturing)),
+                gesture("gain", 0.75, Some(GestureState::Gesturing)),
+                gesture("freq", 0.625, Some(GestureState::GestureFinished)),
+                gesture("gain", 0.75, Some(GestureState::GestureFinished)),
+            ]
+        );
+
+        // The response at the center frequency of a peak band is its gain.
+        let hz = config.normal_to_hz(0.625);
+        assert!((app.app().eq_curve.response_db(hz) - 12.0).abs() < 1e-3);
+
+        // Scrolling over the node changes the Q.
+        app.scroll_wheel(
+            MAIN_WINDOW,
+            WheelDeltaType::Points(Vector::new(0.0, -100.0)),
+        );
+        app.process_updates();
+        let actions = app.drain_actions();
+        assert_eq!(
+            actions[0],
+            gesture("q", 0.5, Some(GestureState::GestureStarted))
+        );
+        assert!(matches!(
+            &actions[1],
+            TestAction::Gesture(ParamUpdate {
+                gesture_state: Some(GestureState::Gesturing),
+                ..
+            })
+        ));
+        assert!(app.app().eq_curve.normal_value("q").unwrap() > 0.5);
+
+        // Values set by the host are applied without cancelling the Q
+        // gesture.
+        assert!(app.app_mut().eq_curve.set_normal_value("gain", 0.5));
+        assert!(!app.app_mut().eq_curve.set_normal_value("unknown", 0.5));
+        app.process_updates();
+        assert_eq!(app.drain_actions(), vec![gesture("gain", 0.5, None)]);
+        assert!(app.app().eq_curve.response_db(hz).abs() < 1e-3);
+        assert!(!app.app_mut().eq_curve.set_normal_value("gain", 0.5));
+
+        // Disabled bands don't contribute to the response.
+        assert!(app.app_mut().eq_curve.set_normal_value("gain", 1.0));
+        assert!(app.app_mut().eq_curve.set_band_enabled(0, false));
+        app.process_updates();
+        assert_eq!(app.app().eq_curve.response_db(hz), 0.0);
+    }
+}
@@ -685,6 +685,16 @@ impl<A: Clone + 'static> Element<A> for LabelElement {
             primitives.add_text(icon_primitive);
         }
     }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        // Icon-only labels are purely decorative.
+        shared_state
+            .inner
+            .text()
+            .map(|text| AccessNode::new(AccessRole::Label).label(Some(text)))
+    }
 }
 
 struct SharedState {
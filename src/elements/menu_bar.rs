@@ -0,0 +1,887 @@
+use derive_where::derive_where;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::text::TextPrimitive;
+
+use super::drop_down_menu::{MenuInner, MenuPopup, MenuPopupResponse};
+use super::label::{LabelInner, LabelPaddingInfo, LabelStyle};
+
+/// A top-level menu in a [`MenuBar`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MenuBarMenu {
+    pub title: String,
+    pub entries: Vec<MenuEntry>,
+}
+
+impl MenuBarMenu {
+    pub fn new(title: impl Into<String>, entries: Vec<MenuEntry>) -> Self {
+        Self {
+            title: title.into(),
+            entries,
+        }
+    }
+}
+
+/// The style of a [`MenuBar`] element
+///
+/// The menus themselves are styled with the [`DropDownMenuStyle`] of the same
+/// class.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuBarStyle {
+    pub text_properties: TextProperties,
+
+    /// The color of the title text
+    ///
+    /// By default this is set to `color::WHITE`.
+    pub text_color: RGBA8,
+    /// The color of the title text when the title is hovered, highlighted
+    /// with the keyboard, or its menu is open.
+    ///
+    /// If this is `None`, then `text_color` will be used.
+    ///
+    /// By default this is set to `None`.
+    pub text_color_hover: Option<RGBA8>,
+
+    /// The background of the menu bar.
+    ///
+    /// By default this is set to `QuadStyle::TRANSPARENT`.
+    pub back_quad: QuadStyle,
+    /// The background of a title when it is hovered, highlighted with the
+    /// keyboard, or its menu is open.
+    ///
+    /// By default this is set to `QuadStyle::TRANSPARENT`.
+    pub title_bg_quad_hover: QuadStyle,
+
+    /// The padding around the text of each title.
+    ///
+    /// By default this has all values set to `0.0`.
+    pub title_padding: Padding,
+    /// The space between the left edge of the menu bar and the first title.
+    ///
+    /// By default this is set to `0.0`.
+    pub outer_padding: f32,
+
+    /// The cursor icon to show when the user hovers over a title.
+    ///
+    /// If this is `None`, then the cursor icon will not be changed.
+    ///
+    /// By default this is set to `None`.
+    pub cursor_icon: Option<CursorIcon>,
+}
+
+impl Default for MenuBarStyle {
+    fn default() -> Self {
+        Self {
+            text_properties: Default::default(),
+            text_color: color::WHITE,
+            text_color_hover: None,
+            back_quad: QuadStyle::TRANSPARENT,
+            title_bg_quad_hover: QuadStyle::TRANSPARENT,
+            title_padding: Padding::default(),
+            outer_padding: 0.0,
+            cursor_icon: None,
+        }
+    }
+}
+
+impl MenuBarStyle {
+    fn label_style(&self, hovered: bool) -> LabelStyle {
+        LabelStyle {
+            text_properties: self.text_properties,
+            text_color: if hovered {
+                self.text_color_hover.unwrap_or(self.text_color)
+            } else {
+                self.text_color
+            },
+            text_padding: self.title_padding,
+            ..Default::default()
+        }
+    }
+
+    fn padding_info(&self) -> LabelPaddingInfo {
+        LabelPaddingInfo {
+            default_icon_size: 0.0,
+            text_padding: self.title_padding,
+            icon_padding: Padding::zero(),
+            text_icon_spacing: 0.0,
+        }
+    }
+}
+
+impl ElementStyle for MenuBarStyle {
+    const ID: &'static str = "menubar";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            text_color: color::BLACK,
+            ..Default::default()
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[derive_where(Default)]
+pub struct MenuBarBuilder<A: Clone + 'static> {
+    pub action: Option<Box<dyn FnMut(usize) -> A>>,
+    pub menus: Vec<MenuBarMenu>,
+}
+
+impl<A: Clone + 'static> MenuBarBuilder<A> {
+    /// The action to send when an entry in any of the menus is selected.
+    ///
+    /// Note the `unique_id` of each entry should be unique across all of
+    /// the menus.
+    pub fn on_entry_selected<F: FnMut(usize) -> A + 'static>(mut self, f: F) -> Self {
+        self.action = Some(Box::new(f));
+        self
+    }
+
+    pub fn menus(mut self, menus: Vec<MenuBarMenu>) -> Self {
+        self.menus = menus;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> MenuBar {
+        let MenuBarBuilder {
+            action,
+            menus,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+        } = self;
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            new_menus: None,
+            checked_changes: Vec::new(),
+        }));
+
+        let class = window_cx.builder_class(class);
+
+        let cursor_icon = window_cx
+            .res
+            .style_system
+            .get::<MenuBarStyle>(class)
+            .cursor_icon;
+
+        let titles = build_titles(menus, class, window_cx.res);
+
+        let el = ElementBuilder::new(MenuBarElement {
+            shared_state: Rc::clone(&shared_state),
+            action,
+            titles,
+            popup: MenuPopup::new(),
+            open_title: None,
+            highlighted_title: None,
+            active: false,
+            alt_pressed: false,
+            bar_rect: rect,
+            bounds: rect,
+            cursor_icon,
+        })
+        .builder_values(z_index, scissor_rect, Some(class), window_cx)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_POSITION_CHANGE
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE
+                | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_UNCAPTURED_KEYS,
+        )
+        .build(window_cx);
+
+        MenuBar { el, shared_state }
+    }
+}
+
+struct MenuBarTitle {
+    label: LabelInner,
+    menu: MenuInner,
+    /// The horizontal bounds of the title relative to the menu bar.
+    start_x: f32,
+    end_x: f32,
+}
+
+struct MenuBarElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    action: Option<Box<dyn FnMut(usize) -> A>>,
+    titles: Vec<MenuBarTitle>,
+    popup: MenuPopup,
+    /// The title whose menu is open.
+    open_title: Option<usize>,
+    /// The title which is hovered or highlighted with the keyboard.
+    highlighted_title: Option<usize>,
+    /// Whether or not the menu bar has focus, either because a menu is open
+    /// or because keyboard navigation was activated.
+    active: bool,
+    /// Whether or not the Alt key has been pressed without any other key.
+    alt_pressed: bool,
+    /// The bounds of the bar itself.
+    bar_rect: Rect,
+    /// The bounds of this element, which contain the bar and the open menus.
+    bounds: Rect,
+    cursor_icon: Option<CursorIcon>,
+}
+
+impl<A: Clone + 'static> MenuBarElement<A> {
+    fn title_at(&self, position: Point) -> Option<usize> {
+        if !self.bar_rect.contains(position) {
+            return None;
+        }
+
+        let x = position.x - self.bar_rect.min_x();
+        self.titles
+            .iter()
+            .position(|title| x >= title.start_x && x < title.end_x)
+    }
+
+    fn activate(&mut self, cx: &mut ElementContext<'_, A>) {
+        if !self.active {
+            self.active = true;
+            cx.steal_temporary_focus();
+            cx.listen_to_pointer_clicked_off();
+        }
+    }
+
+    /// Activate keyboard navigation with the first title highlighted.
+    fn activate_keyboard(&mut self, cx: &mut ElementContext<'_, A>) {
+        if self.titles.is_empty() {
+            return;
+        }
+
+        self.activate(cx);
+        self.highlighted_title = Some(0);
+        cx.request_repaint();
+    }
+
+    /// Open the menu of the given title.
+    ///
+    /// If `highlight_first` is `true`, then the first entry in the menu is
+    /// highlighted.
+    fn open_menu(&mut self, index: usize, highlight_first: bool, cx: &mut ElementContext<'_, A>) {
+        let position = Point::new(
+            self.bar_rect.min_x() + self.titles[index].start_x,
+            self.bar_rect.max_y(),
+        );
+
+        self.open_title = Some(index);
+        self.highlighted_title = Some(index);
+        self.popup
+            .open(&self.titles[index].menu, position, highlight_first, cx);
+
+        self.activate(cx);
+        self.update_bounds(cx);
+    }
+
+    fn close_menu(&mut self, cx: &mut ElementContext<'_, A>) {
+        self.open_title = None;
+        self.popup.close();
+        self.update_bounds(cx);
+        cx.request_repaint();
+    }
+
+    /// Move the keyboard highlight to the next (or previous if `reverse` is
+    /// `true`) title, wrapping around at the ends.
+    fn next_title(&self, reverse: bool) -> Option<usize> {
+        let len = self.titles.len();
+        if len == 0 {
+            return None;
+        }
+
+        Some(match (self.highlighted_title, reverse) {
+            (Some(i), false) => (i + 1) % len,
+            (Some(i), true) => (i + len - 1) % len,
+            (None, false) => 0,
+            (None, true) => len - 1,
+        })
+    }
+
+    /// Resize this element to contain the bar and the open menus.
+    fn update_bounds(&mut self, cx: &mut ElementContext<'_, A>) {
+        let bounds = self
+            .popup
+            .bounds()
+            .map(|b| b.union(&self.bar_rect))
+            .unwrap_or(self.bar_rect);
+
+        if self.bounds != bounds {
+            self.bounds = bounds;
+            cx.set_rect(bounds);
+        }
+    }
+
+    fn handle_popup_response(
+        &mut self,
+        response: MenuPopupResponse,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match response {
+            MenuPopupResponse::NotCaptured => return EventCaptureStatus::NotCaptured,
+            MenuPopupResponse::Captured => {
+                self.update_bounds(cx);
+            }
+            MenuPopupResponse::Selected(unique_id) => {
+                if let Some(action) = &mut self.action {
+                    cx.send_action((action)(unique_id)).unwrap();
+                }
+
+                cx.release_focus();
+                cx.cursor_icon = CursorIcon::Default;
+            }
+            MenuPopupResponse::Close => {
+                // Keep the title highlighted so the user can continue
+                // navigating with the keyboard.
+                self.close_menu(cx);
+            }
+            MenuPopupResponse::NavigateOut { reverse } => {
+                if let Some(index) = self.next_title(reverse) {
+                    self.open_menu(index, true, cx);
+                }
+            }
+        }
+
+        EventCaptureStatus::Captured
+    }
+
+    fn handle_key_down(
+        &mut self,
+        key_event: &KeyboardEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        if self.popup.is_open() {
+            let Some(index) = self.open_title else {
+                return EventCaptureStatus::NotCaptured;
+            };
+            let response = self
+                .popup
+                .key_down(&mut self.titles[index].menu, key_event, cx);
+            return self.handle_popup_response(response, cx);
+        }
+
+        if key_event.state != KeyState::Down
+            || key_event
+                .modifiers
+                .intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::META)
+        {
+            return EventCaptureStatus::NotCaptured;
+        }
+
+        match key_event.code {
+            Code::ArrowLeft | Code::ArrowRight => {
+                self.highlighted_title = self.next_title(key_event.code == Code::ArrowLeft);
+                cx.request_repaint();
+            }
+            Code::Home | Code::End => {
+                self.highlighted_title = if key_event.code == Code::Home {
+                    self.next_title(false).map(|_| 0)
+                } else {
+                    self.titles.len().checked_sub(1)
+                };
+                cx.request_repaint();
+            }
+            Code::ArrowDown | Code::Enter | Code::NumpadEnter | Code::Space => {
+                if let Some(index) = self.highlighted_title {
+                    self.open_menu(index, true, cx);
+                }
+            }
+            Code::Escape => {
+                cx.release_focus();
+            }
+            _ => return EventCaptureStatus::NotCaptured,
+        }
+
+        EventCaptureStatus::Captured
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for MenuBarElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged => {
+                let (new_menus, checked_changes) = {
+                    let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                    (
+                        shared_state.new_menus.take(),
+                        std::mem::take(&mut shared_state.checked_changes),
+                    )
+                };
+
+                if let Some(new_menus) = new_menus {
+                    if self.active {
+                        cx.release_focus();
+                    }
+
+                    self.titles = build_titles(new_menus, cx.class(), cx.res);
+                }
+
+                for (unique_id, checked) in checked_changes {
+                    for title in self.titles.iter_mut() {
+                        title.menu.set_checked_by_id(unique_id, checked);
+                    }
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                let style = cx.res.style_system.get::<MenuBarStyle>(cx.class());
+                self.cursor_icon = style.cursor_icon;
+            }
+            ElementEvent::PositionChanged | ElementEvent::SizeChanged => {
+                // Ignore changes caused by opening and closing menus.
+                if cx.rect() == self.bounds {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                self.bar_rect = cx.rect();
+                self.bounds = cx.rect();
+
+                if self.active {
+                    cx.release_focus();
+                }
+            }
+            ElementEvent::ClickedOff => {
+                cx.release_focus();
+            }
+            ElementEvent::Focus(false) => {
+                self.active = false;
+                self.highlighted_title = None;
+                self.close_menu(cx);
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                if let Some(index) = self.open_title {
+                    if self.popup.contains(position) {
+                        let entry_hovered =
+                            self.popup
+                                .pointer_moved(&self.titles[index].menu, position, cx);
+                        self.update_bounds(cx);
+
+                        if let Some(cursor_icon) = self.cursor_icon {
+                            if entry_hovered {
+                                cx.cursor_icon = cursor_icon;
+                            }
+                        }
+
+                        return EventCaptureStatus::Captured;
+                    }
+                }
+
+                let hovered_title = self.title_at(position);
+
+                if let Some(index) = hovered_title {
+                    // Hovering across the titles switches the open menu.
+                    if self.open_title.is_some() && self.open_title != Some(index) {
+                        self.open_menu(index, false, cx);
+                    }
+
+                    if let Some(cursor_icon) = self.cursor_icon {
+                        cx.cursor_icon = cursor_icon;
+                    }
+                }
+
+                if let Some(index) = self.open_title {
+                    self.popup
+                        .pointer_moved(&self.titles[index].menu, position, cx);
+                } else if hovered_title.is_some() || !self.active {
+                    // Keep the keyboard highlight while the pointer is
+                    // outside of the bar.
+                    if self.highlighted_title != hovered_title {
+                        self.highlighted_title = hovered_title;
+                        cx.request_repaint();
+                    }
+                }
+
+                if self.popup.is_open() || self.bar_rect.contains(position) {
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if !self.active && self.highlighted_title.is_some() {
+                    self.highlighted_title = None;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                button, position, ..
+            }) => {
+                if let Some(index) = self.open_title {
+                    let response = self.popup.pointer_pressed(
+                        &mut self.titles[index].menu,
+                        position,
+                        button,
+                        cx,
+                    );
+
+                    if response != MenuPopupResponse::NotCaptured {
+                        return self.handle_popup_response(response, cx);
+                    }
+                }
+
+                match self.title_at(position) {
+                    Some(index) if button == PointerButton::Primary => {
+                        if self.open_title == Some(index) {
+                            cx.release_focus();
+                        } else {
+                            self.open_menu(index, false, cx);
+                        }
+                    }
+                    _ => {
+                        if self.active {
+                            cx.release_focus();
+                        }
+                    }
+                }
+
+                if self.popup.is_open() || self.bar_rect.contains(position) {
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Pointer(event) => {
+                if self.popup.is_open() || self.bar_rect.contains(event.position()) {
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Keyboard(key_event) => {
+                match key_event.code {
+                    Code::AltLeft | Code::AltRight => {
+                        if key_event.state == KeyState::Down {
+                            self.alt_pressed = !key_event.repeat;
+                            return EventCaptureStatus::NotCaptured;
+                        } else if self.alt_pressed {
+                            // The Alt key was pressed and released on its own.
+                            self.alt_pressed = false;
+
+                            if self.active {
+                                cx.release_focus();
+                            } else {
+                                self.activate_keyboard(cx);
+                            }
+
+                            return EventCaptureStatus::Captured;
+                        }
+                    }
+                    Code::F10
+                        if key_event.state == KeyState::Down
+                            && !key_event.repeat
+                            && key_event.modifiers.is_empty() =>
+                    {
+                        self.alt_pressed = false;
+
+                        if self.active {
+                            cx.release_focus();
+                        } else {
+                            self.activate_keyboard(cx);
+                        }
+
+                        return EventCaptureStatus::Captured;
+                    }
+                    _ => {
+                        if key_event.state == KeyState::Down {
+                            self.alt_pressed = false;
+                        }
+                    }
+                }
+
+                if self.active {
+                    return self.handle_key_down(&key_event, cx);
+                }
+            }
+            ElementEvent::TextComposition(composition_event) => {
+                if let Some(index) = self.open_title {
+                    let response = self.popup.text_composition(
+                        &self.titles[index].menu,
+                        &composition_event,
+                        cx,
+                    );
+                    return self.handle_popup_response(response, cx);
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let bar_origin = self.bar_rect.origin - self.bounds.origin.to_vector();
+
+        {
+            let style: &MenuBarStyle = cx.res.style_system.get(cx.class);
+
+            let label_style_idle = style.label_style(false);
+            let label_style_hover = style.label_style(true);
+
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive(Rect::new(bar_origin, self.bar_rect.size)),
+            );
+
+            let mut text_primitives: Vec<TextPrimitive> = Vec::with_capacity(self.titles.len());
+
+            for (i, title) in self.titles.iter_mut().enumerate() {
+                let hovered = self.highlighted_title == Some(i) || self.open_title == Some(i);
+                let title_rect = Rect::new(
+                    bar_origin + Vector::new(title.start_x, 0.0),
+                    Size::new(title.end_x - title.start_x, self.bar_rect.height()),
+                );
+
+                if hovered {
+                    primitives.set_z_index(1);
+                    primitives.add(style.title_bg_quad_hover.create_primitive(title_rect));
+                }
+
+                let label_primitives = title.label.render(
+                    title_rect,
+                    if hovered {
+                        &label_style_hover
+                    } else {
+                        &label_style_idle
+                    },
+                    &mut cx.res.font_system,
+                );
+
+                if let Some(p) = label_primitives.text {
+                    text_primitives.push(p);
+                }
+            }
+
+            primitives.set_z_index(2);
+            primitives.add_text_batch(text_primitives);
+        }
+
+        if let Some(index) = self.open_title {
+            let style: &DropDownMenuStyle = cx.res.style_system.get(cx.class);
+
+            // The menus are drawn on top of the bar.
+            self.popup.render(
+                &mut self.titles[index].menu,
+                self.bounds.origin,
+                3,
+                style,
+                &mut cx.res.font_system,
+                primitives,
+            );
+        }
+    }
+}
+
+struct SharedState {
+    new_menus: Option<Vec<MenuBarMenu>>,
+    checked_changes: Vec<(usize, bool)>,
+}
+
+/// A handle to a [`MenuBarElement`], a horizontal strip of menu titles where
+/// clicking a title opens its menu.
+///
+/// Keyboard navigation is activated by pressing and releasing the Alt key,
+/// or by pressing F10.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct MenuBar {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl MenuBar {
+    pub fn builder<A: Clone + 'static>() -> MenuBarBuilder<A> {
+        MenuBarBuilder::default()
+    }
+
+    /// Set the menus of the menu bar. This will close any open menu.
+    ///
+    /// Note this will *always* trigger an element update, so use
+    /// this method sparingly.
+    pub fn set_menus(&mut self, menus: Vec<MenuBarMenu>) {
+        {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+            shared_state.new_menus = Some(menus);
+            shared_state.checked_changes.clear();
+        }
+        self.el.notify_custom_state_change();
+    }
+
+    /// Set the checked state of the [`MenuEntry::Check`] or
+    /// [`MenuEntry::Radio`] entry with the given `unique_id` in any of the
+    /// menus. Checking a radio entry unchecks the other entries in its group.
+    ///
+    /// Note, check and radio entries update their own checked state when
+    /// they are selected, so this only needs to be called when the state
+    /// is changed by something other than this menu bar.
+    ///
+    /// Note this will *always* trigger an element update, but it is much
+    /// cheaper than rebuilding the menus with [`MenuBar::set_menus`].
+    pub fn set_checked(&mut self, unique_id: usize, checked: bool) {
+        RefCell::borrow_mut(&self.shared_state)
+            .checked_changes
+            .push((unique_id, checked));
+        self.el.notify_custom_state_change();
+    }
+}
+
+fn build_titles(
+    menus: Vec<MenuBarMenu>,
+    class: ClassID,
+    res: &mut ResourceCtx,
+) -> Vec<MenuBarTitle> {
+    let bar_style = res.style_system.get::<MenuBarStyle>(class).clone();
+    let menu_style = res.style_system.get::<DropDownMenuStyle>(class).clone();
+    let label_style = bar_style.label_style(false);
+
+    let mut x = bar_style.outer_padding;
+
+    menus
+        .into_iter()
+        .map(|MenuBarMenu { title, entries }| {
+            let mut label = LabelInner::new(
+                Some(title),
+                None,
+                Vector::default(),
+                Vector::default(),
+                None,
+                IconScale::default(),
+                Default::default(),
+                &label_style,
+                &mut res.font_system,
+            );
+
+            let width = label.desired_size(|| bar_style.padding_info()).width;
+            let start_x = x;
+            x += width;
+
+            MenuBarTitle {
+                label,
+                menu: MenuInner::new(entries, &menu_style, &mut res.font_system),
+                start_x,
+                end_x: x,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        MenuSelected(usize),
+    }
+
+    struct MenuBarTestApp {
+        _menu_bar: MenuBar,
+    }
+
+    impl Application for MenuBarTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            // Make sure the titles have a width even if no fonts are available.
+            cx.res.style_system.add(
+                ClassID::default(),
+                true,
+                MenuBarStyle {
+                    title_padding: padding_vh(0.0, 10.0),
+                    ..Default::default()
+                },
+            );
+
+            let menu_bar = MenuBar::builder()
+                .menus(vec![
+                    MenuBarMenu::new(
+                        "File",
+                        vec![MenuEntry::option("New", 0), MenuEntry::option("Open", 1)],
+                    ),
+                    MenuBarMenu::new(
+                        "Edit",
+                        vec![MenuEntry::option("Undo", 2), MenuEntry::option("Redo", 3)],
+                    ),
+                    MenuBarMenu::new("View", vec![MenuEntry::check("Snap to grid", false, 4)]),
+                ])
+                .on_entry_selected(TestAction::MenuSelected)
+                .rect(rect(0.0, 0.0, 400.0, 24.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self {
+                _menu_bar: menu_bar,
+            })
+        }
+    }
+
+    #[test]
+    fn test_menu_bar() {
+        let mut app = HeadlessApp::<MenuBarTestApp>::new(AppConfig::default()).unwrap();
+
+        let press = |app: &mut HeadlessApp<MenuBarTestApp>, code| {
+            app.key_press(MAIN_WINDOW, code, Modifiers::empty());
+        };
+
+        // Pressing and releasing Alt activates keyboard navigation.
+        press(&mut app, Code::AltLeft);
+        press(&mut app, Code::ArrowRight);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(2)]);
+
+        // The left and right arrow keys move between the open menus.
+        press(&mut app, Code::F10);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::ArrowRight);
+        press(&mut app, Code::ArrowRight);
+        press(&mut app, Code::ArrowLeft);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(3)]);
+
+        // Escape closes the menu, and then deactivates the menu bar.
+        press(&mut app, Code::F10);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Escape);
+        press(&mut app, Code::Escape);
+        press(&mut app, Code::Enter);
+        assert!(app.drain_actions().is_empty());
+
+        // Clicking a title opens its menu, and clicking it again closes it.
+        let file_title = point(2.0, 12.0);
+        app.click(MAIN_WINDOW, file_title, PointerButton::Primary);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(0)]);
+
+        app.click(MAIN_WINDOW, file_title, PointerButton::Primary);
+        app.click(MAIN_WINDOW, file_title, PointerButton::Primary);
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Enter);
+        assert!(app.drain_actions().is_empty());
+
+        // Hovering across the titles switches the open menu.
+        app.click(MAIN_WINDOW, file_title, PointerButton::Primary);
+        for x in (0..400).step_by(4) {
+            app.pointer_moved(MAIN_WINDOW, point(x as f32, 12.0));
+        }
+        press(&mut app, Code::ArrowDown);
+        press(&mut app, Code::Enter);
+        assert_eq!(app.drain_actions(), vec![TestAction::MenuSelected(4)]);
+    }
+}
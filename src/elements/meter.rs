@@ -0,0 +1,1023 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rootvg::text::Metrics;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::text::{RcTextBuffer, TextPrimitive};
+
+#[cfg(feature = "gradient")]
+use crate::vg::{
+    gradient::{Gradient, LinearGradient},
+    math::Angle,
+};
+
+/// A color used for the part of a [`Meter`]'s level bar at and above the
+/// given level (up to the next color stop).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeterColorStop {
+    /// The level in decibels where this color starts. The level of the
+    /// first color stop is ignored, and it always starts at the bottom of
+    /// the meter.
+    pub db: f32,
+    pub color: RGBA8,
+}
+
+impl MeterColorStop {
+    pub const fn new(db: f32, color: RGBA8) -> Self {
+        Self { db, color }
+    }
+}
+
+/// The style of a [`Meter`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeterStyle {
+    /// The background of the track behind the level bar of each channel.
+    ///
+    /// By default this is set to `QuadStyle::TRANSPARENT`.
+    pub track_quad: QuadStyle,
+
+    /// The colors of the level bar, sorted from the lowest level to the
+    /// highest. The first color is also used below its level.
+    ///
+    /// By default this is set to green, yellow above `-18 dB`, and red
+    /// above `-6 dB`.
+    pub level_colors: Vec<MeterColorStop>,
+
+    /// How the level bar is filled with the level colors.
+    ///
+    /// By default this is set to `MeterLevelFill::Gradient`.
+    pub level_fill: MeterLevelFill,
+
+    /// When an RMS level is shown, the peak level is shown behind it with
+    /// the level colors multiplied by this alpha value.
+    ///
+    /// By default this is set to `0.4`.
+    pub peak_alpha_multiplier: f32,
+
+    /// The color of the peak hold line. If this is `None`, then the level
+    /// color at the held level is used.
+    ///
+    /// By default this is set to `None`.
+    pub peak_hold_color: Option<RGBA8>,
+    /// The thickness of the peak hold line.
+    ///
+    /// By default this is set to `2.0`.
+    pub peak_hold_width: f32,
+
+    /// The clip indicator at the top (or right) end of each channel when
+    /// the channel has not clipped.
+    pub clip_indicator_off: QuadStyle,
+    /// The clip indicator at the top (or right) end of each channel after
+    /// the channel has clipped.
+    pub clip_indicator_on: QuadStyle,
+    /// The length of the clip indicators. Set to `0.0` to hide them.
+    ///
+    /// By default this is set to `4.0`.
+    pub clip_indicator_size: f32,
+    /// The space between the clip indicator and the level bar.
+    ///
+    /// By default this is set to `2.0`.
+    pub clip_indicator_spacing: f32,
+
+    /// The space between each channel.
+    ///
+    /// By default this is set to `2.0`.
+    pub channel_spacing: f32,
+
+    /// The width of the scale to the left of the channels (or the height
+    /// of the scale above the channels if the meter is horizontal). This
+    /// is only used if the meter has ticks.
+    ///
+    /// By default this is set to `24.0`.
+    pub scale_width: f32,
+    /// The space between the scale and the channels.
+    ///
+    /// By default this is set to `2.0`.
+    pub scale_spacing: f32,
+    /// The color of the tick marks.
+    pub tick_color: RGBA8,
+    /// The length of the tick marks.
+    ///
+    /// By default this is set to `3.0`.
+    pub tick_length: f32,
+    /// The thickness of the tick marks.
+    ///
+    /// By default this is set to `1.0`.
+    pub tick_width: f32,
+    /// The space between a tick mark and its label.
+    ///
+    /// By default this is set to `2.0`.
+    pub tick_label_spacing: f32,
+    /// The text properties of the tick labels.
+    pub text_properties: TextProperties,
+    /// The color of the tick labels.
+    pub text_color: RGBA8,
+
+    /// Additional flags for the quad primitives.
+    ///
+    /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
+    pub quad_flags: QuadFlags,
+}
+
+/// How the level bar of a [`Meter`] is filled with the colors in
+/// [`MeterStyle::level_colors`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MeterLevelFill {
+    /// Blend from the color of each stop to the color of the next stop.
+    ///
+    /// This requires the `gradient` feature. If it is disabled, then this
+    /// is the same as `MeterLevelFill::Stepped`.
+    #[default]
+    Gradient,
+    /// Fill the whole zone between two stops with the color of the lower
+    /// stop.
+    Stepped,
+}
+
+impl MeterStyle {
+    /// The color of the level bar at the given level.
+    pub fn level_color(&self, db: f32, config: &MeterConfig) -> RGBA8 {
+        self.color_at_normal(config.db_to_normal(db), config)
+    }
+
+    fn gradient_fill(&self) -> bool {
+        cfg!(feature = "gradient") && self.level_fill == MeterLevelFill::Gradient
+    }
+
+    /// The normalized position of the color stop at index `i`.
+    fn stop_normal(&self, i: usize, config: &MeterConfig) -> f32 {
+        if i == 0 {
+            0.0
+        } else {
+            config.db_to_normal(self.level_colors[i].db)
+        }
+    }
+
+    fn color_at_normal(&self, normal: f32, config: &MeterConfig) -> RGBA8 {
+        let Some(i) = (0..self.level_colors.len())
+            .rev()
+            .find(|i| normal >= self.stop_normal(*i, config))
+        else {
+            return color::TRANSPARENT;
+        };
+
+        let color = self.level_colors[i].color;
+
+        if !self.gradient_fill() || i + 1 >= self.level_colors.len() {
+            return color;
+        }
+
+        let start_normal = self.stop_normal(i, config);
+        let end_normal = self.stop_normal(i + 1, config);
+        if end_normal <= start_normal {
+            return color;
+        }
+
+        mix_colors(
+            color,
+            self.level_colors[i + 1].color,
+            (normal - start_normal) / (end_normal - start_normal),
+        )
+    }
+
+    fn level_quad(&self, bg: Background) -> QuadStyle {
+        QuadStyle::new_with_flags(bg, BorderStyle::default(), self.quad_flags)
+    }
+}
+
+fn mix_colors(a: RGBA8, b: RGBA8, t: f32) -> RGBA8 {
+    let mix = |a: u8, b: u8| (a as f32 + ((b as f32 - a as f32) * t)).round() as u8;
+
+    RGBA8::new(mix(a.r, b.r), mix(a.g, b.g), mix(a.b, b.b), mix(a.a, b.a))
+}
+
+/// A gradient going up the level bar (or to the right if the meter is
+/// horizontal).
+#[cfg(feature = "gradient")]
+fn level_gradient(start: RGBA8, end: RGBA8, horizontal: bool) -> Gradient {
+    // A linear gradient with an angle of zero goes from bottom to top.
+    let angle = if horizontal {
+        Angle::frac_pi_2()
+    } else {
+        Angle::zero()
+    };
+
+    Gradient::Linear(
+        LinearGradient::new(angle)
+            .add_stop(0.0, start)
+            .add_stop(1.0, end),
+    )
+}
+
+impl Default for MeterStyle {
+    fn default() -> Self {
+        Self {
+            track_quad: QuadStyle::TRANSPARENT,
+            level_colors: vec![
+                MeterColorStop::new(f32::NEG_INFINITY, RGBA8::new(92, 196, 112, 255)),
+                MeterColorStop::new(-18.0, RGBA8::new(222, 196, 82, 255)),
+                MeterColorStop::new(-6.0, RGBA8::new(228, 84, 72, 255)),
+            ],
+            level_fill: MeterLevelFill::Gradient,
+            peak_alpha_multiplier: 0.4,
+            peak_hold_color: None,
+            peak_hold_width: 2.0,
+            clip_indicator_off: QuadStyle::new(
+                Background::Solid(RGBA8::new(100, 40, 40, 255)),
+                BorderStyle::default(),
+            ),
+            clip_indicator_on: QuadStyle::new(
+                Background::Solid(RGBA8::new(240, 60, 50, 255)),
+                BorderStyle::default(),
+            ),
+            clip_indicator_size: 4.0,
+            clip_indicator_spacing: 2.0,
+            channel_spacing: 2.0,
+            scale_width: 24.0,
+            scale_spacing: 2.0,
+            tick_color: gray_a(255, 100),
+            tick_length: 3.0,
+            tick_width: 1.0,
+            tick_label_spacing: 2.0,
+            text_properties: TextProperties {
+                metrics: Metrics {
+                    font_size: 10.0,
+                    line_height: 12.0,
+                },
+                ..Default::default()
+            },
+            text_color: gray_a(255, 160),
+            quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        }
+    }
+}
+
+impl ElementStyle for MeterStyle {
+    const ID: &'static str = "meter";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            text_color: gray_a(0, 160),
+            tick_color: gray_a(0, 100),
+            ..Default::default()
+        }
+    }
+}
+
+/// The behavior of a [`Meter`] element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeterConfig {
+    /// The level at the bottom (or left) end of the meter.
+    ///
+    /// By default this is set to `-60.0`.
+    pub min_db: f32,
+    /// The level at the top (or right) end of the meter.
+    ///
+    /// By default this is set to `6.0`.
+    pub max_db: f32,
+    /// How long the peak hold line stays at the highest level before it
+    /// starts to fall.
+    ///
+    /// By default this is set to `1.5`.
+    pub peak_hold_seconds: f64,
+    /// How fast the peak hold line falls once the hold time has passed.
+    ///
+    /// By default this is set to `20.0`.
+    pub peak_decay_db_per_second: f32,
+    /// A channel clips when its peak level goes above this level. The
+    /// clip indicator stays lit until it is clicked or
+    /// [`Meter::reset_clip`] is called.
+    ///
+    /// By default this is set to `0.0`.
+    pub clip_threshold_db: f32,
+}
+
+impl MeterConfig {
+    /// Convert a level in decibels to a normalized position on the meter
+    /// in the range `[0.0, 1.0]`.
+    pub fn db_to_normal(&self, db: f32) -> f32 {
+        if self.max_db <= self.min_db {
+            return 0.0;
+        }
+
+        ((db - self.min_db) / (self.max_db - self.min_db)).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for MeterConfig {
+    fn default() -> Self {
+        Self {
+            min_db: -60.0,
+            max_db: 6.0,
+            peak_hold_seconds: 1.5,
+            peak_decay_db_per_second: 20.0,
+            clip_threshold_db: 0.0,
+        }
+    }
+}
+
+/// The level of one channel of a [`Meter`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeterLevel {
+    /// The peak level in decibels.
+    pub peak_db: f32,
+    /// The RMS level in decibels. If this is `Some`, then it is drawn in
+    /// front of the peak level.
+    pub rms_db: Option<f32>,
+}
+
+impl MeterLevel {
+    pub const fn new(peak_db: f32) -> Self {
+        Self {
+            peak_db,
+            rms_db: None,
+        }
+    }
+
+    pub const fn with_rms(peak_db: f32, rms_db: f32) -> Self {
+        Self {
+            peak_db,
+            rms_db: Some(rms_db),
+        }
+    }
+
+    /// A level for silence.
+    pub const SILENCE: Self = Self::new(f32::NEG_INFINITY);
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+pub struct MeterBuilder {
+    pub num_channels: usize,
+    pub horizontal: bool,
+    pub config: MeterConfig,
+    pub ticks: Vec<f32>,
+}
+
+impl Default for MeterBuilder {
+    fn default() -> Self {
+        Self {
+            num_channels: 2,
+            horizontal: false,
+            config: MeterConfig::default(),
+            ticks: Vec::new(),
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            scissor_rect: None,
+        }
+    }
+}
+
+impl MeterBuilder {
+    /// The number of channels (i.e. `1` for mono, `2` for stereo).
+    ///
+    /// By default this is set to `2`.
+    pub const fn num_channels(mut self, num_channels: usize) -> Self {
+        self.num_channels = num_channels;
+        self
+    }
+
+    pub const fn horizontal(mut self, horizontal: bool) -> Self {
+        self.horizontal = horizontal;
+        self
+    }
+
+    pub const fn config(mut self, config: MeterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The levels in decibels where a tick mark and a label are shown on
+    /// the scale next to the channels.
+    ///
+    /// By default this is empty, and no scale is shown.
+    pub fn ticks(mut self, ticks: impl IntoIterator<Item = f32>) -> Self {
+        self.ticks = ticks.into_iter().collect();
+        self
+    }
+
+    pub fn build<A: Clone + 'static>(self, window_cx: &mut WindowContext<'_, A>) -> Meter {
+        let MeterBuilder {
+            num_channels,
+            horizontal,
+            config,
+            ticks,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<MeterStyle>(window_cx.builder_class(class));
+
+        let tick_labels = ticks
+            .into_iter()
+            .map(|db| TickLabel::new(db, style.text_properties, &mut window_cx.res.font_system))
+            .collect();
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            channels: vec![ChannelState::new(&config); num_channels],
+            config,
+        }));
+
+        let el = ElementBuilder::new(MeterElement {
+            shared_state: Rc::clone(&shared_state),
+            horizontal,
+            tick_labels,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .build(window_cx);
+
+        Meter { el, shared_state }
+    }
+}
+
+struct TickLabel {
+    db: f32,
+    text: String,
+    text_buffer: RcTextBuffer,
+    text_size: Size,
+}
+
+impl TickLabel {
+    fn new(db: f32, text_properties: TextProperties, font_system: &mut FontSystem) -> Self {
+        let text = format!("{}", db);
+        let text_buffer = RcTextBuffer::new(&text, text_properties, None, None, false, font_system);
+
+        let mut label = Self {
+            db,
+            text,
+            text_buffer,
+            // This will be overwritten below.
+            text_size: Size::default(),
+        };
+        label.text_size = label.text_buffer.measure();
+        label
+    }
+
+    fn sync_new_style(&mut self, text_properties: TextProperties, font_system: &mut FontSystem) {
+        self.text_buffer
+            .set_text_and_props(&self.text, text_properties, font_system);
+        self.text_size = self.text_buffer.measure();
+    }
+}
+
+/// The rectangles of a meter, laid out as if the meter were vertical.
+struct MeterLayout {
+    scale: Option<Rect>,
+    /// The level bar and the clip indicator of each channel.
+    channels: Vec<(Rect, Option<Rect>)>,
+}
+
+impl MeterLayout {
+    fn new(bounds_size: Size, style: &MeterStyle, num_channels: usize, has_scale: bool) -> Self {
+        let (scale, channels_x) = if has_scale {
+            (
+                Some(Rect::new(
+                    Point::zero(),
+                    Size::new(style.scale_width, bounds_size.height),
+                )),
+                style.scale_width + style.scale_spacing,
+            )
+        } else {
+            (None, 0.0)
+        };
+
+        let (clip_height, bar_y) = if style.clip_indicator_size > 0.0 {
+            (
+                style.clip_indicator_size,
+                style.clip_indicator_size + style.clip_indicator_spacing,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        let n = num_channels.max(1) as f32;
+        let channel_width =
+            ((bounds_size.width - channels_x - (style.channel_spacing * (n - 1.0))) / n).max(0.0);
+
+        let channels = (0..num_channels)
+            .map(|i| {
+                let x = channels_x + (i as f32 * (channel_width + style.channel_spacing));
+
+                let bar = Rect::new(
+                    Point::new(x, bar_y),
+                    Size::new(channel_width, (bounds_size.height - bar_y).max(0.0)),
+                );
+                let clip = (clip_height > 0.0)
+                    .then(|| Rect::new(Point::new(x, 0.0), Size::new(channel_width, clip_height)));
+
+                (bar, clip)
+            })
+            .collect();
+
+        Self { scale, channels }
+    }
+}
+
+/// Returns the part of the vertical level bar `bar` between the given
+/// normalized positions, where `0.0` is the bottom of the bar.
+fn bar_part(bar: Rect, start_normal: f32, end_normal: f32) -> Rect {
+    let y_start = bar.max_y() - (bar.height() * start_normal);
+    let y_end = bar.max_y() - (bar.height() * end_normal);
+
+    Rect::new(
+        Point::new(bar.min_x(), y_end),
+        Size::new(bar.width(), y_start - y_end),
+    )
+}
+
+struct MeterElement {
+    shared_state: Rc<RefCell<SharedState>>,
+    horizontal: bool,
+    tick_labels: Vec<TickLabel>,
+}
+
+impl<A: Clone + 'static> Element<A> for MeterElement {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                let style = cx.res.style_system.get::<MeterStyle>(cx.class());
+
+                for label in self.tick_labels.iter_mut() {
+                    label.sync_new_style(style.text_properties, &mut cx.res.font_system);
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                if button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+                let rect = cx.rect();
+                let position = if self.horizontal {
+                    // Convert to the coordinates of a vertical meter.
+                    Point::new(position.y - rect.min_y(), rect.max_x() - position.x)
+                } else {
+                    position - rect.origin.to_vector()
+                };
+                let vertical_size = if self.horizontal {
+                    Size::new(rect.height(), rect.width())
+                } else {
+                    rect.size
+                };
+
+                let style = cx.res.style_system.get::<MeterStyle>(cx.class());
+                let layout = MeterLayout::new(
+                    vertical_size,
+                    style,
+                    shared_state.channels.len(),
+                    !self.tick_labels.is_empty(),
+                );
+
+                let clicked_clip_indicator = layout
+                    .channels
+                    .iter()
+                    .filter_map(|(_, clip)| *clip)
+                    .any(|clip| clip.contains(position));
+
+                if clicked_clip_indicator {
+                    if shared_state.reset_clip() {
+                        cx.request_repaint();
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<MeterStyle>(cx.class);
+
+        let vertical_size = if self.horizontal {
+            Size::new(cx.bounds_size.height, cx.bounds_size.width)
+        } else {
+            cx.bounds_size
+        };
+        let to_bounds = |r: Rect| -> Rect {
+            if self.horizontal {
+                Rect::new(
+                    Point::new(cx.bounds_size.width - r.max_y(), r.min_x()),
+                    Size::new(r.height(), r.width()),
+                )
+            } else {
+                r
+            }
+        };
+
+        let config = &shared_state.config;
+        let layout = MeterLayout::new(
+            vertical_size,
+            style,
+            shared_state.channels.len(),
+            !self.tick_labels.is_empty(),
+        );
+
+        for ((bar, clip), channel) in layout.channels.iter().zip(shared_state.channels.iter()) {
+            if !style.track_quad.is_transparent() {
+                primitives.add(style.track_quad.create_primitive(to_bounds(*bar)));
+            }
+
+            if let Some(clip) = clip {
+                let clip_quad = if channel.clipped {
+                    &style.clip_indicator_on
+                } else {
+                    &style.clip_indicator_off
+                };
+
+                if !clip_quad.is_transparent() {
+                    primitives.add(clip_quad.create_primitive(to_bounds(*clip)));
+                }
+            }
+
+            // Draw the peak level dimmed behind the RMS level, or on its own
+            // if there is no RMS level.
+            let add_level = |primitives: &mut PrimitiveGroup, db: f32, alpha_multiplier: f32| {
+                let end_normal = config.db_to_normal(db.min(config.max_db));
+
+                for i in 0..style.level_colors.len() {
+                    let start_normal = style.stop_normal(i, config);
+                    let stop_end_normal = if i + 1 < style.level_colors.len() {
+                        style.stop_normal(i + 1, config)
+                    } else {
+                        1.0
+                    }
+                    .min(end_normal);
+
+                    if stop_end_normal <= start_normal {
+                        continue;
+                    }
+
+                    let color =
+                        color::multiply_alpha(style.level_colors[i].color, alpha_multiplier);
+                    #[cfg(feature = "gradient")]
+                    let bg = if style.level_fill == MeterLevelFill::Gradient {
+                        let end_color = color::multiply_alpha(
+                            style.color_at_normal(stop_end_normal, config),
+                            alpha_multiplier,
+                        );
+
+                        Background::Gradient(level_gradient(color, end_color, self.horizontal))
+                    } else {
+                        Background::Solid(color)
+                    };
+                    #[cfg(not(feature = "gradient"))]
+                    let bg = Background::Solid(color);
+
+                    primitives.add(style.level_quad(bg).create_primitive(to_bounds(bar_part(
+                        *bar,
+                        start_normal,
+                        stop_end_normal,
+                    ))));
+                }
+            };
+
+            primitives.set_z_index(1);
+            if let Some(rms_db) = channel.rms_db {
+                add_level(primitives, channel.peak_db, style.peak_alpha_multiplier);
+                add_level(primitives, rms_db, 1.0);
+            } else {
+                add_level(primitives, channel.peak_db, 1.0);
+            }
+            primitives.set_z_index(0);
+
+            if channel.hold_db > config.min_db && style.peak_hold_width > 0.0 {
+                let color = style
+                    .peak_hold_color
+                    .unwrap_or_else(|| style.level_color(channel.hold_db, config));
+                let y = bar.max_y()
+                    - (bar.height() * config.db_to_normal(channel.hold_db.min(config.max_db)));
+                let hold_rect = Rect::new(
+                    Point::new(
+                        bar.min_x(),
+                        (y - (style.peak_hold_width * 0.5)).max(bar.min_y()),
+                    ),
+                    Size::new(bar.width(), style.peak_hold_width),
+                );
+
+                primitives.set_z_index(2);
+                primitives.add(
+                    style
+                        .level_quad(Background::Solid(color))
+                        .create_primitive(to_bounds(hold_rect)),
+                );
+                primitives.set_z_index(0);
+            }
+        }
+
+        let (Some(scale), Some((bar, _))) = (layout.scale, layout.channels.first()) else {
+            return;
+        };
+
+        for label in self.tick_labels.iter() {
+            let y = bar.max_y() - (bar.height() * config.db_to_normal(label.db));
+
+            let tick_rect = Rect::new(
+                Point::new(
+                    scale.max_x() - style.tick_length,
+                    y - (style.tick_width * 0.5),
+                ),
+                Size::new(style.tick_length, style.tick_width),
+            );
+            let tick_rect = to_bounds(tick_rect);
+
+            primitives.add(
+                style
+                    .level_quad(Background::Solid(style.tick_color))
+                    .create_primitive(tick_rect),
+            );
+
+            let label_pos = if self.horizontal {
+                Point::new(
+                    (tick_rect.center().x - (label.text_size.width * 0.5))
+                        .clamp(0.0, (cx.bounds_size.width - label.text_size.width).max(0.0)),
+                    tick_rect.min_y() - style.tick_label_spacing - label.text_size.height,
+                )
+            } else {
+                Point::new(
+                    tick_rect.min_x() - style.tick_label_spacing - label.text_size.width,
+                    (y - (label.text_size.height * 0.5)).clamp(
+                        0.0,
+                        (cx.bounds_size.height - label.text_size.height).max(0.0),
+                    ),
+                )
+            };
+
+            primitives.add_text(TextPrimitive::new(
+                label.text_buffer.clone(),
+                label_pos,
+                style.text_color,
+                None,
+            ));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ChannelState {
+    peak_db: f32,
+    rms_db: Option<f32>,
+    hold_db: f32,
+    hold_seconds_left: f64,
+    clipped: bool,
+}
+
+impl ChannelState {
+    fn new(config: &MeterConfig) -> Self {
+        Self {
+            peak_db: config.min_db,
+            rms_db: None,
+            hold_db: config.min_db,
+            hold_seconds_left: 0.0,
+            clipped: false,
+        }
+    }
+
+    /// Returns `true` if the meter needs to be repainted.
+    fn update(&mut self, level: &MeterLevel, dt: f64, config: &MeterConfig) -> bool {
+        // Levels below the bottom of the meter all look the same, so clamp
+        // them to avoid repainting a silent meter.
+        let peak_db = level.peak_db.max(config.min_db);
+        let rms_db = level.rms_db.map(|db| db.max(config.min_db));
+
+        let mut changed = self.peak_db != peak_db || self.rms_db != rms_db;
+        self.peak_db = peak_db;
+        self.rms_db = rms_db;
+
+        if peak_db >= self.hold_db {
+            changed |= self.hold_db != peak_db;
+            self.hold_db = peak_db;
+            self.hold_seconds_left = config.peak_hold_seconds;
+        } else if self.hold_seconds_left > 0.0 {
+            self.hold_seconds_left -= dt;
+        } else {
+            self.hold_db =
+                (self.hold_db - (config.peak_decay_db_per_second * dt as f32)).max(peak_db);
+            changed = true;
+        }
+
+        if !self.clipped && level.peak_db > config.clip_threshold_db {
+            self.clipped = true;
+            changed = true;
+        }
+
+        changed
+    }
+}
+
+struct SharedState {
+    channels: Vec<ChannelState>,
+    config: MeterConfig,
+}
+
+impl SharedState {
+    /// Returns `true` if any channel had clipped.
+    fn reset_clip(&mut self) -> bool {
+        let mut changed = false;
+        for channel in self.channels.iter_mut() {
+            changed |= channel.clipped;
+            channel.clipped = false;
+        }
+        changed
+    }
+}
+
+/// A handle to a [`MeterElement`], a level meter with peak hold and clip
+/// indicators.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct Meter {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Meter {
+    pub fn builder() -> MeterBuilder {
+        MeterBuilder::default()
+    }
+
+    /// Update the meter with the latest level of each channel, where `dt`
+    /// is the time in seconds since the last update. This is meant to be
+    /// called in [`Application::on_tick`].
+    ///
+    /// Channels without a level in `levels` are left unchanged.
+    ///
+    /// Returns `true` if the meter needs to be repainted.
+    ///
+    /// This will *NOT* trigger an element update unless the meter has
+    /// changed, so a silent meter costs almost nothing.
+    pub fn update(&mut self, levels: &[MeterLevel], dt: f64) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let SharedState { channels, config } = &mut *shared_state;
+
+        let mut changed = false;
+        for (channel, level) in channels.iter_mut().zip(levels.iter()) {
+            changed |= channel.update(level, dt, config);
+        }
+
+        if changed {
+            self.el.notify_custom_state_change();
+        }
+
+        changed
+    }
+
+    /// Whether the given channel has clipped since the clip indicators were
+    /// last reset.
+    pub fn clipped(&self, channel: usize) -> bool {
+        RefCell::borrow(&self.shared_state)
+            .channels
+            .get(channel)
+            .map(|c| c.clipped)
+            .unwrap_or(false)
+    }
+
+    /// Turn off the clip indicators of all channels.
+    ///
+    /// Returns `true` if any channel had clipped.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn reset_clip(&mut self) -> bool {
+        let changed = RefCell::borrow_mut(&self.shared_state).reset_clip();
+        if changed {
+            self.el.notify_custom_state_change();
+        }
+        changed
+    }
+
+    /// The level of the peak hold line of the given channel in decibels.
+    pub fn peak_hold_db(&self, channel: usize) -> Option<f32> {
+        RefCell::borrow(&self.shared_state)
+            .channels
+            .get(channel)
+            .map(|c| c.hold_db)
+    }
+
+    pub fn num_channels(&self) -> usize {
+        RefCell::borrow(&self.shared_state).channels.len()
+    }
+
+    pub fn config(&self) -> MeterConfig {
+        RefCell::borrow(&self.shared_state).config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    struct MeterTestApp {
+        meter: Meter,
+    }
+
+    impl Application for MeterTestApp {
+        type Action = ();
+
+        fn init(cx: &mut AppContext<()>) -> Result<Self, Box<dyn std::error::Error>> {
+            let meter = Meter::builder()
+                .num_channels(2)
+                .rect(rect(10.0, 10.0, 30.0, 100.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { meter })
+        }
+    }
+
+    #[test]
+    fn test_meter() {
+        let mut app = HeadlessApp::<MeterTestApp>::new(AppConfig::default()).unwrap();
+        let meter = &mut app.app_mut().meter;
+
+        // Silence does not need a repaint.
+        assert!(!meter.update(&[MeterLevel::SILENCE, MeterLevel::SILENCE], 0.1));
+
+        assert!(meter.update(&[MeterLevel::new(-6.0), MeterLevel::new(-12.0)], 0.1));
+        assert!(!meter.update(&[MeterLevel::new(-6.0), MeterLevel::new(-12.0)], 0.1));
+
+        // The peak is held, then it falls.
+        assert!(meter.update(&[MeterLevel::new(-20.0), MeterLevel::new(-12.0)], 1.0));
+        assert_eq!(meter.peak_hold_db(0), Some(-6.0));
+        meter.update(&[MeterLevel::new(-20.0), MeterLevel::new(-12.0)], 1.0);
+        assert_eq!(meter.peak_hold_db(0), Some(-6.0));
+        meter.update(&[MeterLevel::new(-20.0), MeterLevel::new(-12.0)], 0.1);
+        assert!((meter.peak_hold_db(0).unwrap() + 8.0).abs() < 1e-4);
+        assert_eq!(meter.peak_hold_db(1), Some(-12.0));
+
+        // Clipping latches until the clip indicator is clicked.
+        meter.update(&[MeterLevel::new(1.0), MeterLevel::new(-12.0)], 0.1);
+        meter.update(&[MeterLevel::new(-20.0), MeterLevel::new(-12.0)], 0.1);
+        assert!(meter.clipped(0));
+        assert!(!meter.clipped(1));
+
+        app.click(MAIN_WINDOW, point(15.0, 60.0), PointerButton::Primary);
+        app.process_updates();
+        assert!(app.app().meter.clipped(0));
+
+        app.click(MAIN_WINDOW, point(30.0, 12.0), PointerButton::Primary);
+        app.process_updates();
+        assert!(!app.app().meter.clipped(0));
+    }
+
+    #[test]
+    fn test_meter_level_color() {
+        let config = MeterConfig::default();
+        let black = RGBA8::new(0, 0, 0, 255);
+        let orange = RGBA8::new(200, 100, 0, 255);
+
+        let mut style = MeterStyle {
+            level_colors: vec![
+                MeterColorStop::new(f32::NEG_INFINITY, black),
+                MeterColorStop::new(-20.0, orange),
+            ],
+            level_fill: MeterLevelFill::Stepped,
+            ..Default::default()
+        };
+
+        assert_eq!(style.level_color(-40.0, &config), black);
+        assert_eq!(style.level_color(-20.0, &config), orange);
+        assert_eq!(style.level_color(0.0, &config), orange);
+
+        // The first stop starts at the bottom of the meter (-60 dB).
+        style.level_fill = MeterLevelFill::Gradient;
+        #[cfg(feature = "gradient")]
+        assert_eq!(
+            style.level_color(-40.0, &config),
+            RGBA8::new(100, 50, 0, 255)
+        );
+        assert_eq!(style.level_color(-20.0, &config), orange);
+        assert_eq!(style.level_color(0.0, &config), orange);
+    }
+}
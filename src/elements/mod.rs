@@ -1,13 +1,18 @@
 pub mod button;
 pub mod click_area;
+pub mod combo_box;
 pub mod drop_down_menu;
 pub mod label;
+pub mod menu_bar;
+pub mod meter;
+pub mod number_field;
 pub mod paragraph;
 pub mod quad;
 pub mod radio_button;
 pub mod resize_handle;
 pub mod scroll_area;
 pub mod separator;
+pub mod spectrum;
 pub mod switch;
 pub mod tab;
 pub mod text_input;
@@ -15,5 +20,11 @@ pub mod toggle_button;
 pub mod tooltip;
 pub mod virtual_slider;
 
+#[cfg(feature = "tessellation")]
+pub mod envelope_editor;
+#[cfg(feature = "tessellation")]
+pub mod eq_curve;
 #[cfg(feature = "svg-icons")]
 pub mod icon;
+#[cfg(feature = "tessellation")]
+pub mod waveform;
@@ -0,0 +1,1104 @@
+use smol_str::{SmolStr, ToSmolStr};
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+
+use super::text_input::{TextInputInner, TextInputUpdateResult};
+use super::virtual_slider::{parse_value_with_unit, InnerParamUpdate, VirtualSliderInner};
+
+/// How the value of a [`NumberField`] is mapped, displayed, and parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFieldFormat {
+    /// The value at a normalized value of `0.0`.
+    ///
+    /// By default this is set to `0.0`.
+    pub min: f64,
+    /// The value at a normalized value of `1.0`.
+    ///
+    /// By default this is set to `1.0`.
+    pub max: f64,
+    /// The number of digits shown after the decimal point.
+    ///
+    /// By default this is set to `2`.
+    pub decimals: usize,
+    /// The unit shown after the value, such as `"Hz"`, `"dB"`, or `"ms"`.
+    ///
+    /// Typing the unit is optional when entering a value, and it is
+    /// matched case-insensitively.
+    ///
+    /// By default this is set to an empty string.
+    pub unit: SmolStr,
+    /// Whether values of `1000` and above are shown with a `k` prefix
+    /// (i.e. `"2.50 kHz"`).
+    ///
+    /// A `k` suffix is always accepted when entering a value (i.e. `"2.5k"`).
+    ///
+    /// By default this is set to `false`.
+    pub kilo_prefix: bool,
+}
+
+impl NumberFieldFormat {
+    pub fn new(min: f64, max: f64, unit: impl ToSmolStr) -> Self {
+        Self {
+            min,
+            max,
+            unit: unit.to_smolstr(),
+            ..Default::default()
+        }
+    }
+
+    pub const fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub const fn kilo_prefix(mut self, kilo_prefix: bool) -> Self {
+        self.kilo_prefix = kilo_prefix;
+        self
+    }
+
+    /// Convert a value to a normalized value in the range `[0.0, 1.0]`.
+    pub fn value_to_normal(&self, value: f64) -> f64 {
+        if self.max == self.min {
+            0.0
+        } else {
+            ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Convert a normalized value in the range `[0.0, 1.0]` to a value.
+    pub fn normal_to_value(&self, normal: f64) -> f64 {
+        self.min + normal.clamp(0.0, 1.0) * (self.max - self.min)
+    }
+
+    /// Format the value along with its unit.
+    pub fn format(&self, value: f64) -> String {
+        let (value, prefix) = if self.kilo_prefix && value.abs() >= 1000.0 {
+            (value / 1000.0, "k")
+        } else {
+            (value, "")
+        };
+
+        if prefix.is_empty() && self.unit.is_empty() {
+            format!("{:.*}", self.decimals, value)
+        } else {
+            format!("{:.*} {}{}", self.decimals, value, prefix, self.unit)
+        }
+    }
+
+    /// Parse text entered by the user into a value.
+    ///
+    /// Returns `None` if the text is not a number. The value is not clamped
+    /// to the range of the field.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        parse_value_with_unit(text, &self.unit)
+    }
+}
+
+impl Default for NumberFieldFormat {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            max: 1.0,
+            decimals: 2,
+            unit: SmolStr::default(),
+            kilo_prefix: false,
+        }
+    }
+}
+
+impl TextValidator for NumberFieldFormat {
+    fn is_valid(&self, text: &str) -> bool {
+        self.parse(text).is_some()
+    }
+}
+
+/// The style of a [`NumberField`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFieldStyle {
+    /// The style of the background and of the text, both when showing the
+    /// value and when editing it.
+    pub text_input: TextInputStyle,
+
+    /// The background of the bar that shows the current value. The bar is
+    /// hidden while the value is being edited.
+    ///
+    /// By default this is set to `Background::TRANSPARENT`.
+    pub fill_bg: Background,
+    /// The background of the value bar when the element is hovered.
+    ///
+    /// If this is `None`, then `fill_bg` will be used.
+    ///
+    /// By default this is set to `None`.
+    pub fill_bg_hover: Option<Background>,
+    pub fill_bg_disabled: DisabledBackground,
+}
+
+impl Default for NumberFieldStyle {
+    fn default() -> Self {
+        Self {
+            text_input: TextInputStyle::default(),
+            fill_bg: Background::TRANSPARENT,
+            fill_bg_hover: None,
+            fill_bg_disabled: Default::default(),
+        }
+    }
+}
+
+impl ElementStyle for NumberFieldStyle {
+    const ID: &'static str = "nmbrfld";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            text_input: TextInputStyle::default_light_style(),
+            ..Default::default()
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+pub struct NumberFieldBuilder<A: Clone + 'static> {
+    pub on_gesture: Option<Box<dyn FnMut(ParamUpdate) -> A>>,
+    pub param_id: SmolStr,
+    pub normal_value: f64,
+    pub default_normal: f64,
+    pub format: NumberFieldFormat,
+    pub config: VirtualSliderConfig,
+}
+
+impl<A: Clone + 'static> NumberFieldBuilder<A> {
+    pub fn new(param_id: impl ToSmolStr) -> Self {
+        Self {
+            on_gesture: None,
+            param_id: param_id.to_smolstr(),
+            normal_value: 0.0,
+            default_normal: 0.0,
+            format: NumberFieldFormat::default(),
+            config: VirtualSliderConfig::default(),
+            z_index: Default::default(),
+            scissor_rect: Default::default(),
+            class: Default::default(),
+            rect: Default::default(),
+            manually_hidden: Default::default(),
+            disabled: Default::default(),
+        }
+    }
+
+    pub fn on_gesture<F: FnMut(ParamUpdate) -> A + 'static>(mut self, f: F) -> Self {
+        self.on_gesture = Some(Box::new(f));
+        self
+    }
+
+    pub const fn normal_value(mut self, normal: f64) -> Self {
+        self.normal_value = normal;
+        self
+    }
+
+    pub const fn default_normal(mut self, normal: f64) -> Self {
+        self.default_normal = normal;
+        self
+    }
+
+    /// How the value is mapped, displayed, and parsed.
+    pub fn format(mut self, format: NumberFieldFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The drag, scroll wheel, and keyboard behavior. This is shared with
+    /// knobs and sliders.
+    ///
+    /// The open text entry options of the config are used to enter text
+    /// edit mode.
+    pub const fn config(mut self, config: VirtualSliderConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> NumberField {
+        let NumberFieldBuilder {
+            on_gesture,
+            param_id,
+            normal_value,
+            default_normal,
+            format,
+            config,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+        } = self;
+
+        let style: &NumberFieldStyle = window_cx
+            .res
+            .style_system
+            .get(window_cx.builder_class(class));
+
+        let slider = VirtualSliderInner::new(
+            param_id,
+            normal_value,
+            default_normal,
+            None,
+            config,
+            true,
+            false,
+        );
+
+        let text_input = TextInputInner::new(
+            format.format(format.normal_to_value(slider.normal_value())),
+            String::new(),
+            false,
+            256,
+            rect.size,
+            disabled,
+            true,
+            Some(Box::new(format.clone())),
+            &style.text_input,
+            &mut window_cx.res.font_system,
+        );
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            slider,
+            text_input,
+            format,
+            editing: false,
+            queued_new_normal: None,
+        }));
+
+        let mut flags = ElementFlags::PAINTS
+            | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+            | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_SIZE_CHANGE
+            | ElementFlags::LISTENS_TO_FOCUS_CHANGE;
+        if !disabled {
+            flags.insert(ElementFlags::FOCUSABLE);
+        }
+
+        let el = ElementBuilder::new(NumberFieldElement {
+            shared_state: Rc::clone(&shared_state),
+            on_gesture,
+            hovered: false,
+            focused: false,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(flags)
+        .build(window_cx);
+
+        NumberField { el, shared_state }
+    }
+}
+
+struct NumberFieldElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    on_gesture: Option<Box<dyn FnMut(ParamUpdate) -> A>>,
+    hovered: bool,
+    focused: bool,
+}
+
+impl<A: Clone + 'static> Element<A> for NumberFieldElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let on_gesture = &mut self.on_gesture;
+
+        let captured = TextInputUpdateResult {
+            capture_status: EventCaptureStatus::Captured,
+            ..Default::default()
+        };
+
+        let res = match event {
+            ElementEvent::Animation { .. } => shared_state.text_input.on_animation(),
+            ElementEvent::CustomStateChanged => {
+                if let Some(normal) = shared_state.queued_new_normal.take() {
+                    if let Some(param_update) = shared_state.slider.set_normal_value(normal) {
+                        shared_state.send_param_update(param_update, on_gesture, cx);
+                    }
+                }
+
+                if shared_state.text_input.disabled {
+                    self.hovered = false;
+
+                    if shared_state.editing {
+                        shared_state.finish_editing(false, on_gesture, cx);
+                    }
+                    if let Some(param_update) = shared_state.slider.finish_gesture() {
+                        shared_state.send_param_update(param_update, on_gesture, cx);
+                    }
+                }
+
+                shared_state
+                    .text_input
+                    .on_custom_state_changed(cx.clipboard, &mut cx.res.font_system)
+            }
+            ElementEvent::StyleChanged => {
+                let style = cx.res.style_system.get::<NumberFieldStyle>(cx.class());
+                shared_state
+                    .text_input
+                    .sync_new_style(&style.text_input, &mut cx.res.font_system);
+                TextInputUpdateResult {
+                    needs_repaint: true,
+                    ..Default::default()
+                }
+            }
+            ElementEvent::SizeChanged => {
+                let bounds_size = cx.rect().size;
+                let style = cx.res.style_system.get::<NumberFieldStyle>(cx.class());
+                shared_state.text_input.on_size_changed(
+                    bounds_size,
+                    &style.text_input,
+                    &mut cx.res.font_system,
+                );
+                TextInputUpdateResult::default()
+            }
+            ElementEvent::Pointer(PointerEvent::Moved {
+                position,
+                delta,
+                modifiers,
+                ..
+            }) => {
+                if shared_state.text_input.disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let hovered = cx.rect().contains(position);
+                if self.hovered != hovered {
+                    self.hovered = hovered;
+                    cx.request_repaint();
+                }
+
+                if shared_state.editing {
+                    shared_state.text_input.on_pointer_moved(
+                        position,
+                        cx.rect(),
+                        &mut cx.res.font_system,
+                    )
+                } else {
+                    let config = &shared_state.slider.config;
+                    let cursor_icon = if shared_state.slider.is_gesturing() {
+                        config.cursor_icon_gesturing
+                    } else {
+                        config.cursor_icon_hover
+                    };
+                    if let Some(cursor_icon) = cursor_icon.filter(|_| hovered) {
+                        cx.cursor_icon = cursor_icon;
+                    }
+
+                    let delta = delta.filter(|_| cx.is_pointer_locked());
+
+                    if let Some(param_update) = shared_state
+                        .slider
+                        .handle_pointer_moved(position, delta, modifiers)
+                    {
+                        shared_state.send_param_update(
+                            InnerParamUpdate {
+                                inner: param_update,
+                                pointer_lock_request: None,
+                            },
+                            on_gesture,
+                            cx,
+                        );
+                    }
+
+                    captured
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hovered {
+                    self.hovered = false;
+                    cx.request_repaint();
+                }
+
+                shared_state.text_input.on_pointer_left()
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                click_count,
+                modifiers,
+                ..
+            }) => {
+                if shared_state.text_input.disabled || !cx.rect().contains(position) {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if shared_state.editing {
+                    shared_state.text_input.on_pointer_button_just_pressed(
+                        position,
+                        button,
+                        click_count,
+                        cx.rect(),
+                        &mut cx.res.font_system,
+                    )
+                } else {
+                    let config = &shared_state.slider.config;
+                    let begin_editing = match button {
+                        PointerButton::Primary => {
+                            click_count == 2 || config.open_text_entry_modifier == Some(modifiers)
+                        }
+                        PointerButton::Auxiliary => config.open_text_entry_on_middle_click,
+                        PointerButton::Secondary => config.open_text_entry_on_right_click,
+                        _ => false,
+                    };
+
+                    if let Some(param_update) = shared_state.slider.finish_gesture() {
+                        shared_state.send_param_update(param_update, on_gesture, cx);
+                    }
+
+                    if begin_editing {
+                        shared_state.begin_editing(cx)
+                    } else if button == PointerButton::Primary {
+                        if let Some(param_update) = shared_state.slider.begin_drag_gesture(position)
+                        {
+                            shared_state.send_param_update(param_update, on_gesture, cx);
+                            cx.steal_focus();
+                        }
+
+                        captured
+                    } else {
+                        TextInputUpdateResult::default()
+                    }
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
+                button, position, ..
+            }) => {
+                if shared_state.text_input.disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if shared_state.editing {
+                    shared_state.text_input.on_pointer_button_just_released(
+                        position,
+                        button,
+                        cx.rect(),
+                    )
+                } else {
+                    if button == PointerButton::Primary && cx.has_focus() {
+                        cx.release_focus();
+                    }
+
+                    if cx.rect().contains(position) {
+                        captured
+                    } else {
+                        TextInputUpdateResult::default()
+                    }
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheel {
+                position,
+                delta_type,
+                modifiers,
+                ..
+            }) => {
+                if shared_state.text_input.disabled
+                    || shared_state.editing
+                    || !cx.rect().contains(position)
+                    || !shared_state.slider.config.use_scroll_wheel
+                {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if let Some(param_update) = shared_state.slider.begin_scroll_wheel_gesture() {
+                    shared_state.send_param_update(
+                        InnerParamUpdate {
+                            inner: param_update,
+                            pointer_lock_request: None,
+                        },
+                        on_gesture,
+                        cx,
+                    );
+
+                    cx.steal_focus();
+                    cx.start_scroll_wheel_timeout();
+                }
+
+                if let Some(param_update) = shared_state
+                    .slider
+                    .handle_scroll_wheel(delta_type, modifiers)
+                {
+                    shared_state.send_param_update(
+                        InnerParamUpdate {
+                            inner: param_update,
+                            pointer_lock_request: None,
+                        },
+                        on_gesture,
+                        cx,
+                    );
+                }
+
+                captured
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheelTimeout) => {
+                if !shared_state.editing && cx.has_focus() {
+                    cx.release_focus();
+                }
+
+                TextInputUpdateResult::default()
+            }
+            ElementEvent::AccessAction(action) => {
+                if shared_state.text_input.disabled || shared_state.editing {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let param_update = match action {
+                    AccessAction::Increment => shared_state.slider.increment(1.0),
+                    AccessAction::Decrement => shared_state.slider.increment(-1.0),
+                    AccessAction::SetValue(normal) => shared_state.slider.set_normal_value(normal),
+                    AccessAction::Click => None,
+                };
+
+                if let Some(param_update) = param_update {
+                    shared_state.send_param_update(param_update, on_gesture, cx);
+                }
+
+                TextInputUpdateResult::default()
+            }
+            ElementEvent::Keyboard(key_event) => {
+                if shared_state.text_input.disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if shared_state.editing {
+                    let mut res = shared_state.text_input.on_keyboard_event(
+                        &key_event,
+                        cx.clipboard,
+                        &mut cx.res.font_system,
+                    );
+
+                    // Invalid text keeps the field in edit mode.
+                    let finished = if res.enter_key_pressed
+                        && shared_state
+                            .format
+                            .parse(shared_state.text_input.text())
+                            .is_some()
+                    {
+                        Some(shared_state.finish_editing(true, on_gesture, cx))
+                    } else if res.escape_key_pressed {
+                        Some(shared_state.finish_editing(false, on_gesture, cx))
+                    } else {
+                        None
+                    };
+
+                    if let Some(finished) = finished {
+                        res.needs_repaint = true;
+                        res.set_animating = finished.set_animating;
+                    }
+
+                    res
+                } else {
+                    match key_event.state {
+                        KeyState::Down => {
+                            if matches!(key_event.code, Code::Enter | Code::NumpadEnter)
+                                && !shared_state.slider.is_gesturing()
+                            {
+                                shared_state.begin_editing(cx)
+                            } else if let Some(update) = shared_state
+                                .slider
+                                .handle_key_down(key_event.code, key_event.modifiers)
+                            {
+                                for param_update in
+                                    [update.begin, update.changed].into_iter().flatten()
+                                {
+                                    shared_state.send_param_update(
+                                        InnerParamUpdate {
+                                            inner: param_update,
+                                            pointer_lock_request: None,
+                                        },
+                                        on_gesture,
+                                        cx,
+                                    );
+                                }
+
+                                captured
+                            } else {
+                                TextInputUpdateResult::default()
+                            }
+                        }
+                        KeyState::Up => {
+                            if let Some(param_update) =
+                                shared_state.slider.handle_key_up(key_event.code)
+                            {
+                                shared_state.send_param_update(param_update, on_gesture, cx);
+
+                                captured
+                            } else {
+                                TextInputUpdateResult::default()
+                            }
+                        }
+                    }
+                }
+            }
+            ElementEvent::TextComposition(comp_event) => {
+                if shared_state.editing {
+                    shared_state
+                        .text_input
+                        .on_text_composition_event(&comp_event, &mut cx.res.font_system)
+                } else {
+                    TextInputUpdateResult::default()
+                }
+            }
+            ElementEvent::Focus(focused) => {
+                if self.focused != focused {
+                    self.focused = focused;
+                    cx.request_repaint();
+                }
+
+                if focused {
+                    TextInputUpdateResult::default()
+                } else {
+                    if let Some(param_update) = shared_state.slider.finish_gesture() {
+                        shared_state.send_param_update(param_update, on_gesture, cx);
+                    }
+
+                    if shared_state.editing {
+                        shared_state.finish_editing(true, on_gesture, cx)
+                    } else {
+                        TextInputUpdateResult::default()
+                    }
+                }
+            }
+            ElementEvent::ClickedOff => {
+                if shared_state.editing {
+                    cx.release_focus();
+                    shared_state.finish_editing(true, on_gesture, cx)
+                } else {
+                    TextInputUpdateResult::default()
+                }
+            }
+            _ => TextInputUpdateResult::default(),
+        };
+
+        if res.needs_repaint {
+            cx.request_repaint();
+        }
+        if let Some(focus) = res.set_focus {
+            if focus {
+                cx.steal_focus();
+            } else {
+                cx.release_focus();
+            }
+        }
+        if res.hovered && shared_state.editing {
+            cx.cursor_icon = CursorIcon::Text;
+        }
+        if res.listen_to_pointer_clicked_off {
+            cx.listen_to_pointer_clicked_off();
+        }
+        if let Some(animating) = res.set_animating {
+            cx.set_animating(animating);
+        }
+
+        res.capture_status
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style: &NumberFieldStyle = cx.res.style_system.get(cx.class);
+        let text_style = &style.text_input;
+        let disabled = shared_state.text_input.disabled;
+
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        let mut p = shared_state.text_input.create_primitives(
+            text_style,
+            bounds,
+            Vector::default(),
+            self.hovered,
+        );
+
+        let back_quad = text_style.back_quad_style(
+            disabled,
+            self.focused,
+            self.hovered,
+            shared_state.text_input.invalid(),
+        );
+        if !back_quad.is_transparent() {
+            primitives.add(back_quad.create_primitive(bounds));
+        }
+
+        if !shared_state.editing {
+            let fill_bg = if disabled {
+                style.fill_bg_disabled.get(style.fill_bg)
+            } else if self.hovered {
+                style.fill_bg_hover.unwrap_or(style.fill_bg)
+            } else {
+                style.fill_bg
+            };
+
+            let inner_bounds =
+                bounds.inflate(-text_style.back_border_width, -text_style.back_border_width);
+            let fill_width = inner_bounds.width() * shared_state.slider.normal_value() as f32;
+
+            if !fill_bg.is_transparent() && fill_width > 0.0 {
+                primitives.set_z_index(1);
+                primitives.add(
+                    QuadStyle::new_with_flags(
+                        fill_bg,
+                        border_radius_only(text_style.back_border_radius),
+                        text_style.quad_flags,
+                    )
+                    .create_primitive(Rect::new(
+                        inner_bounds.origin,
+                        Size::new(fill_width, inner_bounds.height()),
+                    )),
+                );
+            }
+        }
+
+        if let Some(highlight_range) = p.highlight_range.take() {
+            primitives.set_z_index(1);
+            primitives.add_solid_quad(highlight_range);
+        }
+        if let Some(text) = p.text.take() {
+            primitives.set_z_index(2);
+            primitives.add_text(text);
+        }
+        if let Some(cursor) = p.cursor.take() {
+            primitives.set_z_index(3);
+            primitives.add_solid_quad(cursor);
+        }
+
+        if self.focused && !disabled && !text_style.focus_ring.is_transparent() {
+            primitives.set_z_index(3);
+            primitives.add(text_style.focus_ring.create_primitive(bounds));
+        }
+    }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let slider = &shared_state.slider;
+
+        Some(
+            AccessNode::new(AccessRole::Slider)
+                .label(Some(slider.param_id.as_str()))
+                .value(AccessValue::Range {
+                    value: slider.normal_value(),
+                    min: 0.0,
+                    max: 1.0,
+                    step: Some(f64::from(slider.config.increment_step)),
+                })
+                .disabled(shared_state.text_input.disabled),
+        )
+    }
+}
+
+struct SharedState {
+    slider: VirtualSliderInner,
+    text_input: TextInputInner,
+    format: NumberFieldFormat,
+    editing: bool,
+    queued_new_normal: Option<f64>,
+}
+
+impl SharedState {
+    fn send_param_update<A: Clone + 'static>(
+        &mut self,
+        param_update: InnerParamUpdate,
+        on_gesture: &mut Option<Box<dyn FnMut(ParamUpdate) -> A>>,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        if let Some(f) = on_gesture.as_mut() {
+            cx.send_action((f)(param_update.inner)).unwrap();
+        }
+
+        if let Some(lock) = param_update.pointer_lock_request {
+            cx.request_pointer_lock(lock);
+        }
+
+        self.sync_display_text(&mut cx.res.font_system);
+        cx.request_repaint();
+    }
+
+    /// Show the current value, unless the user is editing the text.
+    fn sync_display_text(&mut self, font_system: &mut FontSystem) {
+        if !self.editing {
+            let text = self
+                .format
+                .format(self.format.normal_to_value(self.slider.normal_value()));
+            self.text_input.set_text(text, font_system, false);
+        }
+    }
+
+    fn begin_editing<A: Clone + 'static>(
+        &mut self,
+        cx: &mut ElementContext<'_, A>,
+    ) -> TextInputUpdateResult {
+        self.editing = true;
+
+        if !cx.has_focus() {
+            cx.steal_focus();
+        }
+
+        let mut res = self
+            .text_input
+            .on_focus_changed(true, cx.clipboard, &mut cx.res.font_system);
+        res.capture_status = EventCaptureStatus::Captured;
+        res
+    }
+
+    /// Leave text edit mode, setting the value from the entered text if
+    /// `commit` is `true` and the text is valid.
+    fn finish_editing<A: Clone + 'static>(
+        &mut self,
+        commit: bool,
+        on_gesture: &mut Option<Box<dyn FnMut(ParamUpdate) -> A>>,
+        cx: &mut ElementContext<'_, A>,
+    ) -> TextInputUpdateResult {
+        self.editing = false;
+
+        let value = commit
+            .then(|| self.format.parse(self.text_input.text()))
+            .flatten();
+
+        let mut res =
+            self.text_input
+                .on_focus_changed(false, cx.clipboard, &mut cx.res.font_system);
+
+        match value.and_then(|value| {
+            self.slider
+                .set_normal_value(self.format.value_to_normal(value))
+        }) {
+            Some(param_update) => self.send_param_update(param_update, on_gesture, cx),
+            None => self.sync_display_text(&mut cx.res.font_system),
+        }
+
+        res.capture_status = EventCaptureStatus::Captured;
+        res
+    }
+}
+
+/// A handle to a [`NumberFieldElement`].
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct NumberField {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl NumberField {
+    pub fn builder<A: Clone + 'static>(param_id: impl ToSmolStr) -> NumberFieldBuilder<A> {
+        NumberFieldBuilder::new(param_id)
+    }
+
+    /// Set the normalized value of the parameter.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_normal_value(&mut self, new_normal: f64) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.slider.normal_value() != new_normal {
+            shared_state.queued_new_normal = Some(new_normal);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set the value of the parameter (not normalized).
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_value(&mut self, new_value: f64) -> bool {
+        let new_normal = RefCell::borrow(&self.shared_state)
+            .format
+            .value_to_normal(new_value);
+        self.set_normal_value(new_normal)
+    }
+
+    pub fn param_info(&self) -> ParamInfo {
+        RefCell::borrow(&self.shared_state).slider.param_info()
+    }
+
+    pub fn normal_value(&self) -> f64 {
+        RefCell::borrow(&self.shared_state).slider.normal_value()
+    }
+
+    pub fn default_normal(&self) -> f64 {
+        RefCell::borrow(&self.shared_state).slider.default_normal()
+    }
+
+    /// The value of the parameter (not normalized).
+    pub fn value(&self) -> f64 {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        shared_state
+            .format
+            .normal_to_value(shared_state.slider.normal_value())
+    }
+
+    /// The text currently shown in the field.
+    pub fn text<'a>(&'a self) -> Ref<'a, str> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| s.text_input.text())
+    }
+
+    pub fn format<'a>(&'a self) -> Ref<'a, NumberFieldFormat> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| &s.format)
+    }
+
+    /// Whether the user is currently editing the value as text.
+    pub fn editing(&self) -> bool {
+        RefCell::borrow(&self.shared_state).editing
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the state has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.text_input.disabled != disabled {
+            shared_state.text_input.disabled = disabled;
+            self.el.set_focusable(!disabled);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).text_input.disabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        Gesture(ParamUpdate),
+    }
+
+    struct NumberFieldTestApp {
+        time: NumberField,
+    }
+
+    impl Application for NumberFieldTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let time = NumberField::builder("time")
+                .on_gesture(TestAction::Gesture)
+                .format(NumberFieldFormat::new(0.0, 1000.0, "ms").decimals(0))
+                .normal_value(0.5)
+                .rect(rect(10.0, 10.0, 100.0, 30.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { time })
+        }
+    }
+
+    #[test]
+    fn test_number_field_format() {
+        let format = NumberFieldFormat::new(20.0, 20_000.0, "Hz").kilo_prefix(true);
+        assert_eq!(format.format(2500.0), "2.50 kHz");
+        assert_eq!(format.format(440.0), "440.00 Hz");
+        assert_eq!(format.parse("2.5 kHz"), Some(2500.0));
+        assert_eq!(format.parse(" 440hz "), Some(440.0));
+        assert_eq!(format.parse("1,5k"), Some(1500.0));
+        assert_eq!(format.parse("Hz"), None);
+    }
+
+    #[test]
+    fn test_number_field() {
+        use GestureState::*;
+
+        let mut app = HeadlessApp::<NumberFieldTestApp>::new(AppConfig::default()).unwrap();
+        assert_eq!(&*app.app().time.text(), "500 ms");
+
+        // Dragging horizontally adjusts the value.
+        app.pointer_moved(MAIN_WINDOW, point(50.0, 20.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.process_updates();
+        app.pointer_moved(MAIN_WINDOW, point(150.0, 20.0));
+        app.process_updates();
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+
+        let gestures: Vec<_> = app
+            .drain_actions()
+            .into_iter()
+            .map(|TestAction::Gesture(update)| {
+                (
+                    update.gesture_state.unwrap(),
+                    update.param_info.normal_value,
+                )
+            })
+            .collect();
+        assert_eq!(gestures.len(), 3, "{:?}", gestures);
+        for ((state, normal), (expected_state, expected_normal)) in gestures.iter().zip([
+            (GestureStarted, 0.5),
+            (Gesturing, 0.8),
+            (GestureFinished, 0.8),
+        ]) {
+            assert_eq!(*state, expected_state, "{:?}", gestures);
+            assert!((normal - expected_normal).abs() < 1e-6, "{:?}", gestures);
+        }
+        assert_eq!(&*app.app().time.text(), "800 ms");
+
+        // A double click enters text edit mode, and the unit is optional.
+        app.click(MAIN_WINDOW, point(150.0, 20.0), PointerButton::Primary);
+        app.process_updates();
+        assert!(app.app().time.editing());
+        assert!(app.drain_actions().is_empty());
+
+        app.type_text(MAIN_WINDOW, "250ms");
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        app.process_updates();
+        assert!(!app.app().time.editing());
+        assert_eq!(&*app.app().time.text(), "250 ms");
+        assert!((app.app().time.value() - 250.0).abs() < 1e-6);
+        assert!(matches!(
+            app.drain_actions().as_slice(),
+            [TestAction::Gesture(ParamUpdate {
+                gesture_state: None,
+                ..
+            })]
+        ));
+
+        // Invalid text stays in edit mode, and escape cancels the edit.
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        app.process_updates();
+        assert!(app.app().time.editing());
+
+        app.type_text(MAIN_WINDOW, "fast");
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        app.process_updates();
+        assert!(app.app().time.editing());
+
+        app.key_press(MAIN_WINDOW, Code::Escape, Modifiers::empty());
+        app.process_updates();
+        assert!(!app.app().time.editing());
+        assert_eq!(&*app.app().time.text(), "250 ms");
+        assert!(app.drain_actions().is_empty());
+    }
+}
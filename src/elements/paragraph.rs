@@ -378,6 +378,12 @@ impl<A: Clone + 'static> Element<A> for ParagraphElement {
             primitives.add_text(text_primitive);
         }
     }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        Some(AccessNode::new(AccessRole::Label).label(Some(shared_state.inner.text())))
+    }
 }
 
 struct SharedState {
@@ -46,6 +46,11 @@ pub struct RadioButtonStyle {
     ///
     /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
     pub quad_flags: QuadFlags,
+
+    /// The ring drawn around the radio button when it has keyboard focus.
+    ///
+    /// By default this is transparent.
+    pub focus_ring: FocusRingStyle,
 }
 
 impl Default for RadioButtonStyle {
@@ -72,6 +77,7 @@ impl Default for RadioButtonStyle {
             dot_bg_disabled: Default::default(),
             cursor_icon: None,
             quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            focus_ring: FocusRingStyle::default(),
         }
     }
 }
@@ -136,16 +142,25 @@ impl<A: Clone + 'static> RadioButtonBuilder<A> {
             tooltip_inner: TooltipInner::new(tooltip_data),
         }));
 
+        let mut flags = ElementFlags::PAINTS
+            | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+            | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+            | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED;
+        if !disabled {
+            flags.insert(ElementFlags::FOCUSABLE);
+        }
+
         let el = ElementBuilder::new(RadioButtonElement {
             shared_state: Rc::clone(&shared_state),
             action,
             hovered: false,
+            focused: false,
             cursor_icon,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
         .rect(rect)
         .hidden(manually_hidden)
-        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .flags(flags)
         .build(window_cx);
 
         RadioButton { el, shared_state }
@@ -156,6 +171,7 @@ struct RadioButtonElement<A: Clone + 'static> {
     shared_state: Rc<RefCell<SharedState>>,
     action: Option<A>,
     hovered: bool,
+    focused: bool,
     cursor_icon: Option<CursorIcon>,
 }
 
@@ -179,6 +195,38 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
                 let style = cx.res.style_system.get::<RadioButtonStyle>(cx.class());
                 self.cursor_icon = style.cursor_icon;
             }
+            ElementEvent::Focus(focused) => {
+                if self.focused != focused {
+                    self.focused = focused;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Keyboard(key_event) => {
+                if key_event.is_activation_press() && !shared_state.disabled {
+                    if !shared_state.toggled {
+                        shared_state.toggled = true;
+
+                        if let Some(action) = &self.action {
+                            cx.send_action(action.clone()).unwrap();
+                        }
+
+                        cx.request_repaint();
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::AccessAction(AccessAction::Click) => {
+                if !shared_state.disabled && !shared_state.toggled {
+                    shared_state.toggled = true;
+
+                    if let Some(action) = &self.action {
+                        cx.send_action(action.clone()).unwrap();
+                    }
+
+                    cx.request_repaint();
+                }
+            }
             ElementEvent::Pointer(PointerEvent::Moved { .. }) => {
                 if shared_state.disabled {
                     return EventCaptureStatus::NotCaptured;
@@ -330,6 +378,11 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
 
         primitives.add(bg_quad_style.create_primitive(bg_bounds));
 
+        if self.focused && !style.focus_ring.is_transparent() {
+            primitives.set_z_index(1);
+            primitives.add(style.focus_ring.create_primitive(bg_bounds));
+        }
+
         if shared_state.toggled {
             let quad_style = if shared_state.disabled {
                 QuadStyle {
@@ -362,6 +415,17 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
             primitives.add(quad_style.create_primitive(dot_bounds));
         }
     }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        Some(
+            AccessNode::new(AccessRole::RadioButton)
+                .label(shared_state.tooltip_inner.text())
+                .value(AccessValue::Toggled(shared_state.toggled))
+                .disabled(shared_state.disabled),
+        )
+    }
 }
 
 /// A handle to a [`RadioButtonElement`].
@@ -425,6 +489,7 @@ impl RadioButton {
 
         if shared_state.disabled != disabled {
             shared_state.disabled = disabled;
+            self.el.set_focusable(!disabled);
             self.el.notify_custom_state_change();
             true
         } else {
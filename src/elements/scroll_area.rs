@@ -974,6 +974,36 @@ impl ScrollArea {
         RefCell::borrow(&self.shared_state).scroll_offset
     }
 
+    /// Scroll by the smallest amount needed to bring the given rectangle
+    /// into view, such as the cursor of a [`TextEditor`] inside this area.
+    ///
+    /// The rectangle is in the coordinates of the content, that is relative
+    /// to the top-left corner of the content when the scroll offset is zero.
+    ///
+    /// Returns `true` if the offset has changed.
+    pub fn scroll_to_reveal(&mut self, rect: Rect) -> bool {
+        let view_size = self.el.rect().size;
+        let mut scroll_offset = self.scroll_offset();
+
+        if rect.max_x() > scroll_offset.x + view_size.width {
+            scroll_offset.x = rect.max_x() - view_size.width;
+        }
+        if rect.min_x() < scroll_offset.x {
+            scroll_offset.x = rect.min_x();
+        }
+        if rect.max_y() > scroll_offset.y + view_size.height {
+            scroll_offset.y = rect.max_y() - view_size.height;
+        }
+        if rect.min_y() < scroll_offset.y {
+            scroll_offset.y = rect.min_y();
+        }
+
+        self.set_scroll_offset(Vector::new(
+            scroll_offset.x.max(0.0),
+            scroll_offset.y.max(0.0),
+        ))
+    }
+
     /// Set the content size.
     ///
     /// Returns `true` if the content size has changed.
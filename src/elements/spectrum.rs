@@ -0,0 +1,500 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+
+mod renderer;
+
+#[cfg(feature = "tessellation")]
+pub mod curve;
+
+pub use renderer::*;
+
+/// The axes and the behavior of a [`SpectrumAnalyzer`] element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectrumConfig {
+    /// The frequency at the left edge of the element. This must be greater
+    /// than zero.
+    ///
+    /// By default this is set to `20.0`.
+    pub min_hz: f32,
+    /// The frequency at the right edge of the element.
+    ///
+    /// By default this is set to `20_000.0`.
+    pub max_hz: f32,
+    /// The level in decibels at the bottom edge of the element.
+    ///
+    /// By default this is set to `-90.0`.
+    pub min_db: f32,
+    /// The level in decibels at the top edge of the element.
+    ///
+    /// By default this is set to `6.0`.
+    pub max_db: f32,
+    /// How long it takes (in seconds) for the displayed level of a bin to
+    /// move about two-thirds of the way to a new level. Set this to `0.0`
+    /// to disable temporal smoothing.
+    ///
+    /// By default this is set to `0.1`.
+    pub smoothing_seconds: f64,
+    /// The width (in octaves) of the window that neighboring bins are
+    /// averaged over when drawing the curve. Set this to `0.0` to disable
+    /// smoothing across frequency.
+    ///
+    /// By default this is set to `1.0 / 6.0`.
+    pub frequency_smoothing_octaves: f32,
+    /// Whether or not to keep track of the peak level of each bin.
+    ///
+    /// By default this is set to `true`.
+    pub peak_hold: bool,
+    /// How long (in seconds) the peak level of a bin is held before it
+    /// starts to fall.
+    ///
+    /// By default this is set to `1.0`.
+    pub peak_hold_seconds: f64,
+    /// How fast the peak level falls after being held.
+    ///
+    /// By default this is set to `12.0`.
+    pub peak_decay_db_per_second: f32,
+}
+
+impl SpectrumConfig {
+    /// Map a frequency to the range `[0.0, 1.0]` on a logarithmic axis.
+    pub fn hz_to_normal(&self, hz: f32) -> f32 {
+        let range = (self.max_hz / self.min_hz).ln();
+        if range == 0.0 || !range.is_finite() {
+            return 0.0;
+        }
+
+        ((hz.max(self.min_hz) / self.min_hz).ln() / range).clamp(0.0, 1.0)
+    }
+
+    /// Map a value in the range `[0.0, 1.0]` to a frequency on a logarithmic
+    /// axis.
+    pub fn normal_to_hz(&self, normal: f32) -> f32 {
+        self.min_hz * (self.max_hz / self.min_hz).powf(normal.clamp(0.0, 1.0))
+    }
+
+    /// Map a level in decibels to the range `[0.0, 1.0]`.
+    pub fn db_to_normal(&self, db: f32) -> f32 {
+        if self.max_db <= self.min_db {
+            return 0.0;
+        }
+
+        ((db - self.min_db) / (self.max_db - self.min_db)).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for SpectrumConfig {
+    fn default() -> Self {
+        Self {
+            min_hz: 20.0,
+            max_hz: 20_000.0,
+            min_db: -90.0,
+            max_db: 6.0,
+            smoothing_seconds: 0.1,
+            frequency_smoothing_octaves: 1.0 / 6.0,
+            peak_hold: true,
+            peak_hold_seconds: 1.0,
+            peak_decay_db_per_second: 12.0,
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+pub struct SpectrumBuilder {
+    pub config: SpectrumConfig,
+    pub grid_hz: Vec<f32>,
+    pub grid_db: Vec<f32>,
+}
+
+impl Default for SpectrumBuilder {
+    fn default() -> Self {
+        Self {
+            config: SpectrumConfig::default(),
+            grid_hz: vec![100.0, 1_000.0, 10_000.0],
+            grid_db: Vec::new(),
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            scissor_rect: None,
+        }
+    }
+}
+
+impl SpectrumBuilder {
+    pub const fn config(mut self, config: SpectrumConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The frequencies where grid lines are drawn.
+    ///
+    /// By default this is set to `[100.0, 1_000.0, 10_000.0]`.
+    pub fn grid_hz(mut self, grid_hz: impl IntoIterator<Item = f32>) -> Self {
+        self.grid_hz = grid_hz.into_iter().collect();
+        self
+    }
+
+    /// The levels in decibels where grid lines are drawn.
+    ///
+    /// By default no lines are drawn.
+    pub fn grid_db(mut self, grid_db: impl IntoIterator<Item = f32>) -> Self {
+        self.grid_db = grid_db.into_iter().collect();
+        self
+    }
+
+    pub fn build<A: Clone + 'static, R: SpectrumRenderer>(
+        self,
+        window_cx: &mut WindowContext<'_, A>,
+    ) -> SpectrumAnalyzer<R> {
+        let SpectrumBuilder {
+            config,
+            grid_hz,
+            grid_db,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get_rc::<R::Style>(window_cx.builder_class(class));
+
+        let renderer = R::new(style);
+
+        let mut flags = ElementFlags::empty();
+        if renderer.does_paint() {
+            flags.insert(ElementFlags::PAINTS);
+        }
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            renderer,
+            config,
+            grid_hz,
+            grid_db,
+            levels_db: Vec::new(),
+            peak_db: Vec::new(),
+            peak_hold_remaining: Vec::new(),
+            bin_spacing_hz: 0.0,
+        }));
+
+        let el = ElementBuilder::new(SpectrumElement {
+            shared_state: Rc::clone(&shared_state),
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(flags)
+        .build(window_cx);
+
+        SpectrumAnalyzer { el, shared_state }
+    }
+}
+
+struct SpectrumElement<R: SpectrumRenderer + 'static> {
+    shared_state: Rc<RefCell<SharedState<R>>>,
+}
+
+impl<A: Clone + 'static, R: SpectrumRenderer + 'static> Element<A> for SpectrumElement<R> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                let new_style = cx.res.style_system.get_rc::<R::Style>(cx.class());
+                RefCell::borrow_mut(&self.shared_state)
+                    .renderer
+                    .style_changed(new_style);
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let SharedState {
+            renderer,
+            config,
+            grid_hz,
+            grid_db,
+            levels_db,
+            peak_db,
+            bin_spacing_hz,
+            ..
+        } = &mut *shared_state;
+
+        renderer.render(
+            SpectrumRenderInfo {
+                levels_db: levels_db.as_slice(),
+                peak_db: config.peak_hold.then_some(peak_db.as_slice()),
+                bin_spacing_hz: *bin_spacing_hz,
+                config: &*config,
+                grid_hz: grid_hz.as_slice(),
+                grid_db: grid_db.as_slice(),
+            },
+            cx,
+            primitives,
+        );
+    }
+}
+
+struct SharedState<R: SpectrumRenderer + 'static> {
+    renderer: R,
+    config: SpectrumConfig,
+    grid_hz: Vec<f32>,
+    grid_db: Vec<f32>,
+    levels_db: Vec<f32>,
+    peak_db: Vec<f32>,
+    peak_hold_remaining: Vec<f64>,
+    bin_spacing_hz: f32,
+}
+
+/// A handle to a [`SpectrumElement`], which displays the magnitude
+/// spectrum of a signal.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct SpectrumAnalyzer<R: SpectrumRenderer> {
+    shared_state: Rc<RefCell<SharedState<R>>>,
+}
+
+impl<R: SpectrumRenderer> SpectrumAnalyzer<R> {
+    pub fn builder() -> SpectrumBuilder {
+        SpectrumBuilder::default()
+    }
+
+    /// Feed the analyzer with the output of an FFT.
+    ///
+    /// * `magnitudes` - The linear magnitude of each bin from 0 Hz up to
+    /// and including the Nyquist frequency (i.e. `fft_size / 2 + 1` bins),
+    /// scaled so that a full-scale sine wave has a magnitude of `1.0`.
+    /// * `sample_rate` - The sample rate of the signal.
+    /// * `dt` - The time in seconds since the last call to this method.
+    ///
+    /// Returns `true` if the displayed spectrum has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the displayed
+    /// spectrum has changed, so this method is relatively cheap to call
+    /// frequently.
+    pub fn update(&mut self, magnitudes: &[f32], sample_rate: f32, dt: f64) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let SharedState {
+            config,
+            levels_db,
+            peak_db,
+            peak_hold_remaining,
+            bin_spacing_hz,
+            ..
+        } = &mut *shared_state;
+
+        let new_bin_spacing_hz = if magnitudes.len() > 1 {
+            sample_rate * 0.5 / (magnitudes.len() - 1) as f32
+        } else {
+            0.0
+        };
+
+        let mut changed = false;
+
+        if levels_db.len() != magnitudes.len() || *bin_spacing_hz != new_bin_spacing_hz {
+            // The size of the FFT has changed, so start over.
+            levels_db.clear();
+            levels_db.resize(magnitudes.len(), config.min_db);
+            peak_db.clear();
+            peak_db.resize(magnitudes.len(), config.min_db);
+            peak_hold_remaining.clear();
+            peak_hold_remaining.resize(magnitudes.len(), 0.0);
+
+            *bin_spacing_hz = new_bin_spacing_hz;
+            changed = true;
+        }
+
+        let smoothing = if config.smoothing_seconds > 0.0 {
+            (-dt / config.smoothing_seconds).exp() as f32
+        } else {
+            0.0
+        };
+
+        for (i, magnitude) in magnitudes.iter().enumerate() {
+            let db = if *magnitude > 0.0 {
+                (20.0 * magnitude.log10()).max(config.min_db)
+            } else {
+                config.min_db
+            };
+
+            let level = db + ((levels_db[i] - db) * smoothing);
+            if level != levels_db[i] {
+                levels_db[i] = level;
+                changed = true;
+            }
+
+            if !config.peak_hold {
+                continue;
+            }
+
+            if db >= peak_db[i] {
+                if db != peak_db[i] {
+                    peak_db[i] = db;
+                    changed = true;
+                }
+                peak_hold_remaining[i] = config.peak_hold_seconds;
+            } else if peak_hold_remaining[i] > 0.0 {
+                peak_hold_remaining[i] -= dt;
+            } else {
+                let peak = (peak_db[i] - (config.peak_decay_db_per_second * dt as f32)).max(db);
+                if peak != peak_db[i] {
+                    peak_db[i] = peak;
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            self.el.notify_custom_state_change();
+        }
+
+        changed
+    }
+
+    /// The smoothed level of each bin in decibels.
+    pub fn levels_db(&self) -> Vec<f32> {
+        RefCell::borrow(&self.shared_state).levels_db.clone()
+    }
+
+    /// The peak-hold level of each bin in decibels.
+    pub fn peak_db(&self) -> Vec<f32> {
+        RefCell::borrow(&self.shared_state).peak_db.clone()
+    }
+
+    /// Clear the spectrum and the peak-hold levels.
+    pub fn clear(&mut self) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let min_db = shared_state.config.min_db;
+
+        shared_state.levels_db.fill(min_db);
+        shared_state.peak_db.fill(min_db);
+        shared_state.peak_hold_remaining.fill(0.0);
+
+        self.el.notify_custom_state_change();
+    }
+
+    pub fn set_config(&mut self, config: SpectrumConfig) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.config != config {
+            shared_state.config = config;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn config(&self) -> SpectrumConfig {
+        RefCell::borrow(&self.shared_state).config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_spectrum_curve_points() {
+        let config = SpectrumConfig {
+            min_hz: 1.0,
+            max_hz: 4.0,
+            min_db: -90.0,
+            max_db: 10.0,
+            frequency_smoothing_octaves: 0.0,
+            ..Default::default()
+        };
+        let levels_db = [-90.0, -60.0, -30.0, 0.0, 6.0];
+
+        let info = SpectrumRenderInfo {
+            levels_db: &levels_db,
+            peak_db: None,
+            bin_spacing_hz: 1.0,
+            config: &config,
+            grid_hz: &[],
+            grid_db: &[],
+        };
+
+        let points = info.curve_points(&levels_db, rect(0.0, 0.0, 100.0, 100.0));
+        assert_eq!(points.len(), 101);
+
+        // 1 Hz, 2 Hz, and 4 Hz land exactly on bins 1, 2, and 4.
+        for (point, x, y) in [
+            (points[0], 0.0, 70.0),
+            (points[50], 50.0, 40.0),
+            (points[100], 100.0, 4.0),
+        ] {
+            assert_eq!(point.x, x);
+            assert!((point.y - y).abs() < 1e-3, "{point:?}");
+        }
+    }
+
+    #[cfg(feature = "tessellation")]
+    struct SpectrumTestApp {
+        spectrum: Spectrum,
+    }
+
+    #[cfg(feature = "tessellation")]
+    impl Application for SpectrumTestApp {
+        type Action = ();
+
+        fn init(cx: &mut AppContext<()>) -> Result<Self, Box<dyn std::error::Error>> {
+            let spectrum = Spectrum::builder()
+                .config(SpectrumConfig {
+                    smoothing_seconds: 0.0,
+                    ..Default::default()
+                })
+                .rect(rect(10.0, 10.0, 200.0, 100.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { spectrum })
+        }
+    }
+
+    #[cfg(feature = "tessellation")]
+    #[test]
+    fn test_spectrum() {
+        let mut app = HeadlessApp::<SpectrumTestApp>::new(AppConfig::default()).unwrap();
+        let spectrum = &mut app.app_mut().spectrum;
+
+        let assert_db = |actual: Vec<f32>, expected: [f32; 4]| {
+            for (a, e) in actual.iter().zip(expected) {
+                assert!((a - e).abs() < 1e-3, "{actual:?} != {expected:?}");
+            }
+        };
+
+        assert!(spectrum.update(&[1.0, 0.1, 0.0, 0.01], 6.0, 0.5));
+        assert_db(spectrum.levels_db(), [0.0, -20.0, -90.0, -40.0]);
+
+        // The peaks are held, then they fall.
+        assert!(spectrum.update(&[0.0; 4], 6.0, 0.5));
+        assert_db(spectrum.levels_db(), [-90.0; 4]);
+        assert_db(spectrum.peak_db(), [0.0, -20.0, -90.0, -40.0]);
+        assert!(!spectrum.update(&[0.0; 4], 6.0, 0.5));
+        assert!(spectrum.update(&[0.0; 4], 6.0, 0.5));
+        assert_db(spectrum.peak_db(), [-6.0, -26.0, -90.0, -46.0]);
+
+        spectrum.clear();
+        assert_db(spectrum.peak_db(), [-90.0; 4]);
+        assert!(!spectrum.update(&[0.0; 4], 6.0, 0.5));
+    }
+}
@@ -0,0 +1,320 @@
+use std::{any::Any, rc::Rc};
+
+use rootvg::{
+    mesh::MeshPrimitive,
+    tessellation::{
+        fill::{Fill, FillStyle},
+        path::PathBuilder,
+        stroke::{LineCap, LineDash, LineJoin, Stroke},
+        Tessellator,
+    },
+    text::Metrics,
+};
+
+use crate::prelude::*;
+use crate::vg::text::{RcTextBuffer, TextPrimitive};
+
+use super::{SpectrumAnalyzer, SpectrumRenderInfo, SpectrumRenderer};
+
+/// The style of a [`Spectrum`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpectrumStyle {
+    /// The background quad.
+    ///
+    /// By default this is set to `QuadStyle::TRANSPARENT`.
+    pub back_quad: QuadStyle,
+
+    /// The color of the spectrum curve.
+    pub line_color: RGBA8,
+    /// The thickness of the spectrum curve.
+    ///
+    /// By default this is set to `1.5`.
+    pub line_width: f32,
+    /// The color of the area under the spectrum curve.
+    ///
+    /// By default this is transparent (no fill).
+    pub fill_color: RGBA8,
+
+    /// The color of the peak-hold curve.
+    ///
+    /// By default this is transparent (not shown).
+    pub peak_color: RGBA8,
+    /// The thickness of the peak-hold curve.
+    ///
+    /// By default this is set to `1.0`.
+    pub peak_line_width: f32,
+
+    /// The color of the grid lines.
+    pub grid_color: RGBA8,
+    /// The thickness of the grid lines.
+    ///
+    /// By default this is set to `1.0`.
+    pub grid_line_width: f32,
+
+    /// The properties of the frequency labels.
+    pub text_properties: TextProperties,
+    /// The color of the frequency labels.
+    pub text_color: RGBA8,
+    /// The space between a frequency label and its grid line, as well as
+    /// between the label and the bottom edge of the element.
+    ///
+    /// By default this is set to `3.0`.
+    pub label_spacing: f32,
+
+    /// Additional flags for the quad primitives.
+    ///
+    /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
+    pub quad_flags: QuadFlags,
+}
+
+impl SpectrumStyle {
+    fn grid_quad(&self) -> QuadStyle {
+        QuadStyle::new_with_flags(
+            Background::Solid(self.grid_color),
+            BorderStyle::default(),
+            self.quad_flags,
+        )
+    }
+}
+
+impl Default for SpectrumStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle::TRANSPARENT,
+            line_color: RGBA8::new(120, 180, 220, 255),
+            line_width: 1.5,
+            fill_color: color::TRANSPARENT,
+            peak_color: color::TRANSPARENT,
+            peak_line_width: 1.0,
+            grid_color: gray_a(255, 24),
+            grid_line_width: 1.0,
+            text_properties: TextProperties {
+                metrics: Metrics {
+                    font_size: 10.0,
+                    line_height: 12.0,
+                },
+                ..Default::default()
+            },
+            text_color: gray_a(255, 120),
+            label_spacing: 3.0,
+            quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        }
+    }
+}
+
+impl ElementStyle for SpectrumStyle {
+    const ID: &'static str = "spectrum";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            line_color: RGBA8::new(40, 100, 160, 255),
+            grid_color: gray_a(0, 24),
+            text_color: gray_a(0, 120),
+            ..Default::default()
+        }
+    }
+}
+
+struct GridLabel {
+    hz: f32,
+    text_buffer: RcTextBuffer,
+    text_size: Size,
+}
+
+impl GridLabel {
+    fn new(hz: f32, text_properties: TextProperties, font_system: &mut FontSystem) -> Self {
+        let text = if hz >= 1_000.0 {
+            format!("{}k", hz / 1_000.0)
+        } else {
+            format!("{}", hz)
+        };
+
+        let mut label = Self {
+            hz,
+            text_buffer: RcTextBuffer::new(&text, text_properties, None, None, false, font_system),
+            // This will be overwritten below.
+            text_size: Size::default(),
+        };
+        label.text_size = label.text_buffer.measure();
+        label
+    }
+}
+
+/// The default [`SpectrumRenderer`], which draws the spectrum as a curve
+/// with an optional fill, along with a peak-hold curve and a frequency grid.
+pub struct SpectrumCurveRenderer {
+    style: Rc<dyn Any>,
+    labels: Vec<GridLabel>,
+    labels_need_sync: bool,
+}
+
+impl SpectrumCurveRenderer {
+    fn sync_labels(&mut self, grid_hz: &[f32], font_system: &mut FontSystem) {
+        let up_to_date = !self.labels_need_sync
+            && self.labels.len() == grid_hz.len()
+            && self.labels.iter().zip(grid_hz).all(|(l, hz)| l.hz == *hz);
+        if up_to_date {
+            return;
+        }
+
+        let style = self.style.downcast_ref::<SpectrumStyle>().unwrap();
+
+        self.labels = grid_hz
+            .iter()
+            .map(|hz| GridLabel::new(*hz, style.text_properties, font_system))
+            .collect();
+        self.labels_need_sync = false;
+    }
+}
+
+impl SpectrumRenderer for SpectrumCurveRenderer {
+    type Style = SpectrumStyle;
+
+    fn new(style: Rc<dyn Any>) -> Self {
+        Self {
+            style,
+            labels: Vec::new(),
+            labels_need_sync: true,
+        }
+    }
+
+    fn style_changed(&mut self, new_style: Rc<dyn Any>) {
+        self.style = new_style;
+        self.labels_need_sync = true;
+    }
+
+    fn render(
+        &mut self,
+        info: SpectrumRenderInfo<'_>,
+        cx: RenderContext,
+        primitives: &mut PrimitiveGroup,
+    ) {
+        self.sync_labels(info.grid_hz, &mut cx.res.font_system);
+
+        let style = self.style.downcast_ref::<SpectrumStyle>().unwrap();
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(style.back_quad.create_primitive(bounds));
+        }
+
+        if style.grid_color != color::TRANSPARENT && style.grid_line_width > 0.0 {
+            let grid_quad = style.grid_quad();
+
+            primitives.set_z_index(1);
+
+            for hz in info.grid_hz {
+                let x = info.config.hz_to_normal(*hz) * bounds.width();
+
+                primitives.add(grid_quad.create_primitive(Rect::new(
+                    Point::new(x - (style.grid_line_width * 0.5), 0.0),
+                    Size::new(style.grid_line_width, bounds.height()),
+                )));
+            }
+
+            for db in info.grid_db {
+                let y = bounds.height() - (info.config.db_to_normal(*db) * bounds.height());
+
+                primitives.add(grid_quad.create_primitive(Rect::new(
+                    Point::new(0.0, y - (style.grid_line_width * 0.5)),
+                    Size::new(bounds.width(), style.grid_line_width),
+                )));
+            }
+        }
+
+        if style.text_color != color::TRANSPARENT {
+            for label in self.labels.iter() {
+                let x = info.config.hz_to_normal(label.hz) * bounds.width();
+
+                let label_pos = Point::new(
+                    x + style.label_spacing,
+                    bounds.height() - label.text_size.height - style.label_spacing,
+                );
+
+                if label_pos.x + label.text_size.width > bounds.width() {
+                    continue;
+                }
+
+                primitives.set_z_index(3);
+                primitives.add_text(TextPrimitive::new(
+                    label.text_buffer.clone(),
+                    label_pos,
+                    style.text_color,
+                    None,
+                ));
+            }
+        }
+
+        if let Some(peak_db) = info.peak_db {
+            if style.peak_color != color::TRANSPARENT {
+                let points = info.curve_points(peak_db, bounds);
+
+                if let Some(mesh) = stroke_curve(&points, style.peak_color, style.peak_line_width) {
+                    primitives.set_z_index(2);
+                    primitives.add_mesh(mesh);
+                }
+            }
+        }
+
+        let points = info.curve_points(info.levels_db, bounds);
+
+        if style.fill_color != color::TRANSPARENT {
+            if let Some(mesh) = fill_curve(&points, bounds.max_y(), style.fill_color) {
+                primitives.set_z_index(2);
+                primitives.add_mesh(mesh);
+            }
+        }
+
+        if let Some(mesh) = stroke_curve(&points, style.line_color, style.line_width) {
+            primitives.set_z_index(2);
+            primitives.add_mesh(mesh);
+        }
+    }
+}
+
+fn stroke_curve(points: &[Point], color: RGBA8, width: f32) -> Option<MeshPrimitive> {
+    let (first, rest) = points.split_first()?;
+
+    let path = rest
+        .iter()
+        .fold(PathBuilder::new().move_to(*first), |path, point| {
+            path.line_to(*point)
+        })
+        .build();
+
+    let stroke = Stroke {
+        style: FillStyle::Solid(color.into()),
+        width,
+        line_cap: LineCap::Round,
+        line_join: LineJoin::default(),
+        line_dash: LineDash::default(),
+    };
+
+    Tessellator::new().stroke(&path, stroke).into_primitive()
+}
+
+fn fill_curve(points: &[Point], bottom: f32, color: RGBA8) -> Option<MeshPrimitive> {
+    let (first, last) = (points.first()?, points.last()?);
+
+    let path = points
+        .iter()
+        .fold(
+            PathBuilder::new().move_to(Point::new(first.x, bottom)),
+            |path, point| path.line_to(*point),
+        )
+        .line_to(Point::new(last.x, bottom))
+        .build();
+
+    let fill = Fill {
+        style: FillStyle::Solid(color.into()),
+        ..Default::default()
+    };
+
+    Tessellator::new().fill(&path, fill).into_primitive()
+}
+
+pub type Spectrum = SpectrumAnalyzer<SpectrumCurveRenderer>;
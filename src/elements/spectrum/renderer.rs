@@ -0,0 +1,121 @@
+use std::{any::Any, rc::Rc};
+
+use rootvg::{
+    math::{Point, Rect},
+    PrimitiveGroup,
+};
+
+use crate::{element_system::element::RenderContext, prelude::ElementStyle};
+
+use super::SpectrumConfig;
+
+/// Draws the contents of a [`SpectrumAnalyzer`](super::SpectrumAnalyzer)
+/// element.
+pub trait SpectrumRenderer: 'static {
+    type Style: ElementStyle;
+
+    fn new(style: Rc<dyn Any>) -> Self;
+
+    fn does_paint(&self) -> bool {
+        true
+    }
+
+    fn style_changed(&mut self, new_style: Rc<dyn Any>);
+
+    #[allow(unused)]
+    fn render(
+        &mut self,
+        info: SpectrumRenderInfo<'_>,
+        cx: RenderContext,
+        primitives: &mut PrimitiveGroup,
+    ) {
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SpectrumRenderInfo<'a> {
+    /// The smoothed level of each bin in decibels, from 0 Hz up to the
+    /// Nyquist frequency.
+    pub levels_db: &'a [f32],
+    /// The peak-hold level of each bin in decibels, or `None` if peak hold
+    /// is disabled.
+    pub peak_db: Option<&'a [f32]>,
+    /// The distance between bins in Hz.
+    pub bin_spacing_hz: f32,
+    pub config: &'a SpectrumConfig,
+    /// The frequencies where grid lines should be drawn.
+    pub grid_hz: &'a [f32],
+    /// The levels in decibels where grid lines should be drawn.
+    pub grid_db: &'a [f32],
+}
+
+impl<'a> SpectrumRenderInfo<'a> {
+    /// Resample the given level of each bin (i.e. [`Self::levels_db`] or
+    /// [`Self::peak_db`]) to one point per logical point across the given
+    /// bounds, using a logarithmic frequency axis.
+    ///
+    /// Bins that fall within the same point are averaged together (along
+    /// with any bins within the frequency smoothing window), and points
+    /// that fall between bins are interpolated.
+    pub fn curve_points(&self, bins_db: &[f32], bounds: Rect) -> Vec<Point> {
+        if bins_db.len() < 2 || self.bin_spacing_hz <= 0.0 || bounds.width() <= 0.0 {
+            return Vec::new();
+        }
+
+        let config = self.config;
+        let width = bounds.width();
+        let half_window = 2.0f32.powf(config.frequency_smoothing_octaves.max(0.0) * 0.5);
+        let num_columns = width.ceil() as usize;
+
+        (0..=num_columns)
+            .map(|column| {
+                let x = (column as f32).min(width);
+                let hz = config.normal_to_hz(x / width);
+
+                let low_hz = config.normal_to_hz((x - 0.5) / width).min(hz / half_window);
+                let high_hz = config.normal_to_hz((x + 0.5) / width).max(hz * half_window);
+
+                let db = average_db(
+                    bins_db,
+                    low_hz / self.bin_spacing_hz,
+                    high_hz / self.bin_spacing_hz,
+                )
+                .unwrap_or_else(|| interpolate_db(bins_db, hz / self.bin_spacing_hz));
+
+                Point::new(
+                    bounds.min_x() + x,
+                    bounds.max_y() - (config.db_to_normal(db) * bounds.height()),
+                )
+            })
+            .collect()
+    }
+}
+
+/// The average power of the bins in the given range, in decibels.
+fn average_db(bins_db: &[f32], low_bin: f32, high_bin: f32) -> Option<f32> {
+    let start = low_bin.max(0.0).ceil() as usize;
+    let end = (high_bin.max(0.0).floor() as usize).min(bins_db.len() - 1);
+
+    if start > end {
+        return None;
+    }
+
+    let power_sum: f32 = bins_db[start..=end]
+        .iter()
+        .map(|db| 10.0f32.powf(db * 0.1))
+        .sum();
+
+    Some(10.0 * (power_sum / (end - start + 1) as f32).log10())
+}
+
+fn interpolate_db(bins_db: &[f32], bin: f32) -> f32 {
+    let bin = bin.max(0.0);
+    let i = bin.floor() as usize;
+
+    if i + 1 >= bins_db.len() {
+        return bins_db[bins_db.len() - 1];
+    }
+
+    let t = bin - i as f32;
+    bins_db[i] + ((bins_db[i + 1] - bins_db[i]) * t)
+}
@@ -59,6 +59,11 @@ pub struct SwitchStyle {
     ///
     /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
     pub quad_flags: QuadFlags,
+
+    /// The ring drawn around the switch when it has keyboard focus.
+    ///
+    /// By default this is transparent.
+    pub focus_ring: FocusRingStyle,
 }
 
 impl Default for SwitchStyle {
@@ -96,6 +101,7 @@ impl Default for SwitchStyle {
             slider_border_color_on_disabled: Default::default(),
             cursor_icon: None,
             quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            focus_ring: FocusRingStyle::default(),
         }
     }
 }
@@ -160,16 +166,25 @@ impl<A: Clone + 'static> SwitchBuilder<A> {
             tooltip_inner: TooltipInner::new(tooltip_data),
         }));
 
+        let mut flags = ElementFlags::PAINTS
+            | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+            | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+            | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED;
+        if !disabled {
+            flags.insert(ElementFlags::FOCUSABLE);
+        }
+
         let el = ElementBuilder::new(SwitchElement {
             shared_state: Rc::clone(&shared_state),
             action,
             hovered: false,
+            focused: false,
             cursor_icon,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
         .rect(rect)
         .hidden(manually_hidden)
-        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .flags(flags)
         .build(window_cx);
 
         Switch { el, shared_state }
@@ -180,6 +195,7 @@ struct SwitchElement<A: Clone + 'static> {
     shared_state: Rc<RefCell<SharedState>>,
     action: Option<Box<dyn FnMut(bool) -> A>>,
     hovered: bool,
+    focused: bool,
     cursor_icon: Option<CursorIcon>,
 }
 
@@ -203,6 +219,34 @@ impl<A: Clone + 'static> Element<A> for SwitchElement<A> {
                 let style = cx.res.style_system.get::<SwitchStyle>(cx.class());
                 self.cursor_icon = style.cursor_icon;
             }
+            ElementEvent::Focus(focused) => {
+                if self.focused != focused {
+                    self.focused = focused;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Keyboard(key_event) => {
+                if key_event.is_activation_press() && !shared_state.disabled {
+                    shared_state.toggled = !shared_state.toggled;
+                    cx.request_repaint();
+
+                    if let Some(action) = &mut self.action {
+                        cx.send_action((action)(shared_state.toggled)).unwrap();
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::AccessAction(AccessAction::Click) => {
+                if !shared_state.disabled {
+                    shared_state.toggled = !shared_state.toggled;
+                    cx.request_repaint();
+
+                    if let Some(action) = &mut self.action {
+                        cx.send_action((action)(shared_state.toggled)).unwrap();
+                    }
+                }
+            }
             ElementEvent::Pointer(PointerEvent::Moved { .. }) => {
                 if shared_state.disabled {
                     return EventCaptureStatus::NotCaptured;
@@ -450,6 +494,22 @@ impl<A: Clone + 'static> Element<A> for SwitchElement<A> {
         primitives.add(bg_quad_style.create_primitive(bg_bounds));
         primitives.set_z_index(1);
         primitives.add(slider_quad_style.create_primitive(slider_bounds));
+
+        if self.focused && !style.focus_ring.is_transparent() {
+            primitives.add(style.focus_ring.create_primitive(bg_bounds));
+        }
+    }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        // Switches have no text of their own, so use the tooltip as the label.
+        Some(
+            AccessNode::new(AccessRole::Switch)
+                .label(shared_state.tooltip_inner.text())
+                .value(AccessValue::Toggled(shared_state.toggled))
+                .disabled(shared_state.disabled),
+        )
     }
 }
 
@@ -511,6 +571,7 @@ impl Switch {
 
         if shared_state.disabled != disabled {
             shared_state.disabled = disabled;
+            self.el.set_focusable(!disabled);
             self.el.notify_custom_state_change();
             true
         } else {
@@ -302,6 +302,17 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
                     return EventCaptureStatus::Captured;
                 }
             }
+            ElementEvent::AccessAction(AccessAction::Click) => {
+                if !shared_state.inner.disabled() && !shared_state.inner.toggled {
+                    shared_state.inner.toggled = true;
+
+                    if let Some(action) = &self.action {
+                        cx.send_action(action.clone()).unwrap();
+                    }
+
+                    cx.request_repaint();
+                }
+            }
             ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
                 position, button, ..
             }) => {
@@ -400,6 +411,22 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
             primitives.add(style.on_indicator_line_style.create_primitive(line_rect));
         }
     }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        Some(
+            AccessNode::new(AccessRole::Tab)
+                .label(
+                    shared_state
+                        .inner
+                        .text()
+                        .or_else(|| shared_state.tooltip_inner.text()),
+                )
+                .value(AccessValue::Toggled(shared_state.inner.toggled))
+                .disabled(shared_state.inner.disabled()),
+        )
+    }
 }
 
 struct SharedState {
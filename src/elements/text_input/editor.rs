@@ -0,0 +1,1691 @@
+use smallvec::SmallVec;
+use std::cell::{Ref, RefCell};
+use std::fmt::Write;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::clipboard::{Clipboard, ClipboardKind};
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::quad::SolidQuadBuilder;
+use crate::vg::text::glyphon::{
+    cosmic_text::{Action, Buffer, Cursor, Motion, Selection},
+    Edit,
+};
+use crate::vg::text::{EditorBorrowStatus, RcTextBuffer, TextPrimitive};
+
+use super::history::{select_all, EditHistory, EditKind, EditState};
+use super::inner::cursor_x_in_run;
+use super::{TextInputAction, TextInputStyle, TextInputUpdateResult};
+
+/// The width of the highlight shown for a selected line break.
+const NEWLINE_HIGHLIGHT_WIDTH: f32 = 4.0;
+
+/// The style of a [`TextEditor`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEditorStyle {
+    /// The style of the text, the selection, the cursor, and the background.
+    ///
+    /// The text is always wrapped, so the `wrap` value of the text properties
+    /// is ignored.
+    pub text_input: TextInputStyle,
+
+    /// The color of the line numbers
+    ///
+    /// If this is `None`, then `text_input.text_color_placeholder` will be used.
+    ///
+    /// By default this is set to `None`.
+    pub line_number_color: Option<RGBA8>,
+    /// The padding around the line numbers. The gutter background covers the
+    /// line numbers and this padding.
+    ///
+    /// By default this is set to `Padding::new(0.0, 8.0, 0.0, 0.0)`.
+    pub line_number_padding: Padding,
+    /// The background of the gutter behind the line numbers.
+    ///
+    /// By default this is set to `Background::TRANSPARENT`.
+    pub gutter_bg: Background,
+}
+
+impl Default for TextEditorStyle {
+    fn default() -> Self {
+        Self {
+            text_input: TextInputStyle::default(),
+            line_number_color: None,
+            line_number_padding: Padding::new(0.0, 8.0, 0.0, 0.0),
+            gutter_bg: Background::TRANSPARENT,
+        }
+    }
+}
+
+impl TextEditorStyle {
+    fn text_properties(&self) -> TextProperties {
+        let mut text_properties = self.text_input.text_properties;
+        text_properties.wrap = Wrap::WordOrGlyph;
+        text_properties.shaping = Shaping::Advanced;
+        text_properties
+    }
+
+    fn line_number_properties(&self) -> TextProperties {
+        let mut text_properties = self.text_input.text_properties;
+        text_properties.wrap = Wrap::None;
+        text_properties.align = Some(TextAlign::Right);
+        text_properties
+    }
+}
+
+impl ElementStyle for TextEditorStyle {
+    const ID: &'static str = "txtedtr";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            text_input: TextInputStyle::default_light_style(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The layout of a [`TextEditor`], used to size the element and to scroll
+/// a [`ScrollArea`] it is placed in.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct TextEditorLayout {
+    /// The height the element needs to show all of its text, including
+    /// padding.
+    pub content_height: f32,
+    /// The rectangle of the text cursor relative to the element, or `None`
+    /// if the element is not focused.
+    pub cursor_rect: Option<Rect>,
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+pub struct TextEditorBuilder<A: Clone + 'static> {
+    pub action: Option<Box<dyn FnMut(String) -> A>>,
+    pub layout_action: Option<Box<dyn FnMut(TextEditorLayout) -> A>>,
+    pub right_click_action: Option<Box<dyn FnMut(Point) -> A>>,
+    pub placeholder_text: String,
+    pub text: String,
+    pub line_numbers: bool,
+}
+
+impl<A: Clone + 'static> TextEditorBuilder<A> {
+    pub fn new() -> Self {
+        Self {
+            action: None,
+            layout_action: None,
+            right_click_action: None,
+            placeholder_text: String::new(),
+            text: String::new(),
+            line_numbers: false,
+            z_index: Default::default(),
+            scissor_rect: Default::default(),
+            class: Default::default(),
+            rect: Default::default(),
+            manually_hidden: Default::default(),
+            disabled: Default::default(),
+        }
+    }
+
+    /// The action to send when the text has been edited and the element
+    /// loses focus.
+    pub fn on_changed<F: FnMut(String) -> A + 'static>(mut self, f: F) -> Self {
+        self.action = Some(Box::new(f));
+        self
+    }
+
+    /// The action to send when the height of the contents or the position
+    /// of the text cursor has changed.
+    ///
+    /// To scroll the text, place this element inside of a [`ScrollArea`],
+    /// set its height to the `content_height`, and use
+    /// [`ScrollArea::scroll_to_reveal`] with the `cursor_rect` to keep the
+    /// cursor in view.
+    pub fn on_layout_changed<F: FnMut(TextEditorLayout) -> A + 'static>(mut self, f: F) -> Self {
+        self.layout_action = Some(Box::new(f));
+        self
+    }
+
+    pub fn on_right_click<F: FnMut(Point) -> A + 'static>(mut self, f: F) -> Self {
+        self.right_click_action = Some(Box::new(f));
+        self
+    }
+
+    pub fn placeholder_text(mut self, text: impl Into<String>) -> Self {
+        self.placeholder_text = text.into();
+        self
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// If set to `true`, then the line numbers will be shown to the left of
+    /// the text.
+    ///
+    /// By default this is set to `false`.
+    pub const fn line_numbers(mut self, show: bool) -> Self {
+        self.line_numbers = show;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> TextEditor {
+        let TextEditorBuilder {
+            action,
+            layout_action,
+            right_click_action,
+            placeholder_text,
+            text,
+            line_numbers,
+            disabled,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<TextEditorStyle>(window_cx.builder_class(class))
+            .clone();
+
+        let inner = TextEditorInner::new(
+            text,
+            placeholder_text,
+            line_numbers,
+            rect.size,
+            disabled,
+            &style,
+            &mut window_cx.res.font_system,
+        );
+        let layout = inner.layout();
+
+        let shared_state = Rc::new(RefCell::new(SharedState { inner }));
+
+        let mut flags = ElementFlags::PAINTS
+            | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+            | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_SIZE_CHANGE
+            | ElementFlags::LISTENS_TO_FOCUS_CHANGE;
+        if !disabled {
+            flags.insert(ElementFlags::FOCUSABLE);
+        }
+
+        let el = ElementBuilder::new(TextEditorElement {
+            shared_state: Rc::clone(&shared_state),
+            action,
+            layout_action,
+            right_click_action,
+            hovered: false,
+            prev_layout: layout,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(flags)
+        .build(window_cx);
+
+        TextEditor { el, shared_state }
+    }
+}
+
+struct TextEditorElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    action: Option<Box<dyn FnMut(String) -> A>>,
+    layout_action: Option<Box<dyn FnMut(TextEditorLayout) -> A>>,
+    right_click_action: Option<Box<dyn FnMut(Point) -> A>>,
+    hovered: bool,
+    prev_layout: TextEditorLayout,
+}
+
+impl<A: Clone + 'static> Element<A> for TextEditorElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let inner = &mut shared_state.inner;
+
+        let res = match event {
+            ElementEvent::Animation { .. } => inner.on_animation(),
+            ElementEvent::CustomStateChanged => {
+                inner.on_custom_state_changed(cx.clipboard, &mut cx.res.font_system)
+            }
+            ElementEvent::StyleChanged => {
+                let style = cx.res.style_system.get::<TextEditorStyle>(cx.class());
+                inner.sync_new_style(style, &mut cx.res.font_system);
+                TextInputUpdateResult {
+                    needs_repaint: true,
+                    ..Default::default()
+                }
+            }
+            ElementEvent::SizeChanged => {
+                inner.on_size_changed(cx.rect().size, &mut cx.res.font_system);
+                TextInputUpdateResult::default()
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                inner.on_pointer_moved(position, cx.rect(), &mut cx.res.font_system)
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                click_count,
+                ..
+            }) => inner.on_pointer_button_just_pressed(
+                position,
+                button,
+                click_count,
+                cx.rect(),
+                &mut cx.res.font_system,
+            ),
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
+                button, position, ..
+            }) => inner.on_pointer_button_just_released(position, button, cx.rect()),
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => inner.on_pointer_left(),
+            ElementEvent::Keyboard(key_event) => {
+                inner.on_keyboard_event(&key_event, cx.clipboard, &mut cx.res.font_system)
+            }
+            ElementEvent::TextComposition(comp_event) => {
+                inner.on_text_composition_event(&comp_event, &mut cx.res.font_system)
+            }
+            ElementEvent::Focus(has_focus) => {
+                inner.on_focus_changed(has_focus, cx.clipboard, &mut cx.res.font_system)
+            }
+            ElementEvent::ClickedOff => inner.on_clicked_off(),
+            _ => TextInputUpdateResult::default(),
+        };
+
+        if res.needs_repaint {
+            cx.request_repaint();
+        }
+        if res.send_action {
+            if let Some(action) = self.action.as_mut() {
+                cx.send_action((action)(inner.text.clone())).unwrap();
+            }
+        }
+        if let Some(pos) = res.right_clicked_at {
+            if let Some(action) = self.right_click_action.as_mut() {
+                cx.send_action((action)(pos)).unwrap();
+            }
+        }
+        if let Some(focus) = res.set_focus {
+            if focus {
+                cx.steal_focus();
+            } else {
+                cx.release_focus();
+            }
+        }
+        if res.hovered {
+            self.hovered = true;
+            cx.cursor_icon = CursorIcon::Text;
+        } else {
+            self.hovered = false;
+        }
+        if res.listen_to_pointer_clicked_off {
+            cx.listen_to_pointer_clicked_off();
+        }
+        if let Some(animating) = res.set_animating {
+            cx.set_animating(animating);
+        }
+
+        let layout = inner.layout();
+        if self.prev_layout != layout {
+            self.prev_layout = layout;
+
+            if let Some(action) = self.layout_action.as_mut() {
+                cx.send_action((action)(layout)).unwrap();
+            }
+        }
+
+        res.capture_status
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let inner = &shared_state.inner;
+        let style: &TextEditorStyle = cx.res.style_system.get(cx.class);
+        let text_style = &style.text_input;
+
+        let bounds = Rect::from_size(cx.bounds_size);
+        let text_origin = inner.text_origin();
+        let clip_size = Size::new(
+            inner.text_width,
+            (bounds.height() - text_origin.y - inner.padding.bottom).max(0.0),
+        );
+
+        let back_quad =
+            text_style.back_quad_style(inner.disabled, inner.focused, self.hovered, false);
+        if !back_quad.is_transparent() {
+            primitives.add(back_quad.create_primitive(bounds));
+        }
+
+        if let Some(gutter_buffer) = &inner.gutter_buffer {
+            if !style.gutter_bg.is_transparent() {
+                primitives.add(
+                    QuadStyle::new_with_flags(
+                        style.gutter_bg,
+                        BorderStyle::default(),
+                        text_style.quad_flags,
+                    )
+                    .create_primitive(Rect::new(
+                        Point::zero(),
+                        Size::new(text_origin.x, bounds.height()),
+                    )),
+                );
+            }
+
+            let color = style.line_number_color.unwrap_or(
+                text_style
+                    .text_color_placeholder
+                    .unwrap_or(text_style.text_color),
+            );
+            let color = if inner.disabled {
+                text_style.text_color_placeholder_disabled.get(color)
+            } else {
+                color
+            };
+
+            primitives.set_z_index(2);
+            primitives.add_text(TextPrimitive::new(
+                gutter_buffer.clone(),
+                Point::new(
+                    inner.padding.left + inner.line_number_padding.left,
+                    text_origin.y,
+                ),
+                color,
+                Some(Rect::from_size(Size::new(
+                    inner.number_width,
+                    clip_size.height,
+                ))),
+            ));
+        }
+
+        if inner.focused {
+            primitives.set_z_index(1);
+
+            for rect in inner.highlight_rects.iter() {
+                primitives.add_solid_quad(
+                    SolidQuadBuilder::new(Size::new(
+                        rect.width(),
+                        rect.height()
+                            + text_style.highlight_padding.top
+                            + text_style.highlight_padding.bottom,
+                    ))
+                    .position(Point::new(
+                        text_origin.x + rect.min_x(),
+                        text_origin.y + rect.min_y() - text_style.highlight_padding.top,
+                    ))
+                    .bg_color(text_style.highlight_bg_color)
+                    .flags(text_style.quad_flags)
+                    .into(),
+                );
+            }
+        }
+
+        if !inner.text.is_empty() {
+            let color = if inner.disabled {
+                text_style.text_color_disabled.get(text_style.text_color)
+            } else if inner.focused {
+                text_style
+                    .text_color_focused
+                    .unwrap_or(text_style.text_color)
+            } else if self.hovered {
+                text_style.text_color_hover.unwrap_or(text_style.text_color)
+            } else {
+                text_style.text_color
+            };
+
+            primitives.set_z_index(2);
+            primitives.add_text(TextPrimitive::new(
+                inner.buffer.clone(),
+                text_origin,
+                color,
+                Some(Rect::from_size(clip_size)),
+            ));
+        } else if let Some(placeholder_buffer) = &inner.placeholder_buffer {
+            let color = text_style
+                .text_color_placeholder
+                .unwrap_or(text_style.text_color);
+            let color = if inner.disabled {
+                text_style.text_color_placeholder_disabled.get(color)
+            } else {
+                color
+            };
+
+            primitives.set_z_index(2);
+            primitives.add_text(TextPrimitive::new(
+                placeholder_buffer.clone(),
+                text_origin,
+                color,
+                Some(Rect::from_size(clip_size)),
+            ));
+        }
+
+        if inner.focused && inner.cursor_blink_state_on {
+            if let Some(cursor_rect) = inner.cursor_rect {
+                primitives.set_z_index(3);
+                primitives.add_solid_quad(
+                    SolidQuadBuilder::new(Size::new(
+                        text_style.cursor_width,
+                        cursor_rect.height()
+                            + text_style.highlight_padding.top
+                            + text_style.highlight_padding.bottom,
+                    ))
+                    .position(Point::new(
+                        (text_origin.x + cursor_rect.min_x() - (text_style.cursor_width * 0.5))
+                            .round(),
+                        text_origin.y + cursor_rect.min_y() - text_style.highlight_padding.top,
+                    ))
+                    .bg_color(
+                        text_style.cursor_color.unwrap_or(
+                            text_style
+                                .text_color_focused
+                                .unwrap_or(text_style.text_color),
+                        ),
+                    )
+                    .flags(text_style.quad_flags)
+                    .into(),
+                );
+            }
+        }
+
+        if inner.focused && !inner.disabled && !text_style.focus_ring.is_transparent() {
+            primitives.set_z_index(3);
+            primitives.add(text_style.focus_ring.create_primitive(bounds));
+        }
+    }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let inner = &shared_state.inner;
+
+        Some(
+            AccessNode::new(AccessRole::TextInput)
+                .label(
+                    (!inner.placeholder_text.is_empty()).then_some(inner.placeholder_text.as_str()),
+                )
+                .value(AccessValue::Text(inner.text.clone()))
+                .disabled(inner.disabled),
+        )
+    }
+}
+
+/// An edit to apply to the text.
+#[derive(Clone, Copy)]
+enum EditOp<'a> {
+    Action(Action),
+    Insert(&'a str),
+    DeleteSelection,
+}
+
+struct TextEditorInner {
+    disabled: bool,
+
+    buffer: RcTextBuffer,
+    placeholder_buffer: Option<RcTextBuffer>,
+    /// The buffer with the line numbers, or `None` if they are not shown.
+    gutter_buffer: Option<RcTextBuffer>,
+    text: String,
+    placeholder_text: String,
+    gutter_text: String,
+    queued_actions: SmallVec<[TextInputAction; 4]>,
+    history: EditHistory,
+    focused: bool,
+    do_send_action: bool,
+    dragging: bool,
+    pointer_hovered: bool,
+
+    bounds_size: Size,
+    padding: Padding,
+    line_number_padding: Padding,
+    line_height: f32,
+    /// The width of the widest line number.
+    number_width: f32,
+    /// The number of digits `number_width` was measured for.
+    number_digits: usize,
+    text_width: f32,
+    /// The height of the laid out text.
+    text_height: f32,
+    /// The rectangle of the text cursor relative to the text.
+    cursor_rect: Option<Rect>,
+    /// The rectangles of the selected text relative to the text.
+    highlight_rects: Vec<Rect>,
+
+    cursor_blink_state_on: bool,
+    cursor_blink_last_toggle_instant: Instant,
+    cursor_blink_interval: Duration,
+}
+
+impl TextEditorInner {
+    fn new(
+        text: String,
+        placeholder_text: String,
+        line_numbers: bool,
+        bounds_size: Size,
+        disabled: bool,
+        style: &TextEditorStyle,
+        font_system: &mut FontSystem,
+    ) -> Self {
+        let text_properties = style.text_properties();
+
+        let buffer = RcTextBuffer::new(&text, text_properties, None, None, true, font_system);
+
+        let placeholder_buffer = if placeholder_text.is_empty() {
+            None
+        } else {
+            Some(new_placeholder_buffer(
+                &placeholder_text,
+                style,
+                font_system,
+            ))
+        };
+
+        let gutter_buffer = line_numbers.then(|| {
+            RcTextBuffer::new(
+                "",
+                style.line_number_properties(),
+                None,
+                None,
+                false,
+                font_system,
+            )
+        });
+
+        let mut new_self = Self {
+            disabled,
+            buffer,
+            placeholder_buffer,
+            gutter_buffer,
+            text,
+            placeholder_text,
+            gutter_text: String::new(),
+            queued_actions: SmallVec::new(),
+            history: EditHistory::default(),
+            focused: false,
+            do_send_action: false,
+            dragging: false,
+            pointer_hovered: false,
+            bounds_size,
+            padding: style.text_input.padding,
+            line_number_padding: style.line_number_padding,
+            line_height: style.text_input.text_properties.metrics.line_height,
+            number_width: 0.0,
+            number_digits: 0,
+            // This will be overwritten later.
+            text_width: -1.0,
+            text_height: 0.0,
+            cursor_rect: None,
+            highlight_rects: Vec::new(),
+            cursor_blink_state_on: false,
+            cursor_blink_last_toggle_instant: Instant::now(),
+            cursor_blink_interval: style.text_input.cursor_blink_interval,
+        };
+
+        new_self.layout_contents(font_system);
+
+        new_self
+    }
+
+    fn set_text(&mut self, text: &str, font_system: &mut FontSystem) -> bool {
+        if self.text == text {
+            return false;
+        }
+
+        self.history.clear();
+
+        self.buffer.with_editor_mut(
+            |editor, font_system| -> EditorBorrowStatus {
+                select_all(editor);
+                editor.delete_selection();
+                editor.insert_string(text, None);
+                editor.set_cursor(Cursor::new(0, 0));
+                editor.shape_as_needed(font_system, true);
+
+                editor.with_buffer(|buffer| sync_text(buffer, &mut self.text));
+
+                EditorBorrowStatus {
+                    text_changed: true,
+                    has_text: !self.text.is_empty(),
+                }
+            },
+            font_system,
+        );
+
+        self.layout_contents(font_system);
+
+        true
+    }
+
+    fn set_placeholder_text(
+        &mut self,
+        text: &str,
+        style: &TextEditorStyle,
+        font_system: &mut FontSystem,
+    ) -> bool {
+        if self.placeholder_text == text {
+            return false;
+        }
+
+        self.placeholder_text = String::from(text);
+
+        if let Some(buffer) = self.placeholder_buffer.as_mut() {
+            buffer.set_text(&self.placeholder_text, font_system);
+        } else {
+            let mut buffer = new_placeholder_buffer(&self.placeholder_text, style, font_system);
+            buffer.set_bounds(Some(self.text_width), None, font_system);
+            self.placeholder_buffer = Some(buffer);
+        }
+
+        true
+    }
+
+    fn set_line_numbers(
+        &mut self,
+        show: bool,
+        style: &TextEditorStyle,
+        font_system: &mut FontSystem,
+    ) -> bool {
+        if self.gutter_buffer.is_some() == show {
+            return false;
+        }
+
+        self.gutter_buffer = show.then(|| {
+            RcTextBuffer::new(
+                "",
+                style.line_number_properties(),
+                None,
+                None,
+                false,
+                font_system,
+            )
+        });
+        self.gutter_text.clear();
+        self.number_digits = 0;
+
+        self.layout_contents(font_system);
+
+        true
+    }
+
+    fn sync_new_style(&mut self, style: &TextEditorStyle, font_system: &mut FontSystem) {
+        self.buffer
+            .set_text_and_props(&self.text, style.text_properties(), font_system);
+
+        if let Some(placeholder_buffer) = self.placeholder_buffer.as_mut() {
+            *placeholder_buffer =
+                new_placeholder_buffer(&self.placeholder_text, style, font_system);
+        }
+
+        if let Some(gutter_buffer) = self.gutter_buffer.as_mut() {
+            gutter_buffer.set_text_and_props(
+                &self.gutter_text,
+                style.line_number_properties(),
+                font_system,
+            );
+        }
+
+        self.padding = style.text_input.padding;
+        self.line_number_padding = style.line_number_padding;
+        self.line_height = style.text_input.text_properties.metrics.line_height;
+        self.cursor_blink_interval = style.text_input.cursor_blink_interval;
+
+        // Force the text bounds to be updated.
+        self.number_digits = 0;
+        self.text_width = -1.0;
+
+        self.layout_contents(font_system);
+    }
+
+    fn on_animation(&mut self) -> TextInputUpdateResult {
+        let mut res = TextInputUpdateResult::default();
+
+        if !self.focused {
+            return res;
+        }
+
+        if self.cursor_blink_last_toggle_instant.elapsed() >= self.cursor_blink_interval {
+            self.cursor_blink_state_on = !self.cursor_blink_state_on;
+            self.cursor_blink_last_toggle_instant = Instant::now();
+            res.needs_repaint = true;
+        }
+
+        res
+    }
+
+    fn on_custom_state_changed(
+        &mut self,
+        clipboard: &mut Clipboard,
+        font_system: &mut FontSystem,
+    ) -> TextInputUpdateResult {
+        let mut result = TextInputUpdateResult::default();
+
+        self.drain_actions(clipboard, font_system, &mut result);
+
+        if result.needs_repaint {
+            self.layout_contents(font_system);
+        }
+
+        if self.focused && self.disabled {
+            self.focused = false;
+            self.layout_contents(font_system);
+
+            result.set_focus = Some(false);
+
+            result.send_action = self.do_send_action;
+            self.do_send_action = false;
+        }
+
+        result.needs_repaint = true;
+
+        result
+    }
+
+    fn on_size_changed(&mut self, bounds_size: Size, font_system: &mut FontSystem) {
+        if self.bounds_size == bounds_size {
+            return;
+        }
+        self.bounds_size = bounds_size;
+
+        self.layout_contents(font_system);
+    }
+
+    fn on_pointer_moved(
+        &mut self,
+        position: Point,
+        bounds: Rect,
+        font_system: &mut FontSystem,
+    ) -> TextInputUpdateResult {
+        let mut result = TextInputUpdateResult::default();
+
+        if self.disabled {
+            return result;
+        }
+
+        let pointer_in_bounds = bounds.contains(position);
+
+        if !self.pointer_hovered && pointer_in_bounds {
+            result.needs_repaint = true;
+        }
+        self.pointer_hovered = pointer_in_bounds;
+
+        if self.focused && self.dragging {
+            let (buf_x, buf_y) = self.pos_to_buffer_pos(position, bounds.origin);
+
+            self.buffer.with_editor_mut(
+                |editor, font_system| -> EditorBorrowStatus {
+                    editor.action(font_system, Action::Drag { x: buf_x, y: buf_y });
+
+                    EditorBorrowStatus {
+                        text_changed: false,
+                        has_text: !self.text.is_empty(),
+                    }
+                },
+                font_system,
+            );
+
+            result.hovered = true;
+            result.needs_repaint = true;
+            result.capture_status = EventCaptureStatus::Captured;
+        } else if pointer_in_bounds {
+            result.hovered = true;
+            result.capture_status = EventCaptureStatus::Captured;
+        }
+
+        if result.needs_repaint {
+            self.layout_contents(font_system);
+        }
+
+        result
+    }
+
+    fn on_pointer_button_just_pressed(
+        &mut self,
+        pointer_position: Point,
+        button: PointerButton,
+        click_count: usize,
+        bounds: Rect,
+        font_system: &mut FontSystem,
+    ) -> TextInputUpdateResult {
+        let mut result = TextInputUpdateResult::default();
+
+        if self.disabled || !bounds.contains(pointer_position) {
+            return result;
+        }
+
+        if button == PointerButton::Secondary {
+            result.send_action = self.do_send_action;
+            self.do_send_action = false;
+            result.capture_status = EventCaptureStatus::Captured;
+            result.right_clicked_at = Some(pointer_position);
+
+            if !self.focused {
+                result.set_focus = Some(true);
+            }
+
+            return result;
+        } else if button != PointerButton::Primary {
+            return result;
+        }
+
+        result.capture_status = EventCaptureStatus::Captured;
+
+        if !self.focused {
+            result.set_focus = Some(true);
+        }
+
+        self.dragging = true;
+        self.history.break_merge();
+        let (buf_x, buf_y) = self.pos_to_buffer_pos(pointer_position, bounds.origin);
+
+        let action = match click_count {
+            2 => Action::DoubleClick { x: buf_x, y: buf_y },
+            3 => Action::TripleClick { x: buf_x, y: buf_y },
+            _ => Action::Click { x: buf_x, y: buf_y },
+        };
+
+        self.buffer.with_editor_mut(
+            |editor, font_system| -> EditorBorrowStatus {
+                editor.action(font_system, action);
+
+                EditorBorrowStatus {
+                    text_changed: false,
+                    has_text: !self.text.is_empty(),
+                }
+            },
+            font_system,
+        );
+
+        result.needs_repaint = true;
+        self.layout_contents(font_system);
+
+        result
+    }
+
+    fn on_pointer_button_just_released(
+        &mut self,
+        pointer_position: Point,
+        button: PointerButton,
+        bounds: Rect,
+    ) -> TextInputUpdateResult {
+        let mut result = TextInputUpdateResult::default();
+
+        if button == PointerButton::Primary {
+            self.dragging = false;
+        }
+
+        if !self.disabled && bounds.contains(pointer_position) {
+            result.capture_status = EventCaptureStatus::Captured;
+        }
+
+        result
+    }
+
+    fn on_pointer_left(&mut self) -> TextInputUpdateResult {
+        self.pointer_hovered = false;
+        TextInputUpdateResult {
+            hovered: false,
+            needs_repaint: true,
+            ..Default::default()
+        }
+    }
+
+    fn on_keyboard_event(
+        &mut self,
+        event: &KeyboardEvent,
+        clipboard: &mut Clipboard,
+        font_system: &mut FontSystem,
+    ) -> TextInputUpdateResult {
+        let mut result = TextInputUpdateResult::default();
+
+        if self.disabled || event.state == KeyState::Up || !self.focused {
+            return result;
+        }
+
+        let shift = event.modifiers.contains(Modifiers::SHIFT);
+        let ctrl = event.modifiers.contains(Modifiers::CONTROL);
+
+        let motion = match event.code {
+            Code::ArrowLeft if ctrl => Some(Motion::LeftWord),
+            Code::ArrowLeft => Some(Motion::Left),
+            Code::ArrowRight if ctrl => Some(Motion::RightWord),
+            Code::ArrowRight => Some(Motion::Right),
+            Code::ArrowUp => Some(Motion::Up),
+            Code::ArrowDown => Some(Motion::Down),
+            Code::Home if ctrl => Some(Motion::BufferStart),
+            Code::Home => Some(Motion::Home),
+            Code::End if ctrl => Some(Motion::BufferEnd),
+            Code::End => Some(Motion::End),
+            _ => None,
+        };
+
+        if let Some(motion) = motion {
+            result.capture_status = EventCaptureStatus::Captured;
+            result.needs_repaint = true;
+
+            self.move_cursor(motion, shift, font_system);
+        } else {
+            match event.code {
+                Code::Backspace => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    result.needs_repaint |= self.edit(
+                        EditOp::Action(Action::Backspace),
+                        EditKind::Deleting,
+                        font_system,
+                    );
+                }
+                Code::Delete => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    result.needs_repaint |= self.edit(
+                        EditOp::Action(Action::Delete),
+                        EditKind::Deleting,
+                        font_system,
+                    );
+                }
+                Code::Enter | Code::NumpadEnter => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    result.enter_key_pressed = true;
+                    result.needs_repaint |=
+                        self.edit(EditOp::Action(Action::Enter), EditKind::Other, font_system);
+                }
+                Code::Escape => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    result.escape_key_pressed = true;
+
+                    self.history.break_merge();
+                    self.buffer.with_editor_mut(
+                        |editor, font_system| -> EditorBorrowStatus {
+                            editor.action(font_system, Action::Escape);
+
+                            EditorBorrowStatus {
+                                text_changed: false,
+                                has_text: !self.text.is_empty(),
+                            }
+                        },
+                        font_system,
+                    );
+
+                    result.needs_repaint = true;
+                }
+                // TODO: Make these keyboard shortcuts configurable.
+                Code::KeyA if ctrl => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    self.queue_action(TextInputAction::SelectAll);
+                }
+                Code::KeyX if ctrl => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    self.queue_action(TextInputAction::Cut);
+                }
+                Code::KeyC if ctrl => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    self.queue_action(TextInputAction::Copy);
+                }
+                Code::KeyV if ctrl => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    self.queue_action(TextInputAction::Paste);
+                }
+                Code::KeyZ if ctrl && shift => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    self.queue_action(TextInputAction::Redo);
+                }
+                Code::KeyZ if ctrl => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    self.queue_action(TextInputAction::Undo);
+                }
+                Code::KeyY if ctrl => {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    self.queue_action(TextInputAction::Redo);
+                }
+                _ => {}
+            }
+        }
+
+        self.drain_actions(clipboard, font_system, &mut result);
+
+        if result.needs_repaint {
+            self.layout_contents(font_system);
+        }
+
+        result
+    }
+
+    fn on_text_composition_event(
+        &mut self,
+        event: &CompositionEvent,
+        font_system: &mut FontSystem,
+    ) -> TextInputUpdateResult {
+        let mut result = TextInputUpdateResult::default();
+
+        if !self.focused || self.disabled {
+            return result;
+        }
+
+        result.capture_status = EventCaptureStatus::Captured;
+
+        if event.data.is_empty() {
+            return result;
+        }
+
+        if self.edit(EditOp::Insert(&event.data), EditKind::Typing, font_system) {
+            result.needs_repaint = true;
+            self.layout_contents(font_system);
+        }
+
+        result
+    }
+
+    fn on_focus_changed(
+        &mut self,
+        has_focus: bool,
+        clipboard: &mut Clipboard,
+        font_system: &mut FontSystem,
+    ) -> TextInputUpdateResult {
+        let mut result = TextInputUpdateResult::default();
+
+        self.history.break_merge();
+
+        if has_focus {
+            result.listen_to_pointer_clicked_off = true;
+            self.cursor_blink_state_on = true;
+            self.cursor_blink_last_toggle_instant = Instant::now();
+            self.focused = true;
+
+            self.drain_actions(clipboard, font_system, &mut result);
+        } else {
+            self.focused = false;
+            self.dragging = false;
+
+            if self.do_send_action {
+                self.do_send_action = false;
+                result.send_action = true;
+            }
+        }
+
+        self.layout_contents(font_system);
+
+        result.set_animating = Some(has_focus);
+        result.needs_repaint = true;
+
+        result
+    }
+
+    fn on_clicked_off(&mut self) -> TextInputUpdateResult {
+        let mut result = TextInputUpdateResult::default();
+
+        if self.focused {
+            result.set_focus = Some(false);
+        }
+        self.dragging = false;
+
+        result
+    }
+
+    fn queue_action(&mut self, action: TextInputAction) {
+        self.queued_actions.push(action);
+    }
+
+    fn drain_actions(
+        &mut self,
+        clipboard: &mut Clipboard,
+        font_system: &mut FontSystem,
+        result: &mut TextInputUpdateResult,
+    ) {
+        let actions = std::mem::take(&mut self.queued_actions);
+
+        for action in actions {
+            match action {
+                TextInputAction::Cut => {
+                    if let Some(contents) = self.buffer.buffer().editor().unwrap().copy_selection()
+                    {
+                        clipboard.write(ClipboardKind::Standard, contents);
+                        result.needs_repaint |=
+                            self.edit(EditOp::DeleteSelection, EditKind::Other, font_system);
+                    }
+                }
+                TextInputAction::Copy => {
+                    if let Some(contents) = self.buffer.buffer().editor().unwrap().copy_selection()
+                    {
+                        clipboard.write(ClipboardKind::Standard, contents);
+                    }
+                }
+                TextInputAction::Paste => {
+                    if let Some(content) = clipboard.read(ClipboardKind::Standard) {
+                        result.needs_repaint |=
+                            self.edit(EditOp::Insert(&content), EditKind::Other, font_system);
+                    }
+                }
+                TextInputAction::SelectAll => {
+                    self.history.break_merge();
+                    self.buffer.with_editor_mut(
+                        |editor, _| -> EditorBorrowStatus {
+                            select_all(editor);
+
+                            EditorBorrowStatus {
+                                text_changed: false,
+                                has_text: !self.text.is_empty(),
+                            }
+                        },
+                        font_system,
+                    );
+
+                    result.needs_repaint = true;
+                }
+                TextInputAction::Undo | TextInputAction::Redo => {
+                    let current = self.edit_state();
+                    let state = if action == TextInputAction::Undo {
+                        self.history.undo(current)
+                    } else {
+                        self.history.redo(current)
+                    };
+
+                    if let Some(state) = state {
+                        self.buffer.with_editor_mut(
+                            |editor, font_system| -> EditorBorrowStatus {
+                                state.restore(editor, font_system);
+
+                                EditorBorrowStatus {
+                                    text_changed: true,
+                                    has_text: !state.text.is_empty(),
+                                }
+                            },
+                            font_system,
+                        );
+
+                        self.text = state.text;
+                        self.do_send_action = true;
+                        result.needs_repaint = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// A snapshot of the text and the cursor for the edit history.
+    fn edit_state(&self) -> EditState {
+        EditState::new(&self.text, self.buffer.buffer().editor().unwrap())
+    }
+
+    /// Apply the given edit to the text and record it in the history.
+    ///
+    /// Returns `true` if the text has changed.
+    fn edit(&mut self, op: EditOp, kind: EditKind, font_system: &mut FontSystem) -> bool {
+        let before = self.edit_state();
+        let mut text_changed = false;
+
+        self.buffer.with_editor_mut(
+            |editor, font_system| -> EditorBorrowStatus {
+                match op {
+                    EditOp::Action(action) => editor.action(font_system, action),
+                    EditOp::Insert(text) => editor.insert_string(text, None),
+                    EditOp::DeleteSelection => {
+                        editor.delete_selection();
+                    }
+                }
+                editor.shape_as_needed(font_system, true);
+
+                text_changed = editor.with_buffer(|buffer| sync_text(buffer, &mut self.text));
+
+                EditorBorrowStatus {
+                    text_changed,
+                    has_text: !self.text.is_empty(),
+                }
+            },
+            font_system,
+        );
+
+        if text_changed {
+            self.history.record(kind, before);
+            self.do_send_action = true;
+        }
+
+        text_changed
+    }
+
+    /// Move the text cursor, extending the selection if `select` is `true`
+    /// and clearing it otherwise.
+    fn move_cursor(&mut self, motion: Motion, select: bool, font_system: &mut FontSystem) {
+        self.history.break_merge();
+
+        self.buffer.with_editor_mut(
+            |editor, font_system| -> EditorBorrowStatus {
+                if select {
+                    if editor.selection() == Selection::None {
+                        editor.set_selection(Selection::Normal(editor.cursor()));
+                    }
+                } else if editor.selection() != Selection::None {
+                    editor.set_selection(Selection::None);
+                }
+
+                editor.action(font_system, Action::Motion(motion));
+
+                EditorBorrowStatus {
+                    text_changed: false,
+                    has_text: !self.text.is_empty(),
+                }
+            },
+            font_system,
+        );
+    }
+
+    fn gutter_width(&self) -> f32 {
+        if self.gutter_buffer.is_some() {
+            self.line_number_padding.left + self.number_width + self.line_number_padding.right
+        } else {
+            0.0
+        }
+    }
+
+    /// The position of the text relative to the element.
+    fn text_origin(&self) -> Point {
+        Point::new(self.padding.left + self.gutter_width(), self.padding.top)
+    }
+
+    fn pos_to_buffer_pos(&self, pos: Point, bounds_origin: Point) -> (i32, i32) {
+        let p = pos - (bounds_origin.to_vector() + self.text_origin().to_vector());
+
+        // Clamp to the laid out text so that clicking below the last line
+        // places the cursor on the last line.
+        let y = p.y.clamp(0.0, (self.text_height - 1.0).max(0.0));
+
+        (p.x.round() as i32, y.round() as i32)
+    }
+
+    fn layout(&self) -> TextEditorLayout {
+        let text_origin = self.text_origin();
+
+        TextEditorLayout {
+            content_height: self.padding.top + self.text_height + self.padding.bottom,
+            cursor_rect: self
+                .cursor_rect
+                .map(|rect| rect.translate(text_origin.to_vector())),
+        }
+    }
+
+    /// Update the width of the line numbers and the wrapping width of the
+    /// text.
+    fn update_text_bounds(&mut self, font_system: &mut FontSystem) {
+        if let Some(gutter_buffer) = self.gutter_buffer.as_mut() {
+            let digits = self.buffer.raw_buffer().lines.len().max(1).ilog10() as usize + 1;
+
+            if self.number_digits != digits {
+                self.number_digits = digits;
+
+                // Digits have roughly the same width in most fonts.
+                gutter_buffer.set_bounds(None, None, font_system);
+                gutter_buffer.set_text(&"0".repeat(digits), font_system);
+                self.number_width = gutter_buffer.measure().width.ceil();
+                gutter_buffer.set_bounds(Some(self.number_width), None, font_system);
+
+                self.gutter_text.clear();
+                gutter_buffer.set_text("", font_system);
+            }
+        }
+
+        let text_width =
+            (self.bounds_size.width - self.padding.left - self.gutter_width() - self.padding.right)
+                .max(0.0);
+
+        if self.text_width != text_width {
+            self.text_width = text_width;
+
+            self.buffer.set_bounds(Some(text_width), None, font_system);
+
+            if let Some(buffer) = self.placeholder_buffer.as_mut() {
+                buffer.set_bounds(Some(text_width), None, font_system);
+            }
+        }
+    }
+
+    fn layout_contents(&mut self, font_system: &mut FontSystem) {
+        self.update_text_bounds(font_system);
+
+        self.cursor_rect = None;
+        self.highlight_rects.clear();
+
+        if self.focused {
+            self.cursor_blink_state_on = true;
+            self.cursor_blink_last_toggle_instant = Instant::now();
+        }
+
+        let line_height = self.line_height;
+        let (cursor, selection_bounds) = {
+            let buffer = self.buffer.buffer();
+            let editor = buffer.editor().unwrap();
+            (editor.cursor(), editor.selection_bounds())
+        };
+
+        let mut text_height = line_height;
+        let mut gutter_text = String::new();
+        let mut prev_line_i = None;
+        let mut cursor_found = false;
+
+        for run in self.buffer.raw_buffer().layout_runs() {
+            text_height = text_height.max(run.line_top + line_height);
+
+            if self.gutter_buffer.is_some() {
+                if prev_line_i.is_some() {
+                    gutter_text.push('\n');
+                }
+                // Only the first row of a wrapped line is numbered.
+                if prev_line_i != Some(run.line_i) {
+                    write!(gutter_text, "{}", run.line_i + 1).unwrap();
+                }
+                prev_line_i = Some(run.line_i);
+            }
+
+            if !self.focused {
+                continue;
+            }
+
+            let run_start = run.glyphs.iter().map(|g| g.start).min().unwrap_or(0);
+            let run_end = run.glyphs.iter().map(|g| g.end).max().unwrap_or(0);
+
+            if let Some((start, end)) = selection_bounds {
+                let in_selection = run.line_i >= start.line
+                    && run.line_i <= end.line
+                    && !(run.line_i == start.line && start.index > run_end)
+                    && !(run.line_i == end.line && end.index < run_start);
+
+                if in_selection {
+                    let start_x = if run.line_i == start.line && start.index > run_start {
+                        cursor_x_in_run(&run, &start)
+                    } else {
+                        0.0
+                    };
+                    let mut end_x = if run.line_i == end.line && end.index < run_end {
+                        cursor_x_in_run(&run, &end)
+                    } else {
+                        run.line_w
+                    };
+                    if run.line_i < end.line {
+                        end_x = end_x.max(start_x + NEWLINE_HIGHLIGHT_WIDTH);
+                    }
+
+                    let (min_x, max_x) = (start_x.min(end_x), start_x.max(end_x));
+                    if min_x < max_x {
+                        self.highlight_rects.push(Rect::new(
+                            Point::new(min_x, run.line_top),
+                            Size::new(max_x - min_x, line_height),
+                        ));
+                    }
+                }
+            }
+
+            // A cursor at the end of a wrapped row is shown at the start of
+            // the next row.
+            if run.line_i == cursor.line && !cursor_found && cursor.index >= run_start {
+                self.cursor_rect = Some(Rect::new(
+                    Point::new(cursor_x_in_run(&run, &cursor), run.line_top),
+                    Size::new(0.0, line_height),
+                ));
+                cursor_found = cursor.index < run_end;
+            }
+        }
+
+        self.text_height = text_height;
+
+        if let Some(gutter_buffer) = self.gutter_buffer.as_mut() {
+            if self.gutter_text != gutter_text {
+                gutter_buffer.set_text(&gutter_text, font_system);
+                self.gutter_text = gutter_text;
+            }
+        }
+    }
+}
+
+struct SharedState {
+    inner: TextEditorInner,
+}
+
+/// A handle to a [`TextEditorElement`], a multi-line text editor with word
+/// wrapping and optional line numbers.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct TextEditor {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl TextEditor {
+    pub fn builder<A: Clone + 'static>() -> TextEditorBuilder<A> {
+        TextEditorBuilder::new()
+    }
+
+    /// Set the text.
+    ///
+    /// Returns `true` if the text has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently. However, this method still
+    /// involves a string comparison so you may want to call this method
+    /// sparingly.
+    pub fn set_text(&mut self, text: impl AsRef<str>, res: &mut ResourceCtx) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state
+            .inner
+            .set_text(text.as_ref(), &mut res.font_system)
+        {
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn text<'a>(&'a self) -> Ref<'a, str> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| {
+            s.inner.text.as_str()
+        })
+    }
+
+    /// Set the placeholder text.
+    ///
+    /// Returns `true` if the text has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_placeholder_text(&mut self, text: impl AsRef<str>, res: &mut ResourceCtx) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let style = res.style_system.get::<TextEditorStyle>(self.el.class());
+        if shared_state
+            .inner
+            .set_placeholder_text(text.as_ref(), style, &mut res.font_system)
+        {
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn placeholder_text<'a>(&'a self) -> Ref<'a, str> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| {
+            s.inner.placeholder_text.as_str()
+        })
+    }
+
+    /// Show or hide the line numbers.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_line_numbers(&mut self, show: bool, res: &mut ResourceCtx) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let style = res.style_system.get::<TextEditorStyle>(self.el.class());
+        if shared_state
+            .inner
+            .set_line_numbers(show, style, &mut res.font_system)
+        {
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The current layout of the contents.
+    ///
+    /// The layout is updated when the element is resized, so this can be
+    /// used to find the initial height of the element.
+    pub fn layout(&self) -> TextEditorLayout {
+        RefCell::borrow(&self.shared_state).inner.layout()
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.inner.disabled != disabled {
+            shared_state.inner.disabled = disabled;
+            self.el.set_focusable(!disabled);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).inner.disabled
+    }
+
+    /// Perform an action on the text editor.
+    ///
+    /// This will do nothing if the element is currently disabled.
+    pub fn perform_action(&mut self, action: TextInputAction) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if !shared_state.inner.disabled {
+            shared_state.inner.queue_action(action);
+            self.el.notify_custom_state_change();
+        }
+    }
+}
+
+fn new_placeholder_buffer(
+    text: &str,
+    style: &TextEditorStyle,
+    font_system: &mut FontSystem,
+) -> RcTextBuffer {
+    let mut text_properties = style.text_properties();
+    text_properties.attrs = style
+        .text_input
+        .placeholder_text_attrs
+        .unwrap_or(text_properties.attrs);
+
+    RcTextBuffer::new(text, text_properties, None, None, false, font_system)
+}
+
+/// Copy the lines of the buffer into `text`.
+///
+/// Returns `true` if the text has changed.
+fn sync_text(buffer: &Buffer, text: &mut String) -> bool {
+    let mut new_text = String::with_capacity(text.len());
+    for (i, line) in buffer.lines.iter().enumerate() {
+        if i > 0 {
+            new_text.push('\n');
+        }
+        new_text.push_str(line.text());
+    }
+
+    if *text != new_text {
+        *text = new_text;
+        true
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        TextChanged(String),
+    }
+
+    struct TextEditorTestApp {
+        editor: TextEditor,
+    }
+
+    impl Application for TextEditorTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let editor = TextEditor::builder()
+                .on_changed(TestAction::TextChanged)
+                .line_numbers(true)
+                .rect(rect(10.0, 10.0, 200.0, 100.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { editor })
+        }
+    }
+
+    #[test]
+    fn test_text_editor() {
+        let mut app = HeadlessApp::<TextEditorTestApp>::new(AppConfig::default()).unwrap();
+
+        let press = |app: &mut HeadlessApp<TextEditorTestApp>, code, modifiers| {
+            app.key_press(MAIN_WINDOW, code, modifiers);
+            app.process_updates();
+        };
+
+        app.click(MAIN_WINDOW, point(50.0, 20.0), PointerButton::Primary);
+        app.process_updates();
+        let one_line_height = app.app().editor.layout().content_height;
+
+        // Enter inserts a new line instead of submitting the text.
+        app.type_text(MAIN_WINDOW, "first");
+        press(&mut app, Code::Enter, Modifiers::empty());
+        app.type_text(MAIN_WINDOW, "second");
+        app.process_updates();
+        assert_eq!(&*app.app().editor.text(), "first\nsecond");
+        assert!(app.drain_actions().is_empty());
+
+        let layout = app.app().editor.layout();
+        assert!(layout.content_height > one_line_height);
+        let cursor_rect = layout.cursor_rect.unwrap();
+
+        // The cursor moves between lines.
+        press(&mut app, Code::ArrowUp, Modifiers::empty());
+        assert!(app.app().editor.layout().cursor_rect.unwrap().min_y() < cursor_rect.min_y());
+
+        // The selection extends across lines.
+        press(&mut app, Code::Home, Modifiers::empty());
+        press(&mut app, Code::ArrowDown, Modifiers::SHIFT);
+        press(&mut app, Code::Backspace, Modifiers::empty());
+        assert_eq!(&*app.app().editor.text(), "second");
+        assert_eq!(app.app().editor.layout().content_height, one_line_height);
+
+        // The text is sent once the editor loses focus.
+        app.click(MAIN_WINDOW, point(300.0, 300.0), PointerButton::Primary);
+        app.process_updates();
+        assert_eq!(
+            app.drain_actions(),
+            vec![TestAction::TextChanged(String::from("second"))]
+        );
+        assert!(app.app().editor.layout().cursor_rect.is_none());
+    }
+}
@@ -4,7 +4,9 @@ use std::rc::Rc;
 use crate::derive::*;
 use crate::prelude::*;
 
-use super::{TextInputAction, TextInputInner, TextInputStyle, TextInputUpdateResult};
+use super::{
+    TextInputAction, TextInputInner, TextInputStyle, TextInputUpdateResult, TextValidator,
+};
 
 #[element_builder]
 #[element_builder_class]
@@ -17,6 +19,7 @@ pub struct FloatingTextInputBuilder<A: Clone + 'static> {
     pub text_offset: Vector,
     pub select_all_when_focused: bool,
     pub max_characters: usize,
+    pub validator: Option<Box<dyn TextValidator>>,
 }
 
 impl<A: Clone + 'static> FloatingTextInputBuilder<A> {
@@ -29,6 +32,7 @@ impl<A: Clone + 'static> FloatingTextInputBuilder<A> {
             text_offset: Vector::default(),
             select_all_when_focused: true,
             max_characters: 256,
+            validator: None,
             z_index: Default::default(),
             scissor_rect: Default::default(),
             class: Default::default(),
@@ -78,6 +82,15 @@ impl<A: Clone + 'static> FloatingTextInputBuilder<A> {
         self
     }
 
+    /// A validator that filters the text as it is typed and flags invalid
+    /// text, such as a [`NumericValidator`] or a [`HexValidator`].
+    ///
+    /// By default this is set to `None`.
+    pub fn validator(mut self, validator: impl TextValidator + 'static) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
     pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> FloatingTextInput {
         let FloatingTextInputBuilder {
             action,
@@ -87,6 +100,7 @@ impl<A: Clone + 'static> FloatingTextInputBuilder<A> {
             text_offset,
             select_all_when_focused,
             max_characters,
+            validator,
             class,
             z_index,
             rect,
@@ -107,6 +121,7 @@ impl<A: Clone + 'static> FloatingTextInputBuilder<A> {
                 rect.size,
                 false,
                 select_all_when_focused,
+                validator,
                 &style,
                 &mut window_cx.res.font_system,
             ),
@@ -316,6 +331,16 @@ impl<A: Clone + 'static> Element<A> for FloatingTextInputElement<A> {
             primitives.set_z_index(3);
             primitives.add_solid_quad(cursor);
         }
+        if let Some(focus_ring) = p.focus_ring.take() {
+            primitives.set_z_index(3);
+            primitives.add(focus_ring);
+        }
+    }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        Some(shared_state.inner.accessibility_node(None))
     }
 }
 
@@ -466,6 +491,13 @@ impl FloatingTextInput {
         RefCell::borrow(&self.shared_state).inner.max_characters()
     }
 
+    /// Whether the validator has flagged the text as invalid.
+    ///
+    /// This is always `false` if the element has no validator.
+    pub fn invalid(&self) -> bool {
+        RefCell::borrow(&self.shared_state).inner.invalid()
+    }
+
     /// Perform an action on the text input.
     ///
     /// This will do nothing if the element is currently disabled.
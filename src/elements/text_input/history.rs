@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+
+use crate::prelude::*;
+use crate::vg::text::glyphon::{
+    cosmic_text::{Cursor, Selection},
+    Edit,
+};
+
+/// The maximum number of steps that can be undone.
+const MAX_UNDO_STEPS: usize = 100;
+
+/// The kind of an edit recorded in an [`EditHistory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum EditKind {
+    /// Typed text. Consecutive typing is undone in a single step.
+    Typing,
+    /// Text removed with backspace or delete. Consecutive deletions are
+    /// undone in a single step.
+    Deleting,
+    /// Any other edit, such as a cut or a paste.
+    Other,
+}
+
+/// A snapshot of the text and the cursor of an editor.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct EditState {
+    pub text: String,
+    pub cursor: Cursor,
+    pub selection: Selection,
+}
+
+impl EditState {
+    pub fn new<'a>(text: &str, editor: &impl Edit<'a>) -> Self {
+        Self {
+            text: String::from(text),
+            cursor: editor.cursor(),
+            selection: editor.selection(),
+        }
+    }
+
+    /// Replace the contents of the editor with this snapshot.
+    pub fn restore<'a>(&self, editor: &mut impl Edit<'a>, font_system: &mut FontSystem) {
+        select_all(editor);
+        editor.delete_selection();
+        editor.insert_string(&self.text, None);
+
+        editor.set_cursor(self.cursor);
+        editor.set_selection(self.selection);
+        editor.shape_as_needed(font_system, true);
+    }
+}
+
+/// The undo and redo stacks of a text input.
+#[derive(Default)]
+pub(super) struct EditHistory {
+    undo_stack: VecDeque<EditState>,
+    redo_stack: Vec<EditState>,
+    /// The kind of the last recorded edit if it can be merged with the
+    /// next one.
+    merge_with: Option<EditKind>,
+}
+
+impl EditHistory {
+    /// Record an edit given the state before the edit was made.
+    pub fn record(&mut self, kind: EditKind, before: EditState) {
+        self.redo_stack.clear();
+
+        if kind != EditKind::Other && self.merge_with == Some(kind) {
+            return;
+        }
+        self.merge_with = (kind != EditKind::Other).then_some(kind);
+
+        if self.undo_stack.len() == MAX_UNDO_STEPS {
+            self.undo_stack.pop_front();
+        }
+        self.undo_stack.push_back(before);
+    }
+
+    /// Start a new undo step for the next edit, for example when the cursor
+    /// has been moved.
+    pub fn break_merge(&mut self) {
+        self.merge_with = None;
+    }
+
+    /// Returns the state to restore, if any.
+    pub fn undo(&mut self, current: EditState) -> Option<EditState> {
+        let state = self.undo_stack.pop_back()?;
+        self.redo_stack.push(current);
+        self.merge_with = None;
+        Some(state)
+    }
+
+    /// Returns the state to restore, if any.
+    pub fn redo(&mut self, current: EditState) -> Option<EditState> {
+        let state = self.redo_stack.pop()?;
+        self.undo_stack.push_back(current);
+        self.merge_with = None;
+        Some(state)
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.merge_with = None;
+    }
+}
+
+/// Select all of the text in the editor.
+pub(super) fn select_all<'a>(editor: &mut impl Edit<'a>) {
+    let end = editor.with_buffer(|buffer| {
+        let line = buffer.lines.len().saturating_sub(1);
+        Cursor::new(
+            line,
+            buffer.lines.get(line).map(|l| l.text().len()).unwrap_or(0),
+        )
+    });
+
+    editor.set_selection(Selection::Normal(Cursor::new(0, 0)));
+    editor.set_cursor(end);
+}
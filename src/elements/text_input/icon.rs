@@ -7,7 +7,9 @@ use crate::theme::DEFAULT_ICON_SIZE;
 
 use super::super::icon::{IconInner, IconStyle};
 use super::super::tooltip::TooltipInner;
-use super::{TextInputAction, TextInputInner, TextInputStyle, TextInputUpdateResult};
+use super::{
+    TextInputAction, TextInputInner, TextInputStyle, TextInputUpdateResult, TextValidator,
+};
 
 /// The style of an [`IconTextInput`] element
 #[derive(Debug, Clone, PartialEq)]
@@ -132,6 +134,7 @@ pub struct IconTextInputBuilder<A: Clone + 'static> {
     pub select_all_when_focused: bool,
     pub password_mode: bool,
     pub max_characters: usize,
+    pub validator: Option<Box<dyn TextValidator>>,
 }
 
 impl<A: Clone + 'static> IconTextInputBuilder<A> {
@@ -149,6 +152,7 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
             select_all_when_focused: false,
             password_mode: false,
             max_characters: 256,
+            validator: None,
             z_index: Default::default(),
             scissor_rect: Default::default(),
             class: Default::default(),
@@ -233,6 +237,15 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
         self
     }
 
+    /// A validator that filters the text as it is typed and flags invalid
+    /// text, such as a [`NumericValidator`] or a [`HexValidator`].
+    ///
+    /// By default this is set to `None`.
+    pub fn validator(mut self, validator: impl TextValidator + 'static) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
     pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> IconTextInput {
         let IconTextInputBuilder {
             action,
@@ -248,6 +261,7 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
             select_all_when_focused,
             password_mode,
             max_characters,
+            validator,
             disabled,
             class,
             z_index,
@@ -278,6 +292,7 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
                 rect.size,
                 disabled,
                 select_all_when_focused,
+                validator,
                 &layout_res.text_input_style,
                 &mut window_cx.res.font_system,
             ),
@@ -285,6 +300,17 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
             tooltip_inner: TooltipInner::new(tooltip_data),
         }));
 
+        let mut flags = ElementFlags::PAINTS
+            | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+            | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_SIZE_CHANGE
+            | ElementFlags::LISTENS_TO_FOCUS_CHANGE;
+        if !disabled {
+            flags.insert(ElementFlags::FOCUSABLE);
+        }
+
         let el = ElementBuilder::new(IconTextInputElement {
             shared_state: Rc::clone(&shared_state),
             action,
@@ -297,15 +323,7 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
         .builder_values(z_index, scissor_rect, class, window_cx)
         .rect(rect)
         .hidden(manually_hidden)
-        .flags(
-            ElementFlags::PAINTS
-                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
-                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
-                | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
-                | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
-                | ElementFlags::LISTENS_TO_SIZE_CHANGE
-                | ElementFlags::LISTENS_TO_FOCUS_CHANGE,
-        )
+        .flags(flags)
         .build(window_cx);
 
         IconTextInput { el, shared_state }
@@ -474,6 +492,20 @@ impl<A: Clone + 'static> Element<A> for IconTextInputElement<A> {
             primitives.set_z_index(3);
             primitives.add_solid_quad(cursor);
         }
+        if let Some(focus_ring) = p.focus_ring.take() {
+            primitives.set_z_index(3);
+            primitives.add(focus_ring);
+        }
+    }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        Some(
+            shared_state
+                .inner
+                .accessibility_node(shared_state.tooltip_inner.text()),
+        )
     }
 }
 
@@ -575,6 +607,7 @@ impl IconTextInput {
 
         if shared_state.inner.disabled != disabled {
             shared_state.inner.disabled = true;
+            self.el.set_focusable(!disabled);
             self.el.notify_custom_state_change();
             true
         } else {
@@ -605,6 +638,13 @@ impl IconTextInput {
         RefCell::borrow(&self.shared_state).inner.max_characters()
     }
 
+    /// Whether the validator has flagged the text as invalid.
+    ///
+    /// This is always `false` if the element has no validator.
+    pub fn invalid(&self) -> bool {
+        RefCell::borrow(&self.shared_state).inner.invalid()
+    }
+
     /// Perform an action on the text input.
     ///
     /// This will do nothing if the element is currently disabled.
@@ -7,11 +7,14 @@ use crate::prelude::*;
 use crate::theme::DEFAULT_ACCENT_COLOR;
 use crate::vg::quad::{QuadPrimitive, SolidQuadBuilder, SolidQuadPrimitive};
 use crate::vg::text::glyphon::{
-    cosmic_text::{Action, Affinity, Cursor, Motion, Selection},
+    cosmic_text::{Action, Affinity, Cursor, LayoutRun, Motion, Selection},
     Edit,
 };
 use crate::vg::text::{EditorBorrowStatus, RcTextBuffer, TextPrimitive};
 
+use super::history::{EditHistory, EditKind, EditState};
+use super::TextValidator;
+
 /// The style of a [`TextInput`] element
 #[derive(Debug, Clone, PartialEq)]
 pub struct TextInputStyle {
@@ -67,6 +70,12 @@ pub struct TextInputStyle {
     ///
     /// By default this is set to `None`.
     pub text_color_highlighted: Option<RGBA8>,
+    /// The color of the font when the text is invalid
+    ///
+    /// If this is `None`, then the color of the current state will be used.
+    ///
+    /// By default this is set to `None`.
+    pub text_color_invalid: Option<RGBA8>,
 
     /// The color of the font background when highlighted
     ///
@@ -110,6 +119,12 @@ pub struct TextInputStyle {
     /// By default this is set to `None`.
     pub back_bg_focused: Option<Background>,
     pub back_bg_disabled: DisabledBackground,
+    /// The background of the background quad when the text is invalid.
+    ///
+    /// If this is `None`, then the background of the current state will be used.
+    ///
+    /// By default this is set to `None`.
+    pub back_bg_invalid: Option<Background>,
 
     /// The color of the border on the background quad.
     pub back_border_color: RGBA8,
@@ -126,6 +141,12 @@ pub struct TextInputStyle {
     /// By default this is set to `None`.
     pub back_border_color_focused: Option<RGBA8>,
     pub back_border_color_disabled: DisabledColor,
+    /// The color of the border on the background quad when the text is invalid.
+    ///
+    /// If this is `None`, then the border color of the current state will be used.
+    ///
+    /// By default this is set to `None`.
+    pub back_border_color_invalid: Option<RGBA8>,
 
     /// The width of the border on the background quad.
     pub back_border_width: f32,
@@ -154,6 +175,11 @@ pub struct TextInputStyle {
     ///
     /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
     pub quad_flags: QuadFlags,
+
+    /// The ring drawn around the text input when it has keyboard focus.
+    ///
+    /// By default this is transparent.
+    pub focus_ring: FocusRingStyle,
 }
 
 impl Default for TextInputStyle {
@@ -170,6 +196,7 @@ impl Default for TextInputStyle {
             text_color_focused: None,
             text_color_placeholder_focused: None,
             text_color_highlighted: None,
+            text_color_invalid: None,
             highlight_bg_color: DEFAULT_ACCENT_COLOR,
             cursor_width: 1.0,
             cursor_color: None,
@@ -179,16 +206,71 @@ impl Default for TextInputStyle {
             back_bg_hover: None,
             back_bg_focused: None,
             back_bg_disabled: Default::default(),
+            back_bg_invalid: None,
             back_border_color: color::TRANSPARENT,
             back_border_color_hover: None,
             back_border_color_focused: None,
             back_border_color_disabled: Default::default(),
+            back_border_color_invalid: None,
             back_border_width: 0.0,
             back_border_width_hover: None,
             back_border_width_focused: None,
             back_border_radius: Radius::default(),
             cursor_blink_interval: Duration::from_millis(500),
             quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            focus_ring: FocusRingStyle::default(),
+        }
+    }
+}
+
+impl TextInputStyle {
+    /// The style of the background quad in the given state.
+    pub fn back_quad_style(
+        &self,
+        disabled: bool,
+        focused: bool,
+        hovered: bool,
+        invalid: bool,
+    ) -> QuadStyle {
+        let (mut bg, mut border_color, border_width) = if disabled {
+            (
+                self.back_bg_disabled.get(self.back_bg),
+                self.back_border_color_disabled.get(self.back_border_color),
+                self.back_border_width,
+            )
+        } else if focused {
+            (
+                self.back_bg_focused.unwrap_or(self.back_bg),
+                self.back_border_color_focused
+                    .unwrap_or(self.back_border_color),
+                self.back_border_width_focused
+                    .unwrap_or(self.back_border_width),
+            )
+        } else if hovered {
+            (
+                self.back_bg_hover.unwrap_or(self.back_bg),
+                self.back_border_color_hover
+                    .unwrap_or(self.back_border_color),
+                self.back_border_width_hover
+                    .unwrap_or(self.back_border_width),
+            )
+        } else {
+            (self.back_bg, self.back_border_color, self.back_border_width)
+        };
+
+        if invalid && !disabled {
+            bg = self.back_bg_invalid.unwrap_or(bg);
+            border_color = self.back_border_color_invalid.unwrap_or(border_color);
+        }
+
+        QuadStyle {
+            bg,
+            border: BorderStyle {
+                color: border_color,
+                width: border_width,
+                radius: self.back_border_radius,
+            },
+            flags: self.quad_flags,
         }
     }
 }
@@ -232,6 +314,9 @@ pub struct TextInputInner {
     text: String,
     placeholder_text: String,
     queued_actions: SmallVec<[TextInputAction; 4]>,
+    history: EditHistory,
+    validator: Option<Box<dyn TextValidator>>,
+    invalid: bool,
     max_characters: usize,
     focused: bool,
     do_send_action: bool,
@@ -256,6 +341,7 @@ impl TextInputInner {
         bounds_size: Size,
         disabled: bool,
         select_all_when_focused: bool,
+        validator: Option<Box<dyn TextValidator>>,
         style: &TextInputStyle,
         font_system: &mut FontSystem,
     ) -> Self {
@@ -320,6 +406,8 @@ impl TextInputInner {
             None
         };
 
+        let invalid = validator.as_ref().is_some_and(|v| !v.is_valid(&text));
+
         Self {
             buffer,
             placeholder_buffer,
@@ -327,6 +415,9 @@ impl TextInputInner {
             text,
             placeholder_text,
             queued_actions: SmallVec::new(),
+            history: EditHistory::default(),
+            validator,
+            invalid,
             show_password: false,
             max_characters,
             disabled,
@@ -364,6 +455,8 @@ impl TextInputInner {
 
         result.needs_repaint = true;
 
+        self.history.clear();
+
         self.text = text.into();
         if self.text.len() > self.max_characters {
             self.text = String::from(&self.text[0..self.max_characters])
@@ -650,6 +743,7 @@ impl TextInputInner {
         }
 
         self.dragging = true;
+        self.history.break_merge();
         let (buf_x, buf_y) =
             pos_to_buffer_pos(pointer_position, bounds.origin, self.text_bounds_rect);
 
@@ -721,6 +815,7 @@ impl TextInputInner {
             Code::Backspace => {
                 result.capture_status = EventCaptureStatus::Captured;
 
+                let before = self.edit_state();
                 let mut text_changed = false;
 
                 self.buffer.with_editor_mut(
@@ -749,6 +844,7 @@ impl TextInputInner {
                 );
 
                 if text_changed {
+                    self.history.record(EditKind::Deleting, before);
                     result.needs_repaint = true;
                     self.do_send_action = true;
                 }
@@ -757,6 +853,7 @@ impl TextInputInner {
                 result.capture_status = EventCaptureStatus::Captured;
                 result.escape_key_pressed = true;
 
+                self.history.break_merge();
                 self.buffer.with_editor_mut(
                     |editor, font_system| -> EditorBorrowStatus {
                         editor.action(font_system, Action::Escape);
@@ -774,6 +871,7 @@ impl TextInputInner {
             Code::Delete => {
                 result.capture_status = EventCaptureStatus::Captured;
 
+                let before = self.edit_state();
                 let mut text_changed = false;
 
                 self.buffer.with_editor_mut(
@@ -802,6 +900,7 @@ impl TextInputInner {
                 );
 
                 if text_changed {
+                    self.history.record(EditKind::Deleting, before);
                     result.needs_repaint = true;
                     self.do_send_action = true;
                 }
@@ -809,6 +908,7 @@ impl TextInputInner {
             Code::ArrowLeft => {
                 result.capture_status = EventCaptureStatus::Captured;
 
+                self.history.break_merge();
                 self.buffer.with_editor_mut(
                     |editor, font_system| -> EditorBorrowStatus {
                         if editor.selection() != Selection::None {
@@ -830,6 +930,7 @@ impl TextInputInner {
             Code::ArrowRight => {
                 result.capture_status = EventCaptureStatus::Captured;
 
+                self.history.break_merge();
                 self.buffer.with_editor_mut(
                     |editor, font_system| -> EditorBorrowStatus {
                         if editor.selection() != Selection::None {
@@ -886,6 +987,25 @@ impl TextInputInner {
                     self.queue_action(TextInputAction::Paste);
                 }
             }
+            // TODO: Make these keyboard shortcuts configurable.
+            Code::KeyZ => {
+                if event.modifiers.contains(Modifiers::CONTROL) {
+                    result.capture_status = EventCaptureStatus::Captured;
+
+                    if event.modifiers.contains(Modifiers::SHIFT) {
+                        self.queue_action(TextInputAction::Redo);
+                    } else {
+                        self.queue_action(TextInputAction::Undo);
+                    }
+                }
+            }
+            // TODO: Make this keyboard shortcut configurable.
+            Code::KeyY => {
+                if event.modifiers.contains(Modifiers::CONTROL) {
+                    result.capture_status = EventCaptureStatus::Captured;
+                    self.queue_action(TextInputAction::Redo);
+                }
+            }
             _ => {}
         }
 
@@ -911,45 +1031,7 @@ impl TextInputInner {
 
         result.capture_status = EventCaptureStatus::Captured;
 
-        if event.data.is_empty() || self.text.len() >= self.max_characters {
-            return result;
-        }
-
-        let contents = if self.text.len() + event.data.len() > self.max_characters {
-            &event.data[0..self.max_characters - self.text.len()]
-        } else {
-            &event.data
-        };
-
-        let mut text_changed = false;
-
-        self.buffer.with_editor_mut(
-            |editor, font_system| -> EditorBorrowStatus {
-                editor.insert_string(contents, None);
-                editor.shape_as_needed(font_system, false);
-
-                editor.with_buffer(|buffer| {
-                    if let Some(run) = buffer.layout_runs().next() {
-                        if self.text != run.text {
-                            self.text = run.text.into();
-                            text_changed = true;
-                        }
-                    } else if !self.text.is_empty() {
-                        self.text.clear();
-                        text_changed = true;
-                    }
-                });
-
-                EditorBorrowStatus {
-                    text_changed,
-                    has_text: !self.text.is_empty(),
-                }
-            },
-            font_system,
-        );
-
-        if text_changed {
-            self.do_send_action = true;
+        if self.insert_text(&event.data, EditKind::Typing, font_system) {
             result.needs_repaint = true;
 
             self.layout_contents(font_system);
@@ -966,6 +1048,8 @@ impl TextInputInner {
     ) -> TextInputUpdateResult {
         let mut result = TextInputUpdateResult::default();
 
+        self.history.break_merge();
+
         if has_focus {
             result.listen_to_pointer_clicked_off = true;
             self.cursor_blink_state_on = true;
@@ -1018,12 +1102,17 @@ impl TextInputInner {
         font_system: &mut FontSystem,
         result: &mut TextInputUpdateResult,
     ) {
-        for action in self.queued_actions.drain(..) {
+        let actions = std::mem::take(&mut self.queued_actions);
+
+        for action in actions {
             match action {
                 TextInputAction::Cut => {
+                    let before = self.edit_state();
+                    let mut text_changed = false;
+
                     self.buffer.with_editor_mut(
                         |editor, font_system| -> EditorBorrowStatus {
-                            let text_changed = if let Some(contents) = editor.copy_selection() {
+                            text_changed = if let Some(contents) = editor.copy_selection() {
                                 clipboard.write(ClipboardKind::Standard, contents);
                                 editor.delete_selection();
                                 editor.shape_as_needed(font_system, true);
@@ -1054,6 +1143,10 @@ impl TextInputInner {
                         },
                         font_system,
                     );
+
+                    if text_changed {
+                        self.history.record(EditKind::Other, before);
+                    }
                 }
                 TextInputAction::Copy => {
                     self.buffer.with_editor_mut(
@@ -1071,49 +1164,13 @@ impl TextInputInner {
                     );
                 }
                 TextInputAction::Paste => {
-                    if self.text.len() < self.max_characters {
-                        if let Some(content) = clipboard.read(ClipboardKind::Standard) {
-                            let content = if self.text.len() + content.len() > self.max_characters {
-                                &content[0..self.max_characters - self.text.len()]
-                            } else {
-                                &content
-                            };
-
-                            let mut text_changed = false;
-
-                            self.buffer.with_editor_mut(
-                                |editor, font_system| -> EditorBorrowStatus {
-                                    editor.insert_string(&content, None);
-                                    editor.shape_as_needed(font_system, true);
-
-                                    editor.with_buffer(|buffer| {
-                                        if let Some(run) = buffer.layout_runs().next() {
-                                            if self.text != run.text {
-                                                self.text = run.text.into();
-                                                text_changed = true;
-                                            }
-                                        } else if !self.text.is_empty() {
-                                            self.text.clear();
-                                            text_changed = true;
-                                        }
-                                    });
-
-                                    EditorBorrowStatus {
-                                        text_changed,
-                                        has_text: !self.text.is_empty(),
-                                    }
-                                },
-                                font_system,
-                            );
-
-                            if text_changed {
-                                self.do_send_action = true;
-                                result.needs_repaint = true;
-                            }
-                        }
+                    if let Some(content) = clipboard.read(ClipboardKind::Standard) {
+                        result.needs_repaint |=
+                            self.insert_text(&content, EditKind::Other, font_system);
                     }
                 }
                 TextInputAction::SelectAll => {
+                    self.history.break_merge();
                     self.buffer.with_editor_mut(
                         |editor, _| -> EditorBorrowStatus {
                             editor.set_selection(Selection::Line(Cursor {
@@ -1132,10 +1189,122 @@ impl TextInputInner {
 
                     result.needs_repaint = true;
                 }
+                TextInputAction::Undo | TextInputAction::Redo => {
+                    let current = self.edit_state();
+                    let state = if action == TextInputAction::Undo {
+                        self.history.undo(current)
+                    } else {
+                        self.history.redo(current)
+                    };
+
+                    if let Some(state) = state {
+                        self.buffer.with_editor_mut(
+                            |editor, font_system| -> EditorBorrowStatus {
+                                state.restore(editor, font_system);
+
+                                EditorBorrowStatus {
+                                    text_changed: true,
+                                    has_text: !state.text.is_empty(),
+                                }
+                            },
+                            font_system,
+                        );
+
+                        self.text = state.text;
+                        self.do_send_action = true;
+                        result.needs_repaint = true;
+                    }
+                }
             }
         }
     }
 
+    /// Insert text at the cursor, replacing the selected text.
+    ///
+    /// The text is filtered by the validator, and the edit is discarded if
+    /// the validator does not allow the resulting text.
+    ///
+    /// Returns `true` if the text has changed.
+    fn insert_text(
+        &mut self,
+        contents: &str,
+        kind: EditKind,
+        font_system: &mut FontSystem,
+    ) -> bool {
+        let filtered: String;
+        let contents = if let Some(validator) = &self.validator {
+            filtered = contents
+                .chars()
+                .filter_map(|c| validator.filter_char(c))
+                .collect();
+            filtered.as_str()
+        } else {
+            contents
+        };
+
+        if contents.is_empty() || self.text.len() >= self.max_characters {
+            return false;
+        }
+
+        let contents = if self.text.len() + contents.len() > self.max_characters {
+            &contents[0..self.max_characters - self.text.len()]
+        } else {
+            contents
+        };
+
+        let before = self.edit_state();
+        let mut text_changed = false;
+
+        self.buffer.with_editor_mut(
+            |editor, font_system| -> EditorBorrowStatus {
+                editor.insert_string(contents, None);
+                editor.shape_as_needed(font_system, true);
+
+                editor.with_buffer(|buffer| {
+                    if let Some(run) = buffer.layout_runs().next() {
+                        if self.text != run.text {
+                            self.text = run.text.into();
+                            text_changed = true;
+                        }
+                    } else if !self.text.is_empty() {
+                        self.text.clear();
+                        text_changed = true;
+                    }
+                });
+
+                if text_changed
+                    && !self
+                        .validator
+                        .as_ref()
+                        .map(|v| v.allow(&self.text))
+                        .unwrap_or(true)
+                {
+                    before.restore(editor, font_system);
+                    self.text.clone_from(&before.text);
+                    text_changed = false;
+                }
+
+                EditorBorrowStatus {
+                    text_changed,
+                    has_text: !self.text.is_empty(),
+                }
+            },
+            font_system,
+        );
+
+        if text_changed {
+            self.history.record(kind, before);
+            self.do_send_action = true;
+        }
+
+        text_changed
+    }
+
+    /// A snapshot of the text and the cursor for the edit history.
+    fn edit_state(&self) -> EditState {
+        EditState::new(&self.text, self.buffer.buffer().editor().unwrap())
+    }
+
     fn layout_contents(&mut self, font_system: &mut FontSystem) {
         self.cursor_x = 0.0;
         self.select_highlight_range = None;
@@ -1149,73 +1318,19 @@ impl TextInputInner {
             password_buffer.set_text(&text_to_password_text(&self.buffer), font_system);
         }
 
+        if let Some(validator) = &self.validator {
+            self.invalid = !validator.is_valid(&self.text);
+        }
+
         if self.focused {
             let cursor = self.buffer.buffer().editor().unwrap().cursor();
             let selection_bounds = self.buffer.buffer().editor().unwrap().selection_bounds();
 
             for run in self.buffer.raw_buffer().layout_runs() {
-                let cursor_to_x = |cursor: &Cursor| -> f32 {
-                    let mut found_glyph = None;
-
-                    for (glyph_i, glyph) in run.glyphs.iter().enumerate() {
-                        if cursor.index == glyph.start {
-                            found_glyph = Some((glyph_i, 0.0));
-                            break;
-                        } else if cursor.index > glyph.start && cursor.index < glyph.end {
-                            // Guess x offset based on characters
-                            let mut before = 0;
-                            let mut total = 0;
-
-                            let cluster = &run.text[glyph.start..glyph.end];
-                            for (i, _) in cluster.grapheme_indices(true) {
-                                if glyph.start + i < cursor.index {
-                                    before += 1;
-                                }
-                                total += 1;
-                            }
-
-                            let offset = glyph.w * (before as f32) / (total as f32);
-
-                            found_glyph = Some((glyph_i, offset));
-                            break;
-                        }
-                    }
-
-                    let found_glyph = found_glyph.unwrap_or_else(|| match run.glyphs.last() {
-                        Some(_) => (run.glyphs.len(), 0.0),
-                        None => (0, 0.0),
-                    });
-
-                    match run.glyphs.get(found_glyph.0) {
-                        Some(glyph) => {
-                            // Start of detected glyph
-                            if glyph.level.is_rtl() {
-                                glyph.x + glyph.w - found_glyph.1
-                            } else {
-                                glyph.x + found_glyph.1
-                            }
-                        }
-                        None => match run.glyphs.last() {
-                            Some(glyph) => {
-                                // End of last glyph
-                                if glyph.level.is_rtl() {
-                                    glyph.x
-                                } else {
-                                    glyph.x + glyph.w
-                                }
-                            }
-                            None => {
-                                // Start of empty line
-                                0.0
-                            }
-                        },
-                    }
-                };
-
                 if let Some((start, end)) = selection_bounds {
                     if run.line_i == start.line && run.line_i == end.line {
-                        let start_x = cursor_to_x(&start);
-                        let end_x = cursor_to_x(&end);
+                        let start_x = cursor_x_in_run(&run, &start);
+                        let end_x = cursor_x_in_run(&run, &end);
 
                         self.select_highlight_range = if end_x == start_x {
                             None
@@ -1228,7 +1343,7 @@ impl TextInputInner {
                 }
 
                 if run.line_i == cursor.line {
-                    self.cursor_x = cursor_to_x(&cursor);
+                    self.cursor_x = cursor_x_in_run(&run, &cursor);
                 }
             }
         }
@@ -1246,83 +1361,16 @@ impl TextInputInner {
             highlight_range: None,
             text: None,
             cursor: None,
+            focus_ring: None,
         };
 
-        if self.disabled {
-            let quad_style = QuadStyle {
-                bg: style.back_bg_disabled.get(style.back_bg),
-                border: BorderStyle {
-                    color: style
-                        .back_border_color_disabled
-                        .get(style.back_border_color),
-                    width: style.back_border_width,
-                    radius: style.back_border_radius,
-                },
-                flags: style.quad_flags,
-            };
+        let quad_style = style.back_quad_style(self.disabled, self.focused, hovered, self.invalid);
+        if !quad_style.is_transparent() {
+            primitives.back_quad = Some(quad_style.create_primitive(bounds));
+        }
 
-            if !quad_style.is_transparent() {
-                primitives.back_quad = Some(quad_style.create_primitive(bounds));
-            }
-        } else if self.focused {
-            let bg = style.back_bg_focused.unwrap_or(style.back_bg);
-            let border_width = style
-                .back_border_width_focused
-                .unwrap_or(style.back_border_width);
-
-            if !(bg.is_transparent() && border_width == 0.0) {
-                primitives.back_quad = Some(
-                    QuadStyle {
-                        bg,
-                        border: BorderStyle {
-                            color: style
-                                .back_border_color_focused
-                                .unwrap_or(style.back_border_color),
-                            width: border_width,
-                            radius: style.back_border_radius,
-                        },
-                        flags: style.quad_flags,
-                    }
-                    .create_primitive(bounds),
-                );
-            }
-        } else if hovered {
-            let bg = style.back_bg_hover.unwrap_or(style.back_bg);
-            let border_width = style
-                .back_border_width_hover
-                .unwrap_or(style.back_border_width);
-
-            if !(bg.is_transparent() && border_width == 0.0) {
-                primitives.back_quad = Some(
-                    QuadStyle {
-                        bg,
-                        border: BorderStyle {
-                            color: style
-                                .back_border_color_hover
-                                .unwrap_or(style.back_border_color),
-                            width: border_width,
-                            radius: style.back_border_radius,
-                        },
-                        flags: style.quad_flags,
-                    }
-                    .create_primitive(bounds),
-                );
-            }
-        } else {
-            if !(style.back_bg.is_transparent() && style.back_border_width == 0.0) {
-                primitives.back_quad = Some(
-                    QuadStyle {
-                        bg: style.back_bg,
-                        border: BorderStyle {
-                            color: style.back_border_color,
-                            width: style.back_border_width,
-                            radius: style.back_border_radius,
-                        },
-                        flags: style.quad_flags,
-                    }
-                    .create_primitive(bounds),
-                );
-            }
+        if self.focused && !self.disabled && !style.focus_ring.is_transparent() {
+            primitives.focus_ring = Some(style.focus_ring.create_primitive(bounds));
         }
 
         let highlight_height = self.text_bounds_rect.height()
@@ -1366,6 +1414,8 @@ impl TextInputInner {
         if !self.text.is_empty() {
             let color = if self.disabled {
                 style.text_color_disabled.get(style.text_color)
+            } else if let Some(color) = style.text_color_invalid.filter(|_| self.invalid) {
+                color
             } else if self.focused {
                 style.text_color_focused.unwrap_or(style.text_color)
             } else if self.pointer_hovered {
@@ -1454,9 +1504,35 @@ impl TextInputInner {
         self.disabled
     }
 
+    /// Whether the validator has flagged the text as invalid.
+    pub fn invalid(&self) -> bool {
+        self.invalid
+    }
+
     pub fn focused(&self) -> bool {
         self.focused
     }
+
+    /// Describe this text input to assistive technologies, falling back to
+    /// the placeholder text if no label is given.
+    ///
+    /// The contents of a text input in password mode are not exposed.
+    pub fn accessibility_node(&self, label: Option<&str>) -> AccessNode {
+        let label = label.or_else(|| {
+            (!self.placeholder_text.is_empty()).then_some(self.placeholder_text.as_str())
+        });
+
+        let value = if self.password_buffer.is_some() {
+            AccessValue::None
+        } else {
+            AccessValue::Text(self.text.clone())
+        };
+
+        AccessNode::new(AccessRole::TextInput)
+            .label(label)
+            .value(value)
+            .disabled(self.disabled)
+    }
 }
 
 pub struct TextInputPrimitives {
@@ -1464,6 +1540,7 @@ pub struct TextInputPrimitives {
     pub highlight_range: Option<SolidQuadPrimitive>,
     pub text: Option<TextPrimitive>,
     pub cursor: Option<SolidQuadPrimitive>,
+    pub focus_ring: Option<QuadPrimitive>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1472,6 +1549,8 @@ pub enum TextInputAction {
     Copy,
     Paste,
     SelectAll,
+    Undo,
+    Redo,
 }
 
 fn layout_text_bounds(bounds_size: Size, padding: Padding, line_height: f32) -> Rect {
@@ -1501,6 +1580,66 @@ fn pos_to_buffer_pos(pos: Point, bounds_origin: Point, text_bounds: Rect) -> (i3
     (x, y)
 }
 
+/// The x position of the given cursor in a layout run, relative to the start
+/// of the run.
+pub(super) fn cursor_x_in_run(run: &LayoutRun, cursor: &Cursor) -> f32 {
+    let mut found_glyph = None;
+
+    for (glyph_i, glyph) in run.glyphs.iter().enumerate() {
+        if cursor.index == glyph.start {
+            found_glyph = Some((glyph_i, 0.0));
+            break;
+        } else if cursor.index > glyph.start && cursor.index < glyph.end {
+            // Guess x offset based on characters
+            let mut before = 0;
+            let mut total = 0;
+
+            let cluster = &run.text[glyph.start..glyph.end];
+            for (i, _) in cluster.grapheme_indices(true) {
+                if glyph.start + i < cursor.index {
+                    before += 1;
+                }
+                total += 1;
+            }
+
+            let offset = glyph.w * (before as f32) / (total as f32);
+
+            found_glyph = Some((glyph_i, offset));
+            break;
+        }
+    }
+
+    let found_glyph = found_glyph.unwrap_or_else(|| match run.glyphs.last() {
+        Some(_) => (run.glyphs.len(), 0.0),
+        None => (0, 0.0),
+    });
+
+    match run.glyphs.get(found_glyph.0) {
+        Some(glyph) => {
+            // Start of detected glyph
+            if glyph.level.is_rtl() {
+                glyph.x + glyph.w - found_glyph.1
+            } else {
+                glyph.x + found_glyph.1
+            }
+        }
+        None => match run.glyphs.last() {
+            Some(glyph) => {
+                // End of last glyph
+                if glyph.level.is_rtl() {
+                    glyph.x
+                } else {
+                    glyph.x + glyph.w
+                }
+            }
+            None => {
+                // Start of empty line
+                0.0
+            }
+        },
+    }
+}
+
 fn text_to_password_text(buffer: &RcTextBuffer) -> String {
     if let Some(run) = buffer.raw_buffer().layout_runs().next() {
         run.glyphs.iter().map(|_| '\u{2022}').collect()
@@ -1,13 +1,18 @@
+mod editor;
 mod floating;
+mod history;
 mod inner;
 mod standard;
+mod validator;
 
 #[cfg(feature = "svg-icons")]
 mod icon;
 
+pub use editor::*;
 pub use floating::*;
 pub use inner::*;
 pub use standard::*;
+pub use validator::*;
 
 #[cfg(feature = "svg-icons")]
 pub use icon::*;
@@ -4,7 +4,9 @@ use std::rc::Rc;
 use crate::derive::*;
 use crate::prelude::*;
 
-use super::{TextInputAction, TextInputInner, TextInputStyle, TextInputUpdateResult};
+use super::{
+    TextInputAction, TextInputInner, TextInputStyle, TextInputUpdateResult, TextValidator,
+};
 
 #[element_builder]
 #[element_builder_class]
@@ -21,6 +23,7 @@ pub struct TextInputBuilder<A: Clone + 'static> {
     pub select_all_when_focused: bool,
     pub password_mode: bool,
     pub max_characters: usize,
+    pub validator: Option<Box<dyn TextValidator>>,
 }
 
 impl<A: Clone + 'static> TextInputBuilder<A> {
@@ -34,6 +37,7 @@ impl<A: Clone + 'static> TextInputBuilder<A> {
             select_all_when_focused: false,
             password_mode: false,
             max_characters: 256,
+            validator: None,
             z_index: Default::default(),
             scissor_rect: Default::default(),
             class: Default::default(),
@@ -92,6 +96,15 @@ impl<A: Clone + 'static> TextInputBuilder<A> {
         self
     }
 
+    /// A validator that filters the text as it is typed and flags invalid
+    /// text, such as a [`NumericValidator`] or a [`HexValidator`].
+    ///
+    /// By default this is set to `None`.
+    pub fn validator(mut self, validator: impl TextValidator + 'static) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
     pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> TextInput {
         let TextInputBuilder {
             action,
@@ -103,6 +116,7 @@ impl<A: Clone + 'static> TextInputBuilder<A> {
             select_all_when_focused,
             password_mode,
             max_characters,
+            validator,
             disabled,
             class,
             z_index,
@@ -125,6 +139,7 @@ impl<A: Clone + 'static> TextInputBuilder<A> {
                 rect.size,
                 disabled,
                 select_all_when_focused,
+                validator,
                 &style,
                 &mut window_cx.res.font_system,
             ),
@@ -132,6 +147,17 @@ impl<A: Clone + 'static> TextInputBuilder<A> {
             tooltip_inner: TooltipInner::new(tooltip_data),
         }));
 
+        let mut flags = ElementFlags::PAINTS
+            | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+            | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
+            | ElementFlags::LISTENS_TO_SIZE_CHANGE
+            | ElementFlags::LISTENS_TO_FOCUS_CHANGE;
+        if !disabled {
+            flags.insert(ElementFlags::FOCUSABLE);
+        }
+
         let el = ElementBuilder::new(TextInputElement {
             shared_state: Rc::clone(&shared_state),
             action,
@@ -141,15 +167,7 @@ impl<A: Clone + 'static> TextInputBuilder<A> {
         .builder_values(z_index, scissor_rect, class, window_cx)
         .rect(rect)
         .hidden(manually_hidden)
-        .flags(
-            ElementFlags::PAINTS
-                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
-                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
-                | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
-                | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
-                | ElementFlags::LISTENS_TO_SIZE_CHANGE
-                | ElementFlags::LISTENS_TO_FOCUS_CHANGE,
-        )
+        .flags(flags)
         .build(window_cx);
 
         TextInput { el, shared_state }
@@ -291,6 +309,20 @@ impl<A: Clone + 'static> Element<A> for TextInputElement<A> {
             primitives.set_z_index(3);
             primitives.add_solid_quad(cursor);
         }
+        if let Some(focus_ring) = p.focus_ring.take() {
+            primitives.set_z_index(3);
+            primitives.add(focus_ring);
+        }
+    }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        Some(
+            shared_state
+                .inner
+                .accessibility_node(shared_state.tooltip_inner.text()),
+        )
     }
 }
 
@@ -392,6 +424,7 @@ impl TextInput {
 
         if shared_state.inner.disabled != disabled {
             shared_state.inner.disabled = true;
+            self.el.set_focusable(!disabled);
             self.el.notify_custom_state_change();
             true
         } else {
@@ -422,6 +455,13 @@ impl TextInput {
         RefCell::borrow(&self.shared_state).inner.max_characters()
     }
 
+    /// Whether the validator has flagged the text as invalid.
+    ///
+    /// This is always `false` if the element has no validator.
+    pub fn invalid(&self) -> bool {
+        RefCell::borrow(&self.shared_state).inner.invalid()
+    }
+
     /// Perform an action on the text input.
     ///
     /// This will do nothing if the element is currently disabled.
@@ -453,3 +493,151 @@ impl TextInput {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        TextChanged(String),
+    }
+
+    struct UndoTestApp {
+        text_input: TextInput,
+    }
+
+    impl Application for UndoTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let text_input = TextInput::builder()
+                .on_changed(TestAction::TextChanged)
+                .rect(rect(10.0, 10.0, 200.0, 30.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { text_input })
+        }
+    }
+
+    #[test]
+    fn test_text_input_undo_redo() {
+        let mut app = HeadlessApp::<UndoTestApp>::new(AppConfig::default()).unwrap();
+
+        let press = |app: &mut HeadlessApp<UndoTestApp>, code, modifiers| {
+            app.key_press(MAIN_WINDOW, code, modifiers);
+            app.process_updates();
+        };
+
+        app.click(MAIN_WINDOW, point(50.0, 20.0), PointerButton::Primary);
+        app.process_updates();
+
+        // A run of typing is undone in a single step.
+        app.type_text(MAIN_WINDOW, "a");
+        app.type_text(MAIN_WINDOW, "b");
+        app.type_text(MAIN_WINDOW, "c");
+        press(&mut app, Code::Backspace, Modifiers::empty());
+        press(&mut app, Code::Backspace, Modifiers::empty());
+        assert_eq!(&*app.app().text_input.text(), "a");
+
+        press(&mut app, Code::KeyZ, Modifiers::CONTROL);
+        assert_eq!(&*app.app().text_input.text(), "abc");
+        press(&mut app, Code::KeyZ, Modifiers::CONTROL);
+        assert_eq!(&*app.app().text_input.text(), "");
+        press(&mut app, Code::KeyZ, Modifiers::CONTROL);
+        assert_eq!(&*app.app().text_input.text(), "");
+
+        press(&mut app, Code::KeyZ, Modifiers::CONTROL | Modifiers::SHIFT);
+        assert_eq!(&*app.app().text_input.text(), "abc");
+
+        // Moving the cursor starts a new undo step.
+        press(&mut app, Code::ArrowLeft, Modifiers::empty());
+        app.type_text(MAIN_WINDOW, "x");
+        app.process_updates();
+        assert_eq!(&*app.app().text_input.text(), "abxc");
+
+        // A new edit clears the redo history.
+        app.app_mut()
+            .text_input
+            .perform_action(TextInputAction::Undo);
+        app.process_updates();
+        assert_eq!(&*app.app().text_input.text(), "abc");
+        app.type_text(MAIN_WINDOW, "y");
+        press(&mut app, Code::KeyY, Modifiers::CONTROL);
+        assert_eq!(&*app.app().text_input.text(), "abyc");
+
+        press(&mut app, Code::Enter, Modifiers::empty());
+        assert_eq!(
+            app.drain_actions(),
+            vec![TestAction::TextChanged(String::from("abyc"))]
+        );
+    }
+
+    struct ValidatorTestApp {
+        text_input: TextInput,
+        bpm_input: TextInput,
+    }
+
+    impl Application for ValidatorTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let text_input = TextInput::builder()
+                .on_changed(TestAction::TextChanged)
+                .rect(rect(10.0, 10.0, 200.0, 30.0))
+                .build(&mut cx.main_window());
+
+            let bpm_input = TextInput::builder()
+                .validator(NumericValidator::range(20.0, 300.0).max_decimals(2))
+                .text("120")
+                .rect(rect(10.0, 50.0, 200.0, 30.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self {
+                text_input,
+                bpm_input,
+            })
+        }
+    }
+
+    #[test]
+    fn test_text_input_validator() {
+        let mut app = HeadlessApp::<ValidatorTestApp>::new(AppConfig::default()).unwrap();
+
+        assert!(!app.app().bpm_input.invalid());
+        assert!(!app.app().text_input.invalid());
+
+        app.click(MAIN_WINDOW, point(50.0, 60.0), PointerButton::Primary);
+        app.process_updates();
+        app.app_mut()
+            .bpm_input
+            .perform_action(TextInputAction::SelectAll);
+        app.process_updates();
+
+        // Rejected characters are filtered out and a comma is typed as a
+        // decimal point.
+        app.type_text(MAIN_WINDOW, "1x2,5");
+        app.process_updates();
+        assert_eq!(&*app.app().bpm_input.text(), "12.5");
+        assert!(app.app().bpm_input.invalid());
+
+        // Edits that would result in too many decimals are discarded.
+        app.type_text(MAIN_WINDOW, "0");
+        app.type_text(MAIN_WINDOW, "7");
+        app.process_updates();
+        assert_eq!(&*app.app().bpm_input.text(), "12.50");
+
+        // Deleting is always allowed.
+        for _ in 0..4 {
+            app.key_press(MAIN_WINDOW, Code::Backspace, Modifiers::empty());
+        }
+        app.type_text(MAIN_WINDOW, "40");
+        app.process_updates();
+        assert_eq!(&*app.app().bpm_input.text(), "140");
+        assert!(!app.app().bpm_input.invalid());
+
+        let (test_app, res) = app.app_and_res();
+        test_app.bpm_input.set_text("400", res, false);
+        assert!(app.app().bpm_input.invalid());
+    }
+}
@@ -0,0 +1,166 @@
+/// Filters and validates the text of a text input as it is typed.
+///
+/// Pattern-based validation (for example with the `regex` crate) can be
+/// done by implementing [`TextValidator::allow`] and
+/// [`TextValidator::is_valid`].
+pub trait TextValidator {
+    /// Transform or reject a character that is being typed or pasted.
+    ///
+    /// Return `None` to reject the character.
+    ///
+    /// By default all characters are accepted unchanged.
+    fn filter_char(&self, c: char) -> Option<char> {
+        Some(c)
+    }
+
+    /// Whether the text that would result from typing or pasting is
+    /// allowed. If this returns `false`, then the edit is discarded.
+    ///
+    /// Deleting text is always allowed.
+    ///
+    /// By default all text is allowed.
+    #[allow(unused_variables)]
+    fn allow(&self, text: &str) -> bool {
+        true
+    }
+
+    /// Whether the text is valid.
+    ///
+    /// Text that is allowed but not valid (such as an incomplete number) can
+    /// still be entered, but the text input will be shown with the invalid
+    /// style.
+    ///
+    /// By default all text is valid.
+    #[allow(unused_variables)]
+    fn is_valid(&self, text: &str) -> bool {
+        true
+    }
+}
+
+/// A [`TextValidator`] that only accepts decimal numbers.
+///
+/// A `,` is typed as a `.`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericValidator {
+    /// Whether negative numbers are allowed.
+    ///
+    /// By default this is set to `true`.
+    pub allow_negative: bool,
+    /// The maximum number of digits after the decimal point. Set this to
+    /// `Some(0)` to only allow integers.
+    ///
+    /// By default this is set to `None`.
+    pub max_decimals: Option<usize>,
+    /// The smallest valid value.
+    ///
+    /// By default this is set to `f64::NEG_INFINITY`.
+    pub min: f64,
+    /// The largest valid value.
+    ///
+    /// By default this is set to `f64::INFINITY`.
+    pub max: f64,
+}
+
+impl NumericValidator {
+    /// A validator that only accepts integers.
+    pub fn integer() -> Self {
+        Self {
+            max_decimals: Some(0),
+            ..Default::default()
+        }
+    }
+
+    /// A validator that accepts numbers in the given range.
+    pub fn range(min: f64, max: f64) -> Self {
+        Self {
+            allow_negative: min < 0.0,
+            min,
+            max,
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum number of digits after the decimal point.
+    pub fn max_decimals(mut self, max_decimals: usize) -> Self {
+        self.max_decimals = Some(max_decimals);
+        self
+    }
+}
+
+impl Default for NumericValidator {
+    fn default() -> Self {
+        Self {
+            allow_negative: true,
+            max_decimals: None,
+            min: f64::NEG_INFINITY,
+            max: f64::INFINITY,
+        }
+    }
+}
+
+impl TextValidator for NumericValidator {
+    fn filter_char(&self, c: char) -> Option<char> {
+        match c {
+            '0'..='9' => Some(c),
+            '.' | ',' if self.max_decimals != Some(0) => Some('.'),
+            '-' if self.allow_negative => Some(c),
+            _ => None,
+        }
+    }
+
+    fn allow(&self, text: &str) -> bool {
+        let digits = text.strip_prefix('-').unwrap_or(text);
+        if digits.contains('-') {
+            return false;
+        }
+
+        match digits.split_once('.') {
+            Some((_, decimals)) => {
+                !decimals.contains('.')
+                    && self
+                        .max_decimals
+                        .map(|max| decimals.len() <= max)
+                        .unwrap_or(true)
+            }
+            None => true,
+        }
+    }
+
+    fn is_valid(&self, text: &str) -> bool {
+        text.parse::<f64>()
+            .map(|value| value >= self.min && value <= self.max)
+            .unwrap_or(false)
+    }
+}
+
+/// A [`TextValidator`] that only accepts hexadecimal digits, such as for
+/// color codes.
+///
+/// Letters are converted to uppercase.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexValidator {
+    /// The number of digits needed for the text to be valid, or `None` if
+    /// any non-empty text is valid.
+    ///
+    /// By default this is set to `None`.
+    pub digits: Option<usize>,
+}
+
+impl TextValidator for HexValidator {
+    fn filter_char(&self, c: char) -> Option<char> {
+        c.is_ascii_hexdigit().then(|| c.to_ascii_uppercase())
+    }
+
+    fn allow(&self, text: &str) -> bool {
+        self.digits
+            .map(|digits| text.len() <= digits)
+            .unwrap_or(true)
+    }
+
+    fn is_valid(&self, text: &str) -> bool {
+        match self.digits {
+            Some(digits) => text.len() == digits,
+            None => !text.is_empty(),
+        }
+    }
+}
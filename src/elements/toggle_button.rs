@@ -234,6 +234,11 @@ pub struct ToggleButtonStyle {
     ///
     /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
     pub quad_flags: QuadFlags,
+
+    /// The ring drawn around the button when it has keyboard focus.
+    ///
+    /// By default this is transparent.
+    pub focus_ring: FocusRingStyle,
 }
 
 impl Default for ToggleButtonStyle {
@@ -286,6 +291,7 @@ impl Default for ToggleButtonStyle {
             back_border_radius: Default::default(),
             cursor_icon: None,
             quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            focus_ring: FocusRingStyle::default(),
         }
     }
 }
@@ -822,15 +828,24 @@ impl<A: Clone + 'static> ToggleButtonBuilder<A> {
             tooltip_inner: TooltipInner::new(tooltip_data),
         }));
 
+        let mut flags = ElementFlags::PAINTS
+            | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+            | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+            | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED;
+        if !disabled {
+            flags.insert(ElementFlags::FOCUSABLE);
+        }
+
         let el = ElementBuilder::new(ToggleButtonElement {
             shared_state: Rc::clone(&shared_state),
             action,
             cursor_icon,
+            focused: false,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
         .rect(rect)
         .hidden(manually_hidden)
-        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .flags(flags)
         .build(window_cx);
 
         ToggleButton { el, shared_state }
@@ -842,6 +857,7 @@ struct ToggleButtonElement<A: Clone + 'static> {
     shared_state: Rc<RefCell<SharedState>>,
     action: Option<Box<dyn FnMut(bool) -> A>>,
     cursor_icon: Option<CursorIcon>,
+    focused: bool,
 }
 
 impl<A: Clone + 'static> Element<A> for ToggleButtonElement<A> {
@@ -864,6 +880,40 @@ impl<A: Clone + 'static> Element<A> for ToggleButtonElement<A> {
                 let style = cx.res.style_system.get::<ToggleButtonStyle>(cx.class());
                 self.cursor_icon = style.cursor_icon;
             }
+            ElementEvent::Focus(focused) => {
+                if self.focused != focused {
+                    self.focused = focused;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Keyboard(key_event) => {
+                if key_event.is_activation_press()
+                    && shared_state.inner.state != ButtonState::Disabled
+                {
+                    shared_state.inner.toggled = !shared_state.inner.toggled;
+
+                    cx.request_repaint();
+
+                    if let Some(action) = &mut self.action {
+                        cx.send_action((action)(shared_state.inner.toggled))
+                            .unwrap();
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::AccessAction(AccessAction::Click) => {
+                if shared_state.inner.state != ButtonState::Disabled {
+                    shared_state.inner.toggled = !shared_state.inner.toggled;
+
+                    cx.request_repaint();
+
+                    if let Some(action) = &mut self.action {
+                        cx.send_action((action)(shared_state.inner.toggled))
+                            .unwrap();
+                    }
+                }
+            }
             ElementEvent::Pointer(PointerEvent::Moved { .. }) => {
                 if shared_state.inner.state == ButtonState::Disabled {
                     return EventCaptureStatus::NotCaptured;
@@ -945,9 +995,11 @@ impl<A: Clone + 'static> Element<A> for ToggleButtonElement<A> {
     fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
 
+        let style: &ToggleButtonStyle = cx.res.style_system.get(cx.class);
+
         let label_primitives = shared_state.inner.render(
             Rect::from_size(cx.bounds_size),
-            cx.res.style_system.get(cx.class),
+            style,
             &mut cx.res.font_system,
         );
 
@@ -964,6 +1016,31 @@ impl<A: Clone + 'static> Element<A> for ToggleButtonElement<A> {
             primitives.set_z_index(1);
             primitives.add_text(p);
         }
+
+        if self.focused && !style.focus_ring.is_transparent() {
+            primitives.set_z_index(1);
+            primitives.add(
+                style
+                    .focus_ring
+                    .create_primitive(Rect::from_size(cx.bounds_size)),
+            );
+        }
+    }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        Some(
+            AccessNode::new(AccessRole::ToggleButton)
+                .label(
+                    shared_state
+                        .inner
+                        .text()
+                        .or_else(|| shared_state.tooltip_inner.text()),
+                )
+                .value(AccessValue::Toggled(shared_state.inner.toggled))
+                .disabled(shared_state.inner.disabled()),
+        )
     }
 }
 
@@ -1167,10 +1244,12 @@ impl ToggleButton {
 
         if disabled && shared_state.inner.state != ButtonState::Disabled {
             shared_state.inner.set_state(ButtonState::Disabled);
+            self.el.set_focusable(false);
             self.el.notify_custom_state_change();
             true
         } else if !disabled && shared_state.inner.state == ButtonState::Disabled {
             shared_state.inner.set_state(ButtonState::Idle);
+            self.el.set_focusable(true);
             self.el.notify_custom_state_change();
             true
         } else {
@@ -67,6 +67,11 @@ impl TooltipInner {
         state_changed
     }
 
+    /// The text of the tooltip, if one is set.
+    pub fn text(&self) -> Option<&str> {
+        self.data.as_ref().map(|d| d.text.as_str())
+    }
+
     pub fn handle_event<A: Clone + 'static>(
         &self,
         event: &ElementEvent,
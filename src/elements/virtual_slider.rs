@@ -7,12 +7,14 @@ use std::rc::Rc;
 use crate::derive::*;
 use crate::prelude::*;
 
+mod formatter;
 mod inner;
 mod renderer;
 
 pub mod knob;
 pub mod slider;
 
+pub use formatter::*;
 pub use inner::*;
 pub use renderer::*;
 
@@ -21,6 +23,9 @@ pub struct ParamOpenTextEntryInfo {
     pub param_info: ParamInfo,
     /// The bounding rectangle of this element
     pub bounds: Rect,
+    /// The current value as formatted by the [`ParamFormatter`] of this
+    /// element (if it has one)
+    pub text: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
@@ -198,6 +203,41 @@ pub struct VirtualSliderConfig {
     ///
     /// By default this is set to `false`.
     pub disable_pointer_locking: bool,
+
+    /// The amount (in normalized units) to adjust the value by in a single
+    /// increment, such as when an assistive technology requests an increment.
+    ///
+    /// Stepped parameters are always adjusted by one step.
+    ///
+    /// By default this is set to `0.01`.
+    pub increment_step: f32,
+
+    /// Whether or not the user can adjust the value with the keyboard
+    /// while this element has focus.
+    ///
+    /// The arrow keys adjust the value by `increment_step`, PageUp and
+    /// PageDown by `page_increment_step`, Home and End jump to the minimum
+    /// and maximum values, and Delete resets the value to the default.
+    ///
+    /// By default this is set to `true`.
+    pub use_keyboard: bool,
+
+    /// The amount (in normalized units) to adjust the value by when the user
+    /// presses PageUp or PageDown.
+    ///
+    /// Stepped parameters are adjusted by the nearest whole number of steps
+    /// (and always by at least one step).
+    ///
+    /// By default this is set to `0.1`.
+    pub page_increment_step: f32,
+
+    /// The amount (in normalized units) to adjust the value by when the user
+    /// presses an arrow key while holding down `fine_adjustment_modifier`.
+    ///
+    /// Stepped parameters are always adjusted by one step.
+    ///
+    /// By default this is set to `0.001`.
+    pub fine_increment_step: f32,
 }
 
 impl Default for VirtualSliderConfig {
@@ -215,6 +255,10 @@ impl Default for VirtualSliderConfig {
             cursor_icon_hover: None,
             cursor_icon_gesturing: None,
             disable_pointer_locking: false,
+            increment_step: 0.01,
+            use_keyboard: true,
+            page_increment_step: 0.1,
+            fine_increment_step: 0.001,
         }
     }
 }
@@ -224,6 +268,9 @@ pub struct ParamElementTooltipInfo {
     pub param_info: ParamInfo,
     pub rect: Rect,
     pub tooltip_align: Align2,
+    /// The current value as formatted by the [`ParamFormatter`] of this
+    /// element (if it has one)
+    pub text: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -242,7 +289,12 @@ pub struct VirtualSliderBuilder<A: Clone + 'static> {
     pub on_right_click: Option<Box<dyn FnMut(ParamRightClickInfo) -> A>>,
     pub on_open_text_entry: Option<Box<dyn FnMut(ParamOpenTextEntryInfo) -> A>>,
     pub on_tooltip_request: Option<Box<dyn FnMut(ParamElementTooltipInfo) -> A>>,
+    pub on_modulation_depth: Option<Box<dyn FnMut(ModulationDepthUpdate) -> A>>,
     pub tooltip_align: Align2,
+    pub formatter: Option<Box<dyn ParamFormatter>>,
+    pub inline_text_entry: bool,
+    pub text_entry_class: Option<ClassID>,
+    pub accessible_label: Option<String>,
     pub param_id: SmolStr,
     pub normal_value: f64,
     pub default_normal: f64,
@@ -262,8 +314,13 @@ impl<A: Clone + 'static> VirtualSliderBuilder<A> {
             on_right_click: None,
             on_open_text_entry: None,
             on_tooltip_request: None,
+            on_modulation_depth: None,
             class: None,
             tooltip_align: Align2::default(),
+            formatter: None,
+            inline_text_entry: false,
+            text_entry_class: None,
+            accessible_label: None,
             param_id: param_id.to_smolstr(),
             normal_value: 0.0,
             default_normal: 0.0,
@@ -310,12 +367,70 @@ impl<A: Clone + 'static> VirtualSliderBuilder<A> {
         self
     }
 
+    /// Called when the user edits the depth of the selected modulation source
+    /// (see [`AutomationInfo::selected_modulation`]) by dragging on the
+    /// modulation ring (or track) of this element.
+    pub fn on_modulation_depth<F: FnMut(ModulationDepthUpdate) -> A + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.on_modulation_depth = Some(Box::new(f));
+        self
+    }
+
     /// How to align the tooltip relative to this element
     pub const fn tooltip_align(mut self, align: Align2) -> Self {
         self.tooltip_align = align;
         self
     }
 
+    /// Converts the value to text for tooltips and text entry, and parses
+    /// text entered by the user back into a value, such as a
+    /// [`FrequencyFormatter`] or a [`DecibelFormatter`].
+    ///
+    /// By default this is set to `None`.
+    pub fn formatter(mut self, formatter: impl ParamFormatter + 'static) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Whether or not this element should open its own text entry over its
+    /// bounds instead of sending an `on_open_text_entry` event.
+    ///
+    /// The text entry is pre-filled with the value from the [`ParamFormatter`].
+    /// Pressing Enter parses the text with the formatter and sends a single
+    /// [`ParamUpdate`] with `GestureState::GestureFinished`, and pressing
+    /// Escape or clicking off cancels the edit.
+    ///
+    /// This has no effect if this element does not have a formatter.
+    ///
+    /// By default this is set to `false`.
+    pub const fn inline_text_entry(mut self, inline_text_entry: bool) -> Self {
+        self.inline_text_entry = inline_text_entry;
+        self
+    }
+
+    /// The class of the [`TextInputStyle`] to use for the inline text entry.
+    ///
+    /// If this is `None`, then the current class will be used.
+    ///
+    /// By default this is set to `None`.
+    pub const fn text_entry_class(mut self, class: Option<ClassID>) -> Self {
+        self.text_entry_class = class;
+        self
+    }
+
+    /// The name of this element which is read out by assistive technologies,
+    /// i.e. "Cutoff".
+    ///
+    /// If this is `None`, then the parameter ID is used instead.
+    ///
+    /// By default this is set to `None`.
+    pub fn accessible_label(mut self, label: impl Into<String>) -> Self {
+        self.accessible_label = Some(label.into());
+        self
+    }
+
     pub const fn normal_value(mut self, normal: f64) -> Self {
         self.normal_value = normal;
         self
@@ -370,7 +485,12 @@ impl<A: Clone + 'static> VirtualSliderBuilder<A> {
             on_right_click,
             on_open_text_entry,
             on_tooltip_request,
+            on_modulation_depth,
             tooltip_align,
+            formatter,
+            inline_text_entry,
+            text_entry_class,
+            accessible_label,
             param_id,
             normal_value,
             default_normal,
@@ -399,12 +519,27 @@ impl<A: Clone + 'static> VirtualSliderBuilder<A> {
 
         let mut flags = ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
             | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
-            | ElementFlags::LISTENS_TO_FOCUS_CHANGE;
+            | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+            | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED;
+        if !disabled && config.use_keyboard {
+            flags.insert(ElementFlags::FOCUSABLE);
+        }
 
         if renderer.does_paint() {
             flags.insert(ElementFlags::PAINTS);
         }
 
+        let inline_text_entry = inline_text_entry && formatter.is_some();
+        if inline_text_entry {
+            flags.insert(
+                ElementFlags::PAINTS
+                    | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
+                    | ElementFlags::LISTENS_TO_SIZE_CHANGE,
+            );
+        }
+
+        let text_entry_class = window_cx.builder_class(text_entry_class);
+
         let shared_state = Rc::new(RefCell::new(SharedState {
             inner: VirtualSliderInner::new(
                 param_id,
@@ -416,6 +551,8 @@ impl<A: Clone + 'static> VirtualSliderBuilder<A> {
                 scroll_horizontally,
             ),
             renderer,
+            formatter,
+            accessible_label,
             automation_info: AutomationInfo::default(),
             markers,
             bipolar,
@@ -431,9 +568,15 @@ impl<A: Clone + 'static> VirtualSliderBuilder<A> {
             on_right_click,
             on_open_text_entry,
             on_tooltip_request,
+            on_modulation_depth,
             tooltip_align,
             horizontal,
+            inline_text_entry,
+            text_entry_class,
+            text_entry: None,
+            modulation_drag: None,
             hovered: false,
+            focused: false,
             state: if disabled {
                 VirtualSliderState::Disabled
             } else {
@@ -458,10 +601,16 @@ struct VirtualSliderElement<A: Clone + 'static, R: VirtualSliderRenderer + 'stat
     on_right_click: Option<Box<dyn FnMut(ParamRightClickInfo) -> A>>,
     on_open_text_entry: Option<Box<dyn FnMut(ParamOpenTextEntryInfo) -> A>>,
     on_tooltip_request: Option<Box<dyn FnMut(ParamElementTooltipInfo) -> A>>,
+    on_modulation_depth: Option<Box<dyn FnMut(ModulationDepthUpdate) -> A>>,
     tooltip_align: Align2,
     horizontal: bool,
+    inline_text_entry: bool,
+    text_entry_class: ClassID,
+    text_entry: Option<TextInputInner>,
+    modulation_drag: Option<ModulationDrag>,
 
     hovered: bool,
+    focused: bool,
     state: VirtualSliderState,
     global_render_cache_id: Option<u32>,
 }
@@ -478,6 +627,7 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
         let SharedState {
             inner,
             renderer,
+            formatter,
             automation_info,
             markers,
             bipolar,
@@ -485,6 +635,7 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
             disabled,
             needs_repaint,
             queued_new_val,
+            ..
         } = &mut *shared_state;
 
         let send_param_update =
@@ -541,6 +692,196 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                 }
             };
 
+        let finish_modulation_drag =
+            |drag: Option<ModulationDrag>,
+             param_id: &SmolStr,
+             automation_info: &AutomationInfo,
+             cx: &mut ElementContext<'_, A>,
+             hovered: bool,
+             state: &mut VirtualSliderState,
+             renderer: &mut R,
+             disabled: bool,
+             on_depth: &mut Option<Box<dyn FnMut(ModulationDepthUpdate) -> A>>| {
+                let Some(drag) = drag else {
+                    return;
+                };
+
+                if drag.pointer_lock_requested {
+                    cx.request_pointer_lock(false);
+                }
+
+                if let (Some(f), Some(source)) = (
+                    on_depth.as_mut(),
+                    automation_info.modulation.get(drag.source_index),
+                ) {
+                    cx.send_action((f)(ModulationDepthUpdate {
+                        param_id: param_id.clone(),
+                        source_id: source.id.clone(),
+                        depth: source.depth,
+                        gesture_state: GestureState::GestureFinished,
+                    }))
+                    .unwrap();
+                }
+
+                let prev_state = *state;
+                *state = if disabled {
+                    VirtualSliderState::Disabled
+                } else if hovered {
+                    VirtualSliderState::Hovered
+                } else {
+                    VirtualSliderState::Idle
+                };
+
+                if prev_state != *state {
+                    let res = renderer.on_state_changed(prev_state, *state);
+                    if res.repaint {
+                        cx.request_repaint();
+                    }
+                    cx.set_animating(res.animating);
+                }
+            };
+
+        if let Some(text_entry) = self.text_entry.as_mut() {
+            // `Some(commit)` if the text entry should be closed.
+            let mut close = None;
+
+            let res = match &event {
+                ElementEvent::Animation { .. } => Some(text_entry.on_animation()),
+                ElementEvent::CustomStateChanged => {
+                    if *disabled {
+                        close = Some(false);
+                    }
+                    None
+                }
+                ElementEvent::SizeChanged => {
+                    let bounds_size = cx.rect().size;
+                    let style = cx.res.style_system.get(self.text_entry_class);
+                    text_entry.on_size_changed(bounds_size, style, &mut cx.res.font_system);
+                    Some(TextInputUpdateResult::default())
+                }
+                ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                    self.hovered = cx.rect().contains(*position);
+                    Some(text_entry.on_pointer_moved(*position, cx.rect(), &mut cx.res.font_system))
+                }
+                ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                    self.hovered = false;
+                    Some(text_entry.on_pointer_left())
+                }
+                ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                    position,
+                    button,
+                    click_count,
+                    ..
+                }) => {
+                    if cx.rect().contains(*position) {
+                        Some(text_entry.on_pointer_button_just_pressed(
+                            *position,
+                            *button,
+                            *click_count,
+                            cx.rect(),
+                            &mut cx.res.font_system,
+                        ))
+                    } else {
+                        close = Some(false);
+                        Some(TextInputUpdateResult::default())
+                    }
+                }
+                ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
+                    position,
+                    button,
+                    ..
+                }) => {
+                    Some(text_entry.on_pointer_button_just_released(*position, *button, cx.rect()))
+                }
+                ElementEvent::Pointer(_) => Some(TextInputUpdateResult::default()),
+                ElementEvent::Keyboard(key_event) => {
+                    let res = text_entry.on_keyboard_event(
+                        key_event,
+                        cx.clipboard,
+                        &mut cx.res.font_system,
+                    );
+
+                    if res.escape_key_pressed {
+                        close = Some(false);
+                    } else if res.enter_key_pressed
+                        && formatter
+                            .as_ref()
+                            .is_some_and(|formatter| formatter.parse(text_entry.text()).is_some())
+                    {
+                        close = Some(true);
+                    }
+
+                    Some(res)
+                }
+                ElementEvent::TextComposition(comp_event) => {
+                    Some(text_entry.on_text_composition_event(comp_event, &mut cx.res.font_system))
+                }
+                ElementEvent::Focus(focused) => {
+                    self.focused = *focused;
+                    if !*focused {
+                        close = Some(false);
+                    }
+                    Some(TextInputUpdateResult::default())
+                }
+                ElementEvent::ClickedOff => {
+                    close = Some(false);
+                    Some(text_entry.on_clicked_off())
+                }
+                _ => None,
+            };
+
+            if let Some(res) = &res {
+                apply_text_entry_result(res, cx);
+            }
+
+            if let Some(commit) = close {
+                let text_entry = self.text_entry.take().unwrap();
+
+                if commit {
+                    if let Some(mut param_update) = formatter
+                        .as_ref()
+                        .and_then(|formatter| formatter.parse(text_entry.text()))
+                        .and_then(|normal| inner.set_normal_value(normal))
+                    {
+                        // Entering a value is a complete edit, so report it as
+                        // a finished gesture for hosts that record undo and
+                        // automation on gesture boundaries.
+                        param_update.inner.gesture_state = Some(GestureState::GestureFinished);
+
+                        send_param_update(
+                            param_update,
+                            cx,
+                            renderer,
+                            None,
+                            self.state,
+                            &mut self.on_gesture,
+                        );
+                    }
+                }
+
+                if cx.has_focus() {
+                    cx.release_focus();
+                }
+
+                if !*disabled {
+                    let prev_state = self.state;
+                    self.state = if self.hovered {
+                        VirtualSliderState::Hovered
+                    } else {
+                        VirtualSliderState::Idle
+                    };
+                    let res = renderer.on_state_changed(prev_state, self.state);
+                    cx.set_animating(res.animating);
+                }
+
+                cx.request_repaint();
+            }
+
+            if let Some(res) = res {
+                return res.capture_status;
+            }
+        }
+
         match event {
             ElementEvent::Animation { delta_seconds } => {
                 if *disabled {
@@ -559,6 +900,7 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                         bipolar: *bipolar,
                         markers,
                         horizontal: self.horizontal,
+                        focused: self.focused && self.state != VirtualSliderState::Gesturing,
                     },
                 );
                 if res.repaint {
@@ -593,6 +935,17 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                         *disabled,
                         &mut self.on_gesture,
                     );
+                    finish_modulation_drag(
+                        self.modulation_drag.take(),
+                        &inner.param_id,
+                        automation_info,
+                        cx,
+                        self.hovered,
+                        &mut self.state,
+                        renderer,
+                        *disabled,
+                        &mut self.on_modulation_depth,
+                    );
 
                     cx.set_animating(false);
                 } else if self.state == VirtualSliderState::Disabled {
@@ -650,7 +1003,7 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                 if self.hovered != hovered {
                     self.hovered = hovered;
 
-                    if !inner.is_gesturing() {
+                    if !inner.is_gesturing() && self.modulation_drag.is_none() {
                         let prev_state = self.state;
                         self.state = if hovered {
                             VirtualSliderState::Hovered
@@ -679,6 +1032,38 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                     None
                 };
 
+                if let Some(drag) = &mut self.modulation_drag {
+                    let depth = drag.handle_pointer_moved(
+                        position,
+                        delta,
+                        modifiers,
+                        &inner.config,
+                        inner.drag_horizontally,
+                    );
+
+                    if let Some(source) = automation_info.modulation.get_mut(drag.source_index) {
+                        if source.depth != depth {
+                            source.depth = depth;
+
+                            if let Some(f) = self.on_modulation_depth.as_mut() {
+                                cx.send_action((f)(ModulationDepthUpdate {
+                                    param_id: inner.param_id.clone(),
+                                    source_id: source.id.clone(),
+                                    depth,
+                                    gesture_state: GestureState::Gesturing,
+                                }))
+                                .unwrap();
+                            }
+
+                            if renderer.on_automation_info_update(automation_info) {
+                                cx.request_repaint();
+                            }
+                        }
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+
                 if let Some(param_update) = inner.handle_pointer_moved(position, delta, modifiers) {
                     send_param_update(
                         InnerParamUpdate {
@@ -697,7 +1082,7 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
             }
             ElementEvent::Pointer(PointerEvent::PointerLeft) => {
                 if self.hovered {
-                    if !inner.is_gesturing() {
+                    if !inner.is_gesturing() && self.modulation_drag.is_none() {
                         if self.state != VirtualSliderState::Idle {
                             let prev_state = self.state;
                             self.state = VirtualSliderState::Idle;
@@ -724,11 +1109,13 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                     return EventCaptureStatus::NotCaptured;
                 }
 
+                let can_open_text_entry =
+                    self.inline_text_entry || self.on_open_text_entry.is_some();
                 let mut open_text_entry = false;
 
                 if button == PointerButton::Auxiliary
                     && inner.config.open_text_entry_on_middle_click
-                    && self.on_open_text_entry.is_some()
+                    && can_open_text_entry
                 {
                     open_text_entry = true;
                 }
@@ -752,36 +1139,64 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                         .unwrap();
 
                         return EventCaptureStatus::Captured;
-                    } else if inner.config.open_text_entry_on_right_click
-                        && self.on_open_text_entry.is_some()
-                    {
+                    } else if inner.config.open_text_entry_on_right_click && can_open_text_entry {
                         open_text_entry = true;
                     }
                 }
 
                 if button == PointerButton::Primary {
                     if let Some(m) = inner.config.open_text_entry_modifier {
-                        if modifiers == m && self.on_open_text_entry.is_some() {
+                        if modifiers == m && can_open_text_entry {
                             open_text_entry = true;
                         }
                     }
                 }
 
                 if open_text_entry {
-                    if let Some(f) = self.on_open_text_entry.as_mut() {
-                        finish_gesture(
-                            inner,
-                            cx,
-                            self.hovered,
-                            &mut self.state,
-                            renderer,
-                            *disabled,
-                            &mut self.on_gesture,
+                    finish_gesture(
+                        inner,
+                        cx,
+                        self.hovered,
+                        &mut self.state,
+                        renderer,
+                        *disabled,
+                        &mut self.on_gesture,
+                    );
+
+                    if let Some(formatter) = formatter.as_ref().filter(|_| self.inline_text_entry) {
+                        let bounds_size = cx.rect().size;
+                        let style = cx.res.style_system.get(self.text_entry_class);
+                        let mut text_entry = TextInputInner::new(
+                            formatter.format(inner.normal_value()),
+                            String::new(),
+                            false,
+                            256,
+                            bounds_size,
+                            false,
+                            true,
+                            None,
+                            style,
+                            &mut cx.res.font_system,
                         );
 
+                        let res = text_entry.on_focus_changed(
+                            true,
+                            cx.clipboard,
+                            &mut cx.res.font_system,
+                        );
+                        apply_text_entry_result(&res, cx);
+
+                        self.text_entry = Some(text_entry);
+
+                        cx.steal_focus();
+                        cx.request_repaint();
+                    } else if let Some(f) = self.on_open_text_entry.as_mut() {
                         cx.send_action((f)(ParamOpenTextEntryInfo {
                             param_info: inner.param_info(),
                             bounds: cx.rect(),
+                            text: formatter
+                                .as_ref()
+                                .map(|formatter| formatter.format(inner.normal_value())),
                         }))
                         .unwrap();
                     }
@@ -801,6 +1216,51 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                     &mut self.on_gesture,
                 );
 
+                if let (Some(source_index), Some(f)) = (
+                    automation_info.selected_modulation,
+                    self.on_modulation_depth.as_mut(),
+                ) {
+                    if let Some(source) = automation_info.modulation.get(source_index) {
+                        if renderer.modulation_hit_test(
+                            position - cx.rect().origin.to_vector(),
+                            cx.rect().size,
+                            self.horizontal,
+                            automation_info,
+                        ) {
+                            let pointer_lock_requested = !inner.config.disable_pointer_locking;
+                            cx.request_pointer_lock(pointer_lock_requested);
+
+                            self.modulation_drag = Some(ModulationDrag {
+                                source_index,
+                                pointer_start_pos: position,
+                                start_depth: source.depth,
+                                continuous_depth: source.depth,
+                                pointer_lock_requested,
+                            });
+
+                            cx.send_action((f)(ModulationDepthUpdate {
+                                param_id: inner.param_id.clone(),
+                                source_id: source.id.clone(),
+                                depth: source.depth,
+                                gesture_state: GestureState::GestureStarted,
+                            }))
+                            .unwrap();
+
+                            let prev_state = self.state;
+                            self.state = VirtualSliderState::Gesturing;
+                            let res = renderer.on_state_changed(prev_state, self.state);
+                            if res.repaint {
+                                cx.request_repaint();
+                            }
+                            cx.set_animating(res.animating);
+
+                            cx.steal_focus();
+
+                            return EventCaptureStatus::Captured;
+                        }
+                    }
+                }
+
                 if click_count == 1 {
                     if let Some(param_update) = inner.begin_drag_gesture(position) {
                         let prev_state = Some(self.state);
@@ -862,6 +1322,18 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                     }
                 }
 
+                finish_modulation_drag(
+                    self.modulation_drag.take(),
+                    &inner.param_id,
+                    automation_info,
+                    cx,
+                    self.hovered,
+                    &mut self.state,
+                    renderer,
+                    *disabled,
+                    &mut self.on_modulation_depth,
+                );
+
                 if cx.has_focus() {
                     cx.release_focus();
                 }
@@ -879,6 +1351,9 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                             param_info: inner.param_info(),
                             rect: cx.rect(),
                             tooltip_align: self.tooltip_align,
+                            text: formatter
+                                .as_ref()
+                                .map(|formatter| formatter.format(inner.normal_value())),
                         }))
                         .unwrap();
                     }
@@ -935,7 +1410,82 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                     cx.release_focus();
                 }
             }
+            ElementEvent::AccessAction(action) => {
+                if *disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let param_update = match action {
+                    AccessAction::Increment => inner.increment(1.0),
+                    AccessAction::Decrement => inner.increment(-1.0),
+                    AccessAction::SetValue(normal) => inner.set_normal_value(normal),
+                    AccessAction::Click => None,
+                };
+
+                if let Some(param_update) = param_update {
+                    send_param_update(
+                        param_update,
+                        cx,
+                        renderer,
+                        None,
+                        self.state,
+                        &mut self.on_gesture,
+                    );
+                }
+            }
+            ElementEvent::Keyboard(key_event) => {
+                if *disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                match key_event.state {
+                    KeyState::Down => {
+                        let Some(update) =
+                            inner.handle_key_down(key_event.code, key_event.modifiers)
+                        else {
+                            return EventCaptureStatus::NotCaptured;
+                        };
+
+                        for param_update in [update.begin, update.changed].into_iter().flatten() {
+                            send_param_update(
+                                InnerParamUpdate {
+                                    inner: param_update,
+                                    pointer_lock_request: None,
+                                },
+                                cx,
+                                renderer,
+                                None,
+                                self.state,
+                                &mut self.on_gesture,
+                            );
+                        }
+
+                        return EventCaptureStatus::Captured;
+                    }
+                    KeyState::Up => {
+                        if let Some(param_update) = inner.handle_key_up(key_event.code) {
+                            send_param_update(
+                                param_update,
+                                cx,
+                                renderer,
+                                None,
+                                self.state,
+                                &mut self.on_gesture,
+                            );
+
+                            return EventCaptureStatus::Captured;
+                        }
+                    }
+                }
+            }
             ElementEvent::Focus(focused) => {
+                if self.focused != focused {
+                    self.focused = focused;
+                    if renderer.does_paint() {
+                        cx.request_repaint();
+                    }
+                }
+
                 if !focused {
                     finish_gesture(
                         inner,
@@ -946,6 +1496,17 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                         *disabled,
                         &mut self.on_gesture,
                     );
+                    finish_modulation_drag(
+                        self.modulation_drag.take(),
+                        &inner.param_id,
+                        automation_info,
+                        cx,
+                        self.hovered,
+                        &mut self.state,
+                        renderer,
+                        *disabled,
+                        &mut self.on_modulation_depth,
+                    );
                 }
             }
             _ => {}
@@ -965,6 +1526,16 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
             ..
         } = &mut *shared_state;
 
+        let text_entry_primitives = self.text_entry.as_ref().map(|text_entry| {
+            let style = cx.res.style_system.get(self.text_entry_class);
+            text_entry.create_primitives(
+                style,
+                Rect::from_size(cx.bounds_size),
+                Vector::default(),
+                self.hovered,
+            )
+        });
+
         renderer.render(
             VirtualSliderRenderInfo {
                 normal_value: inner.normal_value(),
@@ -975,10 +1546,31 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
                 bipolar: *bipolar,
                 markers: markers,
                 horizontal: self.horizontal,
+                focused: self.focused && self.state != VirtualSliderState::Gesturing,
             },
             cx,
             primitives,
-        )
+        );
+
+        // Draw the text entry above everything the renderer has drawn.
+        if let Some(p) = text_entry_primitives {
+            if let Some(back_quad) = p.back_quad {
+                primitives.set_z_index(3);
+                primitives.add(back_quad);
+            }
+            if let Some(highlight_range) = p.highlight_range {
+                primitives.set_z_index(4);
+                primitives.add_solid_quad(highlight_range);
+            }
+            if let Some(text) = p.text {
+                primitives.set_z_index(5);
+                primitives.add_text(text);
+            }
+            if let Some(cursor) = p.cursor {
+                primitives.set_z_index(6);
+                primitives.add_solid_quad(cursor);
+            }
+        }
     }
 
     fn global_render_cache_id(&self) -> Option<u32> {
@@ -990,12 +1582,148 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
             .renderer
             .global_render_cache()
     }
+
+    fn accessibility_node(&mut self) -> Option<AccessNode> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let inner = &shared_state.inner;
+
+        let step = if let Some(stepped_value) = inner.stepped_value() {
+            (stepped_value.num_steps >= 2).then(|| f64::from(stepped_value.num_steps - 1).recip())
+        } else {
+            Some(f64::from(inner.config.increment_step))
+        };
+
+        let label = shared_state
+            .accessible_label
+            .as_deref()
+            .unwrap_or(inner.param_id.as_str());
+
+        Some(
+            AccessNode::new(AccessRole::Slider)
+                .label(Some(label))
+                .value(AccessValue::Range {
+                    value: inner.normal_value(),
+                    min: 0.0,
+                    max: 1.0,
+                    step,
+                })
+                .value_text(
+                    shared_state
+                        .formatter
+                        .as_ref()
+                        .map(|f| f.format(inner.normal_value())),
+                )
+                .disabled(shared_state.disabled),
+        )
+    }
+}
+
+/// The state of the user dragging on the modulation ring to edit the depth
+/// of a modulation source.
+struct ModulationDrag {
+    source_index: usize,
+    pointer_start_pos: Point,
+    start_depth: f64,
+    continuous_depth: f64,
+    pointer_lock_requested: bool,
+}
+
+impl ModulationDrag {
+    /// Returns the new depth in the range `[-1.0, 1.0]`.
+    ///
+    /// This uses the same drag and fine adjustment behavior as
+    /// [`VirtualSliderInner::handle_pointer_moved`].
+    fn handle_pointer_moved(
+        &mut self,
+        pointer_pos: Point,
+        pointer_delta: Option<Vector>,
+        modifiers: Modifiers,
+        config: &VirtualSliderConfig,
+        drag_horizontally: bool,
+    ) -> f64 {
+        let use_pointer_delta = !config.disable_pointer_locking && pointer_delta.is_some();
+
+        let apply_fine_adjustment_scalar = if let Some(m) = config.fine_adjustment_modifier {
+            modifiers == m
+        } else {
+            false
+        };
+
+        let delta_points = match pointer_delta.filter(|_| use_pointer_delta) {
+            Some(delta) if drag_horizontally => delta.x,
+            Some(delta) => -delta.y,
+            None if drag_horizontally => pointer_pos.x - self.pointer_start_pos.x,
+            None => self.pointer_start_pos.y - pointer_pos.y,
+        };
+
+        let reset_start_pos = use_pointer_delta || apply_fine_adjustment_scalar;
+
+        let new_depth = if reset_start_pos {
+            let mut delta_depth = delta_points * config.drag_scalar;
+            if apply_fine_adjustment_scalar {
+                delta_depth *= config.fine_adjustment_scalar;
+            }
+
+            self.continuous_depth + f64::from(delta_depth)
+        } else {
+            // Use absolute positions instead of deltas for a "better feel".
+            self.start_depth + f64::from(delta_points * config.drag_scalar)
+        };
+
+        self.continuous_depth = new_depth.clamp(-1.0, 1.0);
+
+        if reset_start_pos {
+            self.pointer_start_pos = pointer_pos;
+            self.start_depth = self.continuous_depth;
+        }
+
+        self.continuous_depth
+    }
+}
+
+fn apply_text_entry_result<A: Clone + 'static>(
+    res: &TextInputUpdateResult,
+    cx: &mut ElementContext<'_, A>,
+) {
+    if res.needs_repaint {
+        cx.request_repaint();
+    }
+    if let Some(focus) = res.set_focus {
+        if focus {
+            cx.steal_focus();
+        } else {
+            cx.release_focus();
+        }
+    }
+    if res.hovered {
+        cx.cursor_icon = CursorIcon::Text;
+    }
+    if res.listen_to_pointer_clicked_off {
+        cx.listen_to_pointer_clicked_off();
+    }
+    if let Some(animating) = res.set_animating {
+        cx.set_animating(animating);
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct AutomationInfo {
+    /// The current value of the parameter after modulation is applied (in
+    /// normalized units).
+    ///
+    /// Renderers that draw a modulation indicator show this separately from
+    /// the value of the parameter, and other renderers show this in place of
+    /// the value.
     pub current_normal: Option<f64>,
     pub range: Option<Range<f64>>,
+    /// The sources of modulation applied to this parameter.
+    pub modulation: Vec<ModulationSource>,
+    /// The index of the source in `modulation` whose depth is edited when
+    /// the user drags on the modulation ring (or track) of the element.
+    ///
+    /// If this is `None`, then dragging on the ring adjusts the value of the
+    /// parameter as usual.
+    pub selected_modulation: Option<usize>,
 }
 
 impl AutomationInfo {
@@ -1008,12 +1736,93 @@ impl AutomationInfo {
             let end = r.end.clamp(0.0, 1.0);
             *r = start..end
         }
+        for source in self.modulation.iter_mut() {
+            source.depth = source.depth.clamp(-1.0, 1.0);
+        }
+    }
+}
+
+/// A source of modulation applied to a parameter, such as an LFO or an
+/// envelope.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModulationSource {
+    /// The identifier of this source, which is sent back in a
+    /// [`ModulationDepthUpdate`] when the user edits the depth.
+    pub id: SmolStr,
+    /// The depth of the modulation in normalized units, in the range
+    /// `[-1.0, 1.0]`. A negative depth modulates the parameter downwards.
+    pub depth: f64,
+    /// If `true`, then the source swings in both directions, so the
+    /// modulation extends by `depth` on either side of the value.
+    ///
+    /// By default this is set to `false`.
+    pub bipolar: bool,
+    /// The color to show this source in. If this is `None`, then a color
+    /// from the style of the element is used.
+    ///
+    /// By default this is set to `None`.
+    pub color: Option<RGBA8>,
+}
+
+impl ModulationSource {
+    pub fn new(id: impl ToSmolStr, depth: f64) -> Self {
+        Self {
+            id: id.to_smolstr(),
+            depth,
+            bipolar: false,
+            color: None,
+        }
+    }
+
+    pub const fn bipolar(mut self, bipolar: bool) -> Self {
+        self.bipolar = bipolar;
+        self
+    }
+
+    pub const fn color(mut self, color: RGBA8) -> Self {
+        self.color = Some(color);
+        self
     }
+
+    /// The color of this source, or the color at `index` in `palette`
+    /// (wrapping around) if this source doesn't define its own color.
+    pub fn color_or_palette(&self, palette: &[RGBA8], index: usize) -> Option<RGBA8> {
+        self.color
+            .or_else(|| (!palette.is_empty()).then(|| palette[index % palette.len()]))
+    }
+
+    /// The range of normalized values this source sweeps the parameter over
+    /// when the parameter is set to `normal_value`.
+    pub fn range(&self, normal_value: f64) -> Range<f64> {
+        let (a, b) = if self.bipolar {
+            (normal_value - self.depth, normal_value + self.depth)
+        } else {
+            (normal_value, normal_value + self.depth)
+        };
+
+        a.min(b).clamp(0.0, 1.0)..a.max(b).clamp(0.0, 1.0)
+    }
+}
+
+/// Sent when the user edits the depth of a [`ModulationSource`] by dragging
+/// on the modulation ring (or track) of an element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModulationDepthUpdate {
+    /// The ID of the parameter
+    pub param_id: SmolStr,
+    /// The ID of the modulation source
+    pub source_id: SmolStr,
+    /// The new depth in the range `[-1.0, 1.0]`
+    pub depth: f64,
+    /// The current state of gesturing (dragging)
+    pub gesture_state: GestureState,
 }
 
 struct SharedState<R: VirtualSliderRenderer + 'static> {
     inner: VirtualSliderInner,
     renderer: R,
+    formatter: Option<Box<dyn ParamFormatter>>,
+    accessible_label: Option<String>,
     automation_info: AutomationInfo,
     markers: ParamMarkersConfig,
     bipolar: bool,
@@ -1147,6 +1956,34 @@ impl<R: VirtualSliderRenderer> VirtualSlider<R> {
         }
     }
 
+    /// Set the current value of the parameter after modulation is applied
+    /// (in normalized units), which is shown by the modulation indicator.
+    ///
+    /// This is equivalent to setting [`AutomationInfo::current_normal`], but
+    /// it is cheaper to call on every frame.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_modulated_normal(&mut self, normal: Option<f64>) -> bool {
+        let normal = normal.map(|n| n.clamp(0.0, 1.0));
+
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        if shared_state.automation_info.current_normal != normal {
+            shared_state.automation_info.current_normal = normal;
+            shared_state.automation_info_changed = true;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn automation_info<'a>(&'a self) -> Ref<'a, AutomationInfo> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| &s.automation_info)
+    }
+
     /// Reset the parameter to the default value.
     ///
     /// Returns `true` if the value has changed.
@@ -1189,6 +2026,37 @@ impl<R: VirtualSliderRenderer> VirtualSlider<R> {
             .value()
     }
 
+    /// The current value as formatted by the [`ParamFormatter`] of this
+    /// element, or `None` if it has no formatter.
+    pub fn formatted_value(&self) -> Option<String> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        shared_state
+            .formatter
+            .as_ref()
+            .map(|f| f.format(shared_state.inner.normal_value()))
+    }
+
+    /// Set the value of the parameter from text entered by the user, such
+    /// as from a text entry opened by `on_open_text_entry`.
+    ///
+    /// Returns `true` if the value has changed. Returns `false` if the
+    /// element has no [`ParamFormatter`] or if the text is not a valid value.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively inexpensive to call.
+    pub fn set_value_from_text(&mut self, text: &str) -> bool {
+        let new_normal = RefCell::borrow(&self.shared_state)
+            .formatter
+            .as_ref()
+            .and_then(|f| f.parse(text));
+
+        match new_normal {
+            Some(new_normal) => self.set_normal_value(new_normal),
+            None => false,
+        }
+    }
+
     /// Set the markers config for the element.
     ///
     /// Returns `true` if the value has changed.
@@ -1276,6 +2144,8 @@ impl<R: VirtualSliderRenderer> VirtualSlider<R> {
         if shared_state.disabled != disabled {
             shared_state.disabled = disabled;
             shared_state.needs_repaint = true;
+            self.el
+                .set_focusable(!disabled && shared_state.inner.config.use_keyboard);
             self.el.notify_custom_state_change();
         }
     }
@@ -1300,7 +2170,434 @@ impl<R: VirtualSliderRenderer> VirtualSlider<R> {
         }
     }
 
+    /// Set the name of this element which is read out by assistive
+    /// technologies. If this is `None`, then the parameter ID is used
+    /// instead.
+    ///
+    /// Returns `true` if the label has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_accessible_label(&mut self, label: Option<&str>) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.accessible_label.as_deref() != label {
+            shared_state.accessible_label = label.map(String::from);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn disabled(&self) -> bool {
         RefCell::borrow(&self.shared_state).disabled
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        Gesture(ParamUpdate),
+        ModulationDepth(ModulationDepthUpdate),
+    }
+
+    fn drain_gestures<A: Application<Action = TestAction>>(
+        app: &mut HeadlessApp<A>,
+    ) -> Vec<(GestureState, f64)> {
+        app.drain_actions()
+            .into_iter()
+            .map(|action| match action {
+                TestAction::Gesture(update) => (
+                    update.gesture_state.unwrap(),
+                    update.param_info.normal_value,
+                ),
+                action => panic!("unexpected action {:?}", action),
+            })
+            .collect()
+    }
+
+    fn assert_gestures(actual: Vec<(GestureState, f64)>, expected: &[(GestureState, f64)]) {
+        assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+        for ((state, normal), (expected_state, expected_normal)) in actual.iter().zip(expected) {
+            assert_eq!(state, expected_state, "{:?}", actual);
+            assert!((normal - expected_normal).abs() < 1e-6, "{:?}", actual);
+        }
+    }
+
+    struct KeyboardTestApp {
+        slider: Slider,
+        stepped_slider: Slider,
+    }
+
+    impl Application for KeyboardTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let mut window_cx = cx.main_window();
+
+            let slider = Slider::builder("gain")
+                .on_gesture(TestAction::Gesture)
+                .normal_value(0.5)
+                .default_normal(0.25)
+                .rect(rect(10.0, 10.0, 20.0, 100.0))
+                .build(&mut window_cx);
+            let stepped_slider = Slider::builder("mode")
+                .on_gesture(TestAction::Gesture)
+                .num_quantized_steps(Some(5))
+                .rect(rect(40.0, 10.0, 20.0, 100.0))
+                .build(&mut window_cx);
+
+            Ok(Self {
+                slider,
+                stepped_slider,
+            })
+        }
+    }
+
+    #[test]
+    fn test_slider_keyboard_control() {
+        use GestureState::*;
+
+        let mut app = HeadlessApp::<KeyboardTestApp>::new(AppConfig::default()).unwrap();
+
+        app.key_press(MAIN_WINDOW, Code::Tab, Modifiers::empty());
+
+        // Holding down a key continues the same gesture until it is released.
+        let mut event = KeyboardEvent {
+            state: KeyState::Down,
+            code: Code::ArrowUp,
+            ..Default::default()
+        };
+        assert_eq!(
+            app.keyboard_event(MAIN_WINDOW, event.clone()),
+            EventCaptureStatus::Captured
+        );
+        event.repeat = true;
+        app.keyboard_event(MAIN_WINDOW, event.clone());
+        assert_gestures(
+            drain_gestures(&mut app),
+            &[(GestureStarted, 0.5), (Gesturing, 0.51), (Gesturing, 0.52)],
+        );
+        event.state = KeyState::Up;
+        app.keyboard_event(MAIN_WINDOW, event);
+        assert_gestures(drain_gestures(&mut app), &[(GestureFinished, 0.52)]);
+
+        app.key_press(MAIN_WINDOW, Code::ArrowLeft, Modifiers::SHIFT);
+        assert_gestures(
+            drain_gestures(&mut app),
+            &[
+                (GestureStarted, 0.52),
+                (Gesturing, 0.519),
+                (GestureFinished, 0.519),
+            ],
+        );
+
+        app.key_press(MAIN_WINDOW, Code::PageDown, Modifiers::empty());
+        app.key_press(MAIN_WINDOW, Code::End, Modifiers::empty());
+        app.key_press(MAIN_WINDOW, Code::Delete, Modifiers::empty());
+        assert_gestures(
+            drain_gestures(&mut app),
+            &[
+                (GestureStarted, 0.519),
+                (Gesturing, 0.419),
+                (GestureFinished, 0.419),
+                (GestureStarted, 0.419),
+                (Gesturing, 1.0),
+                (GestureFinished, 1.0),
+                (GestureStarted, 1.0),
+                (Gesturing, 0.25),
+                (GestureFinished, 0.25),
+            ],
+        );
+        assert_eq!(app.app().slider.normal_value(), 0.25);
+
+        // Other keys and modifiers are ignored.
+        app.key_press(MAIN_WINDOW, Code::KeyA, Modifiers::empty());
+        app.key_press(MAIN_WINDOW, Code::ArrowUp, Modifiers::CONTROL);
+        assert!(app.drain_actions().is_empty());
+
+        // Stepped parameters always move by at least one step.
+        app.key_press(MAIN_WINDOW, Code::Tab, Modifiers::empty());
+        app.key_press(MAIN_WINDOW, Code::ArrowRight, Modifiers::SHIFT);
+        app.key_press(MAIN_WINDOW, Code::PageUp, Modifiers::empty());
+        assert_gestures(
+            drain_gestures(&mut app),
+            &[
+                (GestureStarted, 0.0),
+                (Gesturing, 0.25),
+                (GestureFinished, 0.25),
+                (GestureStarted, 0.25),
+                (Gesturing, 0.5),
+                (GestureFinished, 0.5),
+            ],
+        );
+        assert_eq!(app.app().stepped_slider.stepped_value().unwrap().value, 2);
+
+        // Nothing changes at the end of the range, but the gesture is still
+        // reported.
+        app.key_press(MAIN_WINDOW, Code::Home, Modifiers::empty());
+        app.key_press(MAIN_WINDOW, Code::Home, Modifiers::empty());
+        assert_gestures(
+            drain_gestures(&mut app),
+            &[
+                (GestureStarted, 0.5),
+                (Gesturing, 0.0),
+                (GestureFinished, 0.0),
+                (GestureStarted, 0.0),
+                (GestureFinished, 0.0),
+            ],
+        );
+    }
+
+    struct FormatterTestApp {
+        slider: Slider,
+        stepped_slider: Slider,
+    }
+
+    impl Application for FormatterTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let mut window_cx = cx.main_window();
+
+            let slider = Slider::builder("gain")
+                .on_gesture(TestAction::Gesture)
+                .formatter(DecibelFormatter::new(-60.0, 0.0))
+                .accessible_label("Gain")
+                .normal_value(0.5)
+                .rect(rect(10.0, 10.0, 20.0, 100.0))
+                .build(&mut window_cx);
+            let stepped_slider = Slider::builder("mode")
+                .on_gesture(TestAction::Gesture)
+                .formatter(EnumFormatter::new([
+                    "Sine", "Triangle", "Saw", "Square", "Noise",
+                ]))
+                .num_quantized_steps(Some(5))
+                .rect(rect(40.0, 10.0, 20.0, 100.0))
+                .build(&mut window_cx);
+
+            Ok(Self {
+                slider,
+                stepped_slider,
+            })
+        }
+    }
+
+    #[test]
+    fn test_slider_formatted_value() {
+        let mut app = HeadlessApp::<FormatterTestApp>::new(AppConfig::default()).unwrap();
+        assert_eq!(
+            app.app().slider.formatted_value().as_deref(),
+            Some("-30.0 dB")
+        );
+        assert_eq!(
+            app.app().stepped_slider.formatted_value().as_deref(),
+            Some("Sine")
+        );
+
+        assert!(app.app_mut().slider.set_value_from_text("-6"));
+        assert!(!app.app_mut().slider.set_value_from_text("quiet"));
+        assert!(app.app_mut().stepped_slider.set_value_from_text("saw"));
+        app.process_updates();
+        assert!((app.app().slider.normal_value() - 0.9).abs() < 1e-9);
+        assert_eq!(
+            app.app().stepped_slider.formatted_value().as_deref(),
+            Some("Saw")
+        );
+        assert_eq!(app.drain_actions().len(), 2);
+    }
+
+    struct TextEntryTestApp {
+        slider: Slider,
+    }
+
+    impl Application for TextEntryTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let slider = Slider::builder("gain")
+                .on_gesture(TestAction::Gesture)
+                .formatter(DecibelFormatter::new(-60.0, 0.0))
+                .inline_text_entry(true)
+                .normal_value(0.5)
+                .rect(rect(10.0, 10.0, 20.0, 100.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { slider })
+        }
+    }
+
+    #[cfg(feature = "accesskit")]
+    #[test]
+    fn test_slider_accessibility_node() {
+        let mut app = HeadlessApp::<FormatterTestApp>::new(AppConfig::default()).unwrap();
+
+        let update = app.accessibility_tree_update(MAIN_WINDOW).unwrap();
+        let node = |name: &str| {
+            update
+                .nodes
+                .iter()
+                .map(|(_, node)| node)
+                .find(|node| node.name() == Some(name))
+                .unwrap()
+                .clone()
+        };
+
+        assert_eq!(node("Gain").value(), Some("-30.0 dB"));
+        // The parameter ID is used when no label is given.
+        assert_eq!(node("mode").value(), Some("Sine"));
+
+        assert!(app.app_mut().slider.set_accessible_label(None));
+        app.process_updates();
+        assert!(app.accessibility_needs_update(MAIN_WINDOW));
+
+        let update = app.accessibility_tree_update(MAIN_WINDOW).unwrap();
+        assert!(update
+            .nodes
+            .iter()
+            .any(|(_, node)| node.name() == Some("gain")));
+    }
+
+    #[test]
+    fn test_slider_inline_text_entry() {
+        let mut app = HeadlessApp::<TextEntryTestApp>::new(AppConfig::default()).unwrap();
+
+        // The text entry is pre-filled and selected, so typing replaces the
+        // value. Enter commits it as a single finished update.
+        app.click(MAIN_WINDOW, point(20.0, 60.0), PointerButton::Auxiliary);
+        app.process_updates();
+        assert_eq!(
+            app.type_text(MAIN_WINDOW, "-6"),
+            EventCaptureStatus::Captured
+        );
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        app.process_updates();
+        match app.drain_actions().as_slice() {
+            [TestAction::Gesture(update)] => {
+                assert_eq!(update.gesture_state, Some(GestureState::GestureFinished));
+                assert!((update.param_info.normal_value - 0.9).abs() < 1e-9);
+            }
+            actions => panic!("unexpected actions {:?}", actions),
+        }
+        assert_eq!(
+            app.type_text(MAIN_WINDOW, "-12"),
+            EventCaptureStatus::NotCaptured
+        );
+
+        // Escape cancels the edit.
+        app.set_modifiers(MAIN_WINDOW, Modifiers::CONTROL);
+        app.click(MAIN_WINDOW, point(20.0, 60.0), PointerButton::Primary);
+        app.set_modifiers(MAIN_WINDOW, Modifiers::empty());
+        app.process_updates();
+        app.type_text(MAIN_WINDOW, "-12");
+        app.key_press(MAIN_WINDOW, Code::Escape, Modifiers::empty());
+        app.process_updates();
+        assert!(app.drain_actions().is_empty());
+        assert!((app.app().slider.normal_value() - 0.9).abs() < 1e-9);
+
+        // Text that the formatter can't parse keeps the text entry open, and
+        // clicking off cancels it.
+        app.click(MAIN_WINDOW, point(20.0, 60.0), PointerButton::Auxiliary);
+        app.process_updates();
+        app.type_text(MAIN_WINDOW, "loud");
+        app.key_press(MAIN_WINDOW, Code::Enter, Modifiers::empty());
+        app.process_updates();
+        assert_eq!(
+            app.type_text(MAIN_WINDOW, "er"),
+            EventCaptureStatus::Captured
+        );
+        app.click(MAIN_WINDOW, point(200.0, 200.0), PointerButton::Primary);
+        app.process_updates();
+        assert!(app.drain_actions().is_empty());
+        assert!((app.app().slider.normal_value() - 0.9).abs() < 1e-9);
+        assert_eq!(
+            app.type_text(MAIN_WINDOW, "-12"),
+            EventCaptureStatus::NotCaptured
+        );
+    }
+
+    struct ModulationTestApp {
+        slider: Slider,
+    }
+
+    impl Application for ModulationTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let slider = Slider::builder("gain")
+                .on_gesture(TestAction::Gesture)
+                .on_modulation_depth(TestAction::ModulationDepth)
+                .normal_value(0.5)
+                .rect(rect(10.0, 10.0, 20.0, 100.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { slider })
+        }
+    }
+
+    #[test]
+    fn test_slider_modulation_depth() {
+        use GestureState::*;
+
+        let lfo = ModulationSource::new("lfo", 0.25);
+        let env = ModulationSource::new("env", -0.5).bipolar(true);
+        assert_eq!(lfo.range(0.5), 0.5..0.75);
+        assert_eq!(lfo.range(0.9), 0.9..1.0);
+        assert_eq!(env.range(0.5), 0.0..1.0);
+
+        let mut app = HeadlessApp::<ModulationTestApp>::new(AppConfig::default()).unwrap();
+        app.app_mut().slider.set_automation_info(AutomationInfo {
+            modulation: vec![lfo, env],
+            selected_modulation: Some(1),
+            ..Default::default()
+        });
+        app.process_updates();
+
+        // Dragging on the modulation bars edits the depth of the selected
+        // source instead of the value.
+        app.pointer_moved(MAIN_WINDOW, point(28.0, 60.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.pointer_moved(MAIN_WINDOW, point(28.0, 50.0));
+        app.pointer_moved(MAIN_WINDOW, point(28.0, 40.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+
+        let updates: Vec<(GestureState, f64)> = app
+            .drain_actions()
+            .into_iter()
+            .map(|action| match action {
+                TestAction::ModulationDepth(update) => {
+                    assert_eq!(update.param_id, "gain");
+                    assert_eq!(update.source_id, "env");
+                    (update.gesture_state, update.depth)
+                }
+                action => panic!("unexpected action {:?}", action),
+            })
+            .collect();
+        assert_gestures(
+            updates,
+            &[
+                (GestureStarted, -0.5),
+                (Gesturing, -0.47),
+                (Gesturing, -0.44),
+                (GestureFinished, -0.44),
+            ],
+        );
+        assert!((app.app().slider.automation_info().modulation[1].depth + 0.44).abs() < 1e-6);
+        assert_eq!(app.app().slider.normal_value(), 0.5);
+
+        // Dragging anywhere else still adjusts the value.
+        app.pointer_moved(MAIN_WINDOW, point(15.0, 60.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+        assert!(matches!(
+            app.drain_actions().first(),
+            Some(TestAction::Gesture(_))
+        ));
+    }
+}
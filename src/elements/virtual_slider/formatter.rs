@@ -0,0 +1,350 @@
+use smol_str::{SmolStr, ToSmolStr};
+
+use super::{param_normal_to_quantized, param_quantized_to_normal};
+
+/// Converts the normalized value of a parameter to text for display, and
+/// parses text entered by the user back into a normalized value.
+pub trait ParamFormatter {
+    /// Format the normalized value in the range `[0.0, 1.0]` for display,
+    /// such as in a tooltip or a text entry.
+    fn format(&self, normal: f64) -> String;
+
+    /// Parse text entered by the user into a normalized value in the range
+    /// `[0.0, 1.0]`.
+    ///
+    /// Returns `None` if the text is not a valid value.
+    fn parse(&self, text: &str) -> Option<f64>;
+}
+
+/// Parse a number followed by an optional unit, such as `"-6 dB"`, `"440hz"`,
+/// or `"440"`. The unit is matched case-insensitively.
+///
+/// A `k` suffix multiplies the number by `1000`, so `"2.5k"` and `"2.5 kHz"`
+/// are both parsed as `2500.0`. A `,` is accepted as a decimal point.
+pub fn parse_value_with_unit(text: &str, unit: &str) -> Option<f64> {
+    let text = strip_unit(text, unit);
+
+    let (text, scale) = match text.strip_suffix(['k', 'K']) {
+        Some(text) => (text.trim_end(), 1000.0),
+        None => (text, 1.0),
+    };
+
+    text.replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .map(|value| value * scale)
+}
+
+fn strip_unit<'a>(text: &'a str, unit: &str) -> &'a str {
+    let text = text.trim();
+
+    if !unit.is_empty() && text.len() >= unit.len() {
+        let split = text.len() - unit.len();
+        if text.is_char_boundary(split) && text[split..].eq_ignore_ascii_case(unit) {
+            return text[..split].trim_end();
+        }
+    }
+
+    text
+}
+
+fn format_value(value: f64, decimals: usize, unit: &str) -> String {
+    if unit.is_empty() {
+        format!("{:.*}", decimals, value)
+    } else {
+        format!("{:.*} {}", decimals, value, unit)
+    }
+}
+
+/// A [`ParamFormatter`] for a value that is mapped linearly to the
+/// normalized range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearFormatter {
+    /// The value at a normalized value of `0.0`.
+    pub min: f64,
+    /// The value at a normalized value of `1.0`.
+    pub max: f64,
+    /// The number of digits shown after the decimal point.
+    ///
+    /// By default this is set to `2`.
+    pub decimals: usize,
+    /// The unit shown after the value, such as `"ms"`. Typing the unit is
+    /// optional when entering a value.
+    pub unit: SmolStr,
+}
+
+impl LinearFormatter {
+    pub fn new(min: f64, max: f64, unit: impl ToSmolStr) -> Self {
+        Self {
+            min,
+            max,
+            decimals: 2,
+            unit: unit.to_smolstr(),
+        }
+    }
+
+    pub const fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+}
+
+impl ParamFormatter for LinearFormatter {
+    fn format(&self, normal: f64) -> String {
+        let value = self.min + normal.clamp(0.0, 1.0) * (self.max - self.min);
+        format_value(value, self.decimals, &self.unit)
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        let value = parse_value_with_unit(text, &self.unit)?;
+
+        Some(if self.max == self.min {
+            0.0
+        } else {
+            ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        })
+    }
+}
+
+/// A [`ParamFormatter`] for a frequency in Hz that is mapped logarithmically
+/// to the normalized range, so that each octave takes up the same amount of
+/// space.
+///
+/// Frequencies of 1000 Hz and above are shown in kHz.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyFormatter {
+    /// The frequency at a normalized value of `0.0`. This must be greater
+    /// than zero.
+    pub min_hz: f64,
+    /// The frequency at a normalized value of `1.0`.
+    pub max_hz: f64,
+}
+
+impl FrequencyFormatter {
+    pub const fn new(min_hz: f64, max_hz: f64) -> Self {
+        Self { min_hz, max_hz }
+    }
+
+    pub fn normal_to_hz(&self, normal: f64) -> f64 {
+        self.min_hz * (self.max_hz / self.min_hz).powf(normal.clamp(0.0, 1.0))
+    }
+
+    pub fn hz_to_normal(&self, hz: f64) -> f64 {
+        let range = (self.max_hz / self.min_hz).ln();
+        if range == 0.0 || !range.is_finite() {
+            return 0.0;
+        }
+
+        ((hz.max(self.min_hz) / self.min_hz).ln() / range).clamp(0.0, 1.0)
+    }
+}
+
+impl ParamFormatter for FrequencyFormatter {
+    fn format(&self, normal: f64) -> String {
+        let hz = self.normal_to_hz(normal);
+
+        if hz >= 10_000.0 {
+            format_value(hz / 1000.0, 1, "kHz")
+        } else if hz >= 1000.0 {
+            format_value(hz / 1000.0, 2, "kHz")
+        } else if hz >= 100.0 {
+            format_value(hz, 0, "Hz")
+        } else {
+            format_value(hz, 1, "Hz")
+        }
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        parse_value_with_unit(text, "Hz").map(|hz| self.hz_to_normal(hz))
+    }
+}
+
+/// A [`ParamFormatter`] for a gain in decibels that is mapped linearly to
+/// the normalized range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecibelFormatter {
+    /// The gain at a normalized value of `0.0`.
+    pub min_db: f64,
+    /// The gain at a normalized value of `1.0`.
+    pub max_db: f64,
+    /// The number of digits shown after the decimal point.
+    ///
+    /// By default this is set to `1`.
+    pub decimals: usize,
+    /// If `true`, then a normalized value of `0.0` is shown as `"-inf dB"`
+    /// (silence), and `"-inf"` can be entered.
+    ///
+    /// By default this is set to `false`.
+    pub min_is_negative_infinity: bool,
+}
+
+impl DecibelFormatter {
+    pub const fn new(min_db: f64, max_db: f64) -> Self {
+        Self {
+            min_db,
+            max_db,
+            decimals: 1,
+            min_is_negative_infinity: false,
+        }
+    }
+
+    pub const fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub const fn min_is_negative_infinity(mut self, min_is_negative_infinity: bool) -> Self {
+        self.min_is_negative_infinity = min_is_negative_infinity;
+        self
+    }
+}
+
+impl ParamFormatter for DecibelFormatter {
+    fn format(&self, normal: f64) -> String {
+        if self.min_is_negative_infinity && normal <= 0.0 {
+            return String::from("-inf dB");
+        }
+
+        let db = self.min_db + normal.clamp(0.0, 1.0) * (self.max_db - self.min_db);
+        format_value(db, self.decimals, "dB")
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        if self.min_is_negative_infinity && strip_unit(text, "dB").eq_ignore_ascii_case("-inf") {
+            return Some(0.0);
+        }
+
+        let db = parse_value_with_unit(text, "dB")?;
+
+        Some(if self.max_db == self.min_db {
+            0.0
+        } else {
+            ((db - self.min_db) / (self.max_db - self.min_db)).clamp(0.0, 1.0)
+        })
+    }
+}
+
+/// A [`ParamFormatter`] that shows the normalized value as a percentage.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PercentFormatter {
+    /// The number of digits shown after the decimal point.
+    ///
+    /// By default this is set to `0`.
+    pub decimals: usize,
+    /// If `true`, then the normalized range is shown as `-100%` to `100%`
+    /// instead of `0%` to `100%`.
+    ///
+    /// By default this is set to `false`.
+    pub bipolar: bool,
+}
+
+impl PercentFormatter {
+    pub const fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub const fn bipolar(mut self, bipolar: bool) -> Self {
+        self.bipolar = bipolar;
+        self
+    }
+}
+
+impl ParamFormatter for PercentFormatter {
+    fn format(&self, normal: f64) -> String {
+        let normal = normal.clamp(0.0, 1.0);
+        let percent = if self.bipolar {
+            (normal * 2.0 - 1.0) * 100.0
+        } else {
+            normal * 100.0
+        };
+
+        format!("{:.*}%", self.decimals, percent)
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        let percent = parse_value_with_unit(text, "%")?;
+
+        let normal = if self.bipolar {
+            (percent / 100.0 + 1.0) * 0.5
+        } else {
+            percent / 100.0
+        };
+
+        Some(normal.clamp(0.0, 1.0))
+    }
+}
+
+/// A [`ParamFormatter`] for a stepped parameter that shows a label for
+/// each step, such as the waveform of an oscillator.
+///
+/// The number of labels should match the number of steps of the parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumFormatter {
+    pub labels: Vec<String>,
+}
+
+impl EnumFormatter {
+    pub fn new(labels: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            labels: labels.into_iter().map(|l| l.into()).collect(),
+        }
+    }
+}
+
+impl ParamFormatter for EnumFormatter {
+    fn format(&self, normal: f64) -> String {
+        let step = param_normal_to_quantized(normal, self.labels.len() as u32) as usize;
+
+        self.labels.get(step).cloned().unwrap_or_default()
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        let text = text.trim();
+
+        self.labels
+            .iter()
+            .position(|label| label.eq_ignore_ascii_case(text))
+            .map(|step| param_quantized_to_normal(step as u32, self.labels.len() as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_param_formatters() {
+        let frequency = FrequencyFormatter::new(20.0, 20_000.0);
+        assert_eq!(frequency.format(0.0), "20.0 Hz");
+        assert_eq!(frequency.format(1.0 / 3.0), "200 Hz");
+        assert_eq!(frequency.format(2.0 / 3.0), "2.00 kHz");
+        assert_eq!(frequency.format(1.0), "20.0 kHz");
+        assert!((frequency.parse("2k").unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert!((frequency.parse("2 kHz").unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert!((frequency.parse("200hz").unwrap() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(frequency.parse("5"), Some(0.0));
+        assert_eq!(frequency.parse("loud"), None);
+
+        let gain = DecibelFormatter::new(-60.0, 0.0).min_is_negative_infinity(true);
+        assert_eq!(gain.format(0.0), "-inf dB");
+        assert_eq!(gain.format(0.9), "-6.0 dB");
+        assert_eq!(gain.parse("-inf dB"), Some(0.0));
+        assert_eq!(gain.parse("-6,0"), Some(0.9));
+
+        let pan = PercentFormatter::default().bipolar(true);
+        assert_eq!(pan.format(0.25), "-50%");
+        assert_eq!(pan.parse("50 %"), Some(0.75));
+
+        let time = LinearFormatter::new(0.0, 1000.0, "ms").decimals(0);
+        assert_eq!(time.format(0.25), "250 ms");
+        assert_eq!(time.parse("2000"), Some(1.0));
+
+        let waveform = EnumFormatter::new(["Sine", "Triangle", "Saw", "Square", "Noise"]);
+        assert_eq!(waveform.format(0.0), "Sine");
+        assert_eq!(waveform.format(0.5), "Saw");
+        assert_eq!(waveform.parse(" square "), Some(0.75));
+        assert_eq!(waveform.parse("Pulse"), None);
+    }
+}
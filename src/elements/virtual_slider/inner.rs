@@ -1,4 +1,4 @@
-use keyboard_types::Modifiers;
+use keyboard_types::{Code, Modifiers};
 use rootvg::math::{Point, Vector};
 use smol_str::SmolStr;
 
@@ -83,6 +83,15 @@ impl ParamUpdate {
     }
 }
 
+/// The result of [`VirtualSliderInner::handle_key_down`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyboardParamUpdate {
+    /// Set if this key press began a new keyboard gesture.
+    pub begin: Option<ParamUpdate>,
+    /// Set if this key press changed the value.
+    pub changed: Option<ParamUpdate>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum BeginGestureType {
     Dragging {
@@ -90,6 +99,7 @@ enum BeginGestureType {
         start_normal: f64,
     },
     ScrollWheel,
+    Keyboard,
 }
 
 /// A reusable "virtual slider" struct that can be used to make
@@ -190,6 +200,107 @@ impl VirtualSliderInner {
         self.current_gesture.is_some()
     }
 
+    /// Handle a key being pressed while this element has keyboard focus.
+    ///
+    /// The first adjustment begins a keyboard gesture, which lasts until
+    /// the key is released (see [`VirtualSliderInner::handle_key_up`]).
+    ///
+    /// Returns `None` if the key is not used to adjust the value, or if
+    /// the user is currently gesturing this element with the pointer.
+    pub fn handle_key_down(
+        &mut self,
+        code: Code,
+        modifiers: Modifiers,
+    ) -> Option<KeyboardParamUpdate> {
+        if !self.config.use_keyboard || !is_adjustment_key(code) {
+            return None;
+        }
+
+        let apply_fine_adjustment = if let Some(m) = self.config.fine_adjustment_modifier {
+            modifiers == m
+        } else {
+            false
+        };
+
+        if !(modifiers.is_empty() || apply_fine_adjustment) {
+            return None;
+        }
+
+        let begin = match &self.current_gesture {
+            Some(BeginGestureType::Keyboard) => None,
+            Some(_) => return None,
+            None => {
+                self.current_gesture = Some(BeginGestureType::Keyboard);
+                self.continuous_gesture_normal = self.normal_value;
+
+                Some(ParamUpdate {
+                    param_info: self.param_info(),
+                    gesture_state: Some(GestureState::GestureStarted),
+                })
+            }
+        };
+
+        let new_gesture_normal = match code {
+            Code::Home => 0.0,
+            Code::End => 1.0,
+            Code::Delete => self.default_normal,
+            _ => {
+                let (increments, page) = match code {
+                    Code::ArrowUp | Code::ArrowRight => (1.0, false),
+                    Code::ArrowDown | Code::ArrowLeft => (-1.0, false),
+                    Code::PageUp => (1.0, true),
+                    _ => (-1.0, true),
+                };
+
+                if let Some(stepped_value) = self.stepped_value {
+                    let num_steps = stepped_value.num_steps.max(2);
+                    let steps = if page {
+                        (self.config.page_increment_step * (num_steps - 1) as f32)
+                            .round()
+                            .max(1.0)
+                    } else {
+                        1.0
+                    };
+
+                    param_quantized_to_normal(
+                        (f64::from(stepped_value.value) + increments * f64::from(steps)).max(0.0)
+                            as u32,
+                        stepped_value.num_steps,
+                    )
+                } else {
+                    let step = if page {
+                        self.config.page_increment_step
+                    } else if apply_fine_adjustment {
+                        self.config.fine_increment_step
+                    } else {
+                        self.config.increment_step
+                    };
+
+                    self.continuous_gesture_normal + increments * f64::from(step)
+                }
+            }
+        };
+
+        let changed = self.set_new_gesture_normal(new_gesture_normal);
+
+        if begin.is_none() && changed.is_none() {
+            None
+        } else {
+            Some(KeyboardParamUpdate { begin, changed })
+        }
+    }
+
+    /// Handle a key being released while this element has keyboard focus.
+    ///
+    /// This finishes the current keyboard gesture (if there is one).
+    pub fn handle_key_up(&mut self, code: Code) -> Option<InnerParamUpdate> {
+        if self.current_gesture == Some(BeginGestureType::Keyboard) && is_adjustment_key(code) {
+            self.finish_gesture()
+        } else {
+            None
+        }
+    }
+
     pub fn handle_pointer_moved(
         &mut self,
         pointer_pos: Point,
@@ -481,6 +592,24 @@ impl VirtualSliderInner {
         }
     }
 
+    /// Adjust the value by the given number of increments.
+    ///
+    /// Stepped parameters are adjusted by whole steps, and continuous
+    /// parameters by `config.increment_step` per increment.
+    ///
+    /// If the slider is currently gesturing, then the gesture will
+    /// be cancelled.
+    pub fn increment(&mut self, increments: f64) -> Option<InnerParamUpdate> {
+        if let Some(stepped_value) = self.stepped_value {
+            let new_val = (f64::from(stepped_value.value) + increments.round()).max(0.0);
+            self.set_stepped_value(new_val as u32)
+        } else {
+            self.set_normal_value(
+                self.normal_value + increments * f64::from(self.config.increment_step),
+            )
+        }
+    }
+
     /// Set the normalized default value of the virtual slider.
     ///
     /// Returns `true` if the default value has changed.
@@ -509,6 +638,21 @@ impl VirtualSliderInner {
     }
 }
 
+fn is_adjustment_key(code: Code) -> bool {
+    matches!(
+        code,
+        Code::ArrowUp
+            | Code::ArrowDown
+            | Code::ArrowLeft
+            | Code::ArrowRight
+            | Code::PageUp
+            | Code::PageDown
+            | Code::Home
+            | Code::End
+            | Code::Delete
+    )
+}
+
 pub fn param_quantized_to_normal(value: u32, num_steps: u32) -> f64 {
     if value == 0 || num_steps < 2 {
         0.0
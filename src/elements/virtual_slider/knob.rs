@@ -1,5 +1,5 @@
 use rootvg::{
-    math::{Rect, Size},
+    math::{Point, Rect, Size},
     PrimitiveGroup,
 };
 use std::{any::Any, rc::Rc};
@@ -7,10 +7,12 @@ use std::{any::Any, rc::Rc};
 use crate::{
     layout::SizeType,
     prelude::{ElementRenderCache, ElementStyle, RenderContext},
+    style::FocusRingStyle,
 };
 
 use super::{
-    UpdateResult, VirtualSlider, VirtualSliderRenderInfo, VirtualSliderRenderer, VirtualSliderState,
+    AutomationInfo, UpdateResult, VirtualSlider, VirtualSliderRenderInfo, VirtualSliderRenderer,
+    VirtualSliderState,
 };
 
 mod angle_range;
@@ -43,6 +45,11 @@ pub struct KnobStyle {
     pub notch: KnobNotchStyle,
     pub markers: KnobMarkersStyle,
     pub angle_range: KnobAngleRange,
+
+    /// The ring drawn around the back of the knob when it has keyboard focus.
+    ///
+    /// By default this is transparent.
+    pub focus_ring: FocusRingStyle,
 }
 
 impl KnobStyle {
@@ -126,6 +133,8 @@ impl Default for KnobMarkersStyle {
 pub struct KnobRenderer {
     #[cfg(feature = "tessellation")]
     cached_arc_marker_front_mesh: arc::CachedKnobMarkerArcFrontMesh,
+    #[cfg(feature = "tessellation")]
+    cached_modulation_meshes: arc::CachedKnobModulationMeshes,
     style: Rc<dyn Any>,
 }
 
@@ -136,6 +145,8 @@ impl VirtualSliderRenderer for KnobRenderer {
         Self {
             #[cfg(feature = "tessellation")]
             cached_arc_marker_front_mesh: Default::default(),
+            #[cfg(feature = "tessellation")]
+            cached_modulation_meshes: Default::default(),
             style,
         }
     }
@@ -165,6 +176,27 @@ impl VirtualSliderRenderer for KnobRenderer {
         }
     }
 
+    fn on_automation_info_update(&mut self, _info: &AutomationInfo) -> bool {
+        true
+    }
+
+    #[allow(unused)]
+    fn modulation_hit_test(
+        &self,
+        point: Point,
+        bounds_size: Size,
+        horizontal: bool,
+        info: &AutomationInfo,
+    ) -> bool {
+        let style = self.style.downcast_ref::<KnobStyle>().unwrap();
+
+        match &style.markers {
+            #[cfg(feature = "tessellation")]
+            KnobMarkersStyle::Arc(s) => s.ring_contains(style.back_bounds(bounds_size), point),
+            _ => false,
+        }
+    }
+
     #[allow(unused_mut)]
     fn render(
         &mut self,
@@ -176,6 +208,17 @@ impl VirtualSliderRenderer for KnobRenderer {
 
         let back_bounds = style.back_bounds(cx.bounds_size);
 
+        // The arc markers show the modulated value with a separate indicator,
+        // so the notch stays at the value of the parameter.
+        let notch_normal_val = match &style.markers {
+            #[cfg(feature = "tessellation")]
+            KnobMarkersStyle::Arc(_) => info.normal_value,
+            _ => info
+                .automation_info
+                .current_normal
+                .unwrap_or(info.normal_value),
+        } as f32;
+
         match &style.back {
             KnobBackStyle::Quad(s) => {
                 primitives.add(s.create_primitive(info.state, back_bounds));
@@ -195,7 +238,7 @@ impl VirtualSliderRenderer for KnobRenderer {
                 );
             }
             #[cfg(feature = "tessellation")]
-            KnobMarkersStyle::Arc(_) => {
+            KnobMarkersStyle::Arc(arc_style) => {
                 let render_cache = cx
                     .render_cache
                     .as_mut()
@@ -215,10 +258,8 @@ impl VirtualSliderRenderer for KnobRenderer {
                         .unwrap(),
                 );
 
-                let normal_val = info
-                    .automation_info
-                    .current_normal
-                    .unwrap_or(info.normal_value) as f32;
+                let normal_val = info.normal_value as f32;
+                let disabled = info.state == VirtualSliderState::Disabled;
 
                 if let Some(front_mesh) = self.cached_arc_marker_front_mesh.create_primitive(
                     cx.class,
@@ -231,20 +272,38 @@ impl VirtualSliderRenderer for KnobRenderer {
                     primitives.set_z_index(1);
                     primitives.add_mesh(front_mesh);
                 }
+
+                for mesh in self.cached_modulation_meshes.create_primitives(
+                    cx.class,
+                    style,
+                    back_bounds,
+                    normal_val,
+                    &info.automation_info.modulation,
+                    disabled,
+                ) {
+                    primitives.set_z_index(1);
+                    primitives.add_mesh(mesh.clone());
+                }
+
+                if let Some(modulated_normal) = info.automation_info.current_normal {
+                    if !disabled && !arc_style.modulation_indicator.is_transparent() {
+                        primitives.set_z_index(2);
+                        primitives.add(arc_style.create_modulation_indicator_primitive(
+                            back_bounds,
+                            modulated_normal as f32,
+                            style.angle_range,
+                        ));
+                    }
+                }
             }
             KnobMarkersStyle::None => {}
         }
 
         match &style.notch {
             KnobNotchStyle::Quad(s) => {
-                let normal_val = info
-                    .automation_info
-                    .current_normal
-                    .unwrap_or(info.normal_value) as f32;
-
                 primitives.set_z_index(1);
                 primitives.add(s.create_primitive(
-                    normal_val,
+                    notch_normal_val,
                     style.angle_range,
                     info.state,
                     back_bounds,
@@ -252,11 +311,6 @@ impl VirtualSliderRenderer for KnobRenderer {
             }
             #[cfg(feature = "mesh")]
             KnobNotchStyle::Line(_) => {
-                let normal_val = info
-                    .automation_info
-                    .current_normal
-                    .unwrap_or(info.normal_value) as f32;
-
                 let render_cache = cx
                     .render_cache
                     .as_mut()
@@ -271,7 +325,7 @@ impl VirtualSliderRenderer for KnobRenderer {
 
                 primitives.set_z_index(1);
                 primitives.add(meshes.transformed_mesh(
-                    normal_val,
+                    notch_normal_val,
                     style.angle_range,
                     info.state,
                     back_bounds,
@@ -279,6 +333,21 @@ impl VirtualSliderRenderer for KnobRenderer {
             }
             KnobNotchStyle::None => {}
         }
+
+        if info.focused && !style.focus_ring.is_transparent() {
+            // Surround the markers as well as the back of the knob.
+            let size = cx.bounds_size.width.min(cx.bounds_size.height);
+            let ring_bounds = Rect::new(
+                Point::new(
+                    (cx.bounds_size.width - size) * 0.5,
+                    (cx.bounds_size.height - size) * 0.5,
+                ),
+                Size::new(size, size),
+            );
+
+            primitives.set_z_index(1);
+            primitives.add(style.focus_ring.create_primitive(ring_bounds));
+        }
     }
 
     /// A unique identifier for the optional global render cache.
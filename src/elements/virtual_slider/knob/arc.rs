@@ -1,8 +1,11 @@
 use std::f32::consts::PI;
+use std::ops::Range;
 
 use rootvg::{
-    math::{Angle, Point, Rect},
+    color::{self, RGBA8},
+    math::{Angle, Point, Rect, Size, Vector},
     mesh::MeshPrimitive,
+    quad::{QuadFlags, QuadPrimitive, Radius},
     tessellation::{
         fill::FillStyle,
         path::{ArcPath, PathBuilder},
@@ -15,10 +18,12 @@ use rootvg::{
 use rootvg::gradient::PackedGradient;
 
 use crate::{
-    elements::virtual_slider::VirtualSliderState,
-    layout::SizeType,
-    style::{Background, ClassID, DisabledBackground},
-    theme::DEFAULT_ACCENT_COLOR,
+    elements::virtual_slider::{ModulationSource, VirtualSliderState},
+    layout::{centered_rect, SizeType},
+    style::{
+        border_radius_only, Background, ClassID, DisabledBackground, DisabledColor, QuadStyle,
+    },
+    theme::{DEFAULT_ACCENT_COLOR, DEFAULT_MODULATION_COLORS},
 };
 
 use super::{KnobAngleRange, KnobMarkersStyle, KnobStyle};
@@ -36,6 +41,33 @@ pub struct KnobMarkersArcStyle {
     pub back_bg: Background,
     pub back_bg_disabled: DisabledBackground,
     pub hide_threshold_normal: f32,
+
+    /// The width of the arcs showing the range of each modulation source.
+    ///
+    /// The arcs are stacked inside of the value arc.
+    ///
+    /// By default this is set to `SizeType::Scale(0.06)`.
+    pub modulation_width: SizeType,
+    /// The space between the value arc and the first modulation arc, and
+    /// between each modulation arc.
+    ///
+    /// By default this is set to `SizeType::Scale(0.03)`.
+    pub modulation_spacing: SizeType,
+    /// The colors used in order for modulation sources that don't define
+    /// their own color.
+    ///
+    /// By default this is set to [`DEFAULT_MODULATION_COLORS`].
+    pub modulation_colors: Vec<RGBA8>,
+    pub modulation_color_disabled: DisabledColor,
+    /// The dot on the value arc showing the current value after modulation
+    /// is applied.
+    ///
+    /// By default this is a white circle.
+    pub modulation_indicator: QuadStyle,
+    /// The diameter of the modulation indicator.
+    ///
+    /// By default this is set to `SizeType::Scale(0.15)`.
+    pub modulation_indicator_size: SizeType,
 }
 
 impl Default for KnobMarkersArcStyle {
@@ -52,11 +84,126 @@ impl Default for KnobMarkersArcStyle {
             back_bg: Background::TRANSPARENT,
             back_bg_disabled: Default::default(),
             hide_threshold_normal: 0.005,
+            modulation_width: SizeType::Scale(0.06),
+            modulation_spacing: SizeType::Scale(0.03),
+            modulation_colors: DEFAULT_MODULATION_COLORS.to_vec(),
+            modulation_color_disabled: Default::default(),
+            modulation_indicator: QuadStyle {
+                bg: Background::Solid(color::WHITE),
+                border: border_radius_only(Radius::CIRCLE),
+                flags: QuadFlags::empty(),
+            },
+            modulation_indicator_size: SizeType::Scale(0.15),
         }
     }
 }
 
 impl KnobMarkersArcStyle {
+    /// The radius of the center of the value arc.
+    fn value_radius(&self, back_size: f32) -> f32 {
+        let width = self.width.points(back_size);
+        let edge_offset = self.edge_offset.points(back_size);
+
+        (back_size * 0.5) + (width * 0.5) + edge_offset
+    }
+
+    /// The radius of the center of the modulation arc at the given index.
+    fn modulation_radius(&self, back_size: f32, index: usize) -> f32 {
+        let width = self.width.points(back_size);
+        let modulation_width = self.modulation_width.points(back_size);
+        let spacing = self.modulation_spacing.points(back_size);
+
+        self.value_radius(back_size)
+            - (width * 0.5)
+            - spacing
+            - (modulation_width * 0.5)
+            - (index as f32 * (modulation_width + spacing))
+    }
+
+    /// Whether or not the given point lies on the ring between the back of
+    /// the knob and the outer edge of the value arc.
+    pub fn ring_contains(&self, back_bounds: Rect, point: Point) -> bool {
+        let back_size = back_bounds.width();
+        let distance = (point - back_bounds.center()).length();
+
+        distance >= back_size * 0.5
+            && distance <= self.value_radius(back_size) + (self.width.points(back_size) * 0.5)
+    }
+
+    /// The color to show the modulation source at the given index in, or
+    /// `None` if there are no colors to choose from.
+    pub fn modulation_color(
+        &self,
+        source: &ModulationSource,
+        index: usize,
+        disabled: bool,
+    ) -> Option<RGBA8> {
+        let color = source.color_or_palette(&self.modulation_colors, index)?;
+
+        Some(if disabled {
+            self.modulation_color_disabled.get(color)
+        } else {
+            color
+        })
+    }
+
+    pub fn create_modulation_primitive(
+        &self,
+        back_bounds: Rect,
+        range: Range<f32>,
+        index: usize,
+        color: RGBA8,
+        angle_range: KnobAngleRange,
+    ) -> Option<MeshPrimitive> {
+        if range.end - range.start < self.hide_threshold_normal {
+            return None;
+        }
+
+        let start_angle =
+            angle_range.min() + (angle_range.span() * range.start) + Angle { radians: PI * 0.5 };
+        let end_angle =
+            angle_range.min() + (angle_range.span() * range.end) + Angle { radians: PI * 0.5 };
+
+        let arc_path = PathBuilder::new()
+            .arc(ArcPath {
+                center: back_bounds.center(),
+                radius: self.modulation_radius(back_bounds.width(), index),
+                start_angle,
+                end_angle,
+            })
+            .build();
+
+        let stroke = Stroke {
+            style: FillStyle::Solid(color.into()),
+            width: self.modulation_width.points(back_bounds.width()),
+            line_cap: self.line_cap,
+            line_join: LineJoin::default(),
+            line_dash: LineDash::default(),
+        };
+
+        Tessellator::new()
+            .stroke(&arc_path, stroke)
+            .into_primitive()
+    }
+
+    pub fn create_modulation_indicator_primitive(
+        &self,
+        back_bounds: Rect,
+        normal_val: f32,
+        angle_range: KnobAngleRange,
+    ) -> QuadPrimitive {
+        let angle =
+            angle_range.min() + (angle_range.span() * normal_val) + Angle { radians: PI * 0.5 };
+        let (y, x) = angle.sin_cos();
+        let radius = self.value_radius(back_bounds.width());
+        let size = self.modulation_indicator_size.points(back_bounds.width());
+
+        self.modulation_indicator.create_primitive(centered_rect(
+            back_bounds.center() + Vector::new(x * radius, y * radius),
+            Size::new(size, size),
+        ))
+    }
+
     pub fn create_back_primitive(
         &self,
         back_size: f32,
@@ -256,3 +403,62 @@ impl CachedKnobMarkerArcFrontMesh {
         self.mesh.clone()
     }
 }
+
+#[derive(Default)]
+pub(super) struct CachedKnobModulationMeshes {
+    meshes: Vec<MeshPrimitive>,
+    class: ClassID,
+    back_bounds: Rect,
+    normal_val: f32,
+    disabled: bool,
+    modulation: Vec<ModulationSource>,
+}
+
+impl CachedKnobModulationMeshes {
+    pub fn create_primitives(
+        &mut self,
+        class: ClassID,
+        style: &KnobStyle,
+        back_bounds: Rect,
+        normal_val: f32,
+        modulation: &[ModulationSource],
+        disabled: bool,
+    ) -> &[MeshPrimitive] {
+        let KnobMarkersStyle::Arc(arc_style) = &style.markers else {
+            return &[];
+        };
+
+        let changed = self.normal_val != normal_val
+            || self.modulation != modulation
+            || self.disabled != disabled
+            || self.class != class
+            || self.back_bounds != back_bounds;
+
+        if changed {
+            self.meshes = modulation
+                .iter()
+                .enumerate()
+                .filter_map(|(i, source)| {
+                    let color = arc_style.modulation_color(source, i, disabled)?;
+                    let range = source.range(f64::from(normal_val));
+
+                    arc_style.create_modulation_primitive(
+                        back_bounds,
+                        range.start as f32..range.end as f32,
+                        i,
+                        color,
+                        style.angle_range,
+                    )
+                })
+                .collect();
+
+            self.class = class;
+            self.back_bounds = back_bounds;
+            self.normal_val = normal_val;
+            self.disabled = disabled;
+            self.modulation = modulation.to_vec();
+        }
+
+        &self.meshes
+    }
+}
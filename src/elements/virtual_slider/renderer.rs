@@ -1,6 +1,9 @@
 use std::{any::Any, rc::Rc};
 
-use rootvg::{math::Size, PrimitiveGroup};
+use rootvg::{
+    math::{Point, Size},
+    PrimitiveGroup,
+};
 
 use crate::{
     element_system::element::{ElementRenderCache, RenderContext},
@@ -43,6 +46,9 @@ pub struct VirtualSliderRenderInfo<'a> {
     pub markers: &'a ParamMarkersConfig,
     pub bipolar: bool,
     pub horizontal: bool,
+    /// Whether or not the element has keyboard focus (and is not currently
+    /// being gestured with the pointer).
+    pub focused: bool,
 }
 
 pub trait VirtualSliderRenderer: 'static {
@@ -75,6 +81,22 @@ pub trait VirtualSliderRenderer: 'static {
         false
     }
 
+    /// Return `true` if the given point (relative to the bounds of the
+    /// element) is on the ring (or track) where modulation is shown.
+    ///
+    /// Dragging on this area edits the depth of the selected modulation
+    /// source instead of the value.
+    #[allow(unused)]
+    fn modulation_hit_test(
+        &self,
+        point: Point,
+        bounds_size: Size,
+        horizontal: bool,
+        info: &AutomationInfo,
+    ) -> bool {
+        false
+    }
+
     #[allow(unused)]
     fn on_animation(
         &mut self,
@@ -11,11 +11,15 @@ use crate::{
     element_system::element::RenderContext,
     layout::{Padding, SizeType},
     prelude::ElementStyle,
-    style::{Background, BorderStyle, DisabledBackground, DisabledColor, QuadStyle},
+    style::{
+        Background, BorderStyle, DisabledBackground, DisabledColor, FocusRingStyle, QuadStyle,
+    },
+    theme::DEFAULT_MODULATION_COLORS,
 };
 
 use super::{
-    UpdateResult, VirtualSlider, VirtualSliderRenderInfo, VirtualSliderRenderer, VirtualSliderState,
+    AutomationInfo, UpdateResult, VirtualSlider, VirtualSliderRenderInfo, VirtualSliderRenderer,
+    VirtualSliderState,
 };
 
 #[derive(Debug, Clone)]
@@ -108,9 +112,49 @@ pub struct SliderStyleModern {
     ///
     /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
     pub quad_flags: QuadFlags,
+
+    /// The ring drawn around the slider when it has keyboard focus.
+    ///
+    /// By default this is transparent.
+    pub focus_ring: FocusRingStyle,
+
+    /// The width of the bars showing the range of each modulation source.
+    ///
+    /// The bars are stacked inwards from the right edge of the fill area (or
+    /// the bottom edge if the slider is horizontal).
+    ///
+    /// By default this is set to `3.0`.
+    pub modulation_width: f32,
+    /// The space between each modulation bar.
+    ///
+    /// By default this is set to `1.0`.
+    pub modulation_spacing: f32,
+    /// The colors used in order for modulation sources that don't define
+    /// their own color.
+    ///
+    /// By default this is set to [`DEFAULT_MODULATION_COLORS`].
+    pub modulation_colors: Vec<RGBA8>,
+    pub modulation_color_disabled: DisabledColor,
+    /// The line across the slider showing the current value after modulation
+    /// is applied.
+    ///
+    /// By default this is transparent.
+    pub modulation_indicator: QuadStyle,
+    /// The thickness of the modulation indicator.
+    ///
+    /// By default this is set to `2.0`.
+    pub modulation_indicator_width: f32,
 }
 
 impl SliderStyleModern {
+    fn modulation_bar_quad(&self, color: RGBA8) -> QuadStyle {
+        QuadStyle::new_with_flags(
+            Background::Solid(color),
+            BorderStyle::default(),
+            self.quad_flags,
+        )
+    }
+
     fn state(&self, state: VirtualSliderState) -> SliderStyleModernState {
         match state {
             VirtualSliderState::Gesturing => SliderStyleModernState {
@@ -324,6 +368,13 @@ impl Default for SliderStyleModern {
             fill_hide_threshold_normal: 0.005,
             fill_mode: SliderFillMode::default(),
             quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            focus_ring: FocusRingStyle::default(),
+            modulation_width: 3.0,
+            modulation_spacing: 1.0,
+            modulation_colors: DEFAULT_MODULATION_COLORS.to_vec(),
+            modulation_color_disabled: Default::default(),
+            modulation_indicator: QuadStyle::TRANSPARENT,
+            modulation_indicator_width: 2.0,
         }
     }
 }
@@ -362,6 +413,37 @@ impl VirtualSliderRenderer for SliderRenderer {
         }
     }
 
+    fn on_automation_info_update(&mut self, _info: &AutomationInfo) -> bool {
+        true
+    }
+
+    fn modulation_hit_test(
+        &self,
+        point: Point,
+        bounds_size: Size,
+        horizontal: bool,
+        info: &AutomationInfo,
+    ) -> bool {
+        let style = self.style.downcast_ref::<SliderStyle>().unwrap();
+
+        match style {
+            SliderStyle::Modern(style) => {
+                // Convert to the coordinates of a vertical slider.
+                let (x, width) = if horizontal {
+                    (point.y, bounds_size.height)
+                } else {
+                    (point.x, bounds_size.width)
+                };
+
+                let num_bars = info.modulation.len().max(1) as f32;
+                let bars_width = (style.modulation_width * num_bars)
+                    + (style.modulation_spacing * (num_bars - 1.0));
+
+                x >= width - style.fill_padding.right - bars_width && x <= width
+            }
+        }
+    }
+
     fn render(
         &mut self,
         info: VirtualSliderRenderInfo<'_>,
@@ -373,6 +455,7 @@ impl VirtualSliderRenderer for SliderRenderer {
         match style {
             SliderStyle::Modern(style) => {
                 let style_state = style.state(info.state);
+                let focused = info.focused;
 
                 if info.horizontal {
                     let to_horizontal = |r: Rect| -> Rect {
@@ -382,12 +465,11 @@ impl VirtualSliderRenderer for SliderRenderer {
                         )
                     };
 
-                    let r = ModerStyleRects::new(
-                        Size::new(cx.bounds_size.height, cx.bounds_size.width),
-                        style,
-                        &style_state,
-                        info,
-                    );
+                    let vertical_size = Size::new(cx.bounds_size.height, cx.bounds_size.width);
+
+                    let m =
+                        ModerStyleModulationRects::new(vertical_size, style, &style_state, &info);
+                    let r = ModerStyleRects::new(vertical_size, style, &style_state, info);
 
                     if r.back {
                         primitives.add(
@@ -410,7 +492,27 @@ impl VirtualSliderRenderer for SliderRenderer {
                         primitives.set_z_index(2);
                         primitives.add(style_state.handle_quad.create_primitive(handle_rect));
                     }
+
+                    for (bar_rect, color) in m.bars {
+                        primitives.set_z_index(2);
+                        primitives.add(
+                            style
+                                .modulation_bar_quad(color)
+                                .create_primitive(to_horizontal(bar_rect)),
+                        );
+                    }
+
+                    if let Some(indicator_rect) = m.indicator {
+                        primitives.set_z_index(2);
+                        primitives.add(
+                            style
+                                .modulation_indicator
+                                .create_primitive(to_horizontal(indicator_rect)),
+                        );
+                    }
                 } else {
+                    let m =
+                        ModerStyleModulationRects::new(cx.bounds_size, style, &style_state, &info);
                     let r = ModerStyleRects::new(cx.bounds_size, style, &style_state, info);
 
                     if r.back {
@@ -430,6 +532,25 @@ impl VirtualSliderRenderer for SliderRenderer {
                         primitives.set_z_index(2);
                         primitives.add(style_state.handle_quad.create_primitive(handle_rect));
                     }
+
+                    for (bar_rect, color) in m.bars {
+                        primitives.set_z_index(2);
+                        primitives.add(style.modulation_bar_quad(color).create_primitive(bar_rect));
+                    }
+
+                    if let Some(indicator_rect) = m.indicator {
+                        primitives.set_z_index(2);
+                        primitives.add(style.modulation_indicator.create_primitive(indicator_rect));
+                    }
+                }
+
+                if focused && !style.focus_ring.is_transparent() {
+                    primitives.set_z_index(2);
+                    primitives.add(
+                        style
+                            .focus_ring
+                            .create_primitive(Rect::from_size(cx.bounds_size)),
+                    );
                 }
             }
         }
@@ -609,3 +730,76 @@ impl ModerStyleRects {
         }
     }
 }
+
+struct ModerStyleModulationRects {
+    bars: Vec<(Rect, RGBA8)>,
+    indicator: Option<Rect>,
+}
+
+impl ModerStyleModulationRects {
+    fn new(
+        bounds_size: Size,
+        style: &SliderStyleModern,
+        style_state: &SliderStyleModernState,
+        info: &VirtualSliderRenderInfo<'_>,
+    ) -> Self {
+        let disabled = info.state == VirtualSliderState::Disabled;
+        let padding = style_state.fill_padding;
+        let span = bounds_size.height - padding.top - padding.bottom;
+        let normal_to_y =
+            |normal: f64| bounds_size.height - padding.bottom - (span * normal as f32);
+
+        let bars = info
+            .automation_info
+            .modulation
+            .iter()
+            .enumerate()
+            .filter_map(|(i, source)| {
+                let range = source.range(info.normal_value);
+                if range.end - range.start < style.fill_hide_threshold_normal {
+                    return None;
+                }
+
+                let color = source.color_or_palette(&style.modulation_colors, i)?;
+                let color = if disabled {
+                    style.modulation_color_disabled.get(color)
+                } else {
+                    color
+                };
+
+                let x = bounds_size.width
+                    - padding.right
+                    - style.modulation_width
+                    - (i as f32 * (style.modulation_width + style.modulation_spacing));
+                let y = normal_to_y(range.end);
+
+                Some((
+                    Rect::new(
+                        Point::new(x, y),
+                        Size::new(style.modulation_width, normal_to_y(range.start) - y),
+                    ),
+                    color,
+                ))
+            })
+            .collect();
+
+        let indicator = info
+            .automation_info
+            .current_normal
+            .filter(|_| !disabled && !style.modulation_indicator.is_transparent())
+            .map(|normal| {
+                Rect::new(
+                    Point::new(
+                        padding.left,
+                        normal_to_y(normal) - (style.modulation_indicator_width * 0.5),
+                    ),
+                    Size::new(
+                        bounds_size.width - padding.left - padding.right,
+                        style.modulation_indicator_width,
+                    ),
+                )
+            });
+
+        Self { bars, indicator }
+    }
+}
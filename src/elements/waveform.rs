@@ -0,0 +1,1329 @@
+use std::cell::{Ref, RefCell};
+use std::ops::Range;
+use std::rc::Rc;
+
+use derive_where::derive_where;
+use rootvg::{
+    mesh::MeshPrimitive,
+    tessellation::{
+        fill::FillStyle,
+        path::PathBuilder,
+        stroke::{LineCap, LineDash, LineJoin, Stroke},
+        Tessellator,
+    },
+};
+
+use crate::derive::*;
+use crate::prelude::*;
+
+/// The number of blocks of one level of a [`WaveformPeaks`] pyramid that
+/// make up one block of the next level.
+const PEAK_LEVEL_FACTOR: usize = 4;
+
+struct PeakLevel {
+    block_size: usize,
+    /// The minimum and maximum sample of each block.
+    peaks: Vec<(f32, f32)>,
+}
+
+struct ChannelPeaks {
+    samples: Vec<f32>,
+    /// Sorted from the smallest block size to the largest.
+    levels: Vec<PeakLevel>,
+}
+
+impl ChannelPeaks {
+    fn new(samples: Vec<f32>) -> Self {
+        let mut levels: Vec<PeakLevel> = Vec::new();
+
+        loop {
+            let (block_size, peaks) = match levels.last() {
+                None => (
+                    PEAK_LEVEL_FACTOR,
+                    samples
+                        .chunks(PEAK_LEVEL_FACTOR)
+                        .map(min_max_of_samples)
+                        .collect::<Vec<_>>(),
+                ),
+                Some(prev) if prev.peaks.len() > 1 => (
+                    prev.block_size * PEAK_LEVEL_FACTOR,
+                    prev.peaks
+                        .chunks(PEAK_LEVEL_FACTOR)
+                        .map(min_max_of_peaks)
+                        .collect::<Vec<_>>(),
+                ),
+                Some(_) => break,
+            };
+
+            if peaks.is_empty() {
+                break;
+            }
+
+            levels.push(PeakLevel { block_size, peaks });
+        }
+
+        Self { samples, levels }
+    }
+
+    fn min_max(&self, start: usize, end: usize) -> (f32, f32) {
+        let span = end - start;
+
+        // Use the coarsest level whose blocks still fit in the span, so that
+        // only a handful of blocks need to be looked at no matter the zoom.
+        match self.levels.iter().rev().find(|l| l.block_size <= span) {
+            Some(level) => {
+                let first_block = start / level.block_size;
+                let last_block = ((end - 1) / level.block_size).min(level.peaks.len() - 1);
+
+                min_max_of_peaks(&level.peaks[first_block..=last_block])
+            }
+            None => min_max_of_samples(&self.samples[start..end]),
+        }
+    }
+}
+
+fn min_max_of_samples(samples: &[f32]) -> (f32, f32) {
+    samples
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), s| {
+            (min.min(*s), max.max(*s))
+        })
+}
+
+fn min_max_of_peaks(peaks: &[(f32, f32)]) -> (f32, f32) {
+    peaks
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), p| {
+            (min.min(p.0), max.max(p.1))
+        })
+}
+
+/// The samples of an audio buffer along with precomputed min/max peaks at
+/// multiple resolutions, used by a [`Waveform`] element.
+///
+/// Computing the peaks takes time proportional to the length of the buffer,
+/// so this should be created once (ideally on a background thread) and then
+/// shared with [`Rc`].
+pub struct WaveformPeaks {
+    channels: Vec<ChannelPeaks>,
+    num_frames: usize,
+}
+
+impl WaveformPeaks {
+    /// Create the peaks from one buffer of samples per channel.
+    pub fn new<C: AsRef<[f32]>>(channels: &[C]) -> Self {
+        let channels: Vec<ChannelPeaks> = channels
+            .iter()
+            .map(|c| ChannelPeaks::new(c.as_ref().to_vec()))
+            .collect();
+        let num_frames = channels.iter().map(|c| c.samples.len()).max().unwrap_or(0);
+
+        Self {
+            channels,
+            num_frames,
+        }
+    }
+
+    /// Create the peaks from a buffer of interleaved samples.
+    pub fn from_interleaved(samples: &[f32], num_channels: usize) -> Self {
+        let num_channels = num_channels.max(1);
+
+        let channels: Vec<Vec<f32>> = (0..num_channels)
+            .map(|ch| {
+                samples
+                    .iter()
+                    .skip(ch)
+                    .step_by(num_channels)
+                    .copied()
+                    .collect()
+            })
+            .collect();
+
+        Self::new(&channels)
+    }
+
+    pub fn num_channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// The length of the longest channel in frames.
+    pub fn num_frames(&self) -> usize {
+        self.num_frames
+    }
+
+    /// The sample at the given frame.
+    pub fn sample(&self, channel: usize, frame: usize) -> Option<f32> {
+        self.channels.get(channel)?.samples.get(frame).copied()
+    }
+
+    /// The minimum and maximum sample in the given range of frames.
+    ///
+    /// Blocks of samples at the edges of the range may be included, so the
+    /// result can be slightly larger than the exact range.
+    ///
+    /// Returns `None` if the range does not contain any samples.
+    pub fn min_max(&self, channel: usize, frames: Range<f64>) -> Option<(f32, f32)> {
+        let channel = self.channels.get(channel)?;
+
+        let start = frames.start.max(0.0).floor() as usize;
+        let end = (frames.end.max(0.0).ceil() as usize).min(channel.samples.len());
+
+        (start < end).then(|| channel.min_max(start, end))
+    }
+}
+
+/// The part of the audio buffer that is visible in a [`Waveform`] element.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct WaveformView {
+    /// The frame at the left edge of the element.
+    pub start_frame: f64,
+    /// The number of frames shown per logical point. Values less than
+    /// `1.0` zoom in far enough to see individual samples.
+    pub frames_per_point: f64,
+}
+
+impl WaveformView {
+    pub const fn new(start_frame: f64, frames_per_point: f64) -> Self {
+        Self {
+            start_frame,
+            frames_per_point,
+        }
+    }
+
+    /// Convert a frame to an x coordinate relative to the element.
+    pub fn frame_to_x(&self, frame: f64) -> f32 {
+        ((frame - self.start_frame) / self.frames_per_point) as f32
+    }
+
+    /// Convert an x coordinate relative to the element to a frame.
+    pub fn x_to_frame(&self, x: f32) -> f64 {
+        self.start_frame + (f64::from(x) * self.frames_per_point)
+    }
+
+    /// Returns the view clamped so that it stays within the audio buffer.
+    fn clamped(&self, num_frames: usize, width: f32, config: &WaveformConfig) -> Self {
+        let width = f64::from(width.max(1.0));
+        let max_frames_per_point = (num_frames as f64 / width).max(config.min_frames_per_point);
+
+        let frames_per_point = self
+            .frames_per_point
+            .clamp(config.min_frames_per_point, max_frames_per_point);
+        let max_start_frame = (num_frames as f64 - (width * frames_per_point)).max(0.0);
+
+        Self {
+            start_frame: self.start_frame.clamp(0.0, max_start_frame),
+            frames_per_point,
+        }
+    }
+
+    fn fit(num_frames: usize, width: f32, config: &WaveformConfig) -> Self {
+        Self::new(0.0, num_frames as f64 / f64::from(width.max(1.0)))
+            .clamped(num_frames, width, config)
+    }
+}
+
+/// The behavior of a [`Waveform`] element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaveformConfig {
+    /// The modifier keys that make the scroll wheel zoom instead of scroll.
+    /// Most platforms send pinch gestures on a touchpad as the scroll wheel
+    /// with the control key held down.
+    ///
+    /// By default this is set to `Modifiers::CONTROL`.
+    pub zoom_modifier: Modifiers,
+    /// How much the view is zoomed per point of scrolling. A value of
+    /// `0.01` zooms by a factor of two every 100 points.
+    ///
+    /// By default this is set to `0.01`.
+    pub zoom_per_point: f64,
+    /// The number of points to scroll per line when the scroll wheel
+    /// reports lines instead of points.
+    ///
+    /// By default this is set to `24.0`.
+    pub points_per_line: f32,
+    /// The smallest number of frames per logical point (how far the user
+    /// can zoom in).
+    ///
+    /// By default this is set to `1.0 / 32.0`.
+    pub min_frames_per_point: f64,
+    /// How close (in points) the pointer needs to be to the edge of a
+    /// region to drag it.
+    ///
+    /// By default this is set to `4.0`.
+    pub region_edge_grab_width: f32,
+}
+
+impl Default for WaveformConfig {
+    fn default() -> Self {
+        Self {
+            zoom_modifier: Modifiers::CONTROL,
+            zoom_per_point: 0.01,
+            points_per_line: 24.0,
+            min_frames_per_point: 1.0 / 32.0,
+            region_edge_grab_width: 4.0,
+        }
+    }
+}
+
+/// A region of a [`Waveform`] that can be edited by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WaveformRegionKind {
+    /// The selection, which the user can create by dragging across the
+    /// waveform.
+    Selection,
+    /// The loop region. This is set by the application, but the user can
+    /// drag its edges.
+    Loop,
+}
+
+/// Sent when the user edits a region of a [`Waveform`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveformRegionUpdate {
+    pub kind: WaveformRegionKind,
+    /// The new range in frames, or `None` if the region was cleared.
+    pub range: Option<Range<f64>>,
+    pub gesture_state: GestureState,
+}
+
+/// The style of a [`Waveform`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveformStyle {
+    /// The background quad.
+    ///
+    /// By default this is set to `QuadStyle::TRANSPARENT`.
+    pub back_quad: QuadStyle,
+
+    /// The color of the waveform.
+    pub wave_color: RGBA8,
+    /// The thickness of the waveform line.
+    ///
+    /// By default this is set to `1.0`.
+    pub wave_line_width: f32,
+    /// The color of the line at zero in each channel.
+    ///
+    /// By default this is transparent.
+    pub center_line_color: RGBA8,
+    /// The space between each channel.
+    ///
+    /// By default this is set to `2.0`.
+    pub channel_spacing: f32,
+
+    /// The background of the selection.
+    pub selection_color: RGBA8,
+    /// The color of the edges of the selection.
+    pub selection_edge_color: RGBA8,
+    /// The background of the loop region.
+    pub loop_color: RGBA8,
+    /// The color of the edges of the loop region.
+    pub loop_edge_color: RGBA8,
+    /// The thickness of the edges of the regions.
+    ///
+    /// By default this is set to `1.0`.
+    pub region_edge_width: f32,
+
+    /// The color of the playhead.
+    pub playhead_color: RGBA8,
+    /// The thickness of the playhead.
+    ///
+    /// By default this is set to `1.0`.
+    pub playhead_width: f32,
+
+    /// Additional flags for the quad primitives.
+    ///
+    /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
+    pub quad_flags: QuadFlags,
+}
+
+impl WaveformStyle {
+    fn quad(&self, color: RGBA8) -> QuadStyle {
+        QuadStyle::new_with_flags(
+            Background::Solid(color),
+            BorderStyle::default(),
+            self.quad_flags,
+        )
+    }
+}
+
+impl Default for WaveformStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle::TRANSPARENT,
+            wave_color: RGBA8::new(120, 180, 220, 255),
+            wave_line_width: 1.0,
+            center_line_color: color::TRANSPARENT,
+            channel_spacing: 2.0,
+            selection_color: gray_a(255, 40),
+            selection_edge_color: gray_a(255, 140),
+            loop_color: RGBA8::new(120, 200, 120, 30),
+            loop_edge_color: RGBA8::new(120, 200, 120, 160),
+            region_edge_width: 1.0,
+            playhead_color: color::WHITE,
+            playhead_width: 1.0,
+            quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        }
+    }
+}
+
+impl ElementStyle for WaveformStyle {
+    const ID: &'static str = "wvfrm";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            wave_color: RGBA8::new(40, 100, 160, 255),
+            selection_color: gray_a(0, 30),
+            selection_edge_color: gray_a(0, 120),
+            playhead_color: color::BLACK,
+            ..Default::default()
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[derive_where(Default)]
+pub struct WaveformBuilder<A: Clone + 'static> {
+    pub on_region_changed: Option<Box<dyn FnMut(WaveformRegionUpdate) -> A>>,
+    pub on_view_changed: Option<Box<dyn FnMut(WaveformView) -> A>>,
+    pub peaks: Option<Rc<WaveformPeaks>>,
+    pub selection: Option<Range<f64>>,
+    pub loop_region: Option<Range<f64>>,
+    pub playhead: Option<f64>,
+    pub config: WaveformConfig,
+}
+
+impl<A: Clone + 'static> WaveformBuilder<A> {
+    /// Called when the user drags the selection or the edges of the loop
+    /// region.
+    pub fn on_region_changed<F: FnMut(WaveformRegionUpdate) -> A + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.on_region_changed = Some(Box::new(f));
+        self
+    }
+
+    /// Called when the user scrolls or zooms the view.
+    pub fn on_view_changed<F: FnMut(WaveformView) -> A + 'static>(mut self, f: F) -> Self {
+        self.on_view_changed = Some(Box::new(f));
+        self
+    }
+
+    pub fn peaks(mut self, peaks: Rc<WaveformPeaks>) -> Self {
+        self.peaks = Some(peaks);
+        self
+    }
+
+    /// The selection in frames.
+    pub fn selection(mut self, selection: Option<Range<f64>>) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// The loop region in frames.
+    pub fn loop_region(mut self, loop_region: Option<Range<f64>>) -> Self {
+        self.loop_region = loop_region;
+        self
+    }
+
+    /// The position of the playhead in frames.
+    pub const fn playhead(mut self, playhead: Option<f64>) -> Self {
+        self.playhead = playhead;
+        self
+    }
+
+    pub const fn config(mut self, config: WaveformConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> Waveform {
+        let WaveformBuilder {
+            on_region_changed,
+            on_view_changed,
+            peaks,
+            selection,
+            loop_region,
+            playhead,
+            config,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+        } = self;
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            peaks,
+            view: WaveformView::default(),
+            fit_view: true,
+            selection,
+            loop_region,
+            playhead,
+        }));
+
+        let el = ElementBuilder::new(WaveformElement {
+            shared_state: Rc::clone(&shared_state),
+            on_region_changed,
+            on_view_changed,
+            config,
+            region_drag: None,
+            cached_meshes: CachedWaveformMeshes::default(),
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE
+                | ElementFlags::LISTENS_TO_INIT,
+        )
+        .build(window_cx);
+
+        Waveform { el, shared_state }
+    }
+}
+
+struct RegionDrag {
+    kind: WaveformRegionKind,
+    /// The edge of the region that stays in place.
+    anchor_frame: f64,
+}
+
+#[derive(Default)]
+struct CachedWaveformMeshes {
+    meshes: Vec<MeshPrimitive>,
+    peaks: Option<Rc<WaveformPeaks>>,
+    view: WaveformView,
+    bounds_size: Size,
+    class: ClassID,
+}
+
+impl CachedWaveformMeshes {
+    fn create_primitives(
+        &mut self,
+        class: ClassID,
+        style: &WaveformStyle,
+        peaks: Option<&Rc<WaveformPeaks>>,
+        view: WaveformView,
+        bounds_size: Size,
+    ) -> &[MeshPrimitive] {
+        let peaks_changed = match (&self.peaks, peaks) {
+            (Some(a), Some(b)) => !Rc::ptr_eq(a, b),
+            (None, None) => false,
+            _ => true,
+        };
+
+        let changed = peaks_changed
+            || self.view != view
+            || self.bounds_size != bounds_size
+            || self.class != class;
+
+        if changed {
+            self.meshes = peaks
+                .map(|peaks| {
+                    (0..peaks.num_channels())
+                        .filter_map(|channel| {
+                            let lane =
+                                channel_lane(bounds_size, style, channel, peaks.num_channels());
+                            create_channel_mesh(peaks, channel, lane, view, style)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            self.peaks = peaks.cloned();
+            self.view = view;
+            self.bounds_size = bounds_size;
+            self.class = class;
+        }
+
+        &self.meshes
+    }
+
+    fn clear(&mut self) {
+        self.meshes.clear();
+        self.peaks = None;
+    }
+}
+
+/// The area of the element that the given channel is drawn in.
+fn channel_lane(
+    bounds_size: Size,
+    style: &WaveformStyle,
+    channel: usize,
+    num_channels: usize,
+) -> Rect {
+    let n = num_channels.max(1) as f32;
+    let height = ((bounds_size.height - (style.channel_spacing * (n - 1.0))) / n).max(0.0);
+
+    Rect::new(
+        Point::new(0.0, channel as f32 * (height + style.channel_spacing)),
+        Size::new(bounds_size.width, height),
+    )
+}
+
+fn create_channel_mesh(
+    peaks: &WaveformPeaks,
+    channel: usize,
+    lane: Rect,
+    view: WaveformView,
+    style: &WaveformStyle,
+) -> Option<MeshPrimitive> {
+    if lane.width() <= 0.0 || lane.height() <= 0.0 || view.frames_per_point <= 0.0 {
+        return None;
+    }
+
+    let center_y = lane.center().y;
+    let half_height = lane.height() * 0.5;
+    let sample_to_y = |s: f32| center_y - (s.clamp(-1.0, 1.0) * half_height);
+
+    let mut path = PathBuilder::new();
+    let mut started = false;
+    let mut add_point = |path: PathBuilder, point: Point| {
+        if started {
+            path.line_to(point)
+        } else {
+            started = true;
+            path.move_to(point)
+        }
+    };
+
+    if view.frames_per_point >= 1.0 {
+        // Zoomed out, so draw the range of samples under each column as a
+        // vertical line, zig-zagging between columns.
+        let num_columns = lane.width().ceil() as usize;
+        for column in 0..num_columns {
+            let x = column as f32;
+            let Some((min, max)) =
+                peaks.min_max(channel, view.x_to_frame(x)..view.x_to_frame(x + 1.0))
+            else {
+                continue;
+            };
+
+            let x = x + 0.5;
+            path = add_point(path, Point::new(x, sample_to_y(max)));
+            path = add_point(path, Point::new(x, sample_to_y(min)));
+        }
+    } else {
+        // Zoomed in far enough to draw a line through each sample.
+        let first_frame = view.start_frame.ceil() as usize;
+        let last_frame = view.x_to_frame(lane.width()).floor() as usize;
+
+        for frame in first_frame..=last_frame {
+            let Some(sample) = peaks.sample(channel, frame) else {
+                break;
+            };
+
+            path = add_point(
+                path,
+                Point::new(view.frame_to_x(frame as f64), sample_to_y(sample)),
+            );
+        }
+    }
+
+    if !started {
+        return None;
+    }
+
+    let stroke = Stroke {
+        style: FillStyle::Solid(style.wave_color.into()),
+        width: style.wave_line_width,
+        line_cap: LineCap::Butt,
+        line_join: LineJoin::default(),
+        line_dash: LineDash::default(),
+    };
+
+    Tessellator::new()
+        .stroke(&path.build(), stroke)
+        .into_primitive()
+}
+
+struct WaveformElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    on_region_changed: Option<Box<dyn FnMut(WaveformRegionUpdate) -> A>>,
+    on_view_changed: Option<Box<dyn FnMut(WaveformView) -> A>>,
+    config: WaveformConfig,
+    region_drag: Option<RegionDrag>,
+    cached_meshes: CachedWaveformMeshes,
+}
+
+impl<A: Clone + 'static> WaveformElement<A> {
+    /// Returns the region and the frame of its opposite edge if the given
+    /// x coordinate is over the edge of a region.
+    fn region_edge_at(&self, shared_state: &SharedState, x: f32) -> Option<RegionDrag> {
+        let view = shared_state.view;
+
+        [
+            (WaveformRegionKind::Selection, &shared_state.selection),
+            (WaveformRegionKind::Loop, &shared_state.loop_region),
+        ]
+        .into_iter()
+        .find_map(|(kind, range)| {
+            let range = range.as_ref()?;
+
+            if (view.frame_to_x(range.start) - x).abs() <= self.config.region_edge_grab_width {
+                Some(RegionDrag {
+                    kind,
+                    anchor_frame: range.end,
+                })
+            } else if (view.frame_to_x(range.end) - x).abs() <= self.config.region_edge_grab_width {
+                Some(RegionDrag {
+                    kind,
+                    anchor_frame: range.start,
+                })
+            } else {
+                None
+            }
+        })
+    }
+
+    fn send_region_update(
+        &mut self,
+        kind: WaveformRegionKind,
+        range: Option<Range<f64>>,
+        gesture_state: GestureState,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        if let Some(f) = self.on_region_changed.as_mut() {
+            cx.send_action((f)(WaveformRegionUpdate {
+                kind,
+                range,
+                gesture_state,
+            }))
+            .unwrap();
+        }
+    }
+
+    fn finish_region_drag(&mut self, cx: &mut ElementContext<'_, A>) {
+        let Some(drag) = self.region_drag.take() else {
+            return;
+        };
+
+        let range = {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+            let region = shared_state.region_mut(drag.kind);
+
+            // A click without dragging clears the selection.
+            if drag.kind == WaveformRegionKind::Selection
+                && region.as_ref().is_some_and(|r| r.is_empty())
+            {
+                *region = None;
+                cx.request_repaint();
+            }
+
+            region.clone()
+        };
+
+        self.send_region_update(drag.kind, range, GestureState::GestureFinished, cx);
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for WaveformElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::Init | ElementEvent::CustomStateChanged | ElementEvent::SizeChanged => {
+                let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                shared_state.sync_view(cx.rect().width(), &self.config);
+
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                self.cached_meshes.clear();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                let rect = cx.rect();
+                let x = position.x - rect.min_x();
+
+                if let Some(drag) = &self.region_drag {
+                    let kind = drag.kind;
+
+                    let range = {
+                        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                        let num_frames = shared_state.num_frames() as f64;
+                        let frame = shared_state.view.x_to_frame(x).clamp(0.0, num_frames);
+
+                        let range = if frame < drag.anchor_frame {
+                            frame..drag.anchor_frame
+                        } else {
+                            drag.anchor_frame..frame
+                        };
+
+                        let region = shared_state.region_mut(kind);
+                        if region.as_ref() == Some(&range) {
+                            None
+                        } else {
+                            *region = Some(range.clone());
+                            Some(range)
+                        }
+                    };
+
+                    if let Some(range) = range {
+                        cx.request_repaint();
+                        self.send_region_update(kind, Some(range), GestureState::Gesturing, cx);
+                    }
+
+                    cx.cursor_icon = CursorIcon::EwResize;
+                    return EventCaptureStatus::Captured;
+                }
+
+                if rect.contains(position) {
+                    let shared_state = RefCell::borrow(&self.shared_state);
+                    if self.region_edge_at(&shared_state, x).is_some() {
+                        cx.cursor_icon = CursorIcon::EwResize;
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                let rect = cx.rect();
+                if button != PointerButton::Primary || !rect.contains(position) {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let x = position.x - rect.min_x();
+
+                let (drag, range) = {
+                    let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                    if shared_state.peaks.is_none() {
+                        return EventCaptureStatus::Captured;
+                    }
+
+                    match self.region_edge_at(&shared_state, x) {
+                        Some(drag) => {
+                            let range = shared_state.region_mut(drag.kind).clone();
+                            (drag, range)
+                        }
+                        None => {
+                            // Start a new selection.
+                            let num_frames = shared_state.num_frames() as f64;
+                            let frame = shared_state.view.x_to_frame(x).clamp(0.0, num_frames);
+
+                            shared_state.selection = Some(frame..frame);
+                            cx.request_repaint();
+
+                            (
+                                RegionDrag {
+                                    kind: WaveformRegionKind::Selection,
+                                    anchor_frame: frame,
+                                },
+                                shared_state.selection.clone(),
+                            )
+                        }
+                    }
+                };
+
+                let kind = drag.kind;
+                self.region_drag = Some(drag);
+                cx.steal_temporary_focus();
+                cx.cursor_icon = CursorIcon::EwResize;
+
+                self.send_region_update(kind, range, GestureState::GestureStarted, cx);
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased { button, .. }) => {
+                if button == PointerButton::Primary && self.region_drag.is_some() {
+                    self.finish_region_drag(cx);
+                    cx.release_focus();
+
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Focus(false) => {
+                self.finish_region_drag(cx);
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheel {
+                position,
+                delta_type,
+                modifiers,
+                ..
+            }) => {
+                let rect = cx.rect();
+                if !rect.contains(position) {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let delta = delta_type.points(self.config.points_per_line, rect.width());
+
+                let new_view = {
+                    let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                    if shared_state.peaks.is_none() {
+                        return EventCaptureStatus::NotCaptured;
+                    }
+
+                    let view = shared_state.view;
+
+                    let new_view = if modifiers.contains(self.config.zoom_modifier)
+                        && !self.config.zoom_modifier.is_empty()
+                    {
+                        // Zoom around the frame under the pointer.
+                        let x = position.x - rect.min_x();
+                        let pointer_frame = view.x_to_frame(x);
+                        let frames_per_point = view.frames_per_point
+                            * 2.0f64.powf(f64::from(delta.y) * self.config.zoom_per_point);
+
+                        WaveformView::new(
+                            pointer_frame - (f64::from(x) * frames_per_point),
+                            frames_per_point,
+                        )
+                    } else {
+                        let delta = if delta.x != 0.0 { delta.x } else { delta.y };
+
+                        WaveformView::new(
+                            view.start_frame + (f64::from(delta) * view.frames_per_point),
+                            view.frames_per_point,
+                        )
+                    };
+
+                    let new_view =
+                        new_view.clamped(shared_state.num_frames(), rect.width(), &self.config);
+
+                    if new_view == view {
+                        None
+                    } else {
+                        shared_state.view = new_view;
+                        shared_state.fit_view = false;
+                        Some(new_view)
+                    }
+                };
+
+                if let Some(new_view) = new_view {
+                    cx.request_repaint();
+
+                    if let Some(f) = self.on_view_changed.as_mut() {
+                        cx.send_action((f)(new_view)).unwrap();
+                    }
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<WaveformStyle>(cx.class);
+
+        let bounds_rect = Rect::from_size(cx.bounds_size);
+        let view = shared_state.view;
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(style.back_quad.create_primitive(bounds_rect));
+        }
+
+        let region_rect = |range: &Range<f64>| -> Option<Rect> {
+            let x_start = view.frame_to_x(range.start).max(0.0);
+            let x_end = view.frame_to_x(range.end).min(cx.bounds_size.width);
+
+            (x_end > x_start).then(|| {
+                Rect::new(
+                    Point::new(x_start, 0.0),
+                    Size::new(x_end - x_start, cx.bounds_size.height),
+                )
+            })
+        };
+        let line_rect = |frame: f64, width: f32| -> Option<Rect> {
+            let x = view.frame_to_x(frame);
+
+            (x >= 0.0 && x <= cx.bounds_size.width).then(|| {
+                Rect::new(
+                    Point::new(x - (width * 0.5), 0.0),
+                    Size::new(width, cx.bounds_size.height),
+                )
+            })
+        };
+
+        let regions = [
+            (
+                &shared_state.loop_region,
+                style.loop_color,
+                style.loop_edge_color,
+            ),
+            (
+                &shared_state.selection,
+                style.selection_color,
+                style.selection_edge_color,
+            ),
+        ];
+
+        for (range, color, edge_color) in regions {
+            let Some(range) = range else {
+                continue;
+            };
+
+            if let Some(rect) = region_rect(range) {
+                primitives.set_z_index(1);
+                primitives.add(style.quad(color).create_primitive(rect));
+            }
+
+            for frame in [range.start, range.end] {
+                if let Some(rect) = line_rect(frame, style.region_edge_width) {
+                    primitives.set_z_index(3);
+                    primitives.add(style.quad(edge_color).create_primitive(rect));
+                }
+            }
+        }
+
+        if let Some(peaks) = &shared_state.peaks {
+            if style.center_line_color != color::TRANSPARENT {
+                for channel in 0..peaks.num_channels() {
+                    let lane = channel_lane(cx.bounds_size, style, channel, peaks.num_channels());
+
+                    primitives.set_z_index(2);
+                    primitives.add(style.quad(style.center_line_color).create_primitive(
+                        Rect::new(
+                            Point::new(0.0, lane.center().y - 0.5),
+                            Size::new(lane.width(), 1.0),
+                        ),
+                    ));
+                }
+            }
+        }
+
+        for mesh in self.cached_meshes.create_primitives(
+            cx.class,
+            style,
+            shared_state.peaks.as_ref(),
+            view,
+            cx.bounds_size,
+        ) {
+            primitives.set_z_index(2);
+            primitives.add_mesh(mesh.clone());
+        }
+
+        if let Some(rect) = shared_state
+            .playhead
+            .and_then(|frame| line_rect(frame, style.playhead_width))
+        {
+            primitives.set_z_index(3);
+            primitives.add(style.quad(style.playhead_color).create_primitive(rect));
+        }
+    }
+}
+
+struct SharedState {
+    peaks: Option<Rc<WaveformPeaks>>,
+    view: WaveformView,
+    /// Whether the view should be zoomed to fit the whole buffer once the
+    /// size of the element is known.
+    fit_view: bool,
+    selection: Option<Range<f64>>,
+    loop_region: Option<Range<f64>>,
+    playhead: Option<f64>,
+}
+
+impl SharedState {
+    fn num_frames(&self) -> usize {
+        self.peaks.as_ref().map(|p| p.num_frames()).unwrap_or(0)
+    }
+
+    fn region_mut(&mut self, kind: WaveformRegionKind) -> &mut Option<Range<f64>> {
+        match kind {
+            WaveformRegionKind::Selection => &mut self.selection,
+            WaveformRegionKind::Loop => &mut self.loop_region,
+        }
+    }
+
+    fn sync_view(&mut self, width: f32, config: &WaveformConfig) {
+        let num_frames = self.num_frames();
+
+        self.view = if self.fit_view {
+            WaveformView::fit(num_frames, width, config)
+        } else {
+            self.view.clamped(num_frames, width, config)
+        };
+    }
+}
+
+/// A handle to a [`WaveformElement`], which displays an audio buffer.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct Waveform {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Waveform {
+    pub fn builder<A: Clone + 'static>() -> WaveformBuilder<A> {
+        WaveformBuilder::default()
+    }
+
+    /// Set the audio buffer to display. The view is zoomed to fit the whole
+    /// buffer.
+    ///
+    /// Returns `true` if the buffer has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_peaks(&mut self, peaks: Option<Rc<WaveformPeaks>>) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let changed = match (&shared_state.peaks, &peaks) {
+            (Some(a), Some(b)) => !Rc::ptr_eq(a, b),
+            (None, None) => false,
+            _ => true,
+        };
+
+        if changed {
+            shared_state.peaks = peaks;
+            shared_state.fit_view = true;
+            self.el.notify_custom_state_change();
+        }
+
+        changed
+    }
+
+    pub fn peaks(&self) -> Option<Rc<WaveformPeaks>> {
+        RefCell::borrow(&self.shared_state).peaks.clone()
+    }
+
+    /// Set the visible part of the buffer. The view is clamped to stay
+    /// within the buffer.
+    ///
+    /// Returns `true` if the view has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_view(&mut self, view: WaveformView) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.view != view || shared_state.fit_view {
+            shared_state.view = view;
+            shared_state.fit_view = false;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The visible part of the buffer.
+    pub fn view(&self) -> WaveformView {
+        RefCell::borrow(&self.shared_state).view
+    }
+
+    /// Zoom out to show the whole buffer.
+    pub fn zoom_to_fit(&mut self) {
+        RefCell::borrow_mut(&self.shared_state).fit_view = true;
+        self.el.notify_custom_state_change();
+    }
+
+    /// Set the position of the playhead in frames.
+    ///
+    /// Returns `true` if the playhead has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_playhead(&mut self, playhead: Option<f64>) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.playhead != playhead {
+            shared_state.playhead = playhead;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn playhead(&self) -> Option<f64> {
+        RefCell::borrow(&self.shared_state).playhead
+    }
+
+    /// Set the selection in frames.
+    ///
+    /// Returns `true` if the selection has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_selection(&mut self, selection: Option<Range<f64>>) -> bool {
+        self.set_region(WaveformRegionKind::Selection, selection)
+    }
+
+    pub fn selection(&self) -> Ref<'_, Option<Range<f64>>> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| &s.selection)
+    }
+
+    /// Set the loop region in frames.
+    ///
+    /// Returns `true` if the loop region has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_loop_region(&mut self, loop_region: Option<Range<f64>>) -> bool {
+        self.set_region(WaveformRegionKind::Loop, loop_region)
+    }
+
+    pub fn loop_region(&self) -> Ref<'_, Option<Range<f64>>> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| &s.loop_region)
+    }
+
+    fn set_region(&mut self, kind: WaveformRegionKind, range: Option<Range<f64>>) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let region = shared_state.region_mut(kind);
+
+        if *region != range {
+            *region = range;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        Region(WaveformRegionUpdate),
+    }
+
+    #[test]
+    fn test_waveform_peaks() {
+        let samples: Vec<f32> = (0..1000)
+            .map(|i| ((i * 7919) % 1000) as f32 / 500.0 - 1.0)
+            .collect();
+        let peaks = WaveformPeaks::from_interleaved(&samples, 2);
+
+        assert_eq!(peaks.num_channels(), 2);
+        assert_eq!(peaks.num_frames(), 500);
+        assert_eq!(peaks.sample(1, 3), Some(samples[7]));
+        assert_eq!(peaks.min_max(0, 500.0..600.0), None);
+
+        // Ranges that line up with the blocks of the pyramid are exact.
+        for (start, end) in [(0, 500), (0, 1), (3, 4), (16, 64), (64, 128), (256, 500)] {
+            let expected = samples
+                .iter()
+                .skip(1)
+                .step_by(2)
+                .take(end)
+                .skip(start)
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), s| {
+                    (min.min(*s), max.max(*s))
+                });
+
+            assert_eq!(
+                peaks.min_max(1, start as f64..end as f64),
+                Some(expected),
+                "{start}..{end}"
+            );
+        }
+    }
+
+    struct WaveformTestApp {
+        waveform: Waveform,
+    }
+
+    impl Application for WaveformTestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let left: Vec<f32> = (0..1600).map(|i| (i as f32 * 0.05).sin()).collect();
+            let right: Vec<f32> = left.iter().map(|s| s * 0.5).collect();
+
+            let waveform = Waveform::builder()
+                .peaks(std::rc::Rc::new(WaveformPeaks::new(&[left, right])))
+                .on_region_changed(TestAction::Region)
+                .rect(rect(10.0, 10.0, 200.0, 60.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { waveform })
+        }
+    }
+
+    #[test]
+    fn test_waveform() {
+        let mut app = HeadlessApp::<WaveformTestApp>::new(AppConfig::default()).unwrap();
+        app.process_updates();
+
+        // The whole buffer fits in the element at first.
+        assert_eq!(app.app().waveform.view(), WaveformView::new(0.0, 8.0));
+
+        // Zoom in around the pointer.
+        app.pointer_moved(MAIN_WINDOW, point(110.0, 40.0));
+        app.set_modifiers(MAIN_WINDOW, Modifiers::CONTROL);
+        app.scroll_wheel(
+            MAIN_WINDOW,
+            WheelDeltaType::Points(Vector::new(0.0, -100.0)),
+        );
+        app.process_updates();
+        assert_eq!(app.app().waveform.view(), WaveformView::new(400.0, 4.0));
+
+        // Scroll.
+        app.set_modifiers(MAIN_WINDOW, Modifiers::empty());
+        app.scroll_wheel(MAIN_WINDOW, WheelDeltaType::Points(Vector::new(0.0, 10.0)));
+        app.process_updates();
+        assert_eq!(app.app().waveform.view(), WaveformView::new(440.0, 4.0));
+
+        // Drag a selection.
+        app.pointer_moved(MAIN_WINDOW, point(60.0, 40.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.pointer_moved(MAIN_WINDOW, point(110.0, 40.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+        assert_eq!(*app.app().waveform.selection(), Some(640.0..840.0));
+        assert_eq!(
+            app.drain_actions(),
+            vec![
+                TestAction::Region(WaveformRegionUpdate {
+                    kind: WaveformRegionKind::Selection,
+                    range: Some(640.0..640.0),
+                    gesture_state: GestureState::GestureStarted,
+                }),
+                TestAction::Region(WaveformRegionUpdate {
+                    kind: WaveformRegionKind::Selection,
+                    range: Some(640.0..840.0),
+                    gesture_state: GestureState::Gesturing,
+                }),
+                TestAction::Region(WaveformRegionUpdate {
+                    kind: WaveformRegionKind::Selection,
+                    range: Some(640.0..840.0),
+                    gesture_state: GestureState::GestureFinished,
+                }),
+            ]
+        );
+
+        // Hovering over the edge of a region shows the resize cursor.
+        app.pointer_moved(MAIN_WINDOW, point(85.0, 40.0));
+        app.process_updates();
+        assert_eq!(app.cursor_icon(MAIN_WINDOW), Some(CursorIcon::Default));
+        app.pointer_moved(MAIN_WINDOW, point(111.0, 40.0));
+        app.process_updates();
+        assert_eq!(app.cursor_icon(MAIN_WINDOW), Some(CursorIcon::EwResize));
+
+        // Drag the end of the selection past its start.
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        app.pointer_moved(MAIN_WINDOW, point(35.0, 40.0));
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.process_updates();
+        assert_eq!(*app.app().waveform.selection(), Some(540.0..640.0));
+        app.drain_actions();
+
+        // Clicking without dragging clears the selection.
+        app.click(MAIN_WINDOW, point(180.0, 40.0), PointerButton::Primary);
+        app.process_updates();
+        assert_eq!(*app.app().waveform.selection(), None);
+    }
+}
@@ -1,7 +1,7 @@
 pub use keyboard_types::{Code, CompositionEvent, KeyState, Location, Modifiers};
 use rootvg::math::Vector;
 
-use crate::{math::Point, window::OpenWindowError};
+use crate::{element_system::AccessAction, math::Point, window::OpenWindowError};
 
 #[derive(Debug)]
 pub enum AppWindowEvent {
@@ -32,7 +32,9 @@ pub(crate) enum CanvasEvent {
 #[derive(Debug, Clone, PartialEq)]
 pub enum ElementEvent {
     CustomStateChanged,
-    Animation { delta_seconds: f64 },
+    Animation {
+        delta_seconds: f64,
+    },
     Hidden,
     Shown,
     StyleChanged,
@@ -45,6 +47,8 @@ pub enum ElementEvent {
     Focus(bool),
     ClickedOff,
     Init,
+    /// An action requested by an assistive technology such as a screen reader.
+    AccessAction(AccessAction),
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -66,6 +70,19 @@ pub struct KeyboardEvent {
     pub is_composing: bool,
 }
 
+impl KeyboardEvent {
+    /// Returns `true` if this is a (non-repeated) press of a key that should
+    /// activate a focused element, such as a button (the Enter or Space key).
+    pub fn is_activation_press(&self) -> bool {
+        self.state == KeyState::Down
+            && !self.repeat
+            && matches!(self.code, Code::Enter | Code::NumpadEnter | Code::Space)
+            && !self
+                .modifiers
+                .intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::META)
+    }
+}
+
 /// Contains the platform-native logical key identifier
 ///
 /// Exactly what that means differs from platform to platform, but the values are to some degree
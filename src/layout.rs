@@ -1,5 +1,15 @@
 use crate::math::{Point, Rect, SideOffsets, Size};
 
+mod attached;
+mod child;
+mod flex;
+mod grid;
+
+pub use attached::*;
+pub use child::*;
+pub use flex::*;
+pub use grid::*;
+
 pub type Padding = SideOffsets;
 pub type Margin = SideOffsets;
 
@@ -572,4 +582,124 @@ mod tests {
     }
 
     // TODO: write tests for the layout_margin_padding_bounded function
+
+    #[test]
+    fn test_flex_row_grow_and_gap() {
+        let layout = FlexLayout::row().gap(10.0).padding(padding_all_same(5.0));
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(210.0, 50.0));
+
+        let rects = layout.compute(
+            bounds,
+            &[
+                FlexItem::new(Size::new(40.0, 20.0)),
+                FlexItem::new(Size::new(40.0, 30.0)).grow(1.0),
+                FlexItem::new(Size::new(20.0, 20.0)).align_self(CrossAlign::Stretch),
+            ],
+        );
+
+        assert_eq!(
+            rects,
+            vec![
+                Rect::new(Point::new(5.0, 5.0), Size::new(40.0, 20.0)),
+                Rect::new(Point::new(55.0, 5.0), Size::new(120.0, 30.0)),
+                Rect::new(Point::new(185.0, 5.0), Size::new(20.0, 40.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flex_column_shrink_and_justify() {
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0));
+
+        let rects = FlexLayout::column().compute(
+            bounds,
+            &[
+                FlexItem::new(Size::new(10.0, 100.0)),
+                FlexItem::new(Size::new(10.0, 100.0))
+                    .shrink(0.0)
+                    .max_size(Size::new(100.0, 50.0)),
+            ],
+        );
+        assert_eq!(rects[0].size.height, 50.0);
+        assert_eq!(rects[1].origin.y, 50.0);
+        assert_eq!(rects[1].size.height, 50.0);
+
+        let rects = FlexLayout::column()
+            .justify(JustifyContent::SpaceBetween)
+            .align_items(CrossAlign::Center)
+            .compute(
+                bounds,
+                &[
+                    FlexItem::new(Size::new(20.0, 10.0)),
+                    FlexItem::new(Size::new(20.0, 10.0)),
+                ],
+            );
+        assert_eq!(
+            rects,
+            vec![
+                Rect::new(Point::new(40.0, 0.0), Size::new(20.0, 10.0)),
+                Rect::new(Point::new(40.0, 90.0), Size::new(20.0, 10.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flex_wrap() {
+        let layout = FlexLayout::row().wrap(true).gap(10.0).line_gap(5.0);
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0));
+
+        let rects = layout.compute(
+            bounds,
+            &[
+                FlexItem::new(Size::new(40.0, 20.0)),
+                FlexItem::new(Size::new(40.0, 30.0)),
+                FlexItem::new(Size::new(40.0, 20.0)),
+            ],
+        );
+
+        assert_eq!(
+            rects,
+            vec![
+                Rect::new(Point::new(0.0, 0.0), Size::new(40.0, 20.0)),
+                Rect::new(Point::new(50.0, 0.0), Size::new(40.0, 30.0)),
+                Rect::new(Point::new(0.0, 35.0), Size::new(40.0, 20.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grid_tracks_and_spans() {
+        let layout = GridLayout::new(
+            vec![
+                GridTrack::Fixed(50.0),
+                GridTrack::Auto,
+                GridTrack::Fraction(1.0),
+            ],
+            vec![GridTrack::Fraction(1.0), GridTrack::Fraction(3.0)],
+        )
+        .gap(10.0, 10.0);
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(200.0, 90.0));
+
+        let rects = layout.compute(
+            bounds,
+            &[
+                GridItem::new(Size::new(20.0, 10.0), 0, 0),
+                GridItem::new(Size::new(30.0, 10.0), 1, 0).align(Align2::CENTER),
+                GridItem::new(Size::new(10.0, 10.0), 1, 1)
+                    .span(2, 1)
+                    .stretch(Stretch::All),
+                GridItem::new(Size::new(10.0, 10.0), 3, 0),
+            ],
+        );
+
+        assert_eq!(
+            rects,
+            vec![
+                Rect::new(Point::new(0.0, 0.0), Size::new(20.0, 10.0)),
+                Rect::new(Point::new(60.0, 5.0), Size::new(30.0, 10.0)),
+                Rect::new(Point::new(60.0, 30.0), Size::new(140.0, 60.0)),
+                Rect::zero(),
+            ]
+        );
+    }
 }
@@ -0,0 +1,302 @@
+use std::cell::{Ref, RefCell};
+use std::rc::{Rc, Weak};
+
+use crate::application::ResourceCtx;
+use crate::math::{Rect, Size};
+use crate::WindowContext;
+
+use super::{FlexItem, FlexLayout, GridItem, GridLayout, LayoutChild};
+
+/// A child of an [`AttachedLayout`].
+///
+/// The child is shared between the application and the layout, so the
+/// application can still use the element handle after attaching it.
+pub type SharedLayoutChild = Rc<RefCell<dyn LayoutChild>>;
+
+/// A layout container which can be attached to a window with
+/// [`AttachedLayout::new`].
+pub trait LayoutContainer: 'static {
+    /// How a single child should be laid out (i.e. [`FlexItem`] or
+    /// [`GridItem`]).
+    type Item: Clone + 'static;
+
+    fn set_desired_size(item: &mut Self::Item, desired_size: Size);
+
+    /// Compute the rectangle of each item inside of `bounds`.
+    fn compute_rects(&self, bounds: Rect, items: &[Self::Item]) -> Vec<Rect>;
+}
+
+impl LayoutContainer for FlexLayout {
+    type Item = FlexItem;
+
+    fn set_desired_size(item: &mut FlexItem, desired_size: Size) {
+        item.desired_size = desired_size;
+    }
+
+    fn compute_rects(&self, bounds: Rect, items: &[FlexItem]) -> Vec<Rect> {
+        self.compute(bounds, items)
+    }
+}
+
+impl LayoutContainer for GridLayout {
+    type Item = GridItem;
+
+    fn set_desired_size(item: &mut GridItem, desired_size: Size) {
+        item.desired_size = desired_size;
+    }
+
+    fn compute_rects(&self, bounds: Rect, items: &[GridItem]) -> Vec<Rect> {
+        self.compute(bounds, items)
+    }
+}
+
+struct AttachedLayoutInner<L: LayoutContainer> {
+    layout: L,
+    bounds: Box<dyn Fn(Size) -> Rect>,
+    children: Vec<(SharedLayoutChild, L::Item)>,
+}
+
+/// Implemented by the layouts attached to a window so they can be stored
+/// together regardless of their container type.
+pub(crate) trait ResizeHook {
+    fn apply(&mut self, window_size: Size, res: &mut ResourceCtx);
+}
+
+impl<L: LayoutContainer> ResizeHook for AttachedLayoutInner<L> {
+    fn apply(&mut self, window_size: Size, res: &mut ResourceCtx) {
+        for (child, item) in self.children.iter_mut() {
+            L::set_desired_size(item, RefCell::borrow(child).desired_size(res));
+        }
+
+        let items: Vec<L::Item> = self.children.iter().map(|(_, item)| item.clone()).collect();
+        let rects = self
+            .layout
+            .compute_rects((self.bounds)(window_size), &items);
+
+        for ((child, _), rect) in self.children.iter().zip(rects) {
+            RefCell::borrow_mut(child).set_rect(rect);
+        }
+    }
+}
+
+/// The layouts attached to a window.
+#[derive(Default)]
+pub(crate) struct AttachedLayouts {
+    layouts: Vec<Weak<RefCell<dyn ResizeHook>>>,
+}
+
+impl AttachedLayouts {
+    pub fn add(&mut self, layout: &Rc<RefCell<dyn ResizeHook>>) {
+        self.layouts.push(Rc::downgrade(layout));
+    }
+
+    /// Re-apply all of the attached layouts, and forget about any layouts
+    /// whose handle has been dropped.
+    pub fn apply(&mut self, window_size: Size, res: &mut ResourceCtx) {
+        self.layouts.retain(|layout| {
+            let Some(layout) = layout.upgrade() else {
+                return false;
+            };
+
+            RefCell::borrow_mut(&layout).apply(window_size, res);
+            true
+        });
+    }
+}
+
+/// A handle to a layout container which is attached to a window.
+///
+/// The layout is re-applied automatically whenever the window is resized
+/// (right before `AppWindowEvent::WindowResized` is sent to the
+/// application), so there is no need to call [`FlexLayout::apply`] or
+/// [`GridLayout::apply`] in your layout function.
+///
+/// Dropping this handle detaches the layout from the window.
+pub struct AttachedLayout<L: LayoutContainer> {
+    inner: Rc<RefCell<AttachedLayoutInner<L>>>,
+}
+
+impl<L: LayoutContainer> AttachedLayout<L> {
+    /// Attach a layout container to the window and apply it.
+    ///
+    /// * `bounds` - Returns the rectangle to lay out the children inside of,
+    /// given the logical size of the window.
+    /// * `children` - The children along with how each one should be laid
+    /// out. The desired size of each item is overwritten with the value of
+    /// [`LayoutChild::desired_size`].
+    pub fn new<A: Clone + 'static>(
+        layout: L,
+        bounds: impl Fn(Size) -> Rect + 'static,
+        children: impl IntoIterator<Item = (SharedLayoutChild, L::Item)>,
+        window_cx: &mut WindowContext<'_, A>,
+    ) -> Self {
+        let inner = Rc::new(RefCell::new(AttachedLayoutInner {
+            layout,
+            bounds: Box::new(bounds),
+            children: children.into_iter().collect(),
+        }));
+
+        let hook: Rc<RefCell<dyn ResizeHook>> = inner.clone();
+        window_cx.attach_layout(&hook);
+
+        let mut new_self = Self { inner };
+        new_self.apply(window_cx);
+        new_self
+    }
+
+    pub fn layout(&self) -> Ref<'_, L> {
+        Ref::map(RefCell::borrow(&self.inner), |inner| &inner.layout)
+    }
+
+    /// Replace the layout container and re-apply the layout.
+    pub fn set_layout<A: Clone + 'static>(
+        &mut self,
+        layout: L,
+        window_cx: &mut WindowContext<'_, A>,
+    ) {
+        RefCell::borrow_mut(&self.inner).layout = layout;
+        self.apply(window_cx);
+    }
+
+    /// Re-apply the layout, i.e. after the desired size of a child has
+    /// changed.
+    pub fn apply<A: Clone + 'static>(&mut self, window_cx: &mut WindowContext<'_, A>) {
+        let window_size = window_cx.logical_size();
+        RefCell::borrow_mut(&self.inner).apply(window_size, window_cx.res);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::prelude::*;
+
+    struct ResizeTestApp {
+        a: Rc<RefCell<Button>>,
+        b: Rc<RefCell<Button>>,
+        cell: Rc<RefCell<Button>>,
+        _row: AttachedLayout<FlexLayout>,
+        _grid: AttachedLayout<GridLayout>,
+    }
+
+    impl Application for ResizeTestApp {
+        type Action = ();
+
+        fn init(cx: &mut AppContext<()>) -> Result<Self, Box<dyn std::error::Error>> {
+            let mut window_cx = cx.main_window();
+
+            let mut new_button = || {
+                Rc::new(RefCell::new(
+                    Button::builder().text("A").build(&mut window_cx),
+                ))
+            };
+
+            let a = new_button();
+            let b = new_button();
+            let cell = new_button();
+
+            // A row along the top of the window. Both buttons have the same
+            // desired size, so they always share the width evenly.
+            let row = AttachedLayout::new(
+                FlexLayout::row().gap(10.0).align_items(CrossAlign::Stretch),
+                |window_size| Rect::new(Point::zero(), Size::new(window_size.width, 40.0)),
+                [
+                    (
+                        a.clone() as SharedLayoutChild,
+                        FlexItem::default().grow(1.0),
+                    ),
+                    (
+                        b.clone() as SharedLayoutChild,
+                        FlexItem::default().grow(1.0),
+                    ),
+                ],
+                &mut window_cx,
+            );
+
+            // A grid below the row, with the child in the bottom-right cell.
+            let grid = AttachedLayout::new(
+                GridLayout::new(
+                    vec![GridTrack::Fraction(1.0), GridTrack::Fixed(50.0)],
+                    vec![GridTrack::Fraction(1.0), GridTrack::Fixed(30.0)],
+                ),
+                |window_size| {
+                    Rect::new(
+                        Point::new(0.0, 40.0),
+                        Size::new(window_size.width, window_size.height - 40.0),
+                    )
+                },
+                [(
+                    cell.clone() as SharedLayoutChild,
+                    GridItem::new(Size::zero(), 1, 1).stretch(Stretch::All),
+                )],
+                &mut window_cx,
+            );
+
+            Ok(Self {
+                a,
+                b,
+                cell,
+                _row: row,
+                _grid: grid,
+            })
+        }
+    }
+
+    #[test]
+    fn test_attached_layouts_follow_window_size() {
+        let mut app = HeadlessApp::<ResizeTestApp>::new(AppConfig {
+            main_window_config: WindowConfig {
+                size: Size::new(210.0, 140.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        app.process_updates();
+
+        let rects = |app: &HeadlessApp<ResizeTestApp>| {
+            let app = app.app();
+            [
+                app.a.borrow().rect(),
+                app.b.borrow().rect(),
+                app.cell.borrow().rect(),
+            ]
+        };
+
+        assert_eq!(
+            rects(&app),
+            [
+                rect(0.0, 0.0, 100.0, 40.0),
+                rect(110.0, 0.0, 100.0, 40.0),
+                rect(160.0, 110.0, 50.0, 30.0),
+            ]
+        );
+
+        app.cx().resize_window(MAIN_WINDOW, Size::new(310.0, 200.0));
+        app.process_updates();
+
+        assert_eq!(
+            rects(&app),
+            [
+                rect(0.0, 0.0, 150.0, 40.0),
+                rect(160.0, 0.0, 150.0, 40.0),
+                rect(260.0, 170.0, 50.0, 30.0),
+            ]
+        );
+
+        // Dropped layouts no longer follow the window.
+        let empty_row = AttachedLayout::new(
+            FlexLayout::row(),
+            |_| Rect::zero(),
+            [],
+            &mut app.cx().main_window(),
+        );
+        app.app_mut()._row = empty_row;
+        app.cx().resize_window(MAIN_WINDOW, Size::new(210.0, 140.0));
+        app.process_updates();
+        assert_eq!(rects(&app)[0], rect(0.0, 0.0, 150.0, 40.0));
+        assert_eq!(rects(&app)[2], rect(160.0, 110.0, 50.0, 30.0));
+    }
+}
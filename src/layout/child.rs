@@ -0,0 +1,67 @@
+use crate::application::ResourceCtx;
+use crate::element_system::element::ElementHandle;
+use crate::elements::virtual_slider::{VirtualSlider, VirtualSliderRenderer};
+use crate::math::{Rect, Size};
+
+/// An element handle which can be laid out by a layout container such as
+/// [`FlexLayout`](super::FlexLayout) or [`GridLayout`](super::GridLayout).
+pub trait LayoutChild {
+    /// The size this element would like to have.
+    fn desired_size(&self, res: &mut ResourceCtx) -> Size;
+
+    /// Set the rectangular area of this element.
+    fn set_rect(&mut self, rect: Rect) -> bool;
+}
+
+impl LayoutChild for ElementHandle {
+    /// Generic element handles have no desired size, so their current size
+    /// is used.
+    fn desired_size(&self, _res: &mut ResourceCtx) -> Size {
+        self.rect().size
+    }
+
+    fn set_rect(&mut self, rect: Rect) -> bool {
+        ElementHandle::set_rect(self, rect)
+    }
+}
+
+impl<R: VirtualSliderRenderer> LayoutChild for VirtualSlider<R> {
+    /// Uses the desired size of the renderer if it has one, or the current
+    /// size of the element otherwise.
+    fn desired_size(&self, _res: &mut ResourceCtx) -> Size {
+        VirtualSlider::desired_size(self).unwrap_or(self.size())
+    }
+
+    fn set_rect(&mut self, rect: Rect) -> bool {
+        VirtualSlider::set_rect(self, rect)
+    }
+}
+
+macro_rules! impl_layout_child {
+    ($($(#[$attr:meta])* $handle:ty),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            impl LayoutChild for $handle {
+                fn desired_size(&self, res: &mut ResourceCtx) -> Size {
+                    <$handle>::desired_size(self, res)
+                }
+
+                fn set_rect(&mut self, rect: Rect) -> bool {
+                    <$handle>::set_rect(self, rect)
+                }
+            }
+        )*
+    };
+}
+
+impl_layout_child!(
+    crate::elements::button::Button,
+    crate::elements::label::Label,
+    crate::elements::paragraph::Paragraph,
+    crate::elements::radio_button::RadioButton,
+    crate::elements::switch::Switch,
+    crate::elements::tab::Tab,
+    crate::elements::toggle_button::ToggleButton,
+    #[cfg(feature = "svg-icons")]
+    crate::elements::icon::Icon,
+);
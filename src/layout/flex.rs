@@ -0,0 +1,512 @@
+use crate::application::ResourceCtx;
+use crate::math::{Point, Rect, Size};
+
+use super::{layout_inner_rect, LayoutChild, LayoutDirection, Padding};
+
+/// How to distribute the leftover space along the main axis of a
+/// [`FlexLayout`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JustifyContent {
+    /// Pack the children towards the start of the main axis.
+    #[default]
+    Start,
+    /// Pack the children around the center of the main axis.
+    Center,
+    /// Pack the children towards the end of the main axis.
+    End,
+    /// The first child is placed at the start, the last child at the end,
+    /// and the leftover space is distributed evenly between them.
+    SpaceBetween,
+    /// The leftover space is distributed evenly around each child, so the
+    /// space at the edges is half the space between children.
+    SpaceAround,
+    /// The leftover space is distributed evenly between the children and
+    /// the edges.
+    SpaceEvenly,
+}
+
+/// How to place a child along the cross axis of its line in a
+/// [`FlexLayout`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CrossAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// Stretch the child to fill the cross axis of its line.
+    Stretch,
+}
+
+/// A row or column container which computes the rectangles of its children
+/// from their desired sizes, similar to a CSS flexbox.
+///
+/// Either call [`FlexLayout::apply`] in your layout function, or attach the
+/// container to a window with [`AttachedLayout`](super::AttachedLayout) to
+/// have it re-run automatically whenever the window is resized. Since setting
+/// the rect of an element is cheap when it hasn't changed, this is relatively
+/// cheap to call frequently.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlexLayout {
+    /// The main axis of the container. `LayoutDirection::Horizontal`
+    /// creates a row and `LayoutDirection::Vertical` creates a column.
+    ///
+    /// By default this is set to `LayoutDirection::Horizontal`.
+    pub direction: LayoutDirection,
+
+    /// How to distribute leftover space along the main axis.
+    ///
+    /// By default this is set to `JustifyContent::Start`.
+    pub justify: JustifyContent,
+
+    /// How to place the children along the cross axis. This can be
+    /// overriden per child with [`FlexItem::align_self`].
+    ///
+    /// By default this is set to `CrossAlign::Start`.
+    pub align_items: CrossAlign,
+
+    /// Whether to wrap the children onto multiple lines when they do not
+    /// fit on the main axis.
+    ///
+    /// By default this is set to `false`.
+    pub wrap: bool,
+
+    /// The spacing between children along the main axis.
+    ///
+    /// By default this is set to `0.0`.
+    pub gap: f32,
+
+    /// The spacing between lines when `wrap` is `true`.
+    ///
+    /// By default this is set to `0.0`.
+    pub line_gap: f32,
+
+    /// The padding between the edges of the bounding rectangle and the
+    /// children.
+    ///
+    /// By default this has all values set to `0.0`.
+    pub padding: Padding,
+}
+
+impl Default for FlexLayout {
+    fn default() -> Self {
+        Self {
+            direction: LayoutDirection::Horizontal,
+            justify: JustifyContent::Start,
+            align_items: CrossAlign::Start,
+            wrap: false,
+            gap: 0.0,
+            line_gap: 0.0,
+            padding: Padding::zero(),
+        }
+    }
+}
+
+/// How a single child of a [`FlexLayout`] should be sized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlexItem {
+    /// The desired size of the child.
+    ///
+    /// When using [`FlexLayout::apply`], this is overwritten with the value
+    /// of [`LayoutChild::desired_size`].
+    ///
+    /// By default this is set to `Size::zero()`.
+    pub desired_size: Size,
+
+    /// The initial size of the child along the main axis before growing
+    /// or shrinking. If this is `None`, then the desired size is used.
+    ///
+    /// By default this is set to `None`.
+    pub basis: Option<f32>,
+
+    /// How much of the leftover space on the main axis this child should
+    /// take up relative to the other children.
+    ///
+    /// By default this is set to `0.0`.
+    pub grow: f32,
+
+    /// How much this child should shrink relative to the other children
+    /// when there is not enough space on the main axis.
+    ///
+    /// By default this is set to `1.0`.
+    pub shrink: f32,
+
+    /// Overrides [`FlexLayout::align_items`] for this child.
+    ///
+    /// By default this is set to `None`.
+    pub align_self: Option<CrossAlign>,
+
+    /// The minimum size of this child.
+    ///
+    /// By default this is set to `Size::zero()`.
+    pub min_size: Size,
+
+    /// The maximum size of this child.
+    ///
+    /// By default this is set to `Size::new(f32::INFINITY, f32::INFINITY)`.
+    pub max_size: Size,
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        Self {
+            desired_size: Size::zero(),
+            basis: None,
+            grow: 0.0,
+            shrink: 1.0,
+            align_self: None,
+            min_size: Size::zero(),
+            max_size: Size::new(f32::INFINITY, f32::INFINITY),
+        }
+    }
+}
+
+impl FlexItem {
+    pub fn new(desired_size: Size) -> Self {
+        Self {
+            desired_size,
+            ..Default::default()
+        }
+    }
+
+    pub const fn basis(mut self, basis: f32) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    pub const fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    pub const fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    pub const fn align_self(mut self, align: CrossAlign) -> Self {
+        self.align_self = Some(align);
+        self
+    }
+
+    pub const fn min_size(mut self, size: Size) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    pub const fn max_size(mut self, size: Size) -> Self {
+        self.max_size = size;
+        self
+    }
+}
+
+/// A child of a [`FlexLayout`] used with [`FlexLayout::apply`].
+pub struct FlexEntry<'a> {
+    pub child: &'a mut dyn LayoutChild,
+    pub item: FlexItem,
+}
+
+impl<'a> FlexEntry<'a> {
+    pub fn new(child: &'a mut dyn LayoutChild) -> Self {
+        Self {
+            child,
+            item: FlexItem::default(),
+        }
+    }
+
+    pub fn basis(mut self, basis: f32) -> Self {
+        self.item.basis = Some(basis);
+        self
+    }
+
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.item.grow = grow;
+        self
+    }
+
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.item.shrink = shrink;
+        self
+    }
+
+    pub fn align_self(mut self, align: CrossAlign) -> Self {
+        self.item.align_self = Some(align);
+        self
+    }
+
+    pub fn min_size(mut self, size: Size) -> Self {
+        self.item.min_size = size;
+        self
+    }
+
+    pub fn max_size(mut self, size: Size) -> Self {
+        self.item.max_size = size;
+        self
+    }
+}
+
+impl FlexLayout {
+    /// A container which lays out its children horizontally.
+    pub fn row() -> Self {
+        Self::default()
+    }
+
+    /// A container which lays out its children vertically.
+    pub fn column() -> Self {
+        Self {
+            direction: LayoutDirection::Vertical,
+            ..Default::default()
+        }
+    }
+
+    pub const fn justify(mut self, justify: JustifyContent) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    pub const fn align_items(mut self, align: CrossAlign) -> Self {
+        self.align_items = align;
+        self
+    }
+
+    pub const fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub const fn gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub const fn line_gap(mut self, line_gap: f32) -> Self {
+        self.line_gap = line_gap;
+        self
+    }
+
+    pub const fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Compute the desired size of each child, lay them out inside of
+    /// `bounds`, and then set the resulting rectangle on each child.
+    pub fn apply(&self, bounds: Rect, children: &mut [FlexEntry<'_>], res: &mut ResourceCtx) {
+        for entry in children.iter_mut() {
+            entry.item.desired_size = entry.child.desired_size(res);
+        }
+
+        let items: Vec<FlexItem> = children.iter().map(|entry| entry.item).collect();
+        let rects = self.compute(bounds, &items);
+
+        for (entry, rect) in children.iter_mut().zip(rects) {
+            entry.child.set_rect(rect);
+        }
+    }
+
+    /// Compute the rectangle of each item inside of `bounds`.
+    ///
+    /// The returned rectangles are in the same order as `items`.
+    pub fn compute(&self, bounds: Rect, items: &[FlexItem]) -> Vec<Rect> {
+        let inner = layout_inner_rect(self.padding, bounds)
+            .unwrap_or_else(|| Rect::new(bounds.center(), Size::zero()));
+
+        let horizontal = self.direction == LayoutDirection::Horizontal;
+        let main = |s: Size| if horizontal { s.width } else { s.height };
+        let cross = |s: Size| if horizontal { s.height } else { s.width };
+
+        let avail_main = main(inner.size);
+        let avail_cross = cross(inner.size);
+
+        let base_sizes: Vec<f32> = items
+            .iter()
+            .map(|item| {
+                item.basis.unwrap_or(main(item.desired_size)).clamp(
+                    main(item.min_size),
+                    main(item.max_size).max(main(item.min_size)),
+                )
+            })
+            .collect();
+
+        // Break the items into lines.
+        let mut lines: Vec<std::ops::Range<usize>> = Vec::new();
+        let mut line_start = 0;
+        let mut line_main = 0.0;
+        for (i, base) in base_sizes.iter().enumerate() {
+            let needed = if i == line_start {
+                *base
+            } else {
+                line_main + self.gap + *base
+            };
+
+            if self.wrap && i > line_start && needed > avail_main {
+                lines.push(line_start..i);
+                line_start = i;
+                line_main = *base;
+            } else {
+                line_main = needed;
+            }
+        }
+        if line_start < items.len() {
+            lines.push(line_start..items.len());
+        }
+
+        let mut rects = vec![Rect::zero(); items.len()];
+        let mut line_cross_start = 0.0;
+
+        for line in lines {
+            let line_items = &items[line.clone()];
+            let main_sizes = resolve_flexible_lengths(
+                line_items,
+                &base_sizes[line.clone()],
+                avail_main - self.gap * (line_items.len().saturating_sub(1)) as f32,
+                main,
+            );
+
+            let line_cross = if self.wrap {
+                line_items
+                    .iter()
+                    .map(|item| {
+                        cross(item.desired_size).clamp(
+                            cross(item.min_size),
+                            cross(item.max_size).max(cross(item.min_size)),
+                        )
+                    })
+                    .fold(0.0, f32::max)
+            } else {
+                avail_cross
+            };
+
+            let used_main: f32 = main_sizes.iter().sum::<f32>()
+                + self.gap * (line_items.len().saturating_sub(1)) as f32;
+            let free = (avail_main - used_main).max(0.0);
+            let n = line_items.len() as f32;
+
+            let (mut main_pos, spacing) = match self.justify {
+                JustifyContent::Start => (0.0, 0.0),
+                JustifyContent::Center => (free * 0.5, 0.0),
+                JustifyContent::End => (free, 0.0),
+                JustifyContent::SpaceBetween => {
+                    if line_items.len() > 1 {
+                        (0.0, free / (n - 1.0))
+                    } else {
+                        (0.0, 0.0)
+                    }
+                }
+                JustifyContent::SpaceAround => (free / n * 0.5, free / n),
+                JustifyContent::SpaceEvenly => (free / (n + 1.0), free / (n + 1.0)),
+            };
+
+            for ((i, item), main_size) in line.clone().zip(line_items.iter()).zip(main_sizes) {
+                let min_cross = cross(item.min_size);
+                let max_cross = cross(item.max_size).max(min_cross);
+                let desired_cross = cross(item.desired_size).clamp(min_cross, max_cross);
+
+                let (cross_offset, cross_size) = match item.align_self.unwrap_or(self.align_items) {
+                    CrossAlign::Start => (0.0, desired_cross),
+                    CrossAlign::Center => ((line_cross - desired_cross) * 0.5, desired_cross),
+                    CrossAlign::End => (line_cross - desired_cross, desired_cross),
+                    CrossAlign::Stretch => (0.0, line_cross.clamp(min_cross, max_cross)),
+                };
+
+                let cross_pos = line_cross_start + cross_offset;
+
+                rects[i] = if horizontal {
+                    Rect::new(
+                        Point::new(inner.min_x() + main_pos, inner.min_y() + cross_pos),
+                        Size::new(main_size, cross_size),
+                    )
+                } else {
+                    Rect::new(
+                        Point::new(inner.min_x() + cross_pos, inner.min_y() + main_pos),
+                        Size::new(cross_size, main_size),
+                    )
+                };
+
+                main_pos += main_size + self.gap + spacing;
+            }
+
+            line_cross_start += line_cross + self.line_gap;
+        }
+
+        rects
+    }
+}
+
+/// Grow or shrink the items in a line so they fill `avail_main`, freezing
+/// any items which hit their minimum or maximum size along the way.
+fn resolve_flexible_lengths(
+    items: &[FlexItem],
+    base_sizes: &[f32],
+    avail_main: f32,
+    main: impl Fn(Size) -> f32,
+) -> Vec<f32> {
+    let mut sizes = base_sizes.to_vec();
+    let mut frozen = vec![false; items.len()];
+
+    let growing = base_sizes.iter().sum::<f32>() < avail_main;
+
+    for (i, item) in items.iter().enumerate() {
+        if (growing && item.grow <= 0.0) || (!growing && item.shrink <= 0.0) {
+            frozen[i] = true;
+        }
+    }
+
+    // Each pass freezes at least one item or finishes, so this always
+    // terminates within `items.len()` passes.
+    for _ in 0..items.len() {
+        let free = avail_main - sizes.iter().sum::<f32>();
+        if free.abs() < f32::EPSILON {
+            break;
+        }
+
+        let total_factor: f32 = items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !frozen[*i])
+            .map(|(i, item)| {
+                if growing {
+                    item.grow
+                } else {
+                    item.shrink * base_sizes[i]
+                }
+            })
+            .sum();
+        if total_factor <= 0.0 {
+            break;
+        }
+
+        let mut clamped_any = false;
+        for (i, item) in items.iter().enumerate() {
+            if frozen[i] {
+                continue;
+            }
+
+            let factor = if growing {
+                item.grow
+            } else {
+                item.shrink * base_sizes[i]
+            };
+
+            let min = main(item.min_size);
+            let max = main(item.max_size).max(min);
+            let target = sizes[i] + free * (factor / total_factor);
+            let clamped = target.clamp(min, max);
+
+            if clamped != target {
+                frozen[i] = true;
+                clamped_any = true;
+            }
+            sizes[i] = clamped;
+        }
+
+        if !clamped_any {
+            break;
+        }
+    }
+
+    sizes
+}
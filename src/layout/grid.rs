@@ -0,0 +1,394 @@
+use crate::application::ResourceCtx;
+use crate::math::{Point, Rect, Size};
+
+use super::{layout_inner_rect, Align, Align2, LayoutChild, Margin, Padding, Stretch};
+
+/// The sizing of a single row or column in a [`GridLayout`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GridTrack {
+    /// A fixed size in points.
+    Fixed(f32),
+    /// A share of the space left over after all fixed and content-sized
+    /// tracks have been sized, relative to the other fractional tracks
+    /// (like `fr` units in CSS).
+    Fraction(f32),
+    /// Sized to fit the largest desired size of the cells in this track.
+    ///
+    /// Note, cells which span multiple tracks are not taken into account.
+    Auto,
+}
+
+impl Default for GridTrack {
+    fn default() -> Self {
+        Self::Fraction(1.0)
+    }
+}
+
+/// A container which places its children into the cells of a grid of rows
+/// and columns, similar to a CSS grid.
+///
+/// Like [`FlexLayout`](super::FlexLayout), either call [`GridLayout::apply`]
+/// in your layout function, or attach the container to a window with
+/// [`AttachedLayout`](super::AttachedLayout) to have it re-run automatically
+/// whenever the window is resized.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridLayout {
+    /// The columns of the grid, from left to right.
+    ///
+    /// By default this is empty.
+    pub columns: Vec<GridTrack>,
+
+    /// The rows of the grid, from top to bottom.
+    ///
+    /// By default this is empty.
+    pub rows: Vec<GridTrack>,
+
+    /// The spacing between columns.
+    ///
+    /// By default this is set to `0.0`.
+    pub column_gap: f32,
+
+    /// The spacing between rows.
+    ///
+    /// By default this is set to `0.0`.
+    pub row_gap: f32,
+
+    /// The padding between the edges of the bounding rectangle and the
+    /// grid.
+    ///
+    /// By default this has all values set to `0.0`.
+    pub padding: Padding,
+}
+
+impl Default for GridLayout {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            column_gap: 0.0,
+            row_gap: 0.0,
+            padding: Padding::zero(),
+        }
+    }
+}
+
+/// Where and how to place a single child in a [`GridLayout`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridItem {
+    /// The desired size of the child.
+    ///
+    /// When using [`GridLayout::apply`], this is overwritten with the value
+    /// of [`LayoutChild::desired_size`].
+    ///
+    /// By default this is set to `Size::zero()`.
+    pub desired_size: Size,
+
+    /// The index of the first column this child occupies.
+    ///
+    /// By default this is set to `0`.
+    pub column: usize,
+
+    /// The index of the first row this child occupies.
+    ///
+    /// By default this is set to `0`.
+    pub row: usize,
+
+    /// The number of columns this child spans.
+    ///
+    /// By default this is set to `1`.
+    pub column_span: usize,
+
+    /// The number of rows this child spans.
+    ///
+    /// By default this is set to `1`.
+    pub row_span: usize,
+
+    /// How to align the child within its cell when it is not stretched.
+    ///
+    /// By default this is set to `Align2::TOP_LEFT`.
+    pub align: Align2,
+
+    /// How to stretch the child to fill its cell.
+    ///
+    /// By default this is set to `Stretch::None`.
+    pub stretch: Stretch,
+
+    /// The margin between the edges of the cell and the child.
+    ///
+    /// By default this has all values set to `0.0`.
+    pub margin: Margin,
+}
+
+impl Default for GridItem {
+    fn default() -> Self {
+        Self {
+            desired_size: Size::zero(),
+            column: 0,
+            row: 0,
+            column_span: 1,
+            row_span: 1,
+            align: Align2::TOP_LEFT,
+            stretch: Stretch::None,
+            margin: Margin::zero(),
+        }
+    }
+}
+
+impl GridItem {
+    pub fn new(desired_size: Size, column: usize, row: usize) -> Self {
+        Self {
+            desired_size,
+            column,
+            row,
+            ..Default::default()
+        }
+    }
+
+    pub const fn span(mut self, columns: usize, rows: usize) -> Self {
+        self.column_span = columns;
+        self.row_span = rows;
+        self
+    }
+
+    pub const fn align(mut self, align: Align2) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub const fn stretch(mut self, stretch: Stretch) -> Self {
+        self.stretch = stretch;
+        self
+    }
+
+    pub const fn margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+}
+
+/// A child of a [`GridLayout`] used with [`GridLayout::apply`].
+pub struct GridEntry<'a> {
+    pub child: &'a mut dyn LayoutChild,
+    pub item: GridItem,
+}
+
+impl<'a> GridEntry<'a> {
+    pub fn new(child: &'a mut dyn LayoutChild, column: usize, row: usize) -> Self {
+        Self {
+            child,
+            item: GridItem::new(Size::zero(), column, row),
+        }
+    }
+
+    pub fn span(mut self, columns: usize, rows: usize) -> Self {
+        self.item.column_span = columns;
+        self.item.row_span = rows;
+        self
+    }
+
+    pub fn align(mut self, align: Align2) -> Self {
+        self.item.align = align;
+        self
+    }
+
+    pub fn stretch(mut self, stretch: Stretch) -> Self {
+        self.item.stretch = stretch;
+        self
+    }
+
+    pub fn margin(mut self, margin: Margin) -> Self {
+        self.item.margin = margin;
+        self
+    }
+}
+
+impl GridLayout {
+    pub fn new(columns: Vec<GridTrack>, rows: Vec<GridTrack>) -> Self {
+        Self {
+            columns,
+            rows,
+            ..Default::default()
+        }
+    }
+
+    pub fn gap(mut self, column_gap: f32, row_gap: f32) -> Self {
+        self.column_gap = column_gap;
+        self.row_gap = row_gap;
+        self
+    }
+
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Compute the desired size of each child, lay them out inside of
+    /// `bounds`, and then set the resulting rectangle on each child.
+    pub fn apply(&self, bounds: Rect, children: &mut [GridEntry<'_>], res: &mut ResourceCtx) {
+        for entry in children.iter_mut() {
+            entry.item.desired_size = entry.child.desired_size(res);
+        }
+
+        let items: Vec<GridItem> = children.iter().map(|entry| entry.item).collect();
+        let rects = self.compute(bounds, &items);
+
+        for (entry, rect) in children.iter_mut().zip(rects) {
+            entry.child.set_rect(rect);
+        }
+    }
+
+    /// Compute the rectangle of each item inside of `bounds`.
+    ///
+    /// The returned rectangles are in the same order as `items`. Items which
+    /// are placed outside of the grid get an empty rectangle.
+    pub fn compute(&self, bounds: Rect, items: &[GridItem]) -> Vec<Rect> {
+        let inner = layout_inner_rect(self.padding, bounds)
+            .unwrap_or_else(|| Rect::new(bounds.center(), Size::zero()));
+
+        let column_sizes = resolve_tracks(
+            &self.columns,
+            inner.width(),
+            self.column_gap,
+            items
+                .iter()
+                .filter(|item| item.column_span == 1)
+                .map(|item| {
+                    (
+                        item.column,
+                        item.desired_size.width + item.margin.horizontal(),
+                    )
+                }),
+        );
+        let row_sizes = resolve_tracks(
+            &self.rows,
+            inner.height(),
+            self.row_gap,
+            items
+                .iter()
+                .filter(|item| item.row_span == 1)
+                .map(|item| (item.row, item.desired_size.height + item.margin.vertical())),
+        );
+
+        let column_starts = track_starts(&column_sizes, inner.min_x(), self.column_gap);
+        let row_starts = track_starts(&row_sizes, inner.min_y(), self.row_gap);
+
+        items
+            .iter()
+            .map(|item| {
+                if item.column >= column_sizes.len() || item.row >= row_sizes.len() {
+                    return Rect::zero();
+                }
+
+                let last_column =
+                    (item.column + item.column_span.max(1)).min(column_sizes.len()) - 1;
+                let last_row = (item.row + item.row_span.max(1)).min(row_sizes.len()) - 1;
+
+                let cell_min = Point::new(column_starts[item.column], row_starts[item.row]);
+                let cell_max = Point::new(
+                    column_starts[last_column] + column_sizes[last_column],
+                    row_starts[last_row] + row_sizes[last_row],
+                );
+
+                let cell_rect = Rect::new(
+                    Point::new(cell_min.x + item.margin.left, cell_min.y + item.margin.top),
+                    Size::new(
+                        (cell_max.x - cell_min.x - item.margin.horizontal()).max(0.0),
+                        (cell_max.y - cell_min.y - item.margin.vertical()).max(0.0),
+                    ),
+                );
+
+                let (stretch_h, stretch_v) = match item.stretch {
+                    Stretch::None => (false, false),
+                    Stretch::Horizontal => (true, false),
+                    Stretch::Vertical => (false, true),
+                    Stretch::All => (true, true),
+                };
+
+                let size = Size::new(
+                    if stretch_h {
+                        cell_rect.width()
+                    } else {
+                        item.desired_size.width.min(cell_rect.width())
+                    },
+                    if stretch_v {
+                        cell_rect.height()
+                    } else {
+                        item.desired_size.height.min(cell_rect.height())
+                    },
+                );
+
+                let x = match item.align.horizontal {
+                    Align::Start => cell_rect.min_x(),
+                    Align::Center => cell_rect.min_x() + (cell_rect.width() - size.width) * 0.5,
+                    Align::End => cell_rect.max_x() - size.width,
+                };
+                let y = match item.align.vertical {
+                    Align::Start => cell_rect.min_y(),
+                    Align::Center => cell_rect.min_y() + (cell_rect.height() - size.height) * 0.5,
+                    Align::End => cell_rect.max_y() - size.height,
+                };
+
+                Rect::new(Point::new(x, y), size)
+            })
+            .collect()
+    }
+}
+
+/// Compute the size of each track given the available space and the
+/// `(track index, desired size)` of each single-track cell.
+fn resolve_tracks(
+    tracks: &[GridTrack],
+    available: f32,
+    gap: f32,
+    cell_sizes: impl Iterator<Item = (usize, f32)>,
+) -> Vec<f32> {
+    let mut sizes: Vec<f32> = tracks
+        .iter()
+        .map(|track| match track {
+            GridTrack::Fixed(points) => *points,
+            _ => 0.0,
+        })
+        .collect();
+
+    for (index, desired) in cell_sizes {
+        if let Some(GridTrack::Auto) = tracks.get(index) {
+            sizes[index] = sizes[index].max(desired);
+        }
+    }
+
+    let total_fraction: f32 = tracks
+        .iter()
+        .map(|track| match track {
+            GridTrack::Fraction(f) => f.max(0.0),
+            _ => 0.0,
+        })
+        .sum();
+
+    if total_fraction > 0.0 {
+        let used: f32 = sizes.iter().sum::<f32>() + gap * tracks.len().saturating_sub(1) as f32;
+        let leftover = (available - used).max(0.0);
+
+        for (size, track) in sizes.iter_mut().zip(tracks.iter()) {
+            if let GridTrack::Fraction(f) = track {
+                *size = leftover * (f.max(0.0) / total_fraction);
+            }
+        }
+    }
+
+    sizes
+}
+
+fn track_starts(sizes: &[f32], start: f32, gap: f32) -> Vec<f32> {
+    let mut pos = start;
+    sizes
+        .iter()
+        .map(|size| {
+            let track_start = pos;
+            pos += size + gap;
+            track_start
+        })
+        .collect()
+}
@@ -21,9 +21,12 @@ pub mod elements;
 pub mod event;
 pub mod layout;
 pub mod prelude;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
 pub(crate) mod stmpsc_queue;
 pub mod style;
 pub mod theme;
+pub mod view;
 pub mod window;
 
 pub use action_queue::action_channel;
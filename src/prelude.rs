@@ -6,24 +6,46 @@ pub use crate::element_system::{
         Element, ElementBuilder, ElementContext, ElementFlags, ElementHandle, ElementRenderCache,
         ElementStyle, RenderContext,
     },
-    ScissorRectID, TooltipInfo,
+    AccessAction, AccessNode, AccessRole, AccessValue, ScissorRectID, TooltipInfo,
 };
 pub use crate::elements::button::{Button, ButtonStyle};
 pub use crate::elements::click_area::ClickArea;
+pub use crate::elements::combo_box::{ComboBox, ComboBoxStyle};
 pub use crate::elements::drop_down_menu::{DropDownMenu, DropDownMenuStyle, MenuEntry};
+#[cfg(feature = "tessellation")]
+pub use crate::elements::envelope_editor::{
+    curvature_shape, EnvelopeEditor, EnvelopeEditorConfig, EnvelopeEditorStyle, EnvelopeMarkerKind,
+    EnvelopeMarkerUpdate, EnvelopePoint, EnvelopePointUpdate, EnvelopeView,
+};
+#[cfg(feature = "tessellation")]
+pub use crate::elements::eq_curve::{
+    BiquadCoefficients, EqBand, EqBandParam, EqBandRightClickInfo, EqCurve, EqCurveConfig,
+    EqCurveStyle, EqFilterType,
+};
 #[cfg(feature = "svg-icons")]
 pub use crate::elements::icon::{Icon, IconStyle};
 pub use crate::elements::label::{Label, LabelStyle, TextIconLayout};
+pub use crate::elements::menu_bar::{MenuBar, MenuBarMenu, MenuBarStyle};
+pub use crate::elements::meter::{
+    Meter, MeterColorStop, MeterConfig, MeterLevel, MeterLevelFill, MeterStyle,
+};
+pub use crate::elements::number_field::{NumberField, NumberFieldFormat, NumberFieldStyle};
 pub use crate::elements::paragraph::{Paragraph, ParagraphStyle};
 pub use crate::elements::quad::QuadElement;
 pub use crate::elements::radio_button::{RadioButton, RadioButtonGroup, RadioButtonStyle};
 pub use crate::elements::resize_handle::{ResizeHandle, ResizeHandleLayout, ResizeHandleStyle};
 pub use crate::elements::scroll_area::{ScrollArea, ScrollBarStyle};
 pub use crate::elements::separator::{Separator, SeparatorSizeType, SeparatorStyle};
+#[cfg(feature = "tessellation")]
+pub use crate::elements::spectrum::curve::{Spectrum, SpectrumStyle};
+pub use crate::elements::spectrum::{
+    SpectrumAnalyzer, SpectrumConfig, SpectrumRenderInfo, SpectrumRenderer,
+};
 pub use crate::elements::switch::{Switch, SwitchStyle};
 pub use crate::elements::tab::{IndicatorLinePlacement, Tab, TabGroup, TabGroupOption, TabStyle};
 pub use crate::elements::text_input::{
-    FloatingTextInput, TextInput, TextInputAction, TextInputStyle,
+    FloatingTextInput, HexValidator, NumericValidator, TextEditor, TextEditorLayout,
+    TextEditorStyle, TextInput, TextInputAction, TextInputStyle, TextValidator,
 };
 #[cfg(feature = "svg-icons")]
 pub use crate::elements::text_input::{IconTextInput, IconTextInputStyle};
@@ -43,11 +65,18 @@ pub use crate::elements::virtual_slider::slider::{
     Slider, SliderFillMode, SliderStyle, SliderStyleModern,
 };
 pub use crate::elements::virtual_slider::{
-    param_normal_to_quantized, param_quantized_to_normal, AutomationInfo, GestureState,
-    ParamElementTooltipInfo, ParamInfo, ParamMarker, ParamMarkersConfig, ParamOpenTextEntryInfo,
-    ParamRightClickInfo, ParamUpdate, ParamValue, ParamerMarkerType, SteppedValue, VirtualSlider,
+    param_normal_to_quantized, param_quantized_to_normal, parse_value_with_unit, AutomationInfo,
+    DecibelFormatter, EnumFormatter, FrequencyFormatter, GestureState, LinearFormatter,
+    ModulationDepthUpdate, ModulationSource, ParamElementTooltipInfo, ParamFormatter, ParamInfo,
+    ParamMarker, ParamMarkersConfig, ParamOpenTextEntryInfo, ParamRightClickInfo, ParamUpdate,
+    ParamValue, ParamerMarkerType, PercentFormatter, SteppedValue, VirtualSlider,
     VirtualSliderConfig,
 };
+#[cfg(feature = "tessellation")]
+pub use crate::elements::waveform::{
+    Waveform, WaveformConfig, WaveformPeaks, WaveformRegionKind, WaveformRegionUpdate,
+    WaveformStyle, WaveformView,
+};
 pub use crate::event::*;
 pub use crate::layout::*;
 pub use crate::math::{
@@ -0,0 +1,293 @@
+//! Golden-image snapshot testing.
+//!
+//! Render a [`HeadlessApp`](crate::window::HeadlessApp) with
+//! [`HeadlessApp::screenshot`](crate::window::HeadlessApp::screenshot) and
+//! then compare the result against a stored PNG with [`assert_snapshot`].
+//!
+//! If the environment variable `YARROW_UPDATE_SNAPSHOTS` is set, then the
+//! stored images will be overwritten instead of compared against.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use crate::window::RgbaImage;
+
+/// The environment variable which, when set, causes [`assert_snapshot`] to
+/// overwrite the stored images.
+pub const UPDATE_SNAPSHOTS_ENV_VAR: &str = "YARROW_UPDATE_SNAPSHOTS";
+
+// The maximum possible value returned by `perceptual_delta`.
+const MAX_YIQ_DELTA: f32 = 35215.0;
+
+/// How much two images are allowed to differ and still be considered
+/// the same.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotTolerance {
+    /// How different the color of two pixels can be before they are counted
+    /// as differing, in the range `[0.0, 1.0]`. The difference is measured
+    /// in the perceptual YIQ color space, so small changes in hue that are
+    /// hard to see are weighted less than changes in brightness.
+    ///
+    /// By default this is set to `0.1`.
+    pub color_threshold: f32,
+
+    /// The fraction of pixels in the range `[0.0, 1.0]` that are allowed to
+    /// differ. This gives some leeway for differences in anti-aliasing
+    /// between GPU drivers.
+    ///
+    /// By default this is set to `0.001` (0.1%).
+    pub max_differing_pixels: f32,
+}
+
+impl Default for SnapshotTolerance {
+    fn default() -> Self {
+        Self {
+            color_threshold: 0.1,
+            max_differing_pixels: 0.001,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SnapshotError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("{0}")]
+    PngDecoding(#[from] png::DecodingError),
+    #[error("{0}")]
+    PngEncoding(#[from] png::EncodingError),
+    #[error("Only 8 bit RGBA PNG images are supported")]
+    UnsupportedPngFormat,
+    #[error("No stored snapshot at {stored:?}. The new image was written to {new:?}. Set {UPDATE_SNAPSHOTS_ENV_VAR}=1 to accept it.")]
+    Missing { stored: PathBuf, new: PathBuf },
+    #[error("Snapshot {stored:?} is {expected_width}x{expected_height} but the new image is {actual_width}x{actual_height}")]
+    SizeMismatch {
+        stored: PathBuf,
+        expected_width: u32,
+        expected_height: u32,
+        actual_width: u32,
+        actual_height: u32,
+    },
+    #[error("Snapshot {stored:?} does not match: {num_differing_pixels} of {total_pixels} pixels differ. See {diff:?} for the differences.")]
+    Mismatch {
+        stored: PathBuf,
+        diff: PathBuf,
+        num_differing_pixels: usize,
+        total_pixels: usize,
+    },
+}
+
+/// The result of comparing two images.
+#[derive(Debug, Clone)]
+pub struct ImageDiff {
+    pub num_differing_pixels: usize,
+    pub total_pixels: usize,
+    /// A faded copy of the expected image where differing pixels are
+    /// highlighted in red.
+    pub diff_image: RgbaImage,
+}
+
+impl ImageDiff {
+    /// Returns `true` if the images are considered to be the same with
+    /// the given tolerance.
+    pub fn passes(&self, tolerance: &SnapshotTolerance) -> bool {
+        if self.total_pixels == 0 {
+            return true;
+        }
+
+        (self.num_differing_pixels as f32 / self.total_pixels as f32)
+            <= tolerance.max_differing_pixels
+    }
+}
+
+/// Compare two images of the same size.
+///
+/// Returns `None` if the images are not the same size.
+pub fn compare_images(
+    expected: &RgbaImage,
+    actual: &RgbaImage,
+    tolerance: &SnapshotTolerance,
+) -> Option<ImageDiff> {
+    if expected.width != actual.width || expected.height != actual.height {
+        return None;
+    }
+
+    let max_delta = MAX_YIQ_DELTA * tolerance.color_threshold * tolerance.color_threshold;
+
+    let mut num_differing_pixels = 0;
+    let mut diff_pixels = Vec::with_capacity(expected.pixels.len());
+
+    for (e, a) in expected
+        .pixels
+        .chunks_exact(4)
+        .zip(actual.pixels.chunks_exact(4))
+    {
+        let e = [e[0], e[1], e[2], e[3]];
+        let a = [a[0], a[1], a[2], a[3]];
+
+        if perceptual_delta(e, a) > max_delta {
+            num_differing_pixels += 1;
+            diff_pixels.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            // Fade out the matching pixels so the differences stand out.
+            let y = rgb_to_y([e[0] as f32, e[1] as f32, e[2] as f32]);
+            let y = blend_on_white(y, 0.1 * e[3] as f32 / 255.0) as u8;
+            diff_pixels.extend_from_slice(&[y, y, y, 255]);
+        }
+    }
+
+    Some(ImageDiff {
+        num_differing_pixels,
+        total_pixels: (expected.width * expected.height) as usize,
+        diff_image: RgbaImage {
+            width: expected.width,
+            height: expected.height,
+            pixels: diff_pixels,
+        },
+    })
+}
+
+/// Compare the given image against the stored snapshot `<dir>/<name>.png`.
+///
+/// * If the stored snapshot does not exist, then the image is written to
+/// `<dir>/<name>.new.png` and an error is returned.
+/// * If the images differ, then the image is written to `<dir>/<name>.new.png`,
+/// an image highlighting the differences is written to `<dir>/<name>.diff.png`,
+/// and an error is returned.
+/// * If the environment variable `YARROW_UPDATE_SNAPSHOTS` is set, then the
+/// stored snapshot is overwritten with the image.
+pub fn assert_snapshot(
+    dir: impl AsRef<Path>,
+    name: &str,
+    image: &RgbaImage,
+    tolerance: &SnapshotTolerance,
+) -> Result<(), SnapshotError> {
+    let dir = dir.as_ref();
+    let stored = dir.join(format!("{name}.png"));
+    let new = dir.join(format!("{name}.new.png"));
+    let diff = dir.join(format!("{name}.diff.png"));
+
+    // Clean up the results of previous runs.
+    let _ = std::fs::remove_file(&new);
+    let _ = std::fs::remove_file(&diff);
+
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV_VAR).is_some() {
+        std::fs::create_dir_all(dir)?;
+        return save_png(&stored, image);
+    }
+
+    if !stored.exists() {
+        std::fs::create_dir_all(dir)?;
+        save_png(&new, image)?;
+        return Err(SnapshotError::Missing { stored, new });
+    }
+
+    let expected = load_png(&stored)?;
+
+    let Some(image_diff) = compare_images(&expected, image, tolerance) else {
+        save_png(&new, image)?;
+        return Err(SnapshotError::SizeMismatch {
+            stored,
+            expected_width: expected.width,
+            expected_height: expected.height,
+            actual_width: image.width,
+            actual_height: image.height,
+        });
+    };
+
+    if image_diff.passes(tolerance) {
+        return Ok(());
+    }
+
+    save_png(&new, image)?;
+    save_png(&diff, &image_diff.diff_image)?;
+
+    Err(SnapshotError::Mismatch {
+        stored,
+        diff,
+        num_differing_pixels: image_diff.num_differing_pixels,
+        total_pixels: image_diff.total_pixels,
+    })
+}
+
+/// Load an 8 bit RGBA PNG image.
+pub fn load_png(path: impl AsRef<Path>) -> Result<RgbaImage, SnapshotError> {
+    let decoder = png::Decoder::new(File::open(path)?);
+    let mut reader = decoder.read_info()?;
+
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels)?;
+
+    if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+        return Err(SnapshotError::UnsupportedPngFormat);
+    }
+
+    pixels.truncate(info.buffer_size());
+
+    Ok(RgbaImage {
+        width: info.width,
+        height: info.height,
+        pixels,
+    })
+}
+
+/// Save an image as an 8 bit RGBA PNG image.
+pub fn save_png(path: impl AsRef<Path>, image: &RgbaImage) -> Result<(), SnapshotError> {
+    let writer = BufWriter::new(File::create(path)?);
+
+    let mut encoder = png::Encoder::new(writer, image.width, image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&image.pixels)?;
+
+    Ok(())
+}
+
+/// The squared distance between two colors in the YIQ color space, after
+/// blending both colors onto a white background.
+///
+/// Based on "Measuring perceived color difference using YIQ NTSC
+/// transmission color space in mobile applications" by Y. Kotsarenko and
+/// F. Ramos.
+fn perceptual_delta(a: [u8; 4], b: [u8; 4]) -> f32 {
+    if a == b {
+        return 0.0;
+    }
+
+    let blend = |c: [u8; 4]| {
+        let alpha = c[3] as f32 / 255.0;
+        [
+            blend_on_white(c[0] as f32, alpha),
+            blend_on_white(c[1] as f32, alpha),
+            blend_on_white(c[2] as f32, alpha),
+        ]
+    };
+
+    let a = blend(a);
+    let b = blend(b);
+
+    let y = rgb_to_y(a) - rgb_to_y(b);
+    let i = rgb_to_i(a) - rgb_to_i(b);
+    let q = rgb_to_q(a) - rgb_to_q(b);
+
+    0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
+
+fn blend_on_white(c: f32, alpha: f32) -> f32 {
+    255.0 + (c - 255.0) * alpha
+}
+
+fn rgb_to_y(c: [f32; 3]) -> f32 {
+    c[0] * 0.29889531 + c[1] * 0.58662247 + c[2] * 0.11448223
+}
+
+fn rgb_to_i(c: [f32; 3]) -> f32 {
+    c[0] * 0.59597799 - c[1] * 0.27417610 - c[2] * 0.32180189
+}
+
+fn rgb_to_q(c: [f32; 3]) -> f32 {
+    c[0] * 0.21147017 - c[1] * 0.52261711 + c[2] * 0.31114694
+}
@@ -208,6 +208,60 @@ pub const fn quad_style(bg: Background, border: BorderStyle) -> QuadStyle {
     QuadStyle::new(bg, border)
 }
 
+/// The style of the ring drawn around an element when it has keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FocusRingStyle {
+    /// The color of the ring.
+    ///
+    /// By default this is set to `color::TRANSPARENT`.
+    pub color: RGBA8,
+
+    /// The width of the ring in logical points.
+    ///
+    /// By default this is set to `2.0`.
+    pub width: f32,
+
+    /// The radius of the ring in logical points.
+    ///
+    /// By default this is set to `Radius::ZERO`.
+    pub radius: Radius,
+
+    /// How far outside the bounds of the element the ring is drawn, in
+    /// logical points. Negative values draw the ring inside the bounds.
+    ///
+    /// By default this is set to `2.0`.
+    pub offset: f32,
+}
+
+impl Default for FocusRingStyle {
+    fn default() -> Self {
+        Self {
+            color: rootvg::color::TRANSPARENT,
+            width: 2.0,
+            radius: Radius::ZERO,
+            offset: 2.0,
+        }
+    }
+}
+
+impl FocusRingStyle {
+    pub fn is_transparent(&self) -> bool {
+        self.width == 0.0 || self.color == rootvg::color::TRANSPARENT
+    }
+
+    /// Create the primitive for the ring around an element with the given
+    /// bounds.
+    pub fn create_primitive(&self, bounds: Rect) -> QuadPrimitive {
+        QuadStyle {
+            bg: Background::TRANSPARENT,
+            border: BorderStyle::new(self.color, self.width, self.radius),
+            flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        }
+        .create_primitive(bounds.inflate(self.offset, self.offset))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum QuadStyleDisabled {
     /// Use a multipler on the alpha channel for all colors.
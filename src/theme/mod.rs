@@ -6,3 +6,11 @@ pub const DEFAULT_ACCENT_COLOR: RGBA8 = RGBA8::new(179, 123, 95, 255);
 pub const DEFAULT_ACCENT_HOVER_COLOR: RGBA8 = RGBA8::new(200, 137, 106, 255);
 pub const DEFAULT_DISABLED_ALPHA_MULTIPLIER: f32 = 0.5;
 pub const DEFAULT_ICON_SIZE: f32 = 20.0;
+/// The colors used in order for modulation sources that don't define their
+/// own color.
+pub const DEFAULT_MODULATION_COLORS: [RGBA8; 4] = [
+    RGBA8::new(86, 182, 194, 255),
+    RGBA8::new(152, 118, 214, 255),
+    RGBA8::new(122, 190, 108, 255),
+    RGBA8::new(214, 178, 82, 255),
+];
@@ -26,6 +26,9 @@ pub const TOGGLE_OFF_BG_COLOR: RGBA8 = gray(27);
 pub const TOGGLE_OFF_BG_COLOR_HOVER: RGBA8 = gray(40);
 
 pub const TEXT_INPUT_BG_COLOR: RGBA8 = gray(24);
+pub const TEXT_INPUT_INVALID_COLOR: RGBA8 = RGBA8::new(224, 82, 82, 255);
+pub const NUMBER_FIELD_FILL_COLOR: RGBA8 = gray_a(255, 16);
+pub const NUMBER_FIELD_FILL_COLOR_HOVER: RGBA8 = gray_a(255, 24);
 pub const DROPDOWN_BG_COLOR: RGBA8 = gray(27);
 pub const DROPDOWN_BORDER_COLOR: RGBA8 = gray(105);
 
@@ -38,11 +41,57 @@ pub const SCROLL_BAR_COLOR_HOVER: RGBA8 = gray_a(255, 145);
 
 pub const SEPERATOR_COLOR: RGBA8 = gray_a(255, 20);
 
+pub const METER_TRACK_COLOR: RGBA8 = gray(24);
+pub const METER_LOW_COLOR: RGBA8 = RGBA8::new(86, 190, 118, 255);
+pub const METER_MID_COLOR: RGBA8 = RGBA8::new(214, 196, 86, 255);
+pub const METER_HIGH_COLOR: RGBA8 = RGBA8::new(226, 96, 72, 255);
+pub const METER_CLIP_OFF_COLOR: RGBA8 = gray(44);
+pub const METER_CLIP_ON_COLOR: RGBA8 = RGBA8::new(240, 64, 52, 255);
+pub const METER_TICK_COLOR: RGBA8 = gray_a(255, 60);
+
+pub const WAVEFORM_BG_COLOR: RGBA8 = gray(24);
+pub const WAVEFORM_CENTER_LINE_COLOR: RGBA8 = gray_a(255, 24);
+pub const WAVEFORM_SELECTION_COLOR: RGBA8 = gray_a(255, 36);
+pub const WAVEFORM_SELECTION_EDGE_COLOR: RGBA8 = gray_a(255, 140);
+pub const WAVEFORM_LOOP_COLOR: RGBA8 = RGBA8::new(86, 190, 118, 28);
+pub const WAVEFORM_LOOP_EDGE_COLOR: RGBA8 = RGBA8::new(86, 190, 118, 180);
+
+pub const SPECTRUM_BG_COLOR: RGBA8 = gray(24);
+pub const SPECTRUM_GRID_COLOR: RGBA8 = gray_a(255, 16);
+pub const SPECTRUM_FILL_ALPHA: u8 = 40;
+
+pub const EQ_CURVE_BG_COLOR: RGBA8 = gray(24);
+pub const EQ_CURVE_GRID_COLOR: RGBA8 = gray_a(255, 16);
+pub const EQ_CURVE_FILL_ALPHA: u8 = 32;
+pub const EQ_CURVE_BAND_CURVE_COLOR: RGBA8 = gray_a(255, 40);
+pub const EQ_CURVE_NODE_BORDER_COLOR: RGBA8 = gray(24);
+
+pub const ENVELOPE_BG_COLOR: RGBA8 = gray(24);
+pub const ENVELOPE_GRID_COLOR: RGBA8 = gray_a(255, 16);
+pub const ENVELOPE_FILL_ALPHA: u8 = 32;
+pub const ENVELOPE_HANDLE_COLOR: RGBA8 = gray_a(255, 70);
+pub const ENVELOPE_SUSTAIN_COLOR: RGBA8 = RGBA8::new(214, 188, 86, 200);
+pub const ENVELOPE_POINT_BORDER_COLOR: RGBA8 = gray(24);
+
 pub const PANEL_BG_COLOR: RGBA8 = gray(33);
 
 pub const BORDER_WIDTH: f32 = 1.0;
 pub const BORDER_RADIUS: f32 = 4.0;
 
+pub const FOCUS_RING_WIDTH: f32 = 2.0;
+pub const FOCUS_RING_OFFSET: f32 = 2.0;
+
+/// The ring drawn around an element with keyboard focus, where `radius` is
+/// the border radius of the element itself.
+pub fn focus_ring(config: &Config, radius: f32) -> FocusRingStyle {
+    FocusRingStyle {
+        color: config.accent_color,
+        width: FOCUS_RING_WIDTH,
+        radius: (radius + FOCUS_RING_OFFSET).into(),
+        offset: FOCUS_RING_OFFSET,
+    }
+}
+
 pub fn button(config: &Config) -> ButtonStyle {
     ButtonStyle {
         text_properties: TextProperties {
@@ -63,6 +112,7 @@ pub fn button(config: &Config) -> ButtonStyle {
         back_border_width: BORDER_WIDTH,
         back_border_radius: config.radius.into(),
         cursor_icon: Some(CursorIcon::Pointer),
+        focus_ring: focus_ring(config, config.radius),
         ..Default::default()
     }
 }
@@ -111,6 +161,7 @@ pub fn toggle_button(config: &Config) -> ToggleButtonStyle {
         back_border_width: BORDER_WIDTH,
         back_border_radius: config.radius.into(),
         cursor_icon: Some(CursorIcon::Pointer),
+        focus_ring: focus_ring(config, config.radius),
         ..Default::default()
     }
 }
@@ -126,6 +177,7 @@ pub fn switch(config: &Config) -> SwitchStyle {
         on_bg_hover: Some(background(config.accent_color_hover)),
         slider_bg_off: background(RGBA8::new(255, 255, 255, 180)),
         cursor_icon: Some(CursorIcon::Pointer),
+        focus_ring: focus_ring(config, SwitchStyle::default().rounding),
         ..Default::default()
     }
 }
@@ -143,6 +195,7 @@ pub fn radio_btn(config: &Config) -> RadioButtonStyle {
         dot_bg: background(TEXT_COLOR),
         dot_bg_hover: Some(background(TEXT_COLOR_BRIGHT)),
         cursor_icon: Some(CursorIcon::Pointer),
+        focus_ring: focus_ring(config, RadioButtonStyle::default().size),
         ..Default::default()
     }
 }
@@ -184,8 +237,27 @@ pub fn text_input(config: &Config) -> TextInputStyle {
         back_border_color: BUTTON_BORDER_COLOR,
         back_border_color_hover: Some(BUTTON_BORDER_COLOR_HOVER),
         back_border_color_focused: Some(BUTTON_BORDER_COLOR_HOVER),
+        back_border_color_invalid: Some(TEXT_INPUT_INVALID_COLOR),
         back_border_width: 1.0,
         back_border_radius: config.radius.into(),
+        focus_ring: focus_ring(config, config.radius),
+        ..Default::default()
+    }
+}
+
+pub fn text_editor(config: &Config) -> TextEditorStyle {
+    TextEditorStyle {
+        text_input: text_input(config),
+        line_number_color: Some(TEXT_COLOR_DIMMED),
+        ..Default::default()
+    }
+}
+
+pub fn number_field(config: &Config) -> NumberFieldStyle {
+    NumberFieldStyle {
+        text_input: text_input(config),
+        fill_bg: background(NUMBER_FIELD_FILL_COLOR),
+        fill_bg_hover: Some(background(NUMBER_FIELD_FILL_COLOR_HOVER)),
         ..Default::default()
     }
 }
@@ -260,6 +332,126 @@ pub fn separator() -> SeparatorStyle {
     }
 }
 
+pub fn meter(config: &Config) -> MeterStyle {
+    MeterStyle {
+        track_quad: QuadStyle::new(background(METER_TRACK_COLOR), BorderStyle::default()),
+        level_colors: vec![
+            MeterColorStop::new(f32::NEG_INFINITY, METER_LOW_COLOR),
+            MeterColorStop::new(-18.0, METER_MID_COLOR),
+            MeterColorStop::new(-6.0, METER_HIGH_COLOR),
+        ],
+        level_fill: MeterLevelFill::Gradient,
+        peak_hold_color: Some(TEXT_COLOR_BRIGHT),
+        clip_indicator_off: QuadStyle::new(
+            background(METER_CLIP_OFF_COLOR),
+            BorderStyle::default(),
+        ),
+        clip_indicator_on: QuadStyle::new(background(METER_CLIP_ON_COLOR), BorderStyle::default()),
+        tick_color: METER_TICK_COLOR,
+        text_properties: TextProperties {
+            metrics: Metrics {
+                font_size: 10.0,
+                line_height: 12.0,
+            },
+            attrs: config.text_attrs,
+            ..Default::default()
+        },
+        text_color: TEXT_COLOR_DIMMED,
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "tessellation")]
+pub fn spectrum(config: &Config) -> SpectrumStyle {
+    SpectrumStyle {
+        back_quad: QuadStyle::new(background(SPECTRUM_BG_COLOR), BorderStyle::default()),
+        line_color: config.accent_color,
+        fill_color: RGBA8 {
+            a: SPECTRUM_FILL_ALPHA,
+            ..config.accent_color
+        },
+        peak_color: TEXT_COLOR_DIMMED,
+        grid_color: SPECTRUM_GRID_COLOR,
+        text_properties: TextProperties {
+            metrics: Metrics {
+                font_size: 10.0,
+                line_height: 12.0,
+            },
+            attrs: config.text_attrs,
+            ..Default::default()
+        },
+        text_color: TEXT_COLOR_DIMMED,
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "tessellation")]
+pub fn eq_curve(config: &Config) -> EqCurveStyle {
+    EqCurveStyle {
+        back_quad: QuadStyle::new(background(EQ_CURVE_BG_COLOR), BorderStyle::default()),
+        grid_color: EQ_CURVE_GRID_COLOR,
+        curve_color: config.accent_color,
+        fill_color: RGBA8 {
+            a: EQ_CURVE_FILL_ALPHA,
+            ..config.accent_color
+        },
+        band_curve_color: EQ_CURVE_BAND_CURVE_COLOR,
+        node_color: TEXT_COLOR,
+        node_color_hover: TEXT_COLOR_BRIGHT,
+        node_color_active: config.accent_color_hover,
+        node_color_bypassed: TEXT_COLOR_DIMMED,
+        node_border: BorderStyle {
+            color: EQ_CURVE_NODE_BORDER_COLOR,
+            width: BORDER_WIDTH,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "tessellation")]
+pub fn envelope_editor(config: &Config) -> EnvelopeEditorStyle {
+    EnvelopeEditorStyle {
+        back_quad: QuadStyle::new(background(ENVELOPE_BG_COLOR), BorderStyle::default()),
+        grid_color: ENVELOPE_GRID_COLOR,
+        line_color: config.accent_color,
+        fill_color: RGBA8 {
+            a: ENVELOPE_FILL_ALPHA,
+            ..config.accent_color
+        },
+        point_color: TEXT_COLOR,
+        point_color_hover: TEXT_COLOR_BRIGHT,
+        point_color_active: config.accent_color_hover,
+        point_border: BorderStyle {
+            color: ENVELOPE_POINT_BORDER_COLOR,
+            width: BORDER_WIDTH,
+            ..Default::default()
+        },
+        handle_color: ENVELOPE_HANDLE_COLOR,
+        handle_color_hover: TEXT_COLOR,
+        handle_color_active: config.accent_color_hover,
+        sustain_color: ENVELOPE_SUSTAIN_COLOR,
+        loop_color: WAVEFORM_LOOP_COLOR,
+        loop_edge_color: WAVEFORM_LOOP_EDGE_COLOR,
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "tessellation")]
+pub fn waveform(config: &Config) -> WaveformStyle {
+    WaveformStyle {
+        back_quad: QuadStyle::new(background(WAVEFORM_BG_COLOR), BorderStyle::default()),
+        wave_color: config.accent_color,
+        center_line_color: WAVEFORM_CENTER_LINE_COLOR,
+        selection_color: WAVEFORM_SELECTION_COLOR,
+        selection_edge_color: WAVEFORM_SELECTION_EDGE_COLOR,
+        loop_color: WAVEFORM_LOOP_COLOR,
+        loop_edge_color: WAVEFORM_LOOP_EDGE_COLOR,
+        playhead_color: TEXT_COLOR_BRIGHT,
+        ..Default::default()
+    }
+}
+
 pub fn dropdown_menu(config: &Config) -> DropDownMenuStyle {
     DropDownMenuStyle {
         text_properties: TextProperties {
@@ -270,6 +462,7 @@ pub fn dropdown_menu(config: &Config) -> DropDownMenuStyle {
         icon_size: config.default_icon_size,
         text_color: TEXT_COLOR,
         text_color_hover: Some(TEXT_COLOR_BRIGHT),
+        header_text_color: Some(TEXT_COLOR_DIMMED),
         back_quad: QuadStyle {
             bg: background(DROPDOWN_BG_COLOR),
             border: border(DROPDOWN_BORDER_COLOR, 1.0, config.radius.into()),
@@ -293,6 +486,42 @@ pub fn dropdown_menu(config: &Config) -> DropDownMenuStyle {
     }
 }
 
+pub fn combo_box(config: &Config) -> ComboBoxStyle {
+    ComboBoxStyle {
+        button_style: ButtonStyle {
+            text_padding: padding(6.0, 0.0, 6.0, 7.0),
+            ..button(config)
+        },
+        indicator_padding: padding(6.0, 7.0, 6.0, 4.0),
+        ..Default::default()
+    }
+}
+
+pub fn menu_bar(config: &Config) -> MenuBarStyle {
+    MenuBarStyle {
+        text_properties: TextProperties {
+            metrics: config.text_metrics,
+            attrs: config.text_attrs,
+            ..Default::default()
+        },
+        text_color: TEXT_COLOR,
+        text_color_hover: Some(TEXT_COLOR_BRIGHT),
+        back_quad: QuadStyle {
+            bg: background(PANEL_BG_COLOR),
+            border: BorderStyle::default(),
+            flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        },
+        title_bg_quad_hover: QuadStyle {
+            bg: background(BUTTON_BG_HOVER_COLOR),
+            border: border_radius_only(config.radius.into()),
+            flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        },
+        title_padding: padding_vh(0.0, 8.0),
+        outer_padding: 2.0,
+        cursor_icon: Some(CursorIcon::Pointer),
+    }
+}
+
 pub fn label(config: &Config) -> LabelStyle {
     LabelStyle {
         text_properties: TextProperties {
@@ -349,6 +578,17 @@ pub fn slider_style_modern(
         handle_height: SizeType::FixedPoints(8.0),
         handle_padding: Padding::new(2.0, 2.0, 2.0, 2.0),
         fill_padding: Padding::new(3.0, 5.0, 3.0, 5.0),
+        focus_ring: FocusRingStyle {
+            color: accent_color,
+            width: FOCUS_RING_WIDTH,
+            radius: (radius + FOCUS_RING_OFFSET).into(),
+            offset: FOCUS_RING_OFFSET,
+        },
+        modulation_indicator: QuadStyle {
+            bg: background(TEXT_COLOR_BRIGHT),
+            border: Default::default(),
+            flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        },
         ..Default::default()
     }
 }
@@ -423,6 +663,12 @@ pub fn knob_style(
                 KnobMarkersStyle::None
             }
         },
+        focus_ring: FocusRingStyle {
+            color: accent_color,
+            width: FOCUS_RING_WIDTH,
+            radius: Radius::CIRCLE,
+            offset: FOCUS_RING_OFFSET,
+        },
         ..Default::default()
     }
 }
@@ -466,12 +712,22 @@ pub fn load(config: Config, res: &mut ResourceCtx) {
     res.style_system.add(ClassID::default(), true, scroll_bar());
     res.style_system
         .add(ClassID::default(), true, text_input(&config));
+    res.style_system
+        .add(ClassID::default(), true, text_editor(&config));
+    res.style_system
+        .add(ClassID::default(), true, number_field(&config));
     res.style_system.add(ClassID::default(), true, tab(&config));
     res.style_system
         .add(ClassID::default(), true, tooltip(&config));
     res.style_system.add(ClassID::default(), true, separator());
+    res.style_system
+        .add(ClassID::default(), true, meter(&config));
     res.style_system
         .add(ClassID::default(), true, dropdown_menu(&config));
+    res.style_system
+        .add(ClassID::default(), true, combo_box(&config));
+    res.style_system
+        .add(ClassID::default(), true, menu_bar(&config));
     res.style_system
         .add(ClassID::default(), true, label(&config));
     res.style_system
@@ -496,4 +752,16 @@ pub fn load(config: Config, res: &mut ResourceCtx) {
     #[cfg(feature = "svg-icons")]
     res.style_system
         .add(ClassID::default(), true, icon_text_input(&config));
+    #[cfg(feature = "tessellation")]
+    res.style_system
+        .add(ClassID::default(), true, spectrum(&config));
+    #[cfg(feature = "tessellation")]
+    res.style_system
+        .add(ClassID::default(), true, waveform(&config));
+    #[cfg(feature = "tessellation")]
+    res.style_system
+        .add(ClassID::default(), true, eq_curve(&config));
+    #[cfg(feature = "tessellation")]
+    res.style_system
+        .add(ClassID::default(), true, envelope_editor(&config));
 }
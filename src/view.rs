@@ -0,0 +1,869 @@
+//! An optional declarative layer on top of the element handles.
+//!
+//! Instead of storing every element handle in the application struct and
+//! calling their setters manually, an application can describe its UI as a
+//! tree of views built from its current state, and a [`ViewRoot`] will diff
+//! that tree against the previous one. Elements are only created, updated,
+//! or dropped when something has actually changed, so the retained-mode
+//! performance of the underlying elements is kept.
+//!
+//! ```ignore
+//! fn view(&self) -> Vec<ViewNode<MyAction>> {
+//!     vec![
+//!         view::label(format!("Count: {}", self.count))
+//!             .rect(rect(10.0, 10.0, 100.0, 30.0))
+//!             .into(),
+//!         view::button("Increment")
+//!             .on_select(MyAction::Increment)
+//!             .rect(rect(10.0, 50.0, 100.0, 30.0))
+//!             .into(),
+//!     ]
+//! }
+//!
+//! // Whenever the state of the application changes:
+//! self.view_root.update(self.view(), &mut cx.main_window());
+//! ```
+//!
+//! Children are matched with the previous tree by their key (see
+//! [`ViewNode::key`]) if they have one, or by their position otherwise.
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use smol_str::SmolStr;
+
+use crate::elements::button::Button;
+use crate::elements::label::Label;
+use crate::elements::switch::Switch;
+use crate::elements::toggle_button::ToggleButton;
+use crate::math::{Rect, ZIndex};
+use crate::style::ClassID;
+use crate::{ScissorRectID, WindowContext};
+
+/// A description of an element (or a group of elements) in a view tree.
+pub trait View<A: Clone + 'static>: 'static {
+    /// The retained state of this view, usually the handle of the element
+    /// it creates.
+    type State: 'static;
+
+    /// Create the element(s) described by this view.
+    fn build(&mut self, window_cx: &mut WindowContext<'_, A>) -> Self::State;
+
+    /// Update the element(s) created by `prev` to match this view.
+    ///
+    /// If a property cannot be changed on an existing element, then the
+    /// element may be re-created by replacing `state`.
+    fn update(
+        &mut self,
+        prev: &Self,
+        state: &mut Self::State,
+        window_cx: &mut WindowContext<'_, A>,
+    );
+}
+
+trait AnyView<A: Clone + 'static> {
+    fn as_any(&self) -> &dyn Any;
+
+    fn view_type_id(&self) -> TypeId;
+
+    fn build_any(&mut self, window_cx: &mut WindowContext<'_, A>) -> Box<dyn Any>;
+
+    fn update_any(
+        &mut self,
+        prev: &dyn AnyView<A>,
+        state: &mut Box<dyn Any>,
+        window_cx: &mut WindowContext<'_, A>,
+    );
+}
+
+impl<A: Clone + 'static, V: View<A>> AnyView<A> for V {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn view_type_id(&self) -> TypeId {
+        TypeId::of::<V>()
+    }
+
+    fn build_any(&mut self, window_cx: &mut WindowContext<'_, A>) -> Box<dyn Any> {
+        Box::new(self.build(window_cx))
+    }
+
+    fn update_any(
+        &mut self,
+        prev: &dyn AnyView<A>,
+        state: &mut Box<dyn Any>,
+        window_cx: &mut WindowContext<'_, A>,
+    ) {
+        // The types are always checked before calling this method.
+        let prev = prev.as_any().downcast_ref::<V>().unwrap();
+        let state = state.downcast_mut::<V::State>().unwrap();
+
+        self.update(prev, state, window_cx);
+    }
+}
+
+/// A type-erased node in a view tree.
+pub struct ViewNode<A: Clone + 'static> {
+    key: Option<SmolStr>,
+    view: Box<dyn AnyView<A>>,
+}
+
+impl<A: Clone + 'static> ViewNode<A> {
+    pub fn new(view: impl View<A>) -> Self {
+        Self {
+            key: None,
+            view: Box::new(view),
+        }
+    }
+
+    /// Give this node a key which uniquely identifies it among its siblings.
+    ///
+    /// Keyed nodes keep their element when their position in the list of
+    /// children changes. Nodes without a key are matched by position.
+    pub fn key(mut self, key: impl Into<SmolStr>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+}
+
+impl<A: Clone + 'static, V: View<A>> From<V> for ViewNode<A> {
+    fn from(view: V) -> Self {
+        Self::new(view)
+    }
+}
+
+struct MountedNode<A: Clone + 'static> {
+    node: ViewNode<A>,
+    state: Box<dyn Any>,
+}
+
+/// The mounted state of a list of sibling nodes.
+struct MountedChildren<A: Clone + 'static> {
+    nodes: Vec<MountedNode<A>>,
+}
+
+impl<A: Clone + 'static> MountedChildren<A> {
+    fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    fn diff(&mut self, new_nodes: Vec<ViewNode<A>>, window_cx: &mut WindowContext<'_, A>) {
+        let mut old_nodes: Vec<Option<MountedNode<A>>> = self.nodes.drain(..).map(Some).collect();
+
+        for (i, mut node) in new_nodes.into_iter().enumerate() {
+            let type_id = node.view.view_type_id();
+
+            let matching = if let Some(key) = &node.key {
+                old_nodes.iter().position(|old| {
+                    old.as_ref().is_some_and(|old| {
+                        old.node.key.as_ref() == Some(key)
+                            && old.node.view.view_type_id() == type_id
+                    })
+                })
+            } else {
+                old_nodes.get(i).and_then(|old| {
+                    old.as_ref()
+                        .is_some_and(|old| {
+                            old.node.key.is_none() && old.node.view.view_type_id() == type_id
+                        })
+                        .then_some(i)
+                })
+            };
+
+            let mounted = if let Some(old) = matching.and_then(|i| old_nodes[i].take()) {
+                let MountedNode {
+                    node: old_node,
+                    mut state,
+                } = old;
+
+                node.view
+                    .update_any(old_node.view.as_ref(), &mut state, window_cx);
+
+                MountedNode { node, state }
+            } else {
+                let state = node.view.build_any(window_cx);
+                MountedNode { node, state }
+            };
+
+            self.nodes.push(mounted);
+        }
+
+        // Any old nodes which were not matched are dropped here, which in turn
+        // drops their element handles and removes the elements.
+    }
+}
+
+/// The root of a view tree.
+///
+/// Dropping this drops all of the elements in the tree.
+pub struct ViewRoot<A: Clone + 'static> {
+    children: MountedChildren<A>,
+}
+
+impl<A: Clone + 'static> ViewRoot<A> {
+    pub fn new() -> Self {
+        Self {
+            children: MountedChildren::new(),
+        }
+    }
+
+    /// Diff the given view tree against the previous one, creating, updating,
+    /// or dropping elements as needed.
+    pub fn update(&mut self, nodes: Vec<ViewNode<A>>, window_cx: &mut WindowContext<'_, A>) {
+        self.children.diff(nodes, window_cx);
+    }
+
+    /// The number of top-level nodes currently mounted.
+    pub fn len(&self) -> usize {
+        self.children.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.nodes.is_empty()
+    }
+}
+
+impl<A: Clone + 'static> Default for ViewRoot<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A view which groups a list of child views, i.e. to give a whole subtree
+/// a key.
+pub struct Group<A: Clone + 'static> {
+    children: Vec<ViewNode<A>>,
+}
+
+pub fn group<A: Clone + 'static>(children: Vec<ViewNode<A>>) -> Group<A> {
+    Group { children }
+}
+
+impl<A: Clone + 'static> View<A> for Group<A> {
+    type State = MountedChildrenState<A>;
+
+    fn build(&mut self, window_cx: &mut WindowContext<'_, A>) -> Self::State {
+        let mut children = MountedChildren::new();
+        children.diff(std::mem::take(&mut self.children), window_cx);
+        MountedChildrenState(children)
+    }
+
+    fn update(
+        &mut self,
+        _prev: &Self,
+        state: &mut Self::State,
+        window_cx: &mut WindowContext<'_, A>,
+    ) {
+        // The previous children are stored in the mounted state.
+        state.0.diff(std::mem::take(&mut self.children), window_cx);
+    }
+}
+
+/// The retained state of a [`Group`].
+pub struct MountedChildrenState<A: Clone + 'static>(MountedChildren<A>);
+
+/// Properties shared by all element views.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+struct CommonProps {
+    rect: Rect,
+    class: ClassID,
+    z_index: Option<ZIndex>,
+    scissor_rect: Option<ScissorRectID>,
+    hidden: bool,
+}
+
+impl CommonProps {
+    /// Returns `true` if the element needs to be re-created because a
+    /// property changed which has no setter.
+    fn needs_rebuild(&self, prev: &Self) -> bool {
+        self.z_index != prev.z_index || self.scissor_rect != prev.scissor_rect
+    }
+}
+
+/// A callback which is shared between a view and the element it built, so
+/// that it can be replaced without re-creating the element.
+type SharedCallback<A> = Rc<RefCell<Box<dyn FnMut(bool) -> A>>>;
+
+fn share_callback<A: 'static>(f: Option<Box<dyn FnMut(bool) -> A>>) -> Option<SharedCallback<A>> {
+    f.map(|f| Rc::new(RefCell::new(f)))
+}
+
+/// Returns the callback to give to the builder of the element.
+fn forward_callback<A: 'static>(f: &SharedCallback<A>) -> impl FnMut(bool) -> A {
+    let f = Rc::clone(f);
+    move |toggled| (RefCell::borrow_mut(&f))(toggled)
+}
+
+/// Replace the callback of an element which was built with
+/// [`forward_callback`].
+///
+/// Returns `false` if the element needs to be re-created because a callback
+/// was added or removed.
+fn update_callback<A>(
+    new: &mut Option<Box<dyn FnMut(bool) -> A>>,
+    mounted: &Option<SharedCallback<A>>,
+) -> bool {
+    match (new.take(), mounted) {
+        (Some(f), Some(mounted)) => {
+            *RefCell::borrow_mut(mounted) = f;
+            true
+        }
+        (None, None) => true,
+        (f, _) => {
+            *new = f;
+            false
+        }
+    }
+}
+
+macro_rules! common_view_methods {
+    () => {
+        pub const fn rect(mut self, rect: Rect) -> Self {
+            self.common.rect = rect;
+            self
+        }
+
+        pub const fn class(mut self, class: ClassID) -> Self {
+            self.common.class = class;
+            self
+        }
+
+        pub const fn z_index(mut self, z_index: ZIndex) -> Self {
+            self.common.z_index = Some(z_index);
+            self
+        }
+
+        pub const fn scissor_rect(mut self, scissor_rect: ScissorRectID) -> Self {
+            self.common.scissor_rect = Some(scissor_rect);
+            self
+        }
+
+        pub const fn hidden(mut self, hidden: bool) -> Self {
+            self.common.hidden = hidden;
+            self
+        }
+    };
+}
+
+macro_rules! apply_common_props {
+    ($builder:expr, $common:expr) => {{
+        let mut builder = $builder
+            .rect($common.rect)
+            .class($common.class)
+            .hidden($common.hidden);
+        if let Some(z_index) = $common.z_index {
+            builder = builder.z_index(z_index);
+        }
+        if let Some(scissor_rect) = $common.scissor_rect {
+            builder = builder.scissor_rect(scissor_rect);
+        }
+        builder
+    }};
+}
+
+macro_rules! update_common_props {
+    ($handle:expr, $common:expr) => {{
+        $handle.set_rect($common.rect);
+        $handle.set_class($common.class);
+        $handle.set_hidden($common.hidden);
+    }};
+}
+
+/// A declarative [`Button`].
+pub struct ButtonView<A: Clone + PartialEq + 'static> {
+    text: String,
+    on_select: Option<A>,
+    disabled: bool,
+    common: CommonProps,
+}
+
+pub fn button<A: Clone + PartialEq + 'static>(text: impl Into<String>) -> ButtonView<A> {
+    ButtonView {
+        text: text.into(),
+        on_select: None,
+        disabled: false,
+        common: CommonProps::default(),
+    }
+}
+
+impl<A: Clone + PartialEq + 'static> ButtonView<A> {
+    pub fn on_select(mut self, action: A) -> Self {
+        self.on_select = Some(action);
+        self
+    }
+
+    pub const fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    common_view_methods!();
+}
+
+impl<A: Clone + PartialEq + 'static> View<A> for ButtonView<A> {
+    type State = Button;
+
+    fn build(&mut self, window_cx: &mut WindowContext<'_, A>) -> Button {
+        apply_common_props!(Button::builder(), self.common)
+            .text(self.text.as_str())
+            .on_select_optional(self.on_select.clone())
+            .disabled(self.disabled)
+            .build(window_cx)
+    }
+
+    fn update(&mut self, prev: &Self, state: &mut Button, window_cx: &mut WindowContext<'_, A>) {
+        if self.common.needs_rebuild(&prev.common) || self.on_select != prev.on_select {
+            *state = self.build(window_cx);
+            return;
+        }
+
+        if self.text != prev.text {
+            state.set_text(Some(self.text.as_str()), window_cx.res);
+        }
+        state.set_disabled(self.disabled);
+        update_common_props!(state, self.common);
+    }
+}
+
+/// A declarative [`Label`].
+pub struct LabelView {
+    text: String,
+    common: CommonProps,
+}
+
+pub fn label(text: impl Into<String>) -> LabelView {
+    LabelView {
+        text: text.into(),
+        common: CommonProps::default(),
+    }
+}
+
+impl LabelView {
+    common_view_methods!();
+}
+
+impl<A: Clone + 'static> View<A> for LabelView {
+    type State = Label;
+
+    fn build(&mut self, window_cx: &mut WindowContext<'_, A>) -> Label {
+        apply_common_props!(Label::builder(), self.common)
+            .text(self.text.as_str())
+            .build(window_cx)
+    }
+
+    fn update(&mut self, prev: &Self, state: &mut Label, window_cx: &mut WindowContext<'_, A>) {
+        if self.common.needs_rebuild(&prev.common) {
+            *state = self.build(window_cx);
+            return;
+        }
+
+        if self.text != prev.text {
+            state.set_text(Some(self.text.as_str()), window_cx.res);
+        }
+        update_common_props!(state, self.common);
+    }
+}
+
+/// A declarative [`ToggleButton`].
+pub struct ToggleButtonView<A: Clone + 'static> {
+    text: String,
+    toggled: bool,
+    on_toggled: Option<Box<dyn FnMut(bool) -> A>>,
+    disabled: bool,
+    common: CommonProps,
+}
+
+pub fn toggle_button<A: Clone + 'static>(
+    text: impl Into<String>,
+    toggled: bool,
+) -> ToggleButtonView<A> {
+    ToggleButtonView {
+        text: text.into(),
+        toggled,
+        on_toggled: None,
+        disabled: false,
+        common: CommonProps::default(),
+    }
+}
+
+impl<A: Clone + 'static> ToggleButtonView<A> {
+    pub fn on_toggled<F: FnMut(bool) -> A + 'static>(mut self, f: F) -> Self {
+        self.on_toggled = Some(Box::new(f));
+        self
+    }
+
+    pub const fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    common_view_methods!();
+}
+
+impl<A: Clone + 'static> View<A> for ToggleButtonView<A> {
+    type State = ToggleableState<ToggleButton, A>;
+
+    fn build(&mut self, window_cx: &mut WindowContext<'_, A>) -> Self::State {
+        let on_toggled = share_callback(self.on_toggled.take());
+
+        let mut builder = apply_common_props!(ToggleButton::builder(), self.common)
+            .text(self.text.as_str())
+            .toggled(self.toggled)
+            .disabled(self.disabled);
+        if let Some(f) = &on_toggled {
+            builder = builder.on_toggled(forward_callback(f));
+        }
+
+        ToggleableState {
+            element: builder.build(window_cx),
+            on_toggled,
+        }
+    }
+
+    fn update(
+        &mut self,
+        prev: &Self,
+        state: &mut Self::State,
+        window_cx: &mut WindowContext<'_, A>,
+    ) {
+        if self.common.needs_rebuild(&prev.common)
+            || !update_callback(&mut self.on_toggled, &state.on_toggled)
+        {
+            *state = self.build(window_cx);
+            return;
+        }
+
+        let element = &mut state.element;
+        if self.text != prev.text {
+            element.set_text(Some(self.text.as_str()), window_cx.res);
+        }
+        element.set_toggled(self.toggled);
+        element.set_disabled(self.disabled);
+        update_common_props!(element, self.common);
+    }
+}
+
+/// A declarative [`Switch`].
+pub struct SwitchView<A: Clone + 'static> {
+    toggled: bool,
+    on_toggled: Option<Box<dyn FnMut(bool) -> A>>,
+    disabled: bool,
+    common: CommonProps,
+}
+
+pub fn switch<A: Clone + 'static>(toggled: bool) -> SwitchView<A> {
+    SwitchView {
+        toggled,
+        on_toggled: None,
+        disabled: false,
+        common: CommonProps::default(),
+    }
+}
+
+impl<A: Clone + 'static> SwitchView<A> {
+    pub fn on_toggled<F: FnMut(bool) -> A + 'static>(mut self, f: F) -> Self {
+        self.on_toggled = Some(Box::new(f));
+        self
+    }
+
+    pub const fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    common_view_methods!();
+}
+
+impl<A: Clone + 'static> View<A> for SwitchView<A> {
+    type State = ToggleableState<Switch, A>;
+
+    fn build(&mut self, window_cx: &mut WindowContext<'_, A>) -> Self::State {
+        let on_toggled = share_callback(self.on_toggled.take());
+
+        let mut builder = apply_common_props!(Switch::builder(), self.common)
+            .toggled(self.toggled)
+            .disabled(self.disabled);
+        if let Some(f) = &on_toggled {
+            builder = builder.on_toggled(forward_callback(f));
+        }
+
+        ToggleableState {
+            element: builder.build(window_cx),
+            on_toggled,
+        }
+    }
+
+    fn update(
+        &mut self,
+        prev: &Self,
+        state: &mut Self::State,
+        window_cx: &mut WindowContext<'_, A>,
+    ) {
+        if self.common.needs_rebuild(&prev.common)
+            || !update_callback(&mut self.on_toggled, &state.on_toggled)
+        {
+            *state = self.build(window_cx);
+            return;
+        }
+
+        let element = &mut state.element;
+        element.set_toggled(self.toggled);
+        element.set_disabled(self.disabled);
+        update_common_props!(element, self.common);
+    }
+}
+
+/// The retained state of a [`ToggleButtonView`] or a [`SwitchView`].
+pub struct ToggleableState<E, A: 'static> {
+    pub element: E,
+    on_toggled: Option<SharedCallback<A>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::prelude::{rect, AppConfig, AppContext, Application, HeadlessApp};
+
+    struct ViewTestApp;
+
+    impl Application for ViewTestApp {
+        type Action = bool;
+
+        fn init(_cx: &mut AppContext<bool>) -> Result<Self, Box<dyn std::error::Error>> {
+            Ok(Self)
+        }
+    }
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    /// A view which logs when its state is built, updated, and dropped.
+    struct TrackedView {
+        name: &'static str,
+        log: Log,
+    }
+
+    struct TrackedState {
+        name: &'static str,
+        log: Log,
+    }
+
+    impl Drop for TrackedState {
+        fn drop(&mut self) {
+            self.log.borrow_mut().push(format!("drop {}", self.name));
+        }
+    }
+
+    impl View<bool> for TrackedView {
+        type State = TrackedState;
+
+        fn build(&mut self, _window_cx: &mut WindowContext<'_, bool>) -> TrackedState {
+            self.log.borrow_mut().push(format!("build {}", self.name));
+            TrackedState {
+                name: self.name,
+                log: Rc::clone(&self.log),
+            }
+        }
+
+        fn update(
+            &mut self,
+            prev: &Self,
+            state: &mut TrackedState,
+            _window_cx: &mut WindowContext<'_, bool>,
+        ) {
+            self.log
+                .borrow_mut()
+                .push(format!("update {} -> {}", prev.name, self.name));
+            state.name = self.name;
+        }
+    }
+
+    fn tracked(name: &'static str, log: &Log) -> ViewNode<bool> {
+        TrackedView {
+            name,
+            log: Rc::clone(log),
+        }
+        .into()
+    }
+
+    fn take_log(log: &Log) -> Vec<String> {
+        std::mem::take(&mut *log.borrow_mut())
+    }
+
+    fn new_app() -> HeadlessApp<ViewTestApp> {
+        HeadlessApp::new(AppConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_keyed_reorder_keeps_elements() {
+        let mut app = new_app();
+        let mut window_cx = app.cx().main_window();
+        let log = Log::default();
+        let mut root = ViewRoot::new();
+
+        root.update(
+            vec![
+                tracked("a", &log).key("a"),
+                tracked("b", &log).key("b"),
+                tracked("c", &log).key("c"),
+            ],
+            &mut window_cx,
+        );
+        assert_eq!(take_log(&log), ["build a", "build b", "build c"]);
+
+        root.update(
+            vec![
+                tracked("c", &log).key("c"),
+                tracked("a", &log).key("a"),
+                tracked("b", &log).key("b"),
+            ],
+            &mut window_cx,
+        );
+        assert_eq!(
+            take_log(&log),
+            ["update c -> c", "update a -> a", "update b -> b"]
+        );
+        assert_eq!(root.len(), 3);
+    }
+
+    #[test]
+    fn test_type_change_rebuilds() {
+        let mut app = new_app();
+        let mut window_cx = app.cx().main_window();
+        let log = Log::default();
+        let mut root = ViewRoot::new();
+
+        root.update(vec![tracked("a", &log), tracked("b", &log)], &mut window_cx);
+        assert_eq!(take_log(&log), ["build a", "build b"]);
+
+        // A different type of view at the same position replaces the old one,
+        // while the unchanged sibling is kept.
+        root.update(vec![label("a").into(), tracked("b", &log)], &mut window_cx);
+        assert_eq!(take_log(&log), ["update b -> b", "drop a"]);
+        assert!(root.children.nodes[0].state.is::<Label>());
+
+        // The same goes for keyed nodes.
+        root.update(
+            vec![label("a").into(), tracked("c", &log).key("b")],
+            &mut window_cx,
+        );
+        take_log(&log);
+        root.update(
+            vec![label("a").into(), ViewNode::from(label("b")).key("b")],
+            &mut window_cx,
+        );
+        assert_eq!(take_log(&log), ["drop c"]);
+        assert!(root.children.nodes[1].state.is::<Label>());
+    }
+
+    #[test]
+    fn test_removed_node_drops_element() {
+        let mut app = new_app();
+        let mut window_cx = app.cx().main_window();
+        let log = Log::default();
+        let mut root = ViewRoot::new();
+
+        root.update(
+            vec![
+                tracked("a", &log).key("a"),
+                tracked("b", &log).key("b"),
+                tracked("c", &log).key("c"),
+            ],
+            &mut window_cx,
+        );
+        take_log(&log);
+
+        root.update(
+            vec![tracked("a", &log).key("a"), tracked("c", &log).key("c")],
+            &mut window_cx,
+        );
+        assert_eq!(take_log(&log), ["update a -> a", "update c -> c", "drop b"]);
+        assert_eq!(root.len(), 2);
+
+        drop(root);
+        assert_eq!(take_log(&log), ["drop a", "drop c"]);
+    }
+
+    #[test]
+    fn test_nested_group_children_diff() {
+        let mut app = new_app();
+        let mut window_cx = app.cx().main_window();
+        let log = Log::default();
+        let mut root = ViewRoot::new();
+
+        root.update(
+            vec![
+                ViewNode::from(group(vec![tracked("a", &log), tracked("b", &log)])).key("group"),
+                tracked("c", &log).key("c"),
+            ],
+            &mut window_cx,
+        );
+        assert_eq!(take_log(&log), ["build a", "build b", "build c"]);
+
+        // The group itself is kept, and its children are diffed against its
+        // previous children.
+        root.update(
+            vec![
+                ViewNode::from(group(vec![tracked("a", &log).key("a"), tracked("b", &log)]))
+                    .key("group"),
+                tracked("c", &log).key("c"),
+            ],
+            &mut window_cx,
+        );
+        assert_eq!(
+            take_log(&log),
+            ["build a", "update b -> b", "drop a", "update c -> c"]
+        );
+
+        // Removing the group drops all of its children.
+        root.update(vec![tracked("c", &log).key("c")], &mut window_cx);
+        assert_eq!(take_log(&log), ["update c -> c", "drop a", "drop b"]);
+    }
+
+    #[test]
+    fn test_toggle_callback_is_replaced_without_rebuild() {
+        let mut app = new_app();
+        let mut window_cx = app.cx().main_window();
+        let mut root = ViewRoot::new();
+
+        let mounted_callback = |root: &ViewRoot<bool>| {
+            let state = root.children.nodes[0]
+                .state
+                .downcast_ref::<ToggleableState<Switch, bool>>()
+                .unwrap();
+            Rc::clone(state.on_toggled.as_ref().unwrap())
+        };
+
+        root.update(
+            vec![switch(false)
+                .on_toggled(|toggled| toggled)
+                .rect(rect(0.0, 0.0, 40.0, 20.0))
+                .into()],
+            &mut window_cx,
+        );
+        let first = mounted_callback(&root);
+
+        root.update(
+            vec![switch(true)
+                .on_toggled(|toggled| !toggled)
+                .rect(rect(0.0, 0.0, 40.0, 20.0))
+                .into()],
+            &mut window_cx,
+        );
+        let second = mounted_callback(&root);
+
+        // The element was not re-created, but it now sends the new action.
+        assert!(Rc::ptr_eq(&first, &second));
+        assert!(!(RefCell::borrow_mut(&second))(true));
+
+        let state = root.children.nodes[0]
+            .state
+            .downcast_ref::<ToggleableState<Switch, bool>>()
+            .unwrap();
+        assert!(state.element.toggled());
+    }
+}
@@ -2,6 +2,8 @@ use keyboard_types::{CompositionEvent, Modifiers};
 use rootvg::color::PackedSrgb;
 use rootvg::math::{Rect, RectI32};
 use rootvg::surface::{DefaultSurface, DefaultSurfaceConfig};
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 
 use crate::action_queue::ActionSender;
@@ -11,6 +13,7 @@ use crate::event::{
     CanvasEvent, EventCaptureStatus, KeyboardEvent, PointerButton, PointerEvent, PointerType,
     WheelDeltaType,
 };
+use crate::layout::{AttachedLayouts, ResizeHook};
 use crate::math::{
     to_logical_size_i32, PhysicalPoint, PhysicalSizeI32, Point, ScaleFactor, Size, Vector, ZIndex,
 };
@@ -28,6 +31,11 @@ mod baseview_backend;
 #[cfg(feature = "baseview")]
 use baseview_backend as windowing_backend;
 
+mod headless_backend;
+pub use headless_backend::HeadlessApp;
+#[cfg(feature = "headless")]
+pub use headless_backend::{HeadlessConfig, HeadlessError, RgbaImage};
+
 #[cfg(feature = "baseview")]
 pub use windowing_backend::run_parented;
 pub use windowing_backend::{run_blocking, OpenWindowError};
@@ -111,14 +119,17 @@ pub(crate) struct WindowState<A: Clone + 'static> {
     pub(crate) scale_factor_recip: f32,
     pub(crate) pointer_lock_state: PointerLockState,
 
-    renderer: rootvg::Canvas,
+    renderer: Option<rootvg::Canvas>,
     surface: Option<DefaultSurface<'static>>,
+    #[cfg(feature = "headless")]
+    offscreen: Option<headless_backend::OffscreenTarget>,
     multisample: wgpu::MultisampleState,
     logical_size: Size,
     physical_size: PhysicalSizeI32,
     system_scale_factor: ScaleFactor,
     scale_factor_config: ScaleFactorConfig,
     pointer_btn_states: [PointerBtnState; 5],
+    attached_layouts: AttachedLayouts,
 
     modifiers: Modifiers,
     current_cursor_icon: CursorIcon,
@@ -140,10 +151,13 @@ impl<A: Clone + 'static> WindowState<A> {
         self.scale_factor_recip = scale_factor.recip();
 
         self.element_system.resize(new_size, scale_factor);
-        self.surface
-            .as_mut()
-            .unwrap()
-            .resize(new_size, scale_factor);
+        if let Some(surface) = self.surface.as_mut() {
+            surface.resize(new_size, scale_factor);
+        }
+        #[cfg(feature = "headless")]
+        if let Some(offscreen) = self.offscreen.as_mut() {
+            offscreen.resize(new_size);
+        }
     }
 
     pub fn set_scale_factor_config(&mut self, config: ScaleFactorConfig) -> Option<Size> {
@@ -166,10 +180,9 @@ impl<A: Clone + 'static> WindowState<A> {
         self.scale_factor_recip = scale_factor.recip();
 
         self.element_system.resize(self.physical_size, scale_factor);
-        self.surface
-            .as_mut()
-            .unwrap()
-            .resize(self.physical_size, scale_factor);
+        if let Some(surface) = self.surface.as_mut() {
+            surface.resize(self.physical_size, scale_factor);
+        }
 
         Some(logical_size)
     }
@@ -403,16 +416,60 @@ impl<A: Clone + 'static> WindowState<A> {
             &surface.queue,
             surface.format(),
             self.multisample,
-            &mut self.renderer,
+            self.renderer.as_mut().unwrap(),
             pre_present_notify,
             res,
         )
     }
 
+    /// Render into the offscreen texture of a headless window.
+    ///
+    /// Returns `true` if anything was rendered.
+    #[cfg(feature = "headless")]
+    pub fn render_offscreen(&mut self, res: &mut ResourceCtx) -> bool {
+        let (Some(offscreen), Some(renderer)) = (self.offscreen.as_ref(), self.renderer.as_mut())
+        else {
+            return false;
+        };
+
+        self.element_system.render_offscreen(
+            &offscreen.view,
+            &offscreen.device,
+            &offscreen.queue,
+            #[cfg(feature = "custom-shaders")]
+            offscreen.format(),
+            #[cfg(feature = "custom-shaders")]
+            self.multisample,
+            renderer,
+            res,
+        )
+    }
+
+    #[cfg(feature = "headless")]
+    pub fn has_offscreen_target(&self) -> bool {
+        self.offscreen.is_some()
+    }
+
+    /// Read back the pixels of the offscreen texture of a headless window.
+    ///
+    /// Returns `None` if this window does not have an offscreen texture.
+    #[cfg(feature = "headless")]
+    pub fn read_offscreen_pixels(&self) -> Option<Result<RgbaImage, HeadlessError>> {
+        self.offscreen
+            .as_ref()
+            .map(|offscreen| offscreen.read_pixels())
+    }
+
     pub fn logical_size(&self) -> Size {
         self.logical_size
     }
 
+    /// Re-apply the layouts attached to this window. Call this right before
+    /// sending `AppWindowEvent::WindowResized` to the application.
+    pub(crate) fn apply_attached_layouts(&mut self, res: &mut ResourceCtx) {
+        self.attached_layouts.apply(self.logical_size, res);
+    }
+
     pub fn context<'b>(
         &'b mut self,
         res: &'b mut ResourceCtx,
@@ -421,6 +478,7 @@ impl<A: Clone + 'static> WindowState<A> {
     ) -> WindowContext<'b, A> {
         WindowContext {
             element_system: &mut self.element_system,
+            attached_layouts: &mut self.attached_layouts,
             res,
             clipboard: &mut self.clipboard,
             action_sender,
@@ -462,6 +520,26 @@ impl<A: Clone + 'static> WindowState<A> {
     pub fn needs_repaint(&self) -> bool {
         self.element_system.needs_repaint()
     }
+
+    #[cfg(feature = "accesskit")]
+    pub fn accessibility_needs_update(&self) -> bool {
+        self.element_system.accessibility_needs_update()
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn accessibility_tree_update(&mut self) -> accesskit::TreeUpdate {
+        self.element_system.accessibility_tree_update()
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn handle_accessibility_action(
+        &mut self,
+        request: accesskit::ActionRequest,
+        res: &mut ResourceCtx,
+    ) {
+        self.element_system
+            .handle_accessibility_action(request, res, &mut self.clipboard);
+    }
 }
 
 impl<A: Clone + 'static> Drop for WindowState<A> {
@@ -550,6 +628,7 @@ pub struct WindowContext<'a, A: Clone + 'static> {
     /// The receiving end of the action queue.
     pub action_receiver: &'a mut ActionReceiver<A>,
     element_system: &'a mut ElementSystem<A>,
+    attached_layouts: &'a mut AttachedLayouts,
     z_index_stack: Vec<ZIndex>,
     scissor_rect_stack: Vec<ScissorRectID>,
     class_stack: Vec<ClassID>,
@@ -581,6 +660,10 @@ impl<'a, A: Clone + 'static> WindowContext<'a, A> {
         self.scale_factor_config
     }
 
+    pub(crate) fn attach_layout(&mut self, layout: &Rc<RefCell<dyn ResizeHook>>) {
+        self.attached_layouts.add(layout);
+    }
+
     /// Get the current z index from the stack (peek)
     pub fn z_index(&self) -> ZIndex {
         self.z_index_stack.last().copied().unwrap_or_default()
@@ -26,6 +26,7 @@ use crate::application::Application;
 use crate::clipboard::Clipboard;
 use crate::element_system::{ElementSystem, ElementSystemConfig};
 use crate::event::{EventCaptureStatus, PointerButton, WheelDeltaType};
+use crate::layout::AttachedLayouts;
 use crate::math::{PhysicalPoint, PhysicalSizeI32, ScaleFactor, Size};
 use crate::prelude::{ActionReceiver, AppHandler, ResourceCtx};
 use crate::style::StyleSystem;
@@ -406,6 +407,10 @@ impl<A: Application> BaseviewWindowHandler for BaseviewAppHandler<A> {
                         .cx
                         .main_window
                         .set_size(new_size, scale_factor.into());
+                    self.app_handler
+                        .cx
+                        .main_window
+                        .apply_attached_layouts(&mut self.app_handler.cx.res);
 
                     if self.inner.first_resize {
                         self.inner.first_resize = false;
@@ -467,6 +472,9 @@ impl<A: Application> BaseviewWindowHandler for BaseviewAppHandler<A> {
 pub enum OpenWindowError {
     #[error("Baseview does not yet support multiple windows")]
     MultiWindowNotSupported,
+    #[cfg(feature = "headless")]
+    #[error("{0}")]
+    HeadlessError(#[from] super::HeadlessError),
 }
 
 pub fn run_blocking<A: Application + 'static>(app_config: AppConfig) -> Result<(), Box<dyn Error>> {
@@ -612,8 +620,10 @@ fn new_window<A: Application>(
 
     Ok(WindowState {
         element_system,
-        renderer,
+        renderer: Some(renderer),
         surface: Some(surface),
+        #[cfg(feature = "headless")]
+        offscreen: None,
         multisample: canvas_config.multisample,
         logical_size: config.size,
         physical_size,
@@ -625,6 +635,7 @@ fn new_window<A: Application>(
         queued_pointer_delta: None,
         prev_pointer_pos: None,
         pointer_btn_states: [PointerBtnState::default(); 5],
+        attached_layouts: AttachedLayouts::default(),
         modifiers: Modifiers::empty(),
         current_cursor_icon: CursorIcon::Default,
         pointer_lock_state: PointerLockState::NotLocked,
@@ -0,0 +1,809 @@
+use keyboard_types::{CompositionEvent, CompositionState, Modifiers};
+use rootvg::text::FontSystem;
+use rustc_hash::FxHashMap;
+use std::error::Error;
+
+use crate::action_queue::ActionSender;
+use crate::application::{AppContext, AppHandler, Application};
+use crate::clipboard::Clipboard;
+use crate::element_system::ElementSystemConfig;
+use crate::event::{
+    AppWindowEvent, Code, EventCaptureStatus, KeyState, KeyboardEvent, PointerButton,
+    WheelDeltaType,
+};
+use crate::layout::AttachedLayouts;
+use crate::math::{PhysicalPoint, PhysicalSizeI32, Point, Rect, ScaleFactor, Size};
+use crate::prelude::{ElementHandle, ResourceCtx};
+use crate::style::StyleSystem;
+use crate::window::{WindowID, MAIN_WINDOW};
+use crate::AppConfig;
+
+use super::{
+    CursorIcon, ElementSystem, OpenWindowError, PointerBtnState, PointerLockState, WindowBackend,
+    WindowCloseRequest, WindowConfig, WindowState,
+};
+
+#[cfg(feature = "headless")]
+mod offscreen;
+#[cfg(feature = "headless")]
+pub(crate) use offscreen::OffscreenTarget;
+#[cfg(feature = "headless")]
+pub use offscreen::{HeadlessConfig, HeadlessError, RgbaImage};
+
+struct HeadlessWindow {
+    title: String,
+    has_focus: bool,
+    minimized: bool,
+    maximized: bool,
+    cursor_icon: CursorIcon,
+    requested_size: Option<Size>,
+}
+
+/// A [`WindowBackend`] that does not open any windows.
+struct HeadlessWindowBackend {
+    system_scale_factor: ScaleFactor,
+    #[cfg(feature = "headless")]
+    headless_config: Option<HeadlessConfig>,
+    windows: FxHashMap<WindowID, HeadlessWindow>,
+}
+
+impl HeadlessWindowBackend {
+    fn add_window(&mut self, window_id: WindowID, config: &WindowConfig) {
+        self.windows.insert(
+            window_id,
+            HeadlessWindow {
+                title: config.title.clone(),
+                has_focus: config.focus_on_creation,
+                minimized: false,
+                maximized: false,
+                cursor_icon: CursorIcon::Default,
+                requested_size: None,
+            },
+        );
+    }
+
+    #[cfg_attr(not(feature = "headless"), allow(unused_variables))]
+    fn create_window_state<A: Clone + 'static>(
+        &self,
+        id: WindowID,
+        config: &WindowConfig,
+        action_sender: &ActionSender<A>,
+        res: &mut ResourceCtx,
+    ) -> Result<WindowState<A>, OpenWindowError> {
+        let system_scale_factor = self.system_scale_factor;
+        let scale_factor = config.scale_factor.scale_factor(system_scale_factor);
+
+        let physical_size: PhysicalSizeI32 =
+            crate::math::to_physical_size(config.size, scale_factor)
+                .round()
+                .cast();
+
+        #[cfg(feature = "headless")]
+        let (renderer, offscreen, multisample) =
+            if let Some(headless_config) = &self.headless_config {
+                let offscreen = OffscreenTarget::new(physical_size, headless_config)?;
+
+                // The offscreen texture is single-sampled, so the render
+                // pipelines must be as well.
+                let canvas_config = rootvg::CanvasConfig {
+                    multisample: wgpu::MultisampleState::default(),
+                    ..headless_config.canvas_config
+                };
+
+                let renderer = rootvg::Canvas::new(
+                    &offscreen.device,
+                    &offscreen.queue,
+                    offscreen.format(),
+                    canvas_config,
+                    &mut res.font_system,
+                );
+
+                (Some(renderer), Some(offscreen), canvas_config.multisample)
+            } else {
+                (None, None, wgpu::MultisampleState::default())
+            };
+
+        #[cfg(not(feature = "headless"))]
+        let (renderer, multisample) = (None, wgpu::MultisampleState::default());
+
+        let element_system = ElementSystem::new(
+            physical_size,
+            scale_factor,
+            ElementSystemConfig {
+                clear_color: config.clear_color,
+                preallocate_for_this_many_elements: config.preallocate_for_this_many_elements,
+                hover_timeout_duration: config.hover_timeout_duration,
+                scroll_wheel_timeout_duration: config.scroll_wheel_timeout_duration,
+            },
+            action_sender.clone(),
+            id,
+        );
+
+        Ok(WindowState {
+            element_system,
+            renderer,
+            surface: None,
+            #[cfg(feature = "headless")]
+            offscreen,
+            multisample,
+            logical_size: config.size,
+            physical_size,
+            scale_factor,
+            scale_factor_recip: scale_factor.recip(),
+            system_scale_factor,
+            scale_factor_config: config.scale_factor,
+            queued_pointer_position: None,
+            queued_pointer_delta: None,
+            prev_pointer_pos: None,
+            pointer_btn_states: [PointerBtnState::default(); 5],
+            attached_layouts: AttachedLayouts::default(),
+            modifiers: Modifiers::empty(),
+            current_cursor_icon: CursorIcon::Default,
+            pointer_lock_state: PointerLockState::NotLocked,
+            clipboard: Clipboard {
+                state: crate::clipboard::State::Unavailable,
+            },
+        })
+    }
+}
+
+impl WindowBackend for HeadlessWindowBackend {
+    fn set_pointer_position(
+        &mut self,
+        _window_id: WindowID,
+        _position: PhysicalPoint,
+    ) -> Result<(), ()> {
+        // There is no pointer to move.
+        Ok(())
+    }
+
+    fn unlock_pointer(&mut self, _window_id: WindowID, _prev_lock_state: PointerLockState) {}
+
+    fn request_redraw(&mut self, _window_id: WindowID) {
+        // Windows are only rendered when explicitly requested.
+    }
+
+    fn has_focus(&mut self, window_id: WindowID) -> bool {
+        self.windows
+            .get(&window_id)
+            .map(|w| w.has_focus)
+            .unwrap_or(false)
+    }
+
+    fn try_lock_pointer(&mut self, _window_id: WindowID) -> PointerLockState {
+        PointerLockState::NotLocked
+    }
+
+    fn set_cursor_icon(&mut self, window_id: WindowID, icon: CursorIcon) {
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.cursor_icon = icon;
+        }
+    }
+
+    fn resize(
+        &mut self,
+        window_id: WindowID,
+        logical_size: Size,
+        _scale_factor: ScaleFactor,
+    ) -> Result<(), ()> {
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.requested_size = Some(logical_size);
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    fn set_minimized(&mut self, window_id: WindowID, minimized: bool) {
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.minimized = minimized;
+        }
+    }
+
+    fn set_maximized(&mut self, window_id: WindowID, maximized: bool) {
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.maximized = maximized;
+        }
+    }
+
+    fn focus_window(&mut self, window_id: WindowID) {
+        for (id, window) in self.windows.iter_mut() {
+            window.has_focus = *id == window_id;
+        }
+    }
+
+    fn set_window_title(&mut self, window_id: WindowID, title: String) {
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.title = title;
+        }
+    }
+
+    fn create_window<A: Clone + 'static>(
+        &mut self,
+        window_id: WindowID,
+        config: &WindowConfig,
+        action_sender: &ActionSender<A>,
+        res: &mut ResourceCtx,
+    ) -> Result<WindowState<A>, OpenWindowError> {
+        let window_state = self.create_window_state(window_id, config, action_sender, res)?;
+        self.add_window(window_id, config);
+        Ok(window_state)
+    }
+
+    fn close_window(&mut self, window_id: WindowID) {
+        self.windows.remove(&window_id);
+    }
+}
+
+/// Runs an [`Application`] without opening any windows.
+///
+/// Nothing happens on its own. It is up to the caller to inject input events,
+/// advance the application with [`HeadlessApp::tick`], and process updates with
+/// [`HeadlessApp::process_updates`]. This makes it useful as a harness for
+/// testing applications:
+///
+/// ```ignore
+/// let mut app = HeadlessApp::<MyApp>::new(AppConfig::default())?;
+///
+/// app.click(MAIN_WINDOW, Point::new(20.0, 20.0), PointerButton::Primary);
+///
+/// assert_eq!(app.drain_actions(), vec![MyAction::ButtonPressed]);
+/// ```
+///
+/// With the `headless` feature enabled, windows can also be rendered into an
+/// offscreen texture using [`HeadlessApp::with_renderer`].
+pub struct HeadlessApp<A: Application> {
+    app_handler: AppHandler<A>,
+    backend: HeadlessWindowBackend,
+}
+
+impl<A: Application> HeadlessApp<A> {
+    /// Create a new headless application. The windows of this application
+    /// cannot be rendered.
+    pub fn new(config: AppConfig) -> Result<Self, Box<dyn Error>> {
+        Self::new_inner(
+            config,
+            HeadlessWindowBackend {
+                system_scale_factor: 1.0f64.into(),
+                #[cfg(feature = "headless")]
+                headless_config: None,
+                windows: FxHashMap::default(),
+            },
+        )
+    }
+
+    /// Create a new headless application where every window renders into an
+    /// offscreen texture.
+    #[cfg(feature = "headless")]
+    pub fn with_renderer(
+        config: AppConfig,
+        headless_config: HeadlessConfig,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::new_inner(
+            config,
+            HeadlessWindowBackend {
+                system_scale_factor: 1.0f64.into(),
+                headless_config: Some(headless_config),
+                windows: FxHashMap::default(),
+            },
+        )
+    }
+
+    fn new_inner(
+        config: AppConfig,
+        mut backend: HeadlessWindowBackend,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut res = ResourceCtx {
+            style_system: StyleSystem::new(config.use_dark_theme),
+            font_system: FontSystem::new(),
+            #[cfg(feature = "svg-icons")]
+            svg_icon_system: Default::default(),
+        };
+
+        let (action_sender, action_receiver) = crate::action_channel::<A::Action>();
+
+        let main_window_state = backend.create_window(
+            MAIN_WINDOW,
+            &config.main_window_config,
+            &action_sender,
+            &mut res,
+        )?;
+
+        let app_handler = AppHandler::new(
+            main_window_state,
+            action_sender,
+            action_receiver,
+            config,
+            res,
+            None,
+        )?;
+
+        let mut new_self = Self {
+            app_handler,
+            backend,
+        };
+
+        new_self.process_updates();
+
+        Ok(new_self)
+    }
+
+    pub fn app(&self) -> &A {
+        &self.app_handler.user_app
+    }
+
+    pub fn app_mut(&mut self) -> &mut A {
+        &mut self.app_handler.user_app
+    }
+
+    pub fn cx(&mut self) -> &mut AppContext<A::Action> {
+        &mut self.app_handler.cx
+    }
+
+    /// Borrow the application along with the resource context, i.e. to call
+    /// the methods of an element handle which need the resource context.
+    pub fn app_and_res(&mut self) -> (&mut A, &mut ResourceCtx) {
+        (&mut self.app_handler.user_app, &mut self.app_handler.cx.res)
+    }
+
+    /// Call `Application::on_tick`, send an animation tick to all elements,
+    /// and then process any resulting updates.
+    pub fn tick(&mut self) {
+        self.app_handler.on_tick();
+        self.process_updates();
+    }
+
+    /// Process all pending actions, element updates, and window requests.
+    pub fn process_updates(&mut self) {
+        loop {
+            self.app_handler.process_updates(&mut self.backend);
+
+            let mut resized_windows: Vec<WindowID> = Vec::new();
+            for (window_id, window) in self.backend.windows.iter_mut() {
+                let Some(logical_size) = window.requested_size.take() else {
+                    continue;
+                };
+
+                let cx = &mut self.app_handler.cx;
+                let Some(window_state) =
+                    window_state_mut_split(&mut cx.main_window, &mut cx.window_map, *window_id)
+                else {
+                    continue;
+                };
+
+                let new_size: PhysicalSizeI32 =
+                    crate::math::to_physical_size(logical_size, window_state.scale_factor)
+                        .round()
+                        .cast();
+                let system_scale_factor = window_state.system_scale_factor;
+                window_state.set_size(new_size, system_scale_factor);
+                window_state.apply_attached_layouts(&mut cx.res);
+
+                resized_windows.push(*window_id);
+            }
+
+            if resized_windows.is_empty() {
+                break;
+            }
+
+            for window_id in resized_windows {
+                self.app_handler.user_app.on_window_event(
+                    AppWindowEvent::WindowResized,
+                    window_id,
+                    &mut self.app_handler.cx,
+                );
+            }
+        }
+    }
+
+    /// Simulate the system changing the scale factor of a window.
+    pub fn set_system_scale_factor(&mut self, window_id: WindowID, scale_factor: ScaleFactor) {
+        let cx = &mut self.app_handler.cx;
+        let Some(window_state) =
+            window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+        else {
+            return;
+        };
+
+        let new_size: PhysicalSizeI32 =
+            crate::math::to_physical_size(window_state.logical_size(), scale_factor)
+                .round()
+                .cast();
+        window_state.set_size(new_size, scale_factor);
+        window_state.apply_attached_layouts(&mut cx.res);
+
+        self.app_handler.user_app.on_window_event(
+            AppWindowEvent::WindowResized,
+            window_id,
+            &mut self.app_handler.cx,
+        );
+    }
+
+    /// Simulate the pointer moving to the given position (in logical points).
+    ///
+    /// Note that like the rest of the methods that inject events, this does
+    /// not process updates. Call [`HeadlessApp::process_updates`] afterwards
+    /// to let the application react.
+    pub fn pointer_moved(&mut self, window_id: WindowID, position: Point) {
+        let cx = &mut self.app_handler.cx;
+        let Some(window_state) =
+            window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+        else {
+            return;
+        };
+
+        let position = crate::math::to_physical_point(position, window_state.scale_factor);
+        window_state.handle_pointer_moved(position, &mut cx.res);
+    }
+
+    /// Simulate the pointer leaving the window.
+    pub fn pointer_left(&mut self, window_id: WindowID) {
+        let cx = &mut self.app_handler.cx;
+        if let Some(window_state) =
+            window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+        {
+            window_state.handle_pointer_left(&mut cx.res);
+        }
+    }
+
+    /// Simulate a pointer button being pressed or released at the current
+    /// pointer position.
+    pub fn mouse_button(&mut self, window_id: WindowID, button: PointerButton, is_down: bool) {
+        let cx = &mut self.app_handler.cx;
+        if let Some(window_state) =
+            window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+        {
+            window_state.handle_mouse_button(button, is_down, &mut cx.res);
+        }
+    }
+
+    /// Simulate moving the pointer to the given position (in logical points),
+    /// and then pressing and releasing the given button.
+    pub fn click(&mut self, window_id: WindowID, position: Point, button: PointerButton) {
+        self.pointer_moved(window_id, position);
+        self.mouse_button(window_id, button, true);
+        self.mouse_button(window_id, button, false);
+    }
+
+    /// Simulate the scroll wheel being moved at the current pointer position.
+    pub fn scroll_wheel(&mut self, window_id: WindowID, delta_type: WheelDeltaType) {
+        let cx = &mut self.app_handler.cx;
+        if let Some(window_state) =
+            window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+        {
+            window_state.handle_mouse_wheel(delta_type, &mut cx.res);
+        }
+    }
+
+    /// Simulate the modifier keys changing.
+    pub fn set_modifiers(&mut self, window_id: WindowID, modifiers: Modifiers) {
+        if let Some(window_state) = window_state_mut(&mut self.app_handler.cx, window_id) {
+            window_state.set_modifiers(modifiers);
+        }
+    }
+
+    /// Simulate a keyboard event.
+    ///
+    /// If no element captures the event, then it will be sent to
+    /// `Application::on_keyboard_event`.
+    pub fn keyboard_event(
+        &mut self,
+        window_id: WindowID,
+        event: KeyboardEvent,
+    ) -> EventCaptureStatus {
+        let cx = &mut self.app_handler.cx;
+        let Some(window_state) =
+            window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+        else {
+            return EventCaptureStatus::NotCaptured;
+        };
+
+        let status = window_state.handle_keyboard_event(event.clone(), &mut cx.res);
+
+        if status == EventCaptureStatus::NotCaptured {
+            self.app_handler
+                .user_app
+                .on_keyboard_event(event, window_id, &mut self.app_handler.cx);
+        }
+
+        status
+    }
+
+    /// Simulate pressing and then releasing the key with the given code.
+    pub fn key_press(&mut self, window_id: WindowID, code: Code, modifiers: Modifiers) {
+        let mut event = KeyboardEvent {
+            state: KeyState::Down,
+            code,
+            modifiers,
+            ..Default::default()
+        };
+
+        self.keyboard_event(window_id, event.clone());
+
+        event.state = KeyState::Up;
+        self.keyboard_event(window_id, event);
+    }
+
+    /// Simulate a text composition event.
+    pub fn text_composition(
+        &mut self,
+        window_id: WindowID,
+        event: CompositionEvent,
+    ) -> EventCaptureStatus {
+        let cx = &mut self.app_handler.cx;
+        if let Some(window_state) =
+            window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+        {
+            window_state.handle_text_composition_event(event, &mut cx.res)
+        } else {
+            EventCaptureStatus::NotCaptured
+        }
+    }
+
+    /// Simulate the user typing the given text, in the same way the windowing
+    /// backends synthesize composition events from key presses.
+    pub fn type_text(&mut self, window_id: WindowID, text: &str) -> EventCaptureStatus {
+        let mut status = self.text_composition(
+            window_id,
+            CompositionEvent {
+                state: CompositionState::Start,
+                data: String::new(),
+            },
+        );
+
+        if self.text_composition(
+            window_id,
+            CompositionEvent {
+                state: CompositionState::End,
+                data: text.to_string(),
+            },
+        ) == EventCaptureStatus::Captured
+        {
+            status = EventCaptureStatus::Captured;
+        }
+
+        status
+    }
+
+    /// Simulate the window gaining or losing focus.
+    pub fn set_window_focused(&mut self, window_id: WindowID, focused: bool) {
+        let cx = &mut self.app_handler.cx;
+        let Some(window_state) =
+            window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+        else {
+            return;
+        };
+
+        let event = if focused {
+            window_state.handle_window_focused(&mut cx.res);
+            AppWindowEvent::WindowFocused
+        } else {
+            window_state.handle_window_unfocused(&mut cx.res);
+            AppWindowEvent::WindowUnfocused
+        };
+
+        if let Some(window) = self.backend.windows.get_mut(&window_id) {
+            window.has_focus = focused;
+        }
+
+        self.app_handler
+            .user_app
+            .on_window_event(event, window_id, &mut self.app_handler.cx);
+    }
+
+    /// Simulate the user requesting to close a window.
+    ///
+    /// Returns `true` if the window was closed.
+    pub fn request_close_window(&mut self, window_id: WindowID) -> bool {
+        match self.app_handler.user_app.on_request_to_close_window(
+            window_id,
+            false,
+            &mut self.app_handler.cx,
+        ) {
+            WindowCloseRequest::CloseImmediately => {
+                if window_id != MAIN_WINDOW {
+                    self.app_handler.cx.window_map.remove(&window_id);
+                    self.backend.close_window(window_id);
+                }
+
+                true
+            }
+            WindowCloseRequest::DoNotCloseYet => false,
+        }
+    }
+
+    /// Drain all actions that are currently in the action queue.
+    ///
+    /// Note that if the application drains the action queue itself in
+    /// `Application::on_action_emitted`, then call this before calling
+    /// [`HeadlessApp::process_updates`].
+    pub fn drain_actions(&mut self) -> Vec<A::Action> {
+        self.app_handler.cx.action_receiver.try_iter().collect()
+    }
+
+    /// The rectangle of the given element, or `None` if the element or the
+    /// window does not exist.
+    pub fn element_rect(&self, window_id: WindowID, element: &ElementHandle) -> Option<Rect> {
+        window_state(&self.app_handler.cx, window_id)
+            .and_then(|w| w.element_system.element_rect(element))
+    }
+
+    /// Returns `true` if the given element is currently hovered.
+    pub fn element_is_hovered(&self, window_id: WindowID, element: &ElementHandle) -> bool {
+        window_state(&self.app_handler.cx, window_id)
+            .map(|w| w.element_system.element_is_hovered(element))
+            .unwrap_or(false)
+    }
+
+    /// Build a full update of the accessibility tree of the given window.
+    #[cfg(feature = "accesskit")]
+    pub fn accessibility_tree_update(
+        &mut self,
+        window_id: WindowID,
+    ) -> Option<accesskit::TreeUpdate> {
+        window_state_mut(&mut self.app_handler.cx, window_id).map(|w| w.accessibility_tree_update())
+    }
+
+    /// Returns `true` if the accessibility tree of the given window has
+    /// changed since it was last built.
+    #[cfg(feature = "accesskit")]
+    pub fn accessibility_needs_update(&self, window_id: WindowID) -> bool {
+        window_state(&self.app_handler.cx, window_id)
+            .map(|w| w.accessibility_needs_update())
+            .unwrap_or(false)
+    }
+
+    /// Simulate an assistive technology requesting an action.
+    #[cfg(feature = "accesskit")]
+    pub fn accessibility_action(&mut self, window_id: WindowID, request: accesskit::ActionRequest) {
+        let cx = &mut self.app_handler.cx;
+        if let Some(window_state) =
+            window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+        {
+            window_state.handle_accessibility_action(request, &mut cx.res);
+        }
+    }
+
+    /// Returns `true` if the given window exists.
+    pub fn window_exists(&self, window_id: WindowID) -> bool {
+        window_state(&self.app_handler.cx, window_id).is_some()
+    }
+
+    /// The logical size of the given window.
+    pub fn window_size(&self, window_id: WindowID) -> Option<Size> {
+        window_state(&self.app_handler.cx, window_id).map(|w| w.logical_size())
+    }
+
+    /// The title of the given window, or `None` if the window does not exist.
+    pub fn window_title(&self, window_id: WindowID) -> Option<&str> {
+        self.backend
+            .windows
+            .get(&window_id)
+            .map(|w| w.title.as_str())
+    }
+
+    /// The cursor icon that the given window last requested.
+    pub fn cursor_icon(&self, window_id: WindowID) -> Option<CursorIcon> {
+        self.backend.windows.get(&window_id).map(|w| w.cursor_icon)
+    }
+
+    pub fn is_minimized(&self, window_id: WindowID) -> bool {
+        self.backend
+            .windows
+            .get(&window_id)
+            .map(|w| w.minimized)
+            .unwrap_or(false)
+    }
+
+    pub fn is_maximized(&self, window_id: WindowID) -> bool {
+        self.backend
+            .windows
+            .get(&window_id)
+            .map(|w| w.maximized)
+            .unwrap_or(false)
+    }
+
+    /// Render the given window into its offscreen texture.
+    ///
+    /// Returns `Ok(false)` if the contents of the texture were already up-to-date.
+    #[cfg(feature = "headless")]
+    pub fn render(&mut self, window_id: WindowID) -> Result<bool, HeadlessError> {
+        let cx = &mut self.app_handler.cx;
+
+        let window_state =
+            window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+                .ok_or(HeadlessError::WindowNotFound(window_id))?;
+
+        if !window_state.has_offscreen_target() {
+            return Err(HeadlessError::NoRenderer(window_id));
+        }
+
+        Ok(window_state.render_offscreen(&mut cx.res))
+    }
+
+    /// Process updates, render the given window, and read the resulting pixels
+    /// back into an RGBA buffer.
+    #[cfg(feature = "headless")]
+    pub fn screenshot(&mut self, window_id: WindowID) -> Result<RgbaImage, HeadlessError> {
+        self.process_updates();
+        self.render(window_id)?;
+
+        window_state(&self.app_handler.cx, window_id)
+            .ok_or(HeadlessError::WindowNotFound(window_id))?
+            .read_offscreen_pixels()
+            .ok_or(HeadlessError::NoRenderer(window_id))?
+    }
+}
+
+fn window_state<A: Clone + 'static>(
+    cx: &AppContext<A>,
+    window_id: WindowID,
+) -> Option<&WindowState<A>> {
+    if window_id == MAIN_WINDOW {
+        Some(&cx.main_window)
+    } else {
+        cx.window_map.get(&window_id)
+    }
+}
+
+fn window_state_mut<A: Clone + 'static>(
+    cx: &mut AppContext<A>,
+    window_id: WindowID,
+) -> Option<&mut WindowState<A>> {
+    window_state_mut_split(&mut cx.main_window, &mut cx.window_map, window_id)
+}
+
+// Borrowing the window state through the individual fields lets the caller
+// still borrow `AppContext::res` at the same time.
+fn window_state_mut_split<'a, A: Clone + 'static>(
+    main_window: &'a mut WindowState<A>,
+    window_map: &'a mut FxHashMap<WindowID, WindowState<A>>,
+    window_id: WindowID,
+) -> Option<&'a mut WindowState<A>> {
+    if window_id == MAIN_WINDOW {
+        Some(main_window)
+    } else {
+        window_map.get_mut(&window_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestAction {
+        Pressed,
+    }
+
+    struct TestApp {
+        button: Button,
+    }
+
+    impl Application for TestApp {
+        type Action = TestAction;
+
+        fn init(cx: &mut AppContext<TestAction>) -> Result<Self, Box<dyn std::error::Error>> {
+            let button = Button::builder()
+                .text("Press Me")
+                .on_select(TestAction::Pressed)
+                .rect(rect(10.0, 10.0, 100.0, 30.0))
+                .build(&mut cx.main_window());
+
+            Ok(Self { button })
+        }
+    }
+
+    #[test]
+    fn test_click_button() {
+        let mut app = HeadlessApp::<TestApp>::new(AppConfig::default()).unwrap();
+
+        assert_eq!(app.app().button.rect(), rect(10.0, 10.0, 100.0, 30.0));
+
+        app.click(MAIN_WINDOW, point(5.0, 5.0), PointerButton::Primary);
+        assert!(app.drain_actions().is_empty());
+
+        app.click(MAIN_WINDOW, point(20.0, 20.0), PointerButton::Primary);
+        assert_eq!(app.drain_actions(), vec![TestAction::Pressed]);
+    }
+}
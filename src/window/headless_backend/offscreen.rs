@@ -0,0 +1,254 @@
+use crate::math::PhysicalSizeI32;
+use crate::window::WindowID;
+
+/// The configuration of the offscreen renderer used by a [`HeadlessApp`](super::HeadlessApp).
+#[derive(Debug, Clone)]
+pub struct HeadlessConfig {
+    /// The graphics backends to try when looking for an adapter.
+    ///
+    /// By default this is set to `wgpu::Backends::all()`.
+    pub backends: wgpu::Backends,
+    /// By default this is set to `wgpu::PowerPreference::LowPower`.
+    pub power_preference: wgpu::PowerPreference,
+    /// If `true`, then only a software (fallback) adapter will be used. This
+    /// is useful on build servers that have no GPU.
+    ///
+    /// By default this is set to `false`.
+    pub force_fallback_adapter: bool,
+    /// The format of the offscreen texture. Note that pixels are always read
+    /// back as 8 bit RGBA, so this must be one of the `Rgba8*` formats.
+    ///
+    /// By default this is set to `wgpu::TextureFormat::Rgba8UnormSrgb`.
+    pub texture_format: wgpu::TextureFormat,
+    /// The configuration of the renderer.
+    ///
+    /// The offscreen texture is always single-sampled, so
+    /// `canvas_config.multisample` is ignored.
+    pub canvas_config: rootvg::CanvasConfig,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            backends: wgpu::Backends::all(),
+            power_preference: wgpu::PowerPreference::LowPower,
+            force_fallback_adapter: false,
+            texture_format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            canvas_config: rootvg::CanvasConfig::default(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum HeadlessError {
+    #[error("Could not find a suitable graphics adapter")]
+    NoAdapter,
+    #[error("{0}")]
+    RequestDevice(#[from] wgpu::RequestDeviceError),
+    #[error("Texture format {0:?} cannot be read back as RGBA8")]
+    UnsupportedFormat(wgpu::TextureFormat),
+    #[error("Could not map the readback buffer: {0}")]
+    BufferMap(#[from] wgpu::BufferAsyncError),
+    #[error("Window with ID {0} does not exist")]
+    WindowNotFound(WindowID),
+    #[error("Window with ID {0} was not created with an offscreen renderer")]
+    NoRenderer(WindowID),
+}
+
+/// A buffer of 8 bit RGBA pixels (with no padding between rows).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Returns the RGBA value of the pixel at the given coordinates, or `None`
+    /// if the coordinates are out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let i = ((y * self.width + x) * 4) as usize;
+        Some([
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ])
+    }
+}
+
+/// An offscreen texture that a window renders into instead of a surface.
+pub(crate) struct OffscreenTarget {
+    pub device: wgpu::Device,
+    pub queue: wgpu::Queue,
+    texture: wgpu::Texture,
+    pub view: wgpu::TextureView,
+    format: wgpu::TextureFormat,
+    physical_size: PhysicalSizeI32,
+}
+
+impl OffscreenTarget {
+    pub fn new(
+        physical_size: PhysicalSizeI32,
+        config: &HeadlessConfig,
+    ) -> Result<Self, HeadlessError> {
+        if !matches!(
+            config.texture_format,
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb
+        ) {
+            return Err(HeadlessError::UnsupportedFormat(config.texture_format));
+        }
+
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: config.backends,
+            ..Default::default()
+        });
+
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: config.power_preference,
+            force_fallback_adapter: config.force_fallback_adapter,
+            compatible_surface: None,
+        }))
+        .ok_or(HeadlessError::NoAdapter)?;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("yarrow headless device"),
+                required_features: wgpu::Features::empty(),
+                required_limits:
+                    wgpu::Limits::downlevel_defaults().using_resolution(adapter.limits()),
+                memory_hints: wgpu::MemoryHints::default(),
+            },
+            None,
+        ))?;
+
+        let (texture, view) = create_texture(&device, physical_size, config.texture_format);
+
+        Ok(Self {
+            device,
+            queue,
+            texture,
+            view,
+            format: config.texture_format,
+            physical_size,
+        })
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    pub fn resize(&mut self, physical_size: PhysicalSizeI32) {
+        if self.physical_size == physical_size {
+            return;
+        }
+
+        let (texture, view) = create_texture(&self.device, physical_size, self.format);
+        self.texture = texture;
+        self.view = view;
+        self.physical_size = physical_size;
+    }
+
+    /// Copy the contents of the texture into an RGBA buffer.
+    ///
+    /// This blocks until the GPU has finished rendering.
+    pub fn read_pixels(&self) -> Result<RgbaImage, HeadlessError> {
+        let width = self.physical_size.width.max(1) as u32;
+        let height = self.physical_size.height.max(1) as u32;
+
+        let unpadded_bytes_per_row = width * 4;
+        let padded_bytes_per_row = unpadded_bytes_per_row
+            .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("yarrow headless readback buffer"),
+            size: (padded_bytes_per_row * height) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |res| {
+            let _ = sender.send(res);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+
+        // The callback is guaranteed to have been called after polling with
+        // `Maintain::Wait`.
+        receiver.recv().unwrap()?;
+
+        let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+        {
+            let data = slice.get_mapped_range();
+            for row in data.chunks_exact(padded_bytes_per_row as usize) {
+                pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+            }
+        }
+        buffer.unmap();
+
+        Ok(RgbaImage {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
+
+fn create_texture(
+    device: &wgpu::Device,
+    physical_size: PhysicalSizeI32,
+    format: wgpu::TextureFormat,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("yarrow headless target"),
+        size: wgpu::Extent3d {
+            width: physical_size.width.max(1) as u32,
+            height: physical_size.height.max(1) as u32,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    (texture, view)
+}
@@ -11,6 +11,8 @@ use winit::event::{
     ElementState, MouseButton as WinitMouseButton, MouseScrollDelta, StartCause,
     WindowEvent as WinitWindowEvent,
 };
+#[cfg(feature = "accesskit")]
+use winit::event_loop::EventLoopProxy;
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
 use winit::window::{CursorGrabMode, Window as WinitWindow, WindowId as WinitWindowId};
 
@@ -18,6 +20,7 @@ use crate::action_queue::ActionSender;
 use crate::application::{Application, TimerInterval};
 use crate::element_system::ElementSystemConfig;
 use crate::event::{AppWindowEvent, EventCaptureStatus, PointerButton, WheelDeltaType};
+use crate::layout::AttachedLayouts;
 use crate::math::{PhysicalPoint, PhysicalSizeI32, ScaleFactor, Size, Vector};
 use crate::prelude::{AppHandler, ResourceCtx};
 use crate::style::StyleSystem;
@@ -31,6 +34,12 @@ use super::{
 
 mod convert;
 
+/// The custom event type sent through the winit event loop.
+#[cfg(feature = "accesskit")]
+type UserEvent = accesskit_winit::Event;
+#[cfg(not(feature = "accesskit"))]
+type UserEvent = ();
+
 struct WinitWindowBackend<'a> {
     inner: &'a mut WinitAppHandlerInner,
     event_loop: &'a ActiveEventLoop,
@@ -228,6 +237,10 @@ impl<'a> WindowBackend for WinitWindowBackend<'a> {
                 self.inner
                     .winit_id_to_window_id_map
                     .insert(window_handle.id(), window_id);
+
+                #[cfg(feature = "accesskit")]
+                self.inner.init_accesskit_adapter(window_id, &window_handle);
+
                 self.inner.windows.insert(window_id, window_handle);
 
                 Ok(window_state)
@@ -237,6 +250,9 @@ impl<'a> WindowBackend for WinitWindowBackend<'a> {
     }
 
     fn close_window(&mut self, window_id: WindowID) {
+        #[cfg(feature = "accesskit")]
+        self.inner.accesskit_adapters.remove(&window_id);
+
         if let Some(window_handle) = self.inner.windows.remove(&window_id) {
             self.inner
                 .winit_id_to_window_id_map
@@ -263,6 +279,28 @@ struct WinitAppHandlerInner {
     windows: FxHashMap<WindowID, Arc<winit::window::Window>>,
 
     tick_wait_cancelled: bool,
+
+    #[cfg(feature = "accesskit")]
+    event_loop_proxy: EventLoopProxy<UserEvent>,
+    #[cfg(feature = "accesskit")]
+    accesskit_adapters: FxHashMap<WindowID, accesskit_winit::Adapter>,
+}
+
+impl WinitAppHandlerInner {
+    /// Create the AccessKit adapter for a newly created window, and then
+    /// show the window.
+    ///
+    /// The adapter must be created before the window is first shown, which
+    /// is why windows are created invisible when the `accesskit` feature is
+    /// enabled.
+    #[cfg(feature = "accesskit")]
+    fn init_accesskit_adapter(&mut self, window_id: WindowID, window: &WinitWindow) {
+        let adapter =
+            accesskit_winit::Adapter::with_event_loop_proxy(window, self.event_loop_proxy.clone());
+        self.accesskit_adapters.insert(window_id, adapter);
+
+        window.set_visible(true);
+    }
 }
 
 struct WinitAppHandler<A: Application> {
@@ -272,7 +310,10 @@ struct WinitAppHandler<A: Application> {
 }
 
 impl<A: Application> WinitAppHandler<A> {
-    fn new(config: AppConfig) -> Result<Self, Box<dyn Error>> {
+    fn new(
+        config: AppConfig,
+        #[cfg(feature = "accesskit")] event_loop_proxy: EventLoopProxy<UserEvent>,
+    ) -> Result<Self, Box<dyn Error>> {
         let use_dark_theme = config.use_dark_theme;
 
         Ok(Self {
@@ -286,6 +327,10 @@ impl<A: Application> WinitAppHandler<A> {
                 winit_id_to_window_id_map: FxHashMap::default(),
                 windows: FxHashMap::default(),
                 tick_wait_cancelled: false,
+                #[cfg(feature = "accesskit")]
+                event_loop_proxy,
+                #[cfg(feature = "accesskit")]
+                accesskit_adapters: FxHashMap::default(),
             },
             pre_main_window_data: Some(PreMainWindowData {
                 config,
@@ -306,10 +351,36 @@ impl<A: Application> WinitAppHandler<A> {
                 event_loop,
             });
         }
+
+        #[cfg(feature = "accesskit")]
+        self.update_accessibility();
+    }
+
+    /// Push the accessibility tree of any window which has changed to its
+    /// AccessKit adapter.
+    #[cfg(feature = "accesskit")]
+    fn update_accessibility(&mut self) {
+        let Some(app_handler) = &mut self.app_handler else {
+            return;
+        };
+
+        for (window_id, adapter) in self.inner.accesskit_adapters.iter_mut() {
+            let window_state = if *window_id == MAIN_WINDOW {
+                &mut app_handler.cx.main_window
+            } else if let Some(window_state) = app_handler.cx.window_map.get_mut(window_id) {
+                window_state
+            } else {
+                continue;
+            };
+
+            if window_state.accessibility_needs_update() {
+                adapter.update_if_active(|| window_state.accessibility_tree_update());
+            }
+        }
     }
 }
 
-impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
+impl<A: Application> WinitApplicationHandler<UserEvent> for WinitAppHandler<A> {
     fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
         self.inner.tick_wait_cancelled = false;
 
@@ -416,6 +487,10 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                 .winit_id_to_window_id_map
                 .insert(window_handle.id(), MAIN_WINDOW);
 
+            #[cfg(feature = "accesskit")]
+            self.inner
+                .init_accesskit_adapter(MAIN_WINDOW, &window_handle);
+
             #[cfg(any(
                 target_os = "linux",
                 target_os = "freebsd",
@@ -487,6 +562,14 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
             return;
         };
 
+        #[cfg(feature = "accesskit")]
+        if let (Some(adapter), Some(window_handle)) = (
+            self.inner.accesskit_adapters.get_mut(&window_id),
+            self.inner.windows.get(&window_id),
+        ) {
+            adapter.process_event(window_handle, &event);
+        }
+
         let window_state = if window_id == MAIN_WINDOW {
             &mut app_handler.cx.main_window
         } else if let Some(window_state) = app_handler.cx.window_map.get_mut(&window_id) {
@@ -516,6 +599,8 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                     ) {
                         WindowCloseRequest::CloseImmediately => {
                             app_handler.cx.window_map.remove(&window_id);
+                            #[cfg(feature = "accesskit")]
+                            self.inner.accesskit_adapters.remove(&window_id);
                             self.inner
                                 .winit_id_to_window_id_map
                                 .remove(&winit_window_id);
@@ -560,6 +645,7 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
 
                 let scale_factor = window_handle.scale_factor().into();
                 window_state.set_size(new_size, scale_factor);
+                window_state.apply_attached_layouts(&mut app_handler.cx.res);
                 window_handle.request_redraw();
 
                 app_handler.user_app.on_window_event(
@@ -585,6 +671,7 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                 }
 
                 window_state.set_size(new_size, scale_factor.into());
+                window_state.apply_attached_layouts(&mut app_handler.cx.res);
 
                 app_handler.user_app.on_window_event(
                     AppWindowEvent::WindowResized,
@@ -689,6 +776,8 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                 );
 
                 app_handler.cx.window_map.remove(&window_id);
+                #[cfg(feature = "accesskit")]
+                self.inner.accesskit_adapters.remove(&window_id);
                 self.inner
                     .winit_id_to_window_id_map
                     .remove(&winit_window_id);
@@ -748,6 +837,44 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
         }
     }
 
+    #[cfg(feature = "accesskit")]
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: UserEvent) {
+        let Some(app_handler) = &mut self.app_handler else {
+            return;
+        };
+
+        let Some(window_id) = self
+            .inner
+            .winit_id_to_window_id_map
+            .get(&event.window_id)
+            .copied()
+        else {
+            return;
+        };
+
+        let window_state = if window_id == MAIN_WINDOW {
+            &mut app_handler.cx.main_window
+        } else if let Some(window_state) = app_handler.cx.window_map.get_mut(&window_id) {
+            window_state
+        } else {
+            return;
+        };
+
+        match event.window_event {
+            accesskit_winit::WindowEvent::InitialTreeRequested => {
+                if let Some(adapter) = self.inner.accesskit_adapters.get_mut(&window_id) {
+                    adapter.update_if_active(|| window_state.accessibility_tree_update());
+                }
+            }
+            accesskit_winit::WindowEvent::ActionRequested(request) => {
+                window_state.handle_accessibility_action(request, &mut app_handler.cx.res);
+
+                self.process_updates(event_loop);
+            }
+            accesskit_winit::WindowEvent::AccessibilityDeactivated => {}
+        }
+    }
+
     fn device_event(
         &mut self,
         event_loop: &ActiveEventLoop,
@@ -829,13 +956,19 @@ pub enum OpenWindowError {
     OsError(#[from] winit::error::OsError),
     #[error("{0}")]
     SurfaceError(#[from] rootvg::surface::NewSurfaceError),
+    #[cfg(feature = "headless")]
+    #[error("{0}")]
+    HeadlessError(#[from] super::HeadlessError),
 }
 
 pub fn run_blocking<A: Application>(config: AppConfig) -> Result<(), Box<dyn Error>>
 where
     A::Action: Send,
 {
-    let event_loop = EventLoop::new()?;
+    let event_loop = EventLoop::<UserEvent>::with_user_event().build()?;
+    #[cfg(feature = "accesskit")]
+    let mut app_handler = WinitAppHandler::<A>::new(config, event_loop.create_proxy())?;
+    #[cfg(not(feature = "accesskit"))]
     let mut app_handler = WinitAppHandler::<A>::new(config)?;
 
     event_loop.run_app(&mut app_handler).map_err(Into::into)
@@ -896,6 +1029,13 @@ fn create_window<A: Clone + 'static>(
         }
     }
 
+    // The AccessKit adapter must be created before the window is shown, so
+    // the window is shown once the adapter has been initialized.
+    #[cfg(feature = "accesskit")]
+    {
+        attributes = attributes.with_visible(false);
+    }
+
     let window = event_loop.create_window(attributes).map(|w| Arc::new(w))?;
 
     // Might fix an issue in MacOS with wgpu
@@ -945,8 +1085,10 @@ fn create_window<A: Clone + 'static>(
         window,
         WindowState {
             element_system,
-            renderer,
+            renderer: Some(renderer),
             surface: Some(surface),
+            #[cfg(feature = "headless")]
+            offscreen: None,
             multisample: canvas_config.multisample,
             logical_size: config.size,
             physical_size,
@@ -958,6 +1100,7 @@ fn create_window<A: Clone + 'static>(
             queued_pointer_delta: None,
             prev_pointer_pos: None,
             pointer_btn_states: [PointerBtnState::default(); 5],
+            attached_layouts: AttachedLayouts::default(),
             modifiers: Modifiers::empty(),
             current_cursor_icon: CursorIcon::Default,
             pointer_lock_state: PointerLockState::NotLocked,
@@ -0,0 +1,298 @@
+//! Golden-image snapshot tests for the built-in elements using the
+//! `yarrow_dark` theme.
+//!
+//! Run with `cargo test --features snapshot --test element_snapshots`. The
+//! stored images are rendered with the software (fallback) adapter so that
+//! they don't depend on the GPU of the machine running the tests. See
+//! `tests/snapshots/README.md` for how to (re)generate them.
+
+use yarrow::prelude::*;
+use yarrow::snapshot::{assert_snapshot, SnapshotTolerance};
+
+const SNAPSHOT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots");
+
+const WINDOW_SIZE: Size = Size::new(160.0, 60.0);
+const ELEMENT_RECT: Rect = Rect::new(Point::new(20.0, 15.0), Size::new(120.0, 30.0));
+
+struct EmptyApp;
+
+impl Application for EmptyApp {
+    type Action = ();
+
+    fn init(_cx: &mut AppContext<()>) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self)
+    }
+}
+
+fn new_app(window_size: Size) -> HeadlessApp<EmptyApp> {
+    let mut app = HeadlessApp::<EmptyApp>::with_renderer(
+        AppConfig {
+            main_window_config: WindowConfig {
+                size: window_size,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        // Always use the software rasterizer so that the results are the
+        // same on every machine.
+        HeadlessConfig {
+            force_fallback_adapter: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    yarrow::theme::yarrow_dark::load(Default::default(), &mut app.cx().res);
+    app.cx().main_window().set_clear_color(rgb(20, 20, 20));
+
+    app
+}
+
+fn check(app: &mut HeadlessApp<EmptyApp>, name: &str) {
+    let image = app.screenshot(MAIN_WINDOW).unwrap();
+
+    if let Err(e) = assert_snapshot(SNAPSHOT_DIR, name, &image, &SnapshotTolerance::default()) {
+        panic!("{e}");
+    }
+}
+
+/// Snapshot the idle, hovered, pressed, and disabled states of an element.
+fn check_pointer_states(
+    app: &mut HeadlessApp<EmptyApp>,
+    name: &str,
+    disable: impl FnOnce(&mut HeadlessApp<EmptyApp>),
+) {
+    check(app, &format!("{name}_idle"));
+
+    app.pointer_moved(MAIN_WINDOW, ELEMENT_RECT.center());
+    check(app, &format!("{name}_hovered"));
+
+    app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+    check(app, &format!("{name}_down"));
+
+    app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+    app.pointer_left(MAIN_WINDOW);
+    app.process_updates();
+
+    disable(app);
+    check(app, &format!("{name}_disabled"));
+}
+
+#[test]
+fn button() {
+    let mut app = new_app(WINDOW_SIZE);
+
+    let mut button = Button::builder()
+        .text("Button")
+        .rect(ELEMENT_RECT)
+        .build(&mut app.cx().main_window());
+
+    check_pointer_states(&mut app, "button", |_| {
+        button.set_disabled(true);
+    });
+}
+
+#[test]
+fn toggle_button() {
+    for toggled in [false, true] {
+        let mut app = new_app(WINDOW_SIZE);
+
+        let mut toggle_button = ToggleButton::builder()
+            .text("Toggle")
+            .toggled(toggled)
+            .rect(ELEMENT_RECT)
+            .build(&mut app.cx().main_window());
+
+        // Pressing the element would flip its toggled state, so only
+        // snapshot the hovered state here.
+        let name = if toggled {
+            "toggle_button_on"
+        } else {
+            "toggle_button_off"
+        };
+        check(&mut app, &format!("{name}_idle"));
+
+        app.pointer_moved(MAIN_WINDOW, ELEMENT_RECT.center());
+        check(&mut app, &format!("{name}_hovered"));
+
+        app.pointer_left(MAIN_WINDOW);
+        toggle_button.set_disabled(true);
+        check(&mut app, &format!("{name}_disabled"));
+    }
+}
+
+#[test]
+fn switch() {
+    for toggled in [false, true] {
+        let mut app = new_app(WINDOW_SIZE);
+
+        let mut switch = Switch::builder()
+            .toggled(toggled)
+            .rect(centered_rect(ELEMENT_RECT.center(), Size::new(40.0, 20.0)))
+            .build(&mut app.cx().main_window());
+
+        let name = if toggled { "switch_on" } else { "switch_off" };
+        check(&mut app, &format!("{name}_idle"));
+
+        app.pointer_moved(MAIN_WINDOW, ELEMENT_RECT.center());
+        check(&mut app, &format!("{name}_hovered"));
+
+        app.pointer_left(MAIN_WINDOW);
+        switch.set_disabled(true);
+        check(&mut app, &format!("{name}_disabled"));
+    }
+}
+
+#[test]
+fn radio_button() {
+    for toggled in [false, true] {
+        let mut app = new_app(WINDOW_SIZE);
+
+        let mut radio_button = RadioButton::builder()
+            .toggled(toggled)
+            .rect(centered_rect(ELEMENT_RECT.center(), Size::new(20.0, 20.0)))
+            .build(&mut app.cx().main_window());
+
+        let name = if toggled {
+            "radio_button_on"
+        } else {
+            "radio_button_off"
+        };
+        check(&mut app, &format!("{name}_idle"));
+
+        app.pointer_moved(MAIN_WINDOW, ELEMENT_RECT.center());
+        check(&mut app, &format!("{name}_hovered"));
+
+        app.pointer_left(MAIN_WINDOW);
+        radio_button.set_disabled(true);
+        check(&mut app, &format!("{name}_disabled"));
+    }
+}
+
+#[test]
+fn tab() {
+    for toggled in [false, true] {
+        let mut app = new_app(WINDOW_SIZE);
+
+        let mut tab = Tab::builder()
+            .text("Tab")
+            .toggled(toggled)
+            .rect(ELEMENT_RECT)
+            .build(&mut app.cx().main_window());
+
+        let name = if toggled { "tab_on" } else { "tab_off" };
+        check(&mut app, &format!("{name}_idle"));
+
+        app.pointer_moved(MAIN_WINDOW, ELEMENT_RECT.center());
+        check(&mut app, &format!("{name}_hovered"));
+
+        app.pointer_left(MAIN_WINDOW);
+        tab.set_disabled(true);
+        check(&mut app, &format!("{name}_disabled"));
+    }
+}
+
+#[test]
+fn knob() {
+    let mut app = new_app(Size::new(60.0, 60.0));
+
+    let mut knob = Knob::builder("knob")
+        .normal_value(0.25)
+        .rect(rect(10.0, 10.0, 40.0, 40.0))
+        .build(&mut app.cx().main_window());
+
+    check(&mut app, "knob_idle");
+
+    app.pointer_moved(MAIN_WINDOW, point(30.0, 30.0));
+    check(&mut app, "knob_hovered");
+
+    app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+    check(&mut app, "knob_gesturing");
+
+    app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+    app.pointer_left(MAIN_WINDOW);
+    knob.set_disabled(true);
+    check(&mut app, "knob_disabled");
+}
+
+#[test]
+fn slider() {
+    for horizontal in [false, true] {
+        let (window_size, slider_rect) = if horizontal {
+            (Size::new(140.0, 40.0), rect(10.0, 10.0, 120.0, 20.0))
+        } else {
+            (Size::new(40.0, 140.0), rect(10.0, 10.0, 20.0, 120.0))
+        };
+
+        let mut app = new_app(window_size);
+
+        let mut slider = Slider::builder("slider")
+            .normal_value(0.25)
+            .horizontal(horizontal)
+            .drag_horizontally(horizontal)
+            .rect(slider_rect)
+            .build(&mut app.cx().main_window());
+
+        let name = if horizontal {
+            "slider_horizontal"
+        } else {
+            "slider_vertical"
+        };
+        check(&mut app, &format!("{name}_idle"));
+
+        app.pointer_moved(MAIN_WINDOW, slider_rect.center());
+        check(&mut app, &format!("{name}_hovered"));
+
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, true);
+        check(&mut app, &format!("{name}_gesturing"));
+
+        app.mouse_button(MAIN_WINDOW, PointerButton::Primary, false);
+        app.pointer_left(MAIN_WINDOW);
+        slider.set_disabled(true);
+        check(&mut app, &format!("{name}_disabled"));
+    }
+}
+
+#[test]
+fn drop_down_menu() {
+    let mut app = new_app(Size::new(200.0, 140.0));
+
+    let mut menu = DropDownMenu::builder()
+        .entries(vec![
+            MenuEntry::option("First", 0),
+            MenuEntry::option_with_right_text("Second", Some("Ctrl+S"), 1),
+            MenuEntry::Divider,
+            MenuEntry::option("Third", 2),
+        ])
+        .build(&mut app.cx().main_window());
+
+    menu.open(Some(point(10.0, 10.0)));
+    check(&mut app, "drop_down_menu_open");
+
+    app.pointer_moved(MAIN_WINDOW, point(30.0, 22.0));
+    check(&mut app, "drop_down_menu_hovered");
+}
+
+#[test]
+fn text_input() {
+    let mut app = new_app(WINDOW_SIZE);
+
+    let mut text_input = TextInput::builder()
+        .placeholder_text("Placeholder")
+        .rect(ELEMENT_RECT)
+        .build(&mut app.cx().main_window());
+
+    check(&mut app, "text_input_placeholder");
+
+    app.pointer_moved(MAIN_WINDOW, ELEMENT_RECT.center());
+    check(&mut app, "text_input_hovered");
+
+    app.click(MAIN_WINDOW, ELEMENT_RECT.center(), PointerButton::Primary);
+    app.type_text(MAIN_WINDOW, "Hello");
+    check(&mut app, "text_input_focused");
+
+    app.click(MAIN_WINDOW, point(1.0, 1.0), PointerButton::Primary);
+    app.pointer_left(MAIN_WINDOW);
+    text_input.set_disabled(true);
+    check(&mut app, "text_input_disabled");
+}